@@ -0,0 +1,295 @@
+// Copyright (C) 2025 Berkay Yetgin
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use zip::ZipArchive;
+
+use crate::core::platform::long_path;
+use crate::core::types::{ArchiveKind, CancelToken, ModFile};
+
+/// Signatures at the start of each recognised format
+const MAGIC_BYTES: &[(ArchiveKind, &[u8])] = &[
+    (ArchiveKind::SevenZip, &[0x37, 0x7A, 0xBC, 0xAF, 0x27, 0x1C]),
+    (ArchiveKind::Zip, &[0x50, 0x4B, 0x03, 0x04]),
+    (ArchiveKind::Rar, &[0x52, 0x61, 0x72, 0x21]),
+    (ArchiveKind::Tar, &[0x1F, 0x8B]),
+    (ArchiveKind::Exe, b"MZ"),
+    (ArchiveKind::Bethesda, b"BSA\0"),
+    (ArchiveKind::Bethesda, b"BTDX"),
+];
+
+/// Real format of a file from its first bytes, whatever its extension says; `Other` when
+/// no signature matches. Warns when the extension names a different format, e.g. a zip
+/// saved as `.7z`.
+pub fn detect_archive_format(path: &Path) -> io::Result<ArchiveKind> {
+    let mut header = Vec::with_capacity(8);
+    File::open(long_path(path))?
+        .take(8)
+        .read_to_end(&mut header)?;
+    let detected = MAGIC_BYTES
+        .iter()
+        .find(|(_, magic)| header.starts_with(magic))
+        .map_or(ArchiveKind::Other, |(kind, _)| *kind);
+
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let claimed = ArchiveKind::from_file_name(&file_name);
+    if detected != ArchiveKind::Other && detected != claimed {
+        log::warn!(
+            "{} is named as {} but its content is {}",
+            file_name,
+            claimed.label(),
+            detected.label()
+        );
+    }
+    Ok(detected)
+}
+
+/// Reduce archive entry paths to their unique top-level names (folders get a trailing `/`)
+fn top_level_entries<'a>(names: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let mut top = BTreeSet::new();
+    for name in names {
+        let name = name.replace('\\', "/");
+        let name = name.trim_start_matches('/');
+        match name.split_once('/') {
+            Some((first, _)) if !first.is_empty() => {
+                top.insert(format!("{}/", first));
+            }
+            _ if !name.is_empty() => {
+                top.insert(name.to_string());
+            }
+            _ => {}
+        }
+    }
+    top.into_iter().collect()
+}
+
+/// List top-level entries of a zip or 7z archive without extracting it. The format comes
+/// from the file content, so mislabeled archives still open.
+pub fn list_archive_entries(path: &Path) -> Result<Vec<String>> {
+    let format =
+        detect_archive_format(path).with_context(|| format!("Failed to open {:?}", path))?;
+
+    match format {
+        ArchiveKind::Zip => {
+            let file = File::open(long_path(path))
+                .with_context(|| format!("Failed to open {:?}", path))?;
+            let archive = ZipArchive::new(file).with_context(|| "Failed to read ZIP archive")?;
+            Ok(top_level_entries(archive.file_names()))
+        }
+        ArchiveKind::SevenZip => {
+            let mut file = File::open(long_path(path))
+                .with_context(|| format!("Failed to open {:?}", path))?;
+            let len = file.metadata()?.len();
+            let archive = sevenz_rust::Archive::read(&mut file, len, &[])
+                .map_err(|e| anyhow!("Failed to read 7z archive: {}", e))?;
+            Ok(top_level_entries(archive.files.iter().map(|f| f.name())))
+        }
+        _ => bail!("Preview unavailable for this archive type"),
+    }
+}
+
+/// Cheap structural check of a zip without extracting it: the central directory must
+/// parse, list at least one entry, and every entry must lie within the file. Catches
+/// truncated and interrupted downloads.
+pub fn quick_check_zip(path: &Path) -> bool {
+    match zip_problem(path) {
+        Ok(()) => true,
+        Err(e) => {
+            log::warn!("{:?} failed the zip check: {:#}", path, e);
+            false
+        }
+    }
+}
+
+fn zip_problem(path: &Path) -> Result<()> {
+    let file = File::open(long_path(path))?;
+    let len = file.metadata()?.len();
+    let mut archive = ZipArchive::new(file).context("Central directory unreadable")?;
+    if archive.is_empty() || archive.central_directory_start() > len {
+        bail!("Implausible central directory");
+    }
+    for i in 0..archive.len() {
+        let entry = archive.by_index_raw(i)?;
+        if entry.data_start().saturating_add(entry.compressed_size()) > len {
+            bail!("{} extends past the end of the file", entry.name());
+        }
+    }
+    Ok(())
+}
+
+/// Outcome of the quick check for one archive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveHealth {
+    Ok,
+    Suspect,
+    /// Not a zip; other formats are not checked
+    NotChecked,
+}
+
+/// Quick-check one archive by its real format
+pub fn check_archive_health(path: &Path) -> ArchiveHealth {
+    match detect_archive_format(path) {
+        Ok(ArchiveKind::Zip) if quick_check_zip(path) => ArchiveHealth::Ok,
+        Ok(ArchiveKind::Zip) => ArchiveHealth::Suspect,
+        Ok(_) => ArchiveHealth::NotChecked,
+        Err(e) => {
+            log::warn!("Failed to open {:?}: {}", path, e);
+            ArchiveHealth::Suspect
+        }
+    }
+}
+
+/// Result of quick-checking a set of archives
+#[derive(Debug, Clone, Default)]
+pub struct ArchiveHealthReport {
+    /// Zips that look truncated or corrupt, or files that could not be opened
+    pub suspect: Vec<ModFile>,
+    pub healthy: usize,
+    pub not_checked: usize,
+}
+
+/// Quick-check every file. Returns `None` if cancelled.
+pub fn check_archives_health(
+    files: &[ModFile],
+    cancel: &CancelToken,
+    on_progress: &mut dyn FnMut(usize, usize),
+) -> Option<ArchiveHealthReport> {
+    let mut report = ArchiveHealthReport::default();
+    for (i, file) in files.iter().enumerate() {
+        if cancel.is_cancelled() {
+            return None;
+        }
+        match check_archive_health(&file.full_path) {
+            ArchiveHealth::Ok => report.healthy += 1,
+            ArchiveHealth::Suspect => report.suspect.push(file.clone()),
+            ArchiveHealth::NotChecked => report.not_checked += 1,
+        }
+        on_progress(i + 1, files.len());
+    }
+    report.suspect.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+    Some(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::tempdir;
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    #[test]
+    fn test_list_zip_entries() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Mod-123-1-0-1234567890.zip");
+        let mut zip = ZipWriter::new(File::create(&path).unwrap());
+        let options = SimpleFileOptions::default();
+        for name in [
+            "fomod/ModuleConfig.xml",
+            "fomod/info.xml",
+            "Data/meshes/a.nif",
+            "readme.txt",
+        ] {
+            zip.start_file(name, options).unwrap();
+            zip.write_all(b"x").unwrap();
+        }
+        zip.finish().unwrap();
+
+        let entries = list_archive_entries(&path).unwrap();
+        assert_eq!(entries, vec!["Data/", "fomod/", "readme.txt"]);
+    }
+
+    #[test]
+    fn test_detect_archive_format_by_magic_bytes() {
+        let dir = tempdir().unwrap();
+        let write = |name: &str, bytes: &[u8]| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, bytes).unwrap();
+            path
+        };
+        let seven_zip = [0x37, 0x7A, 0xBC, 0xAF, 0x27, 0x1C, 0, 4];
+        let rar = b"Rar!\x1a\x07\x01\x00";
+
+        assert_eq!(
+            detect_archive_format(&write("a.7z", &seven_zip)).unwrap(),
+            ArchiveKind::SevenZip
+        );
+        assert_eq!(
+            detect_archive_format(&write("b.rar", rar)).unwrap(),
+            ArchiveKind::Rar
+        );
+        // Mislabeled: the content wins over the extension
+        assert_eq!(
+            detect_archive_format(&write("c.7z", rar)).unwrap(),
+            ArchiveKind::Rar
+        );
+        assert_eq!(
+            detect_archive_format(&write("d.zip", &seven_zip)).unwrap(),
+            ArchiveKind::SevenZip
+        );
+        assert_eq!(
+            detect_archive_format(&write("e.7z", b"not an archive")).unwrap(),
+            ArchiveKind::Other
+        );
+        assert_eq!(
+            detect_archive_format(&write("f.7z", b"")).unwrap(),
+            ArchiveKind::Other
+        );
+    }
+
+    #[test]
+    fn test_list_zip_named_as_7z() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Mod-123-1-0-1234567890.7z");
+        let mut zip = ZipWriter::new(File::create(&path).unwrap());
+        zip.start_file("Data/a.esp", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"x").unwrap();
+        zip.finish().unwrap();
+
+        assert_eq!(detect_archive_format(&path).unwrap(), ArchiveKind::Zip);
+        assert_eq!(list_archive_entries(&path).unwrap(), vec!["Data/"]);
+    }
+
+    #[test]
+    fn test_quick_check_zip_flags_truncated_archive() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Mod-123-1-0-1234567890.zip");
+        let mut zip = ZipWriter::new(File::create(&path).unwrap());
+        zip.start_file("Data/a.esp", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(&[7u8; 4096]).unwrap();
+        zip.finish().unwrap();
+        assert!(quick_check_zip(&path));
+        assert_eq!(check_archive_health(&path), ArchiveHealth::Ok);
+
+        // An interrupted download: the tail with the central directory is missing
+        let bytes = std::fs::read(&path).unwrap();
+        let truncated = dir.path().join("Mod-456-1-0-1234567890.zip");
+        std::fs::write(&truncated, &bytes[..bytes.len() / 2]).unwrap();
+        assert!(!quick_check_zip(&truncated));
+        assert_eq!(check_archive_health(&truncated), ArchiveHealth::Suspect);
+
+        let rar = dir.path().join("Mod-789-1-0-1234567890.rar");
+        std::fs::write(&rar, b"Rar!\x1a\x07\x01\x00").unwrap();
+        assert_eq!(check_archive_health(&rar), ArchiveHealth::NotChecked);
+    }
+
+    #[test]
+    fn test_list_unsupported_archive() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Mod-123-1-0-1234567890.rar");
+        File::create(&path).unwrap();
+
+        assert!(list_archive_entries(&path).is_err());
+    }
+}
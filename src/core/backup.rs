@@ -0,0 +1,843 @@
+// Copyright (C) 2025 Berkay Yetgin
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+use std::fs::{self, File};
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::core::platform::long_path;
+use crate::core::types::APP_FOLDER_NAMES;
+
+/// Folder name format of a timestamped recycle bin run
+const RUN_FOLDER_FORMAT: &str = "%Y-%m-%d_%H-%M-%S";
+
+/// Extension of the archive a run writes with compressed backups
+const RUN_ARCHIVE_EXTENSION: &str = ".zip";
+
+/// Added to an archive's name while it is being written
+const PARTIAL_ARCHIVE_SUFFIX: &str = ".partial";
+
+/// One cleanup run kept in the recycle bin, or the loose files of a flat recycle bin
+#[derive(Debug, Clone)]
+pub struct BackupInfo {
+    pub path: PathBuf,
+    /// Run folder name, or the recycle bin name for loose files
+    pub name: String,
+    pub files: usize,
+    pub size: u64,
+    /// From the run folder name, falling back to the newest file time
+    pub created: Option<DateTime<Local>>,
+    /// Files directly inside the recycle bin folder (flat mode) rather than a run folder
+    pub loose: bool,
+    /// A run archive written with compressed backups; it can be restored
+    pub archive: bool,
+}
+
+impl BackupInfo {
+    /// Whole days since the backup was created (0 if unknown)
+    pub fn age_days(&self, now: DateTime<Local>) -> i64 {
+        self.created
+            .map(|c| (now - c).num_days().max(0))
+            .unwrap_or(0)
+    }
+}
+
+fn is_app_folder(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|n| APP_FOLDER_NAMES.iter().any(|f| n == *f))
+}
+
+/// Creation time of a run from its folder or archive name like `2024-03-01_10-00-00`
+fn run_created(name: &str) -> Option<DateTime<Local>> {
+    let name = name.strip_suffix(RUN_ARCHIVE_EXTENSION).unwrap_or(name);
+    NaiveDateTime::parse_from_str(name, RUN_FOLDER_FORMAT)
+        .ok()
+        .and_then(|t| Local.from_local_datetime(&t).earliest())
+}
+
+fn is_run_archive(name: &str) -> bool {
+    name.ends_with(RUN_ARCHIVE_EXTENSION) && run_created(name).is_some()
+}
+
+/// Archive a run writes its backups into when they are compressed: the run folder plus `.zip`
+pub fn backup_archive_path(recycle_bin: &Path) -> PathBuf {
+    PathBuf::from(format!(
+        "{}{}",
+        recycle_bin.display(),
+        RUN_ARCHIVE_EXTENSION
+    ))
+}
+
+/// Downloads folder a recycle bin (or anything in it) belongs to; archive entries are
+/// named relative to it
+pub fn backup_root(recycle_bin: &Path) -> Option<&Path> {
+    recycle_bin.ancestors().find(|p| is_app_folder(p))?.parent()
+}
+
+fn zip_time(time: SystemTime) -> Option<zip::DateTime> {
+    let t = DateTime::<Local>::from(time);
+    zip::DateTime::from_date_and_time(
+        t.year().try_into().ok()?,
+        t.month() as u8,
+        t.day() as u8,
+        t.hour() as u8,
+        t.minute() as u8,
+        t.second() as u8,
+    )
+    .ok()
+}
+
+fn system_time(time: zip::DateTime) -> Option<SystemTime> {
+    let t = NaiveDate::from_ymd_opt(time.year().into(), time.month().into(), time.day().into())?
+        .and_hms_opt(
+            time.hour().into(),
+            time.minute().into(),
+            time.second().into(),
+        )?;
+    Local
+        .from_local_datetime(&t)
+        .earliest()
+        .map(SystemTime::from)
+}
+
+/// Entry name of `path` in a backup archive: its path below `root`, with `/` separators
+fn archive_entry_name(path: &Path, root: &Path) -> Result<String> {
+    let relative = path
+        .strip_prefix(root)
+        .with_context(|| format!("{:?} is not inside {:?}", path, root))?;
+    let parts: Vec<String> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    Ok(parts.join("/"))
+}
+
+/// A backup archive being written. Entries go into a temporary file next to the archive,
+/// after a copy of the entries it already holds, and the archive is only replaced once
+/// that file is complete and synced. A failed write leaves earlier backups untouched, and
+/// the added files stay in place until `finish` succeeds.
+pub struct BackupArchive {
+    path: PathBuf,
+    temp: PathBuf,
+    root: PathBuf,
+    /// `None` once a write failed partway; the temporary file is then discarded
+    zip: Option<ZipWriter<File>>,
+    added: Vec<PathBuf>,
+    size: u64,
+}
+
+impl BackupArchive {
+    /// Start writing the zip at `path`, keeping the entries it already has. Entries are
+    /// named by their path below `root` so a restore can put them back.
+    pub fn create(path: &Path, root: &Path) -> Result<Self> {
+        let temp = PathBuf::from(format!("{}{}", path.display(), PARTIAL_ARCHIVE_SUFFIX));
+        let file = File::create(long_path(&temp))
+            .with_context(|| format!("Failed to create backup archive: {:?}", temp))?;
+        let mut archive = Self {
+            path: path.to_path_buf(),
+            temp,
+            root: root.to_path_buf(),
+            zip: Some(ZipWriter::new(file)),
+            added: Vec::new(),
+            size: 0,
+        };
+        if long_path(path).exists() {
+            let existing = File::open(long_path(path))
+                .map_err(anyhow::Error::from)
+                .and_then(|f| Ok(ZipArchive::new(f)?))
+                .with_context(|| format!("Failed to read backup archive: {:?}", path))?;
+            archive
+                .writer()?
+                .merge_archive(existing)
+                .with_context(|| format!("Failed to copy backup archive: {:?}", path))?;
+        }
+        Ok(archive)
+    }
+
+    fn writer(&mut self) -> Result<&mut ZipWriter<File>> {
+        self.zip
+            .as_mut()
+            .with_context(|| format!("Backup archive {:?} failed earlier", self.path))
+    }
+
+    /// Store `file` uncompressed, as mod archives are compressed already. The file itself
+    /// is left alone. Returns its size.
+    pub fn add(&mut self, file: &Path) -> Result<u64> {
+        let name = archive_entry_name(file, &self.root)?;
+        let mut src = File::open(long_path(file))
+            .with_context(|| format!("Failed to open file: {:?}", file))?;
+        let meta = src.metadata()?;
+        let mut options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Stored)
+            .large_file(meta.len() >= u32::MAX as u64);
+        if let Some(modified) = meta.modified().ok().and_then(zip_time) {
+            options = options.last_modified_time(modified);
+        }
+
+        let path = self.path.clone();
+        let zip = self.writer()?;
+        let written = zip
+            .start_file(name.as_str(), options)
+            .map_err(anyhow::Error::from)
+            .and_then(|()| Ok(io::copy(&mut src, zip)?));
+        if let Err(e) = written {
+            self.discard();
+            return Err(e.context(format!("Failed to add {} to {:?}", name, path)));
+        }
+        self.added.push(file.to_path_buf());
+        self.size += meta.len();
+        Ok(meta.len())
+    }
+
+    /// Files added so far
+    pub fn added(&self) -> &[PathBuf] {
+        &self.added
+    }
+
+    /// Write the archive and put it in place of the old one. Returns the bytes added; the
+    /// added files can be removed once this succeeds.
+    pub fn finish(mut self) -> Result<u64> {
+        let path = self.path.clone();
+        let zip = self
+            .zip
+            .take()
+            .with_context(|| format!("Backup archive {:?} failed earlier", path))?;
+        let written = zip
+            .finish()
+            .map_err(anyhow::Error::from)
+            .and_then(|file| Ok(file.sync_all()?))
+            .and_then(|()| Ok(fs::rename(long_path(&self.temp), long_path(&path))?));
+        if let Err(e) = written {
+            let _ = fs::remove_file(long_path(&self.temp));
+            return Err(e.context(format!("Failed to write backup archive: {:?}", path)));
+        }
+        Ok(self.size)
+    }
+
+    fn discard(&mut self) {
+        if self.zip.take().is_some() {
+            let _ = fs::remove_file(long_path(&self.temp));
+        }
+    }
+}
+
+impl Drop for BackupArchive {
+    fn drop(&mut self) {
+        self.discard();
+    }
+}
+
+/// Move `files` into the zip at `archive_path`, creating it if needed. The files are
+/// removed once the archive is complete. Returns the bytes archived.
+pub fn backup_into_archive(files: &[PathBuf], root: &Path, archive_path: &Path) -> Result<u64> {
+    let mut archive = BackupArchive::create(archive_path, root)?;
+    for path in files {
+        archive.add(path)?;
+    }
+    let archived = archive.finish()?;
+    for path in files {
+        fs::remove_file(long_path(path)).with_context(|| format!("Failed to delete {:?}", path))?;
+    }
+    Ok(archived)
+}
+
+/// Put the files of a backup archive back in their folders below `root`. Files that
+/// exist again are left alone and reported. The archive is removed once every file is
+/// back. Returns the number restored and the errors.
+pub fn restore_backup_archive(archive_path: &Path, root: &Path) -> Result<(usize, Vec<String>)> {
+    let file = File::open(long_path(archive_path))
+        .with_context(|| format!("Failed to open backup archive: {:?}", archive_path))?;
+    let mut zip = ZipArchive::new(file)
+        .with_context(|| format!("Failed to read backup archive: {:?}", archive_path))?;
+
+    let mut restored = 0;
+    let mut errors = Vec::new();
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i)?;
+        // `enclosed_name` rejects absolute paths and `..`, so nothing lands outside `root`
+        let Some(name) = entry
+            .enclosed_name()
+            .filter(|n| n.components().all(|c| matches!(c, Component::Normal(_))))
+        else {
+            errors.push(format!("Skipped unsafe archive entry: {}", entry.name()));
+            continue;
+        };
+        if entry.is_dir() {
+            continue;
+        }
+        let dest = root.join(name);
+        if long_path(&dest).exists() {
+            errors.push(format!("Not restored, already exists: {:?}", dest));
+            continue;
+        }
+        let mut restore = || -> Result<()> {
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(long_path(parent))?;
+            }
+            let mut out = File::create(long_path(&dest))?;
+            io::copy(&mut entry, &mut out)?;
+            if let Some(modified) = entry.last_modified().and_then(system_time) {
+                out.set_modified(modified)?;
+            }
+            Ok(())
+        };
+        match restore() {
+            Ok(()) => {
+                log::info!("Restored {:?}", dest);
+                restored += 1;
+            }
+            Err(e) => {
+                let _ = fs::remove_file(long_path(&dest));
+                errors.push(format!("Failed to restore {:?}: {:#}", dest, e));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        drop(zip);
+        fs::remove_file(long_path(archive_path))
+            .with_context(|| format!("Failed to delete backup archive: {:?}", archive_path))?;
+    }
+    Ok((restored, errors))
+}
+
+/// Why one backed-up file would not come back as it was
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BackupGap {
+    /// The stored data can't be read back, or fails its checksum
+    Unreadable(String),
+    /// Zero bytes; nothing worth restoring was kept
+    Empty,
+    /// Not stored with the place it came from: backup folders keep bare file names
+    NoDestination,
+    /// A file exists where it would be restored, so the restore would skip it
+    DestinationTaken(PathBuf),
+}
+
+impl BackupGap {
+    /// Short explanation in English, for the log
+    pub fn describe(&self) -> String {
+        match self {
+            BackupGap::Unreadable(e) => format!("unreadable ({})", e),
+            BackupGap::Empty => "empty".to_string(),
+            BackupGap::NoDestination => "no recorded destination".to_string(),
+            BackupGap::DestinationTaken(dest) => format!("{:?} already exists", dest),
+        }
+    }
+}
+
+/// Outcome of checking a backup without restoring it
+#[derive(Debug, Clone, Default)]
+pub struct BackupVerification {
+    pub files: usize,
+    /// Bytes read back
+    pub size: u64,
+    /// Files that would not be restored as they were, by their name in the backup
+    pub gaps: Vec<(String, BackupGap)>,
+}
+
+impl BackupVerification {
+    pub fn is_restorable(&self) -> bool {
+        self.gaps.is_empty()
+    }
+}
+
+/// Check that a backup listed by `list_backups` could be restored, without restoring it.
+/// Every file is read back in full (archive entries against their checksum), must not be
+/// empty, and must have a free destination below the downloads folder. Only run archives
+/// record destinations, so the files of backup folders all report `NoDestination`.
+pub fn verify_backup(path: &Path) -> Result<BackupVerification> {
+    if long_path(path).is_file() {
+        return verify_backup_archive(path);
+    }
+    let mut report = BackupVerification::default();
+    verify_backup_folder(path, path, &mut report)?;
+    report.gaps.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(report)
+}
+
+fn verify_backup_archive(archive_path: &Path) -> Result<BackupVerification> {
+    let root = backup_root(archive_path);
+    let file = File::open(long_path(archive_path))
+        .with_context(|| format!("Failed to open backup archive: {:?}", archive_path))?;
+    let mut zip = ZipArchive::new(file)
+        .with_context(|| format!("Failed to read backup archive: {:?}", archive_path))?;
+
+    let mut report = BackupVerification::default();
+    for i in 0..zip.len() {
+        let mut entry = match zip.by_index(i) {
+            Ok(entry) => entry,
+            Err(e) => {
+                report.files += 1;
+                let gap = BackupGap::Unreadable(e.to_string());
+                report.gaps.push((format!("entry #{}", i + 1), gap));
+                continue;
+            }
+        };
+        if entry.is_dir() {
+            continue;
+        }
+        report.files += 1;
+        let name = entry.name().to_string();
+        let dest = entry
+            .enclosed_name()
+            .filter(|n| n.components().all(|c| matches!(c, Component::Normal(_))))
+            .zip(root)
+            .map(|(n, root)| root.join(n));
+        let gap = match io::copy(&mut entry, &mut io::sink()) {
+            Err(e) => Some(BackupGap::Unreadable(e.to_string())),
+            Ok(0) => Some(BackupGap::Empty),
+            Ok(read) => {
+                report.size += read;
+                match dest {
+                    None => Some(BackupGap::NoDestination),
+                    Some(dest) if long_path(&dest).exists() => {
+                        Some(BackupGap::DestinationTaken(dest))
+                    }
+                    Some(_) => None,
+                }
+            }
+        };
+        if let Some(gap) = gap {
+            report.gaps.push((name, gap));
+        }
+    }
+    Ok(report)
+}
+
+fn verify_backup_folder(dir: &Path, base: &Path, report: &mut BackupVerification) -> Result<()> {
+    // In an app folder only the loose files belong to this backup; runs are listed apart
+    let loose_only = is_app_folder(dir);
+    let entries = fs::read_dir(long_path(dir))
+        .with_context(|| format!("Failed to read backup folder: {:?}", dir))?;
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if loose_only && (meta.is_dir() || is_run_archive(&entry.file_name().to_string_lossy())) {
+            continue;
+        }
+        if meta.is_dir() {
+            verify_backup_folder(&path, base, report)?;
+            continue;
+        }
+        report.files += 1;
+        let name = path
+            .strip_prefix(base)
+            .unwrap_or(&path)
+            .display()
+            .to_string();
+        let read = File::open(long_path(&path)).and_then(|mut f| io::copy(&mut f, &mut io::sink()));
+        let gap = match read {
+            Err(e) => BackupGap::Unreadable(e.to_string()),
+            Ok(0) => BackupGap::Empty,
+            Ok(read) => {
+                report.size += read;
+                BackupGap::NoDestination
+            }
+        };
+        report.gaps.push((name, gap));
+    }
+    Ok(())
+}
+
+/// File count and total size below `dir`
+pub(crate) fn dir_usage(dir: &Path) -> (usize, u64, Option<DateTime<Local>>) {
+    let mut files = 0;
+    let mut size = 0;
+    let mut newest = None;
+    let Ok(entries) = fs::read_dir(long_path(dir)) else {
+        return (0, 0, None);
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if meta.is_dir() {
+            let (f, s, n) = dir_usage(&entry.path());
+            files += f;
+            size += s;
+            newest = newest.max(n);
+        } else {
+            files += 1;
+            size += meta.len();
+            newest = newest.max(meta.modified().ok().map(DateTime::<Local>::from));
+        }
+    }
+    (files, size, newest)
+}
+
+/// Backups in every app folder (current and legacy) of `downloads_dir`, oldest first
+pub fn list_backups(downloads_dir: &Path) -> Vec<BackupInfo> {
+    let mut backups = Vec::new();
+
+    for app_folder in APP_FOLDER_NAMES {
+        let root = downloads_dir.join(app_folder);
+        let Ok(entries) = fs::read_dir(long_path(&root)) else {
+            continue;
+        };
+
+        let mut loose = BackupInfo {
+            path: root.clone(),
+            name: app_folder.to_string(),
+            files: 0,
+            size: 0,
+            created: None,
+            loose: true,
+            archive: false,
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            let name = entry.file_name().to_string_lossy().to_string();
+            if meta.is_dir() {
+                let (files, size, newest) = dir_usage(&entry.path());
+                backups.push(BackupInfo {
+                    path: entry.path(),
+                    name: format!("{}/{}", app_folder, name),
+                    files,
+                    size,
+                    created: run_created(&name).or(newest),
+                    loose: false,
+                    archive: false,
+                });
+            } else if is_run_archive(&name) {
+                let files = File::open(long_path(&entry.path()))
+                    .ok()
+                    .and_then(|f| ZipArchive::new(f).ok())
+                    .map_or(0, |zip| zip.len());
+                backups.push(BackupInfo {
+                    path: entry.path(),
+                    name: format!("{}/{}", app_folder, name),
+                    files,
+                    size: meta.len(),
+                    created: run_created(&name),
+                    loose: false,
+                    archive: true,
+                });
+            } else {
+                loose.files += 1;
+                loose.size += meta.len();
+                loose.created = loose
+                    .created
+                    .max(meta.modified().ok().map(DateTime::<Local>::from));
+            }
+        }
+        if loose.files > 0 {
+            backups.push(loose);
+        }
+    }
+
+    backups.sort_by_key(|b| b.created);
+    backups
+}
+
+/// Backups at least `days` old
+pub fn backups_older_than(backups: &[BackupInfo], days: i64, now: DateTime<Local>) -> Vec<bool> {
+    backups
+        .iter()
+        .map(|b| b.created.is_some() && b.age_days(now) >= days)
+        .collect()
+}
+
+/// Permanently delete a backup listed by `list_backups`. For an app folder itself only
+/// the loose files are removed. Returns the bytes freed.
+pub fn purge_backup(path: &Path) -> Result<u64> {
+    if is_app_folder(path) {
+        let mut freed = 0;
+        let entries = fs::read_dir(long_path(path))
+            .with_context(|| format!("Failed to read backup folder: {:?}", path))?;
+        for entry in entries.filter_map(|e| e.ok()) {
+            let meta = entry.metadata()?;
+            if meta.is_file() && !is_run_archive(&entry.file_name().to_string_lossy()) {
+                fs::remove_file(long_path(&entry.path()))
+                    .with_context(|| format!("Failed to delete {:?}", entry.path()))?;
+                freed += meta.len();
+            }
+        }
+        return Ok(freed);
+    }
+
+    if !path.parent().is_some_and(is_app_folder) {
+        bail!("Not a backup folder: {:?}", path);
+    }
+    if path.is_file() {
+        if !path
+            .file_name()
+            .is_some_and(|n| is_run_archive(&n.to_string_lossy()))
+        {
+            bail!("Not a backup archive: {:?}", path);
+        }
+        let size = fs::metadata(long_path(path))?.len();
+        fs::remove_file(long_path(path))
+            .with_context(|| format!("Failed to delete backup: {:?}", path))?;
+        return Ok(size);
+    }
+    let (_, size, _) = dir_usage(path);
+    fs::remove_dir_all(long_path(path))
+        .with_context(|| format!("Failed to delete backup: {:?}", path))?;
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::cleaner::{delete_orphaned_mods_with_options, DeleteOptions};
+    use crate::core::parser::parse_mod_filename;
+    use crate::core::types::{OrphanedMod, RECYCLE_BIN_DIR_NAME};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_list_and_purge_backups() {
+        let dir = tempdir().unwrap();
+        let bin = dir.path().join(RECYCLE_BIN_DIR_NAME);
+        let old_run = bin.join("2024-01-01_10-00-00");
+        let new_run = bin.join("2024-03-01_10-00-00");
+        let legacy = dir.path().join("WLC_Backup").join("2023-06-01_08-30-00");
+        for run in [&old_run, &new_run, &legacy] {
+            fs::create_dir_all(run).unwrap();
+        }
+        fs::write(old_run.join("a.7z"), b"1234").unwrap();
+        fs::write(old_run.join("a.7z.meta"), b"m").unwrap();
+        fs::write(new_run.join("b.7z"), b"12").unwrap();
+        fs::write(legacy.join("c.7z"), b"123").unwrap();
+        fs::write(bin.join("flat.7z"), b"123456").unwrap();
+
+        let backups = list_backups(dir.path());
+        let names: Vec<_> = backups.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "WLC_Backup/2023-06-01_08-30-00",
+                "WLC_RecycleBin/2024-01-01_10-00-00",
+                "WLC_RecycleBin/2024-03-01_10-00-00",
+                "WLC_RecycleBin",
+            ]
+        );
+        assert_eq!((backups[1].files, backups[1].size), (2, 5));
+        assert!(backups[3].loose);
+
+        let now = Local.with_ymd_and_hms(2024, 3, 15, 12, 0, 0).unwrap();
+        assert_eq!(backups[1].age_days(now), 74);
+        let selected = backups_older_than(&backups, 30, now);
+        assert_eq!(&selected[..3], [true, true, false]);
+
+        // Purging a run folder removes it; purging the bin itself removes only loose files
+        assert_eq!(purge_backup(&old_run).unwrap(), 5);
+        assert!(!old_run.exists());
+        assert_eq!(purge_backup(&bin).unwrap(), 6);
+        assert!(!bin.join("flat.7z").exists());
+        assert!(new_run.join("b.7z").exists());
+
+        // Anything outside the backup folders is refused
+        let game = dir.path().join("Skyrim");
+        fs::create_dir(&game).unwrap();
+        assert!(purge_backup(&game).is_err());
+        assert!(game.exists());
+    }
+
+    #[test]
+    fn test_compressed_backup_round_trip() {
+        let dir = tempdir().unwrap();
+        let names = [
+            "First-11111-1-0-1600000000.7z",
+            "Second-22222-1-0-1600000000.7z",
+        ];
+        let mut orphaned = Vec::new();
+        for (game, name) in ["Skyrim", "Fallout4"].iter().zip(names) {
+            let folder = dir.path().join(game);
+            fs::create_dir(&folder).unwrap();
+            fs::write(folder.join(name), name.as_bytes()).unwrap();
+            let mut file = parse_mod_filename(name).unwrap();
+            file.full_path = folder.join(name);
+            file.size = name.len() as u64;
+            orphaned.push(OrphanedMod { file });
+        }
+        let old = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_600_000_000);
+        File::options()
+            .write(true)
+            .open(&orphaned[0].file.full_path)
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+        fs::write(
+            dir.path().join("Skyrim").join(format!("{}.meta", names[0])),
+            b"m",
+        )
+        .unwrap();
+
+        let run = dir
+            .path()
+            .join(RECYCLE_BIN_DIR_NAME)
+            .join("2024-03-01_10-00-00");
+        let options = DeleteOptions {
+            compress_backups: true,
+            ..DeleteOptions::new(vec![dir.path().to_path_buf()])
+        };
+        let result = delete_orphaned_mods_with_options(&orphaned, Some(&run), None, &options);
+        assert_eq!(result.deleted_count, 2);
+        let archive = backup_archive_path(&run);
+        assert_eq!(result.recycle_bin_path.as_ref(), Some(&archive));
+        assert!(!run.exists());
+        assert!(orphaned.iter().all(|m| !m.file.full_path.exists()));
+
+        let backups = list_backups(dir.path());
+        assert_eq!(backups.len(), 1);
+        assert!(backups[0].archive && !backups[0].loose);
+        assert_eq!(backups[0].files, 3);
+        assert_eq!(backup_root(&archive), Some(dir.path()));
+
+        // A file that came back since is not overwritten, and the archive stays
+        fs::write(&orphaned[1].file.full_path, b"new").unwrap();
+        let (restored, errors) = restore_backup_archive(&archive, dir.path()).unwrap();
+        assert_eq!((restored, errors.len()), (2, 1));
+        assert!(archive.exists());
+        assert_eq!(fs::read(&orphaned[1].file.full_path).unwrap(), b"new");
+
+        let first = &orphaned[0].file.full_path;
+        assert_eq!(fs::read(first).unwrap(), names[0].as_bytes());
+        assert!(dir
+            .path()
+            .join("Skyrim")
+            .join(format!("{}.meta", names[0]))
+            .exists());
+        let restored_time = fs::metadata(first).unwrap().modified().unwrap();
+        let diff = restored_time
+            .duration_since(old)
+            .unwrap_or_else(|e| e.duration());
+        assert!(diff.as_secs() <= 2);
+
+        // Purging the archive removes it; the bin's loose files never include it
+        assert_eq!(
+            purge_backup(&dir.path().join(RECYCLE_BIN_DIR_NAME)).unwrap(),
+            0
+        );
+        assert!(purge_backup(&archive).unwrap() > 0);
+        assert!(!archive.exists());
+    }
+
+    #[test]
+    fn test_failed_append_keeps_earlier_backups() {
+        let dir = tempdir().unwrap();
+        let game = dir.path().join("Skyrim");
+        fs::create_dir(&game).unwrap();
+        let first = game.join("First-11111-1-0-1600000000.7z");
+        let second = game.join("Second-22222-1-0-1600000000.7z");
+        fs::write(&first, b"first").unwrap();
+        fs::write(&second, b"second").unwrap();
+        let archive_path = backup_archive_path(
+            &dir.path()
+                .join(RECYCLE_BIN_DIR_NAME)
+                .join("2024-03-01_10-00-00"),
+        );
+        fs::create_dir(archive_path.parent().unwrap()).unwrap();
+        backup_into_archive(std::slice::from_ref(&first), dir.path(), &archive_path).unwrap();
+
+        // A later batch fails partway and is never finished
+        let mut archive = BackupArchive::create(&archive_path, dir.path()).unwrap();
+        archive.add(&second).unwrap();
+        assert!(archive.add(&game).is_err());
+        drop(archive);
+
+        assert!(second.exists());
+        let partial = format!("{}{}", archive_path.display(), PARTIAL_ARCHIVE_SUFFIX);
+        assert!(!Path::new(&partial).exists());
+        let report = verify_backup(&archive_path).unwrap();
+        assert!(report.is_restorable(), "{:?}", report.gaps);
+        assert_eq!(report.files, 1);
+        let (restored, errors) = restore_backup_archive(&archive_path, dir.path()).unwrap();
+        assert_eq!((restored, errors.len()), (1, 0));
+        assert_eq!(fs::read(&first).unwrap(), b"first");
+    }
+
+    #[test]
+    fn test_verify_complete_and_incomplete_backups() {
+        let dir = tempdir().unwrap();
+        let game = dir.path().join("Skyrim");
+        fs::create_dir(&game).unwrap();
+        let bin = dir.path().join(RECYCLE_BIN_DIR_NAME);
+        fs::create_dir(&bin).unwrap();
+        let files: Vec<PathBuf> = [
+            "First-11111-1-0-1600000000.7z",
+            "Second-22222-1-0-1600000000.7z",
+        ]
+        .iter()
+        .map(|name| {
+            fs::write(game.join(name), name.as_bytes()).unwrap();
+            game.join(name)
+        })
+        .collect();
+
+        let complete = backup_archive_path(&bin.join("2024-03-01_10-00-00"));
+        backup_into_archive(&files, dir.path(), &complete).unwrap();
+        let report = verify_backup(&complete).unwrap();
+        assert!(report.is_restorable(), "{:?}", report.gaps);
+        assert_eq!(report.files, 2);
+        assert_eq!(report.size, 59);
+        // Verifying leaves the backup and the downloads folder alone
+        assert!(complete.exists());
+        assert!(files.iter().all(|f| !f.exists()));
+
+        // An archive with an empty entry, a file that is back, and an unsafe name
+        let incomplete = backup_archive_path(&bin.join("2024-04-01_10-00-00"));
+        fs::write(&files[0], b"redownloaded").unwrap();
+        fs::write(game.join("Empty-33333-1-0-1600000000.7z"), b"").unwrap();
+        let mut zip = ZipWriter::new(File::create(&incomplete).unwrap());
+        for (name, data) in [
+            ("Skyrim/First-11111-1-0-1600000000.7z", &b"first"[..]),
+            ("Skyrim/Empty-33333-1-0-1600000000.7z", b""),
+            ("../Escaped-44444-1-0-1600000000.7z", b"escaped"),
+        ] {
+            zip.start_file(name, SimpleFileOptions::default()).unwrap();
+            io::Write::write_all(&mut zip, data).unwrap();
+        }
+        zip.finish().unwrap();
+        let report = verify_backup(&incomplete).unwrap();
+        assert!(!report.is_restorable());
+        assert_eq!(report.files, 3);
+        assert_eq!(
+            report.gaps,
+            [
+                (
+                    "Skyrim/First-11111-1-0-1600000000.7z".to_string(),
+                    BackupGap::DestinationTaken(
+                        dir.path()
+                            .join("Skyrim")
+                            .join("First-11111-1-0-1600000000.7z")
+                    )
+                ),
+                (
+                    "Skyrim/Empty-33333-1-0-1600000000.7z".to_string(),
+                    BackupGap::Empty
+                ),
+                (
+                    "../Escaped-44444-1-0-1600000000.7z".to_string(),
+                    BackupGap::NoDestination
+                ),
+            ]
+        );
+
+        // Backup folders keep no destinations
+        let run = bin.join("2024-05-01_10-00-00");
+        fs::create_dir(&run).unwrap();
+        fs::write(run.join("Loose-55555-1-0-1600000000.7z"), b"loose").unwrap();
+        let report = verify_backup(&run).unwrap();
+        assert_eq!((report.files, report.size), (1, 5));
+        assert_eq!(report.gaps[0].1, BackupGap::NoDestination);
+        // The bin itself holds loose files only; its runs are checked on their own
+        fs::write(bin.join("Flat-66666-1-0-1600000000.7z"), b"flat").unwrap();
+        assert_eq!(verify_backup(&bin).unwrap().files, 1);
+    }
+}
@@ -0,0 +1,245 @@
+// Copyright (C) 2025 Berkay Yetgin
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::core::config::config_file;
+use crate::core::scanner::{scan_mod_files_in_folder, unix_secs};
+use crate::core::types::ModFile;
+
+const SCAN_CACHE_FILE: &str = "scan_cache.json";
+
+/// Cached scan of a single game folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FolderCacheEntry {
+    /// Directory mtime (seconds since epoch) when the folder was scanned
+    pub dir_mtime: u64,
+    /// Number of directory entries (files and folders) when scanned
+    pub entry_count: usize,
+    /// Total size of the cached mod files
+    #[serde(default)]
+    pub total_size: u64,
+    /// Newest mtime (seconds since epoch) among the cached mod files
+    #[serde(default)]
+    pub newest_mtime: u64,
+    pub files: Vec<ModFile>,
+}
+
+/// Scan results keyed by folder path, reused while a folder is unchanged
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScanCache {
+    pub folders: HashMap<PathBuf, FolderCacheEntry>,
+}
+
+impl ScanCache {
+    /// Load a cache file, falling back to an empty cache if missing or unreadable
+    pub fn load(path: &Path) -> Self {
+        let content = match fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(_) => return Self::default(),
+        };
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::warn!("Ignoring invalid scan cache {:?}: {}", path, e);
+            Self::default()
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {:?}", parent))?;
+        }
+        let json = serde_json::to_string(self).context("Failed to serialize scan cache")?;
+        fs::write(path, json).with_context(|| format!("Failed to write scan cache: {:?}", path))
+    }
+}
+
+/// Default scan cache location in the config directory
+pub fn scan_cache_path() -> Option<PathBuf> {
+    config_file(SCAN_CACHE_FILE)
+}
+
+/// Cheap folder fingerprint: directory mtime and entry count, without per-file metadata
+fn folder_fingerprint(folder: &Path) -> Option<(u64, usize)> {
+    let dir_mtime = unix_secs(fs::metadata(folder).ok()?.modified());
+    let entry_count = fs::read_dir(folder).ok()?.count();
+    Some((dir_mtime, entry_count))
+}
+
+/// Total size and newest mtime of `files` as they are on disk now; `None` if one is gone.
+/// A download overwritten in place under the same name leaves the folder fingerprint
+/// alone but shows up here.
+fn files_on_disk(files: &[ModFile]) -> Option<(u64, u64)> {
+    files.iter().try_fold((0, 0), |(size, newest), f| {
+        let meta = fs::metadata(&f.full_path).ok()?;
+        Some((size + meta.len(), newest.max(unix_secs(meta.modified()))))
+    })
+}
+
+/// Collect mod files, reusing cached results for folders whose mtime and entry count are
+/// unchanged and whose cached files still have the same total size and newest mtime
+pub fn get_all_mod_files_cached(
+    game_folders: &[PathBuf],
+    cache: &mut ScanCache,
+) -> Result<Vec<ModFile>> {
+    // (folder, cache hit, refreshed entry, files)
+    let scanned: Vec<(PathBuf, bool, Option<FolderCacheEntry>, Vec<ModFile>)> = game_folders
+        .par_iter()
+        .map(|folder| {
+            let fingerprint = folder_fingerprint(folder);
+
+            if let (Some((dir_mtime, entry_count)), Some(entry)) =
+                (fingerprint, cache.folders.get(folder))
+            {
+                if entry.dir_mtime == dir_mtime
+                    && entry.entry_count == entry_count
+                    && files_on_disk(&entry.files) == Some((entry.total_size, entry.newest_mtime))
+                {
+                    log::debug!("Using cached scan for {:?}", folder);
+                    return (folder.clone(), true, None, entry.files.clone());
+                }
+            }
+
+            let files = scan_mod_files_in_folder(folder);
+            let entry = fingerprint.map(|(dir_mtime, entry_count)| FolderCacheEntry {
+                dir_mtime,
+                entry_count,
+                total_size: files.iter().map(|f| f.size).sum(),
+                newest_mtime: files.iter().map(|f| f.mtime).max().unwrap_or(0),
+                files: files.clone(),
+            });
+            (folder.clone(), false, entry, files)
+        })
+        .collect();
+
+    let mut all_files = Vec::new();
+    for (folder, hit, entry, files) in scanned {
+        if !hit {
+            match entry {
+                Some(entry) => {
+                    cache.folders.insert(folder, entry);
+                }
+                None => {
+                    cache.folders.remove(&folder);
+                }
+            }
+        }
+        all_files.extend(files);
+    }
+
+    Ok(all_files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    fn write_file(path: &Path, content: &[u8]) {
+        let mut file = File::create(path).unwrap();
+        file.write_all(content).unwrap();
+    }
+
+    #[test]
+    fn test_cache_reused_when_unchanged() {
+        let dir = tempdir().unwrap();
+        let game_dir = dir.path().join("Skyrim");
+        fs::create_dir(&game_dir).unwrap();
+        write_file(&game_dir.join("SkyUI-12345-5-0-1234567890.7z"), b"test");
+
+        let mut cache = ScanCache::default();
+        let files = get_all_mod_files_cached(std::slice::from_ref(&game_dir), &mut cache).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(cache.folders[&game_dir].total_size, 4);
+
+        // Tamper with the cached entry: an unchanged folder must return it as-is
+        cache.folders.get_mut(&game_dir).unwrap().files[0].mod_name = "Cached".to_string();
+        let files = get_all_mod_files_cached(std::slice::from_ref(&game_dir), &mut cache).unwrap();
+        assert_eq!(files[0].mod_name, "Cached");
+    }
+
+    #[test]
+    fn test_cache_invalidated_after_file_deleted() {
+        let dir = tempdir().unwrap();
+        let game_dir = dir.path().join("Skyrim");
+        fs::create_dir(&game_dir).unwrap();
+        let file1 = game_dir.join("SkyUI-12345-5-0-1234567890.7z");
+        write_file(&file1, b"test");
+        write_file(&game_dir.join("SKSE-54321-1-0-9876543210.zip"), b"test 2");
+
+        let mut cache = ScanCache::default();
+        let files = get_all_mod_files_cached(std::slice::from_ref(&game_dir), &mut cache).unwrap();
+        assert_eq!(files.len(), 2);
+
+        fs::remove_file(&file1).unwrap();
+
+        let files = get_all_mod_files_cached(std::slice::from_ref(&game_dir), &mut cache).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(cache.folders[&game_dir].files.len(), 1);
+        assert_eq!(cache.folders[&game_dir].total_size, 6);
+    }
+
+    #[test]
+    fn test_cache_refreshed_after_in_place_overwrite() {
+        let dir = tempdir().unwrap();
+        let game_dir = dir.path().join("Skyrim");
+        fs::create_dir(&game_dir).unwrap();
+        let path = game_dir.join("SkyUI-12345-5-0-1234567890.7z");
+        write_file(&path, b"test");
+        let old = std::time::SystemTime::now() - std::time::Duration::from_secs(30 * 86_400);
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+
+        let mut cache = ScanCache::default();
+        get_all_mod_files_cached(std::slice::from_ref(&game_dir), &mut cache).unwrap();
+
+        // Re-downloaded under the same name: same size and entries, fresh mtime
+        write_file(&path, b"TEST");
+        let files = get_all_mod_files_cached(std::slice::from_ref(&game_dir), &mut cache).unwrap();
+        assert!(files[0].mtime > unix_secs(Ok(old)));
+        assert_eq!(cache.folders[&game_dir].newest_mtime, files[0].mtime);
+
+        // A different size is caught as well
+        write_file(&path, b"longer content");
+        let files = get_all_mod_files_cached(std::slice::from_ref(&game_dir), &mut cache).unwrap();
+        assert_eq!(files[0].size, 14);
+        assert_eq!(cache.folders[&game_dir].total_size, 14);
+    }
+
+    #[test]
+    fn test_cache_save_and_load() {
+        let dir = tempdir().unwrap();
+        let game_dir = dir.path().join("Skyrim");
+        fs::create_dir(&game_dir).unwrap();
+        write_file(&game_dir.join("SkyUI-12345-5-0-1234567890.7z"), b"test");
+
+        let mut cache = ScanCache::default();
+        get_all_mod_files_cached(std::slice::from_ref(&game_dir), &mut cache).unwrap();
+
+        let cache_file = dir.path().join("config").join(SCAN_CACHE_FILE);
+        cache.save(&cache_file).unwrap();
+        let loaded = ScanCache::load(&cache_file);
+        assert_eq!(loaded.folders[&game_dir].files.len(), 1);
+
+        // Missing file loads as empty cache
+        assert!(ScanCache::load(&dir.path().join("missing.json"))
+            .folders
+            .is_empty());
+    }
+}
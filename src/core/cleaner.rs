@@ -5,10 +5,394 @@
 // the Free Software Foundation, either version 3 of the License, or
 // (at your option) any later version.
 
-use std::fs;
-use std::path::Path;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
 
-use crate::core::types::{DeletionResult, ModFile, ModGroup, OrphanedMod};
+use crate::core::backup::{backup_archive_path, backup_root, BackupArchive};
+use crate::core::journal::{complete_deletion_entry, finish_deletion_journal, pending_deletion};
+use crate::core::platform::{file_lock_holders, long_path};
+use crate::core::types::{
+    CancelToken, DeletionResult, ModFile, ModGroup, OrphanedMod, RECYCLE_BIN_DIR_NAME,
+};
+
+/// Longest single sleep while throttled, so cancelling stays responsive
+const THROTTLE_SLICE: Duration = Duration::from_millis(50);
+
+/// Bytes copied per chunk when a move falls back to copy and delete
+const COPY_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+/// `EXDEV` on Linux and macOS, `ERROR_NOT_SAME_DEVICE` on Windows
+#[cfg(windows)]
+const CROSS_DEVICE_ERROR: i32 = 17;
+#[cfg(not(windows))]
+const CROSS_DEVICE_ERROR: i32 = 18;
+
+/// Called with `(file_name, bytes_copied, total_bytes)` while a file is copied
+type CopyProgressFn = dyn Fn(&str, u64, u64) + Send + Sync;
+
+/// Byte progress callback for copies, shareable with the cleanup thread
+#[derive(Clone)]
+pub struct CopyProgress(pub Arc<CopyProgressFn>);
+
+impl fmt::Debug for CopyProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CopyProgress")
+    }
+}
+
+/// Pacing and cancellation for a cleanup run
+#[derive(Debug, Clone)]
+pub struct DeleteOptions {
+    /// Pause between files; `None` runs unthrottled
+    pub throttle: Option<Duration>,
+    /// Checked before every file and while pausing
+    pub cancel: CancelToken,
+    /// Leave `.meta` sidecars in place when their archive is removed
+    pub keep_meta: bool,
+    /// Always copy into the recycle bin and delete the original instead of renaming.
+    /// Renames that cross drives fall back to this anyway.
+    pub copy_delete: bool,
+    /// Byte progress while a file is copied
+    pub copy_progress: Option<CopyProgress>,
+    /// Deletion journal to mark each removed file in, for resuming after a crash
+    pub journal: Option<PathBuf>,
+    /// Store files going to the recycle bin in one `.zip` per run instead of moving them
+    pub compress_backups: bool,
+    /// Download roots the files were scanned from; files elsewhere are refused, and so is
+    /// every file when this is empty
+    pub scanned_roots: Vec<PathBuf>,
+}
+
+impl DeleteOptions {
+    /// Unthrottled options that only touch files under `scanned_roots`
+    pub fn new(scanned_roots: Vec<PathBuf>) -> Self {
+        Self {
+            throttle: None,
+            cancel: CancelToken::default(),
+            keep_meta: false,
+            copy_delete: false,
+            copy_progress: None,
+            journal: None,
+            compress_backups: false,
+            scanned_roots,
+        }
+    }
+
+    /// Options with a pause of `millis` between files (0 = unthrottled)
+    pub fn with_throttle_ms(millis: u64, cancel: CancelToken, scanned_roots: Vec<PathBuf>) -> Self {
+        Self {
+            throttle: (millis > 0).then(|| Duration::from_millis(millis)),
+            cancel,
+            ..Self::new(scanned_roots)
+        }
+    }
+
+    /// Where a run's backups end up: the recycle bin folder, or its archive when compressed
+    fn backup_target(&self, recycle_bin: &Path) -> PathBuf {
+        if self.compress_backups {
+            backup_archive_path(recycle_bin)
+        } else {
+            recycle_bin.to_path_buf()
+        }
+    }
+
+    /// Sleep for the throttle interval; returns false if the run was cancelled meanwhile
+    fn pause(&self) -> bool {
+        let Some(throttle) = self.throttle else {
+            return !self.cancel.is_cancelled();
+        };
+        let deadline = Instant::now() + throttle;
+        loop {
+            if self.cancel.is_cancelled() {
+                return false;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return true;
+            }
+            thread::sleep((deadline - now).min(THROTTLE_SLICE));
+        }
+    }
+}
+
+/// Recycle bin folder for a cleanup run, or `None` to delete permanently.
+/// Safe mode always returns a folder; `flat` uses `WLC_RecycleBin` itself instead of a
+/// timestamped subfolder per run.
+pub fn recycle_bin_for_run(
+    downloads_dir: &Path,
+    move_to_recycle_bin: bool,
+    safe_mode: bool,
+    flat: bool,
+) -> Option<PathBuf> {
+    if !move_to_recycle_bin && !safe_mode {
+        return None;
+    }
+    let recycle_bin = downloads_dir.join(RECYCLE_BIN_DIR_NAME);
+    if flat {
+        return Some(recycle_bin);
+    }
+    let ts = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S");
+    Some(recycle_bin.join(ts.to_string()))
+}
+
+/// Path of the `.meta` file that sits next to an archive
+fn meta_path_for(path: &Path) -> PathBuf {
+    PathBuf::from(format!("{}.meta", path.display()))
+}
+
+/// First free `name (n).ext` in `dir`
+fn unique_recycle_path(dir: &Path, file_name: &str) -> PathBuf {
+    let (stem, ext) = match file_name.rfind('.') {
+        Some(i) if i > 0 => file_name.split_at(i),
+        _ => (file_name, ""),
+    };
+    (1..)
+        .map(|n| dir.join(format!("{} ({}){}", stem, n, ext)))
+        .find(|p| !p.exists())
+        .unwrap()
+}
+
+fn modified_time(path: &Path) -> Option<std::time::SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Whether a rename failed only because source and destination are on different drives
+fn is_cross_device(e: &io::Error) -> bool {
+    e.kind() == io::ErrorKind::CrossesDevices || e.raw_os_error() == Some(CROSS_DEVICE_ERROR)
+}
+
+/// Copy `src` to `dest` in chunks, then delete `src`. The copy keeps the modified time
+/// and is flushed to disk before the original goes; a failed copy is removed again.
+fn copy_then_delete(src: &Path, dest: &Path, options: &DeleteOptions) -> io::Result<()> {
+    let name = src
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut reader = File::open(src)?;
+    let meta = reader.metadata()?;
+    let total = meta.len();
+
+    let mut copy = || -> io::Result<()> {
+        let mut writer = File::create(dest)?;
+        let mut buf = vec![0u8; COPY_CHUNK_SIZE];
+        let mut done = 0u64;
+        loop {
+            let read = reader.read(&mut buf)?;
+            if read == 0 {
+                break;
+            }
+            writer.write_all(&buf[..read])?;
+            done += read as u64;
+            if let Some(progress) = &options.copy_progress {
+                (progress.0)(&name, done, total.max(done));
+            }
+        }
+        if let Ok(modified) = meta.modified() {
+            writer.set_modified(modified)?;
+        }
+        writer.sync_all()
+    };
+    if let Err(e) = copy() {
+        let _ = fs::remove_file(dest);
+        return Err(e);
+    }
+    fs::remove_file(src)
+}
+
+/// Rename `src` to `dest`, or copy and delete when forced or when the rename crosses drives
+fn move_file(src: &Path, dest: &Path, options: &DeleteOptions) -> io::Result<()> {
+    move_file_with(src, dest, options, |from, to| fs::rename(from, to))
+}
+
+fn move_file_with(
+    src: &Path,
+    dest: &Path,
+    options: &DeleteOptions,
+    rename: impl Fn(&Path, &Path) -> io::Result<()>,
+) -> io::Result<()> {
+    if !options.copy_delete {
+        match rename(src, dest) {
+            Err(e) if is_cross_device(&e) => {
+                log::info!(
+                    "Recycle bin is on another drive, copying instead: {:?}",
+                    src
+                )
+            }
+            other => return other,
+        }
+    }
+    copy_then_delete(src, dest, options)
+}
+
+/// Move a file (and its `.meta`) into the recycle bin. On a name collision the newer
+/// file keeps the plain name and the older one gets a ` (n)` suffix.
+fn move_to_recycle_bin(
+    path: &Path,
+    recycle_bin: &Path,
+    file_name: &str,
+    options: &DeleteOptions,
+) -> Result<(), String> {
+    let mut dest_path = long_path(&recycle_bin.join(file_name)).into_owned();
+
+    if dest_path.exists() {
+        let incoming_is_newer = modified_time(path) > modified_time(&dest_path);
+        let renamed = long_path(&unique_recycle_path(recycle_bin, file_name)).into_owned();
+        if incoming_is_newer {
+            fs::rename(&dest_path, &renamed)
+                .map_err(|e| format!("Failed to rename older recycled file: {}", e))?;
+            let _ = fs::rename(meta_path_for(&dest_path), meta_path_for(&renamed));
+        } else {
+            dest_path = renamed;
+        }
+    }
+
+    move_file(path, &dest_path, options).map_err(|e| format!("Failed to move file: {}", e))?;
+
+    // Also move .meta file if exists
+    let meta_path = meta_path_for(path);
+    if !options.keep_meta && meta_path.exists() {
+        let _ = move_file(&meta_path, &meta_path_for(&dest_path), options);
+    }
+    Ok(())
+}
+
+/// The backup archive of a run with compressed backups. Files added to it stay in place
+/// until the archive is written, then they are removed together.
+struct RunArchive {
+    recycle_bin: PathBuf,
+    archive: BackupArchive,
+    files: Vec<ModFile>,
+}
+
+/// Add a file (and its `.meta`) to the run's backup archive, keeping its path below the
+/// downloads folder so it can be restored. An archive open for another recycle bin is
+/// finished first.
+fn add_to_run_archive(
+    file: &ModFile,
+    recycle_bin: &Path,
+    options: &DeleteOptions,
+    run_archive: &mut Option<RunArchive>,
+    result: &mut DeletionResult,
+) -> Result<(), String> {
+    check_in_scope(&file.full_path, options)?;
+    check_can_remove(&long_path(&file.full_path))?;
+
+    if run_archive
+        .as_ref()
+        .is_some_and(|open| open.recycle_bin != recycle_bin)
+    {
+        finish_run_archive(run_archive.take(), options, result);
+    }
+    let open = match run_archive {
+        Some(open) => open,
+        None => {
+            let root = backup_root(recycle_bin)
+                .ok_or_else(|| format!("Not a recycle bin folder: {:?}", recycle_bin))?;
+            let archive = BackupArchive::create(&backup_archive_path(recycle_bin), root)
+                .map_err(|e| format!("Failed to archive file: {:#}", e))?;
+            run_archive.insert(RunArchive {
+                recycle_bin: recycle_bin.to_path_buf(),
+                archive,
+                files: Vec::new(),
+            })
+        }
+    };
+
+    open.archive
+        .add(&file.full_path)
+        .map_err(|e| format!("Failed to archive file: {:#}", e))?;
+    let meta_path = meta_path_for(&file.full_path);
+    if !options.keep_meta && long_path(&meta_path).exists() {
+        if let Err(e) = open.archive.add(&meta_path) {
+            log::warn!("Failed to archive {:?}: {:#}", meta_path, e);
+        }
+    }
+    open.files.push(file.clone());
+    Ok(())
+}
+
+/// Write the run's backup archive, then remove the files it holds. If the archive cannot
+/// be written the files are left in place and reported as skipped.
+fn finish_run_archive(
+    run_archive: Option<RunArchive>,
+    options: &DeleteOptions,
+    result: &mut DeletionResult,
+) {
+    let Some(RunArchive { archive, files, .. }) = run_archive else {
+        return;
+    };
+    let archived = archive.added().to_vec();
+    if let Err(e) = archive.finish() {
+        for file in &files {
+            record_in_journal(options, &file.full_path);
+            result.skipped.push(file.file_name.clone());
+        }
+        result
+            .errors
+            .push(format!("Failed to archive file: {:#}", e));
+        return;
+    }
+
+    for file in &files {
+        match fs::remove_file(long_path(&file.full_path)) {
+            Ok(()) => {
+                let meta_path = meta_path_for(&file.full_path);
+                if archived.contains(&meta_path) {
+                    let _ = fs::remove_file(long_path(&meta_path));
+                }
+                log::info!(
+                    "Moved to Recycle Bin: {} ({})",
+                    file.file_name,
+                    format_size(file.size)
+                );
+                result.deleted_count += 1;
+                result.space_freed += file.size;
+            }
+            Err(e) => {
+                result.skipped.push(file.file_name.clone());
+                result
+                    .errors
+                    .push(format!("Archived but failed to delete file: {}", e));
+            }
+        }
+        record_in_journal(options, &file.full_path);
+    }
+}
+
+/// Delete a file, or with compressed backups add it to the run's archive. Returns the
+/// bytes freed, or `None` when the file waits for `finish_run_archive`.
+fn delete_or_archive(
+    file: &ModFile,
+    recycle_bin_dir: Option<&Path>,
+    options: &DeleteOptions,
+    run_archive: &mut Option<RunArchive>,
+    result: &mut DeletionResult,
+) -> Result<Option<u64>, String> {
+    match recycle_bin_dir.filter(|_| options.compress_backups) {
+        Some(recycle_bin) => add_to_run_archive(file, recycle_bin, options, run_archive, result)
+            .inspect_err(|_| record_in_journal(options, &file.full_path))
+            .map(|()| None),
+        None => delete_mod_file(file, recycle_bin_dir, options).map(Some),
+    }
+}
+
+/// Create the folder a run's backups go into
+fn prepare_recycle_bin(recycle_bin: &Path, options: &DeleteOptions) -> io::Result<()> {
+    let dir = if options.compress_backups {
+        backup_archive_path(recycle_bin)
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+    } else {
+        recycle_bin.to_path_buf()
+    };
+    fs::create_dir_all(long_path(&dir))
+}
 
 /// Check if a file is locked (being used by another process)
 pub fn is_file_locked(path: &Path) -> bool {
@@ -20,31 +404,91 @@ pub fn is_file_locked(path: &Path) -> bool {
         .is_err()
 }
 
-/// Delete a single mod file and its associated .meta file
-fn delete_mod_file(file: &ModFile, recycle_bin_dir: Option<&Path>) -> Result<u64, String> {
-    let path = &file.full_path;
-
+/// Check that a file still exists and no other process holds it
+fn check_can_remove(path: &Path) -> Result<(), String> {
     if !path.exists() {
         return Err(format!("File no longer exists: {:?}", path));
     }
 
+    // Shared-read handles don't block the write-open check, so also ask the OS who holds it
+    let holders = file_lock_holders(path);
+    if !holders.is_empty() {
+        return Err(format!(
+            "File is in use: {:?} (held by {})",
+            path,
+            holders.join(", ")
+        ));
+    }
+
     if is_file_locked(path) {
         return Err(format!("File is locked: {:?}", path));
     }
+    Ok(())
+}
 
-    if let Some(recycle_bin) = recycle_bin_dir {
-        // Move to recycle bin folder
-        let dest_path = recycle_bin.join(&file.file_name);
-        fs::rename(path, &dest_path).map_err(|e| format!("Failed to move file: {}", e))?;
+/// Whether `path` lies inside one of `roots`, comparing canonical paths. The file itself is
+/// not resolved, so a link inside a root still counts as inside.
+pub fn is_within_scanned_roots(path: &Path, roots: &[PathBuf]) -> bool {
+    let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+        return false;
+    };
+    let Ok(parent) = fs::canonicalize(long_path(parent)) else {
+        return false;
+    };
+    let path = parent.join(name);
+    roots.iter().any(|root| {
+        fs::canonicalize(long_path(root)).is_ok_and(|root| path.starts_with(&root) && path != root)
+    })
+}
 
-        // Also move .meta file if exists
-        let meta_full = format!("{}.meta", path.display());
-        let meta_path = Path::new(&meta_full);
+/// Refuse files outside the scanned download roots, in case a bug ever hands over a path
+/// from elsewhere on disk
+fn check_in_scope(path: &Path, options: &DeleteOptions) -> Result<(), String> {
+    if is_within_scanned_roots(path, &options.scanned_roots) {
+        return Ok(());
+    }
+    log::error!(
+        "Refusing to touch {:?}: it is outside the scanned download folders {:?}",
+        path,
+        options.scanned_roots
+    );
+    Err(format!("Outside the scanned download folders: {:?}", path))
+}
 
-        if meta_path.exists() {
-            let dest_meta = recycle_bin.join(format!("{}.meta", file.file_name));
-            let _ = fs::rename(meta_path, dest_meta);
+/// Mark a file as handled in the run's journal, whether it was removed or skipped, so a
+/// resumed run only touches files the crashed one never reached
+fn record_in_journal(options: &DeleteOptions, path: &Path) {
+    if let Some(journal) = &options.journal {
+        if let Err(e) = complete_deletion_entry(journal, path) {
+            log::warn!("{:#}", e);
         }
+    }
+}
+
+/// Delete a single mod file and, unless `keep_meta` is set, its associated .meta file
+fn delete_mod_file(
+    file: &ModFile,
+    recycle_bin_dir: Option<&Path>,
+    options: &DeleteOptions,
+) -> Result<u64, String> {
+    let result = remove_mod_file(file, recycle_bin_dir, options);
+    record_in_journal(options, &file.full_path);
+    result
+}
+
+fn remove_mod_file(
+    file: &ModFile,
+    recycle_bin_dir: Option<&Path>,
+    options: &DeleteOptions,
+) -> Result<u64, String> {
+    check_in_scope(&file.full_path, options)?;
+    let path = long_path(&file.full_path);
+    let path = path.as_ref();
+
+    check_can_remove(path)?;
+
+    if let Some(recycle_bin) = recycle_bin_dir {
+        move_to_recycle_bin(path, recycle_bin, &file.file_name, options)?;
 
         log::info!(
             "Moved to Recycle Bin: {} ({})",
@@ -56,9 +500,8 @@ fn delete_mod_file(file: &ModFile, recycle_bin_dir: Option<&Path>) -> Result<u64
         fs::remove_file(path).map_err(|e| format!("Failed to delete file: {}", e))?;
 
         // Also delete .meta file if exists
-        let meta_full = format!("{}.meta", path.display());
-        let meta_path = Path::new(&meta_full);
-        if meta_path.exists() {
+        let meta_path = meta_path_for(path);
+        if !options.keep_meta && meta_path.exists() {
             let _ = fs::remove_file(meta_path);
         }
 
@@ -68,33 +511,227 @@ fn delete_mod_file(file: &ModFile, recycle_bin_dir: Option<&Path>) -> Result<u64
     Ok(file.size)
 }
 
-/// Delete orphaned mods
+/// Finish a cleanup run left in `journal` by a crash or a closed app. Files that are
+/// already gone count as done. The journal is removed afterwards, even if cancelled.
+pub fn resume_pending_deletion(
+    journal: &Path,
+    progress_callback: Option<&dyn Fn(usize, usize)>,
+    options: &DeleteOptions,
+) -> DeletionResult {
+    let mut result = DeletionResult::default();
+    let Some(pending) = pending_deletion(journal) else {
+        return result;
+    };
+    let options = DeleteOptions {
+        journal: Some(journal.to_path_buf()),
+        ..options.clone()
+    };
+    let total = pending.pending.len();
+    log::info!(
+        "Resuming cleanup started {}: {} files left",
+        pending.started,
+        total
+    );
+
+    let mut run_archive = None;
+    for (i, entry) in pending.pending.iter().enumerate() {
+        if (i > 0 && !options.pause()) || options.cancel.is_cancelled() {
+            result.cancelled = true;
+            break;
+        }
+        if let Some(cb) = progress_callback {
+            cb(i + 1, total);
+        }
+
+        if !long_path(&entry.file.full_path).exists() {
+            log::info!("Already removed: {}", entry.file.file_name);
+            continue;
+        }
+        if let Some(recycle_bin) = &entry.recycle_bin {
+            if let Err(e) = prepare_recycle_bin(recycle_bin, &options) {
+                result.skipped.push(entry.file.file_name.clone());
+                result
+                    .errors
+                    .push(format!("Failed to create Recycle Bin folder: {}", e));
+                continue;
+            }
+            result.recycle_bin_path = Some(options.backup_target(recycle_bin));
+        }
+        match delete_or_archive(
+            &entry.file,
+            entry.recycle_bin.as_deref(),
+            &options,
+            &mut run_archive,
+            &mut result,
+        ) {
+            Ok(None) => {}
+            Ok(Some(size)) => {
+                result.deleted_count += 1;
+                result.space_freed += size;
+            }
+            Err(e) => {
+                result.skipped.push(entry.file.file_name.clone());
+                result.errors.push(e);
+            }
+        }
+    }
+    finish_run_archive(run_archive, &options, &mut result);
+
+    finish_deletion_journal(journal);
+    result
+}
+
+/// Delete orphaned mods found under `scanned_roots`
 pub fn delete_orphaned_mods(
     orphaned_mods: &[OrphanedMod],
     recycle_bin_dir: Option<&Path>,
+    scanned_roots: &[PathBuf],
     progress_callback: Option<&dyn Fn(usize, usize)>,
+) -> DeletionResult {
+    delete_orphaned_mods_with_options(
+        orphaned_mods,
+        recycle_bin_dir,
+        progress_callback,
+        &DeleteOptions::new(scanned_roots.to_vec()),
+    )
+}
+
+/// Delete orphaned mods, pausing between files and stopping when cancelled
+pub fn delete_orphaned_mods_with_options(
+    orphaned_mods: &[OrphanedMod],
+    recycle_bin_dir: Option<&Path>,
+    progress_callback: Option<&dyn Fn(usize, usize)>,
+    options: &DeleteOptions,
 ) -> DeletionResult {
     let mut result = DeletionResult::default();
     let total = orphaned_mods.len();
 
     // Create recycle bin directory if specified
     if let Some(recycle_bin) = recycle_bin_dir {
-        if let Err(e) = fs::create_dir_all(recycle_bin) {
+        if let Err(e) = prepare_recycle_bin(recycle_bin, options) {
             result
                 .errors
                 .push(format!("Failed to create Recycle Bin folder: {}", e));
             return result;
         }
-        result.recycle_bin_path = Some(recycle_bin.to_path_buf());
+        result.recycle_bin_path = Some(options.backup_target(recycle_bin));
         log::info!("Created Recycle Bin folder: {:?}", recycle_bin);
     }
 
+    let mut run_archive = None;
     for (i, orphaned) in orphaned_mods.iter().enumerate() {
+        if (i > 0 && !options.pause()) || options.cancel.is_cancelled() {
+            result.cancelled = true;
+            break;
+        }
         if let Some(cb) = progress_callback {
             cb(i + 1, total);
         }
 
-        match delete_mod_file(&orphaned.file, recycle_bin_dir) {
+        match delete_or_archive(
+            &orphaned.file,
+            recycle_bin_dir,
+            options,
+            &mut run_archive,
+            &mut result,
+        ) {
+            Ok(None) => {}
+            Ok(Some(size)) => {
+                result.deleted_count += 1;
+                result.space_freed += size;
+            }
+            Err(e) => {
+                result.skipped.push(orphaned.file.file_name.clone());
+                result.errors.push(e);
+            }
+        }
+    }
+    finish_run_archive(run_archive, options, &mut result);
+
+    result
+}
+
+/// Where a file lands when relocated under `dest_root`: its game folder is kept, so
+/// `SkyrimSE/Mod.7z` becomes `dest_root/SkyrimSE/Mod.7z`
+pub fn relocated_path(file: &ModFile, dest_root: &Path) -> PathBuf {
+    match file.full_path.parent().and_then(|p| p.file_name()) {
+        Some(game) => dest_root.join(game).join(&file.file_name),
+        None => dest_root.join(&file.file_name),
+    }
+}
+
+/// Move one mod file and its .meta file under `dest_root`, never overwriting
+fn relocate_mod_file(
+    file: &ModFile,
+    dest_root: &Path,
+    options: &DeleteOptions,
+) -> Result<u64, String> {
+    check_in_scope(&file.full_path, options)?;
+    let path = long_path(&file.full_path);
+    let path = path.as_ref();
+    check_can_remove(path)?;
+
+    let dest = relocated_path(file, dest_root);
+    let dest = long_path(&dest);
+    if dest.exists() {
+        return Err(format!("Already exists at destination: {:?}", dest));
+    }
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create folder {:?}: {}", parent, e))?;
+    }
+    move_file(path, &dest, options).map_err(|e| format!("Failed to move file: {}", e))?;
+
+    // The .meta file lets Wabbajack and MO2 use the relocated archive as it is
+    let meta_path = meta_path_for(path);
+    if meta_path.exists() {
+        let _ = move_file(&meta_path, &meta_path_for(&dest), options);
+    }
+
+    log::info!(
+        "Relocated: {} ({}) to {:?}",
+        file.file_name,
+        format_size(file.size),
+        dest
+    );
+    Ok(file.size)
+}
+
+/// Move orphaned mods found under `scanned_roots` to another library under `dest_root`,
+/// mirroring their game folders instead of deleting them
+pub fn relocate_orphaned_mods(
+    orphaned_mods: &[OrphanedMod],
+    dest_root: &Path,
+    scanned_roots: &[PathBuf],
+) -> DeletionResult {
+    relocate_orphaned_mods_with_options(
+        orphaned_mods,
+        dest_root,
+        None,
+        &DeleteOptions::new(scanned_roots.to_vec()),
+    )
+}
+
+/// Relocate orphaned mods, pausing between files and stopping when cancelled
+pub fn relocate_orphaned_mods_with_options(
+    orphaned_mods: &[OrphanedMod],
+    dest_root: &Path,
+    progress_callback: Option<&dyn Fn(usize, usize)>,
+    options: &DeleteOptions,
+) -> DeletionResult {
+    let mut result = DeletionResult::default();
+    let total = orphaned_mods.len();
+
+    for (i, orphaned) in orphaned_mods.iter().enumerate() {
+        if (i > 0 && !options.pause()) || options.cancel.is_cancelled() {
+            result.cancelled = true;
+            break;
+        }
+        if let Some(cb) = progress_callback {
+            cb(i + 1, total);
+        }
+
+        match relocate_mod_file(&orphaned.file, dest_root, options) {
             Ok(size) => {
                 result.deleted_count += 1;
                 result.space_freed += size;
@@ -109,41 +746,81 @@ pub fn delete_orphaned_mods(
     result
 }
 
-/// Delete old versions from mod groups
+/// Delete old versions from mod groups found under `scanned_roots`
 pub fn delete_old_versions(
+    duplicates: &[ModGroup],
+    recycle_bin_dir: Option<&Path>,
+    scanned_roots: &[PathBuf],
+    progress_callback: Option<&dyn Fn(usize, usize)>,
+) -> DeletionResult {
+    delete_old_versions_with_options(
+        duplicates,
+        recycle_bin_dir,
+        progress_callback,
+        &DeleteOptions::new(scanned_roots.to_vec()),
+    )
+}
+
+/// Delete old versions, pausing between files and stopping when cancelled
+pub fn delete_old_versions_with_options(
     duplicates: &[ModGroup],
     recycle_bin_dir: Option<&Path>,
     progress_callback: Option<&dyn Fn(usize, usize)>,
+    options: &DeleteOptions,
 ) -> DeletionResult {
     let mut result = DeletionResult::default();
 
-    // Collect all files to delete
-    let files_to_delete: Vec<&ModFile> = duplicates
-        .iter()
-        .flat_map(|group| group.files[..group.newest_idx].iter())
-        .collect();
+    // Collect all files to delete, leaving out groups whose kept file looks broken
+    let mut files_to_delete: Vec<&ModFile> = Vec::new();
+    for group in duplicates {
+        let old_files = &group.files[..group.newest_idx];
+        if old_files.is_empty() {
+            continue;
+        }
+        if let Err(e) = check_keeper_intact(&group.files[group.newest_idx]) {
+            log::error!("Skipping group {}: {}", group.mod_key, e);
+            for file in old_files {
+                record_in_journal(options, &file.full_path);
+            }
+            result
+                .skipped
+                .extend(old_files.iter().map(|f| f.file_name.clone()));
+            result.errors.push(format!(
+                "Kept file looks broken, skipped {}: {}",
+                group.mod_key, e
+            ));
+            continue;
+        }
+        files_to_delete.extend(old_files);
+    }
 
     let total = files_to_delete.len();
 
     // Create recycle bin directory if specified
     if let Some(recycle_bin) = recycle_bin_dir {
-        if let Err(e) = fs::create_dir_all(recycle_bin) {
+        if let Err(e) = prepare_recycle_bin(recycle_bin, options) {
             result
                 .errors
                 .push(format!("Failed to create Recycle Bin folder: {}", e));
             return result;
         }
-        result.recycle_bin_path = Some(recycle_bin.to_path_buf());
+        result.recycle_bin_path = Some(options.backup_target(recycle_bin));
         log::info!("Created Recycle Bin folder: {:?}", recycle_bin);
     }
 
+    let mut run_archive = None;
     for (i, file) in files_to_delete.iter().enumerate() {
+        if (i > 0 && !options.pause()) || options.cancel.is_cancelled() {
+            result.cancelled = true;
+            break;
+        }
         if let Some(cb) = progress_callback {
             cb(i + 1, total);
         }
 
         // Validate before deletion
         if !validate_deletion_safety(duplicates, file) {
+            record_in_journal(options, &file.full_path);
             result.skipped.push(file.file_name.clone());
             result
                 .errors
@@ -151,8 +828,15 @@ pub fn delete_old_versions(
             continue;
         }
 
-        match delete_mod_file(file, recycle_bin_dir) {
-            Ok(size) => {
+        match delete_or_archive(
+            file,
+            recycle_bin_dir,
+            options,
+            &mut run_archive,
+            &mut result,
+        ) {
+            Ok(None) => {}
+            Ok(Some(size)) => {
                 result.deleted_count += 1;
                 result.space_freed += size;
             }
@@ -162,10 +846,27 @@ pub fn delete_old_versions(
             }
         }
     }
+    finish_run_archive(run_archive, options, &mut result);
 
     result
 }
 
+/// Check that the file kept in a group is non-empty and can be read, so old versions are
+/// never deleted in favour of a broken download
+fn check_keeper_intact(keeper: &ModFile) -> Result<(), String> {
+    let mut file = File::open(long_path(&keeper.full_path))
+        .map_err(|e| format!("cannot open {}: {}", keeper.file_name, e))?;
+    let size = file
+        .metadata()
+        .map_err(|e| format!("cannot read {}: {}", keeper.file_name, e))?
+        .len();
+    if size == 0 {
+        return Err(format!("{} is empty", keeper.file_name));
+    }
+    file.read_exact(&mut [0u8; 1])
+        .map_err(|e| format!("cannot read {}: {}", keeper.file_name, e))
+}
+
 /// Validate that we're not deleting the newest file in a group
 fn validate_deletion_safety(duplicates: &[ModGroup], file: &ModFile) -> bool {
     for group in duplicates {
@@ -203,6 +904,45 @@ fn validate_deletion_safety(duplicates: &[ModGroup], file: &ModFile) -> bool {
     true
 }
 
+/// Format a duration like "12.4s", or "3m 05s" from a minute on
+pub fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs_f64();
+    if secs < 60.0 {
+        format!("{:.1}s", secs)
+    } else {
+        let secs = elapsed.as_secs();
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    }
+}
+
+/// Unit of a coarse age like "2 years ago"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgeUnit {
+    Minutes,
+    Hours,
+    Days,
+    Months,
+    Years,
+}
+
+/// Largest whole unit of an age, e.g. 400 days is 1 year; months count 30 days and years
+/// 365. Anything under a minute is 0 minutes.
+pub fn coarse_age(age: Duration) -> (u64, AgeUnit) {
+    let minutes = age.as_secs() / 60;
+    let days = minutes / (60 * 24);
+    if days >= 365 {
+        (days / 365, AgeUnit::Years)
+    } else if days >= 30 {
+        (days / 30, AgeUnit::Months)
+    } else if days > 0 {
+        (days, AgeUnit::Days)
+    } else if minutes >= 60 {
+        (minutes / 60, AgeUnit::Hours)
+    } else {
+        (minutes, AgeUnit::Minutes)
+    }
+}
+
 /// Format file size in human-readable format
 pub fn format_size(bytes: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB", "PB"];
@@ -227,7 +967,6 @@ pub fn format_size(bytes: u64) -> String {
 }
 
 /// Convert timestamp to human-readable date
-#[allow(dead_code)]
 pub fn timestamp_to_date(timestamp: &str) -> String {
     timestamp
         .parse::<i64>()
@@ -253,6 +992,23 @@ mod tests {
         assert_eq!(format_size(1536 * 1024), "1.50 MB");
     }
 
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(format_elapsed(Duration::from_millis(12_400)), "12.4s");
+        assert_eq!(format_elapsed(Duration::ZERO), "0.0s");
+        assert_eq!(format_elapsed(Duration::from_secs(185)), "3m 05s");
+    }
+
+    #[test]
+    fn test_coarse_age() {
+        let days = |d: u64| Duration::from_secs(d * 86_400);
+        assert_eq!(coarse_age(Duration::from_secs(59)), (0, AgeUnit::Minutes));
+        assert_eq!(coarse_age(Duration::from_secs(7_300)), (2, AgeUnit::Hours));
+        assert_eq!(coarse_age(days(29)), (29, AgeUnit::Days));
+        assert_eq!(coarse_age(days(95)), (3, AgeUnit::Months));
+        assert_eq!(coarse_age(days(800)), (2, AgeUnit::Years));
+    }
+
     #[test]
     fn test_timestamp_to_date() {
         assert_eq!(timestamp_to_date("1234567890"), "2009-02-13 23:31");
@@ -291,13 +1047,70 @@ mod tests {
             timestamp: "1234567890".to_string(),
             size: 12,
             is_patch: false,
+            mtime: 0,
         };
 
-        let result = delete_mod_file(&mod_file, None);
+        let result = delete_mod_file(
+            &mod_file,
+            None,
+            &DeleteOptions::new(vec![dir.path().to_path_buf()]),
+        );
         assert!(result.is_ok());
         assert!(!file_path.exists());
     }
 
+    #[test]
+    fn test_file_outside_scanned_roots_refused() {
+        let dir = tempdir().unwrap();
+        let downloads = dir.path().join("downloads");
+        let elsewhere = dir.path().join("Documents");
+        fs::create_dir_all(downloads.join("Skyrim")).unwrap();
+        fs::create_dir(&elsewhere).unwrap();
+        let inside = downloads.join("Skyrim").join("Mod-123-1-0-1234567890.7z");
+        let outside = elsewhere.join("Mod-123-1-0-1234567890.7z");
+        fs::write(&inside, b"inside").unwrap();
+        fs::write(&outside, b"outside").unwrap();
+
+        let roots = vec![downloads.clone()];
+        assert!(is_within_scanned_roots(&inside, &roots));
+        assert!(!is_within_scanned_roots(&outside, &roots));
+        // `..` can't escape a root
+        let sneaky = downloads
+            .join("Skyrim")
+            .join("..")
+            .join("..")
+            .join("Documents");
+        assert!(!is_within_scanned_roots(
+            &sneaky.join("Mod-123-1-0-1234567890.7z"),
+            &roots
+        ));
+        assert!(!is_within_scanned_roots(&downloads, &roots));
+
+        let orphaned: Vec<OrphanedMod> = [&outside, &inside]
+            .into_iter()
+            .map(|path| OrphanedMod {
+                file: ModFile {
+                    full_path: path.clone(),
+                    size: 6,
+                    ..crate::core::parser::parse_mod_filename("Mod-123-1-0-1234567890.7z").unwrap()
+                },
+            })
+            .collect();
+
+        // Without any roots nothing is in scope
+        let result = delete_orphaned_mods(&orphaned, None, &[], None);
+        assert_eq!(result.deleted_count, 0);
+        assert_eq!(result.skipped.len(), 2);
+        assert!(outside.exists() && inside.exists());
+
+        let result = delete_orphaned_mods(&orphaned, None, &roots, None);
+        assert_eq!(result.deleted_count, 1);
+        assert_eq!(result.skipped, ["Mod-123-1-0-1234567890.7z"]);
+        assert!(result.errors[0].contains("Outside the scanned download folders"));
+        assert!(outside.exists());
+        assert!(!inside.exists());
+    }
+
     #[test]
     fn test_delete_mod_file_to_recycle_bin() {
         let dir = tempdir().unwrap();
@@ -320,11 +1133,381 @@ mod tests {
             timestamp: "1234567890".to_string(),
             size: 12,
             is_patch: false,
+            mtime: 0,
         };
 
-        let result = delete_mod_file(&mod_file, Some(&recycle_bin_dir));
+        let result = delete_mod_file(
+            &mod_file,
+            Some(&recycle_bin_dir),
+            &DeleteOptions::new(vec![dir.path().to_path_buf()]),
+        );
         assert!(result.is_ok());
         assert!(!file_path.exists());
         assert!(recycle_bin_dir.join("test-123-1-0-1234567890.7z").exists());
     }
+
+    #[test]
+    fn test_keep_meta_leaves_sidecar_in_place() {
+        let dir = tempdir().unwrap();
+        let recycle_bin_dir = dir.path().join("recycle_bin");
+        let name = "test-123-1-0-1234567890.7z";
+        let file_path = dir.path().join(name);
+        let meta = meta_path_for(&file_path);
+
+        let mod_file = ModFile {
+            file_name: name.to_string(),
+            full_path: file_path.clone(),
+            mod_name: "test".to_string(),
+            mod_id: "123".to_string(),
+            file_id: None,
+            version: "1-0".to_string(),
+            timestamp: "1234567890".to_string(),
+            size: 12,
+            is_patch: false,
+            mtime: 0,
+        };
+
+        fs::write(&file_path, b"test content").unwrap();
+        fs::write(&meta, b"[General]").unwrap();
+        let keep_meta = DeleteOptions {
+            keep_meta: true,
+            ..DeleteOptions::new(vec![dir.path().to_path_buf()])
+        };
+        assert!(delete_mod_file(&mod_file, None, &keep_meta).is_ok());
+        assert!(!file_path.exists());
+        assert!(meta.exists());
+
+        fs::create_dir(&recycle_bin_dir).unwrap();
+        fs::write(&file_path, b"test content").unwrap();
+        assert!(delete_mod_file(&mod_file, Some(&recycle_bin_dir), &keep_meta).is_ok());
+        assert!(recycle_bin_dir.join(name).exists());
+        assert!(!meta_path_for(&recycle_bin_dir.join(name)).exists());
+        assert!(meta.exists());
+
+        // Default behavior still removes the sidecar
+        fs::write(&file_path, b"test content").unwrap();
+        assert!(delete_mod_file(
+            &mod_file,
+            None,
+            &DeleteOptions::new(vec![dir.path().to_path_buf()])
+        )
+        .is_ok());
+        assert!(!meta.exists());
+    }
+
+    #[test]
+    fn test_safe_mode_never_deletes_permanently() {
+        let dir = tempdir().unwrap();
+        assert!(recycle_bin_for_run(dir.path(), false, false, false).is_none());
+
+        // Even with the recycle bin unticked, safe mode routes files into it
+        let recycle_bin = recycle_bin_for_run(dir.path(), false, true, false).unwrap();
+        assert!(recycle_bin.starts_with(dir.path().join(RECYCLE_BIN_DIR_NAME)));
+
+        let file_path = dir.path().join("test-123-1-0-1234567890.7z");
+        fs::write(&file_path, b"test content").unwrap();
+        let orphaned = OrphanedMod {
+            file: ModFile {
+                file_name: "test-123-1-0-1234567890.7z".to_string(),
+                full_path: file_path.clone(),
+                mod_name: "test".to_string(),
+                mod_id: "123".to_string(),
+                file_id: None,
+                version: "1-0".to_string(),
+                timestamp: "1234567890".to_string(),
+                size: 12,
+                is_patch: false,
+                mtime: 0,
+            },
+        };
+
+        let result = delete_orphaned_mods(
+            &[orphaned],
+            Some(&recycle_bin),
+            &[dir.path().to_path_buf()],
+            None,
+        );
+        assert_eq!(result.deleted_count, 1);
+        assert!(!file_path.exists());
+        assert!(recycle_bin.join("test-123-1-0-1234567890.7z").exists());
+    }
+
+    #[test]
+    fn test_relocate_mirrors_game_folders() {
+        let dir = tempdir().unwrap();
+        let downloads = dir.path().join("downloads");
+        let archive = dir.path().join("Archive");
+        let mut orphaned = Vec::new();
+        for game in ["SkyrimSE", "Fallout4"] {
+            let folder = downloads.join(game);
+            fs::create_dir_all(&folder).unwrap();
+            let name = format!("{}Mod-11111-1-0-1600000000.7z", game);
+            fs::write(folder.join(&name), b"data").unwrap();
+            fs::write(folder.join(format!("{}.meta", name)), b"meta").unwrap();
+            let file = crate::core::scanner::scan_mod_files_in_folder(&folder).remove(0);
+            orphaned.push(OrphanedMod { file });
+        }
+        // An existing file at the destination is never overwritten
+        fs::create_dir_all(archive.join("Fallout4")).unwrap();
+        fs::write(
+            archive.join("Fallout4/Fallout4Mod-11111-1-0-1600000000.7z"),
+            b"kept",
+        )
+        .unwrap();
+
+        let result = relocate_orphaned_mods(&orphaned, &archive, &[dir.path().to_path_buf()]);
+        assert_eq!(result.deleted_count, 1);
+        assert_eq!(result.space_freed, 4);
+        assert_eq!(result.skipped, ["Fallout4Mod-11111-1-0-1600000000.7z"]);
+        assert!(archive
+            .join("SkyrimSE/SkyrimSEMod-11111-1-0-1600000000.7z")
+            .exists());
+        assert!(archive
+            .join("SkyrimSE/SkyrimSEMod-11111-1-0-1600000000.7z.meta")
+            .exists());
+        assert!(!downloads
+            .join("SkyrimSE/SkyrimSEMod-11111-1-0-1600000000.7z")
+            .exists());
+        assert!(downloads
+            .join("Fallout4/Fallout4Mod-11111-1-0-1600000000.7z")
+            .exists());
+    }
+
+    #[test]
+    fn test_empty_keeper_skips_group() {
+        let dir = tempdir().unwrap();
+        for (name, content) in [
+            ("Mod-11111-1-0-1600000000.7z", &b"good"[..]),
+            ("Mod-11111-2-0-1700000000.7z", b""),
+            ("Other-22222-1-0-1600000000.7z", b"old"),
+            ("Other-22222-2-0-1700000000.7z", b"new"),
+        ] {
+            fs::write(dir.path().join(name), content).unwrap();
+        }
+        let mut files = crate::core::scanner::scan_mod_files_in_folder(dir.path());
+        files.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+        let group = |key: &str, files: &[ModFile]| ModGroup {
+            mod_key: key.to_string(),
+            files: files.to_vec(),
+            newest_idx: 1,
+            space_to_free: files[0].size,
+            review_recommended: false,
+        };
+        let duplicates = [group("Mod", &files[..2]), group("Other", &files[2..])];
+
+        let result = delete_old_versions(&duplicates, None, &[dir.path().to_path_buf()], None);
+        assert_eq!(result.deleted_count, 1);
+        assert_eq!(result.skipped, ["Mod-11111-1-0-1600000000.7z"]);
+        assert!(result.errors[0].contains("is empty"));
+        assert!(dir.path().join("Mod-11111-1-0-1600000000.7z").exists());
+        assert!(!dir.path().join("Other-22222-1-0-1600000000.7z").exists());
+    }
+
+    #[test]
+    fn test_cancelled_throttled_cleanup_stops_between_files() {
+        let dir = tempdir().unwrap();
+        let orphaned: Vec<OrphanedMod> = (0..3)
+            .map(|i| {
+                let name = format!("test-12{}-1-0-1234567890.7z", i);
+                let full_path = dir.path().join(&name);
+                fs::write(&full_path, b"test").unwrap();
+                OrphanedMod {
+                    file: ModFile {
+                        file_name: name,
+                        full_path,
+                        mod_name: "test".to_string(),
+                        mod_id: format!("12{}", i),
+                        file_id: None,
+                        version: "1-0".to_string(),
+                        timestamp: "1234567890".to_string(),
+                        size: 4,
+                        is_patch: false,
+                        mtime: 0,
+                    },
+                }
+            })
+            .collect();
+
+        let options =
+            DeleteOptions::with_throttle_ms(10, CancelToken::new(), vec![dir.path().to_path_buf()]);
+        let cancel = options.cancel.clone();
+        let progress = |i: usize, _: usize| {
+            if i == 2 {
+                cancel.cancel();
+            }
+        };
+        let result = delete_orphaned_mods_with_options(&orphaned, None, Some(&progress), &options);
+        assert!(result.cancelled);
+        assert_eq!(result.deleted_count, 2);
+        assert!(orphaned[2].file.full_path.exists());
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn test_deep_path_scanned_and_recycled() {
+        let dir = tempdir().unwrap();
+        let mut deep = dir.path().to_path_buf();
+        while deep.as_os_str().len() < 300 {
+            deep.push("a_fairly_long_folder_name_for_testing");
+        }
+        fs::create_dir_all(long_path(&deep)).unwrap();
+        let file_path = deep.join("Deep Mod-123-1-0-1234567890.7z");
+        fs::write(long_path(&file_path), b"deep").unwrap();
+
+        let files = crate::core::scanner::scan_mod_files_in_folder(&deep);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].size, 4);
+
+        let orphaned = OrphanedMod {
+            file: files[0].clone(),
+        };
+        let recycle_bin = deep.join(RECYCLE_BIN_DIR_NAME);
+        let result = delete_orphaned_mods(
+            &[orphaned],
+            Some(&recycle_bin),
+            &[dir.path().to_path_buf()],
+            None,
+        );
+        assert_eq!(result.deleted_count, 1, "{:?}", result.errors);
+        assert!(!long_path(&file_path).exists());
+        assert!(long_path(&recycle_bin.join("Deep Mod-123-1-0-1234567890.7z")).exists());
+    }
+
+    #[test]
+    fn test_flat_recycle_bin_keeps_newer_file_name() {
+        let dir = tempdir().unwrap();
+        let recycle_bin = recycle_bin_for_run(dir.path(), true, false, true).unwrap();
+        assert_eq!(recycle_bin, dir.path().join(RECYCLE_BIN_DIR_NAME));
+        fs::create_dir_all(&recycle_bin).unwrap();
+
+        let name = "test-123-1-0-1234567890.7z";
+        let older = recycle_bin.join(name);
+        fs::write(&older, b"older").unwrap();
+        fs::write(meta_path_for(&older), b"older meta").unwrap();
+        let past = std::time::SystemTime::now() - std::time::Duration::from_secs(3600);
+        fs::File::options()
+            .write(true)
+            .open(&older)
+            .unwrap()
+            .set_modified(past)
+            .unwrap();
+
+        let incoming = dir.path().join(name);
+        fs::write(&incoming, b"newer").unwrap();
+        fs::write(meta_path_for(&incoming), b"newer meta").unwrap();
+        move_to_recycle_bin(
+            &incoming,
+            &recycle_bin,
+            name,
+            &DeleteOptions::new(vec![dir.path().to_path_buf()]),
+        )
+        .unwrap();
+
+        let suffixed = recycle_bin.join("test-123-1-0-1234567890 (1).7z");
+        assert_eq!(fs::read(recycle_bin.join(name)).unwrap(), b"newer");
+        assert_eq!(fs::read(&suffixed).unwrap(), b"older");
+        assert_eq!(fs::read(meta_path_for(&suffixed)).unwrap(), b"older meta");
+        assert_eq!(
+            fs::read(meta_path_for(&recycle_bin.join(name))).unwrap(),
+            b"newer meta"
+        );
+    }
+
+    #[test]
+    fn test_copy_delete_moves_into_recycle_bin() {
+        let dir = tempdir().unwrap();
+        let recycle_bin = dir.path().join(RECYCLE_BIN_DIR_NAME);
+        fs::create_dir(&recycle_bin).unwrap();
+        let name = "test-123-1-0-1234567890.7z";
+        let source = dir.path().join(name);
+        let data = vec![7u8; COPY_CHUNK_SIZE + 10];
+        fs::write(&source, &data).unwrap();
+        fs::write(meta_path_for(&source), b"[General]").unwrap();
+        let past = std::time::SystemTime::now() - std::time::Duration::from_secs(3600);
+        fs::File::options()
+            .write(true)
+            .open(&source)
+            .unwrap()
+            .set_modified(past)
+            .unwrap();
+
+        let ticks = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = ticks.clone();
+        let options = DeleteOptions {
+            copy_delete: true,
+            copy_progress: Some(CopyProgress(Arc::new(move |file, done, total| {
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push((file.to_string(), done, total))
+            }))),
+            ..DeleteOptions::new(vec![dir.path().to_path_buf()])
+        };
+        move_to_recycle_bin(&source, &recycle_bin, name, &options).unwrap();
+
+        let dest = recycle_bin.join(name);
+        assert!(!source.exists());
+        assert!(!meta_path_for(&source).exists());
+        assert_eq!(fs::read(&dest).unwrap(), data);
+        assert_eq!(fs::read(meta_path_for(&dest)).unwrap(), b"[General]");
+        assert_eq!(modified_time(&dest), Some(past));
+
+        let total = data.len() as u64;
+        let ticks = ticks.lock().unwrap();
+        assert_eq!(ticks[0], (name.to_string(), COPY_CHUNK_SIZE as u64, total));
+        assert_eq!(ticks[1], (name.to_string(), total, total));
+    }
+
+    #[test]
+    fn test_cross_device_rename_falls_back_to_copy() {
+        assert!(is_cross_device(&io::Error::from_raw_os_error(
+            CROSS_DEVICE_ERROR
+        )));
+        assert!(is_cross_device(&io::Error::from(
+            io::ErrorKind::CrossesDevices
+        )));
+        assert!(!is_cross_device(&io::Error::from(
+            io::ErrorKind::PermissionDenied
+        )));
+
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("a.7z");
+        let moved = dir.path().join("b.7z");
+        fs::write(&source, b"data").unwrap();
+        let other_drive =
+            |_: &Path, _: &Path| Err(io::Error::from_raw_os_error(CROSS_DEVICE_ERROR));
+        move_file_with(
+            &source,
+            &moved,
+            &DeleteOptions::new(vec![dir.path().to_path_buf()]),
+            other_drive,
+        )
+        .unwrap();
+        assert!(!source.exists());
+        assert_eq!(fs::read(&moved).unwrap(), b"data");
+
+        // Other rename errors are reported, not retried as a copy
+        let denied = |_: &Path, _: &Path| Err(io::Error::from(io::ErrorKind::PermissionDenied));
+        assert!(move_file_with(
+            &moved,
+            &source,
+            &DeleteOptions::new(vec![dir.path().to_path_buf()]),
+            denied
+        )
+        .is_err());
+        assert!(moved.exists());
+        assert!(!source.exists());
+
+        // A failed copy leaves the original alone and no partial file behind
+        fs::rename(&moved, &source).unwrap();
+        let dest = dir.path().join("missing").join("a.7z");
+        assert!(copy_then_delete(
+            &source,
+            &dest,
+            &DeleteOptions::new(vec![dir.path().to_path_buf()])
+        )
+        .is_err());
+        assert!(source.exists());
+        assert!(!dest.exists());
+    }
 }
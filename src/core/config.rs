@@ -0,0 +1,369 @@
+// Copyright (C) 2025 Berkay Yetgin
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::core::hash::DEFAULT_FAST_HASH_ABOVE;
+use crate::core::scanner::default_excluded_folders;
+use crate::core::types::{
+    KeepBy, DEFAULT_PATCH_SIZE_RATIO, DEFAULT_PROTECTED_EXTENSIONS, DEFAULT_SUSPICIOUS_SIZE_RATIO,
+};
+
+const APP_DIR_NAME: &str = "wabbajack-library-cleaner";
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Bumped when saved settings need migrating to new defaults
+const SETTINGS_VERSION: u32 = 1;
+
+/// Rows each results list shows at first, and adds per "Show more"
+pub const DEFAULT_RESULT_ROW_LIMIT: u32 = 200;
+
+/// Height of the log panel until the user resizes it
+pub const DEFAULT_LOG_PANEL_HEIGHT: f32 = 120.0;
+
+/// Per-user directory for settings and caches (%APPDATA% on Windows, XDG config elsewhere)
+pub fn config_dir() -> Option<PathBuf> {
+    #[cfg(target_os = "windows")]
+    let base = std::env::var_os("APPDATA").map(PathBuf::from);
+
+    #[cfg(not(target_os = "windows"))]
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")));
+
+    base.map(|b| b.join(APP_DIR_NAME))
+}
+
+/// Location of a named file inside the config directory
+pub fn config_file(name: &str) -> Option<PathBuf> {
+    config_dir().map(|d| d.join(name))
+}
+
+/// Maximum number of remembered folders per picker
+pub const MAX_RECENT_FOLDERS: usize = 5;
+
+/// Spacing of the interface
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum UiDensity {
+    #[default]
+    Comfortable,
+    /// Tighter spacing and rows, for small screens
+    Compact,
+}
+
+/// User preferences persisted between runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub recent_wabbajack_dirs: Vec<PathBuf>,
+    pub recent_downloads_dirs: Vec<PathBuf>,
+    /// Downloads roots scanned together. Older settings stored a single `downloads_dir`.
+    #[serde(alias = "downloads_dir", deserialize_with = "one_or_many_paths")]
+    pub downloads_dirs: Vec<PathBuf>,
+    /// Always move files to the recycle bin folder; permanent deletion is disabled
+    pub safe_mode: bool,
+    /// Move files straight into `WLC_RecycleBin` instead of a timestamped folder per run.
+    /// Restoring everything is simpler, but files from different runs are mixed together.
+    pub flat_recycle_bin: bool,
+    /// Store each run's recycled files in one uncompressed `.zip` that can be restored
+    pub compress_backups: bool,
+    /// Show a tray icon and notify when work finishes while minimized
+    pub tray_icon: bool,
+    /// One desktop notification per scan category while the window is in the background
+    pub scan_notifications: bool,
+    /// UI language code ("en", "de", "tr")
+    pub language: String,
+    /// Downloads subfolders never scanned, by name pattern (`*` matches any text)
+    pub excluded_folders: Vec<String>,
+    /// Extensions that are scanned but never offered for orphan cleanup
+    pub protected_extensions: Vec<String>,
+    /// Look up the latest GitHub release on startup
+    pub check_for_updates: bool,
+    /// Pause between deleted or moved files in milliseconds (0 = unthrottled)
+    pub delete_throttle_ms: u64,
+    /// Leave `.meta` files in place when their archive is deleted or moved
+    pub keep_meta: bool,
+    /// Copy files into the recycle bin and delete the originals instead of renaming them,
+    /// e.g. for network drives where renames misbehave
+    pub copy_delete_moves: bool,
+    /// Orphaned scan counts a file as used only by name or ModID+FileID, so versions of a
+    /// used mod that no modlist lists become cleanable. Off keeps every file sharing a ModID.
+    pub strict_orphan_matching: bool,
+    /// Match names carrying only a FileID (`ModName-FileID-timestamp`) by that FileID
+    pub file_id_only_names: bool,
+    /// Hold back unreferenced files modified within this many days (0 = off)
+    pub ignore_newer_than_days: u32,
+    /// Hold back unreferenced files smaller than this many KB (0 = off)
+    pub min_mod_size_kb: u32,
+    /// Fingerprint archives from this many MB on instead of hashing them whole (0 = always
+    /// hash in full)
+    pub fast_hash_above_mb: u32,
+    /// Confirm fingerprint matches with a full hash
+    pub verify_fast_hash: bool,
+    /// MO2 `mods` folder whose installed mods keep their downloads from orphan cleanup
+    pub mo2_mods_dir: Option<PathBuf>,
+    /// List every modlist version found instead of only the newest per modlist
+    pub show_all_modlist_versions: bool,
+    /// Which file of an old-version group is kept
+    pub keep_by: KeepBy,
+    /// Newest files smaller than this fraction of an older version count as patches
+    pub patch_size_ratio: f64,
+    /// Same-version files further apart in size than this factor are left alone
+    pub suspicious_size_ratio: f64,
+    /// Height of the log panel in points
+    pub log_panel_height: f32,
+    /// Log panel reduced to its status row
+    pub log_collapsed: bool,
+    pub density: UiDensity,
+    /// Rows each results list shows before "Show more" (0 = all)
+    pub result_row_limit: u32,
+    /// Format of the saved file; missing in files written before versioning
+    #[serde(default)]
+    pub version: u32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            recent_wabbajack_dirs: Vec::new(),
+            recent_downloads_dirs: Vec::new(),
+            downloads_dirs: Vec::new(),
+            safe_mode: true,
+            flat_recycle_bin: false,
+            compress_backups: false,
+            tray_icon: false,
+            scan_notifications: false,
+            language: "en".to_string(),
+            excluded_folders: default_excluded_folders(),
+            protected_extensions: DEFAULT_PROTECTED_EXTENSIONS
+                .iter()
+                .map(|e| e.to_string())
+                .collect(),
+            check_for_updates: false,
+            delete_throttle_ms: 0,
+            keep_meta: false,
+            copy_delete_moves: false,
+            strict_orphan_matching: false,
+            file_id_only_names: false,
+            ignore_newer_than_days: 0,
+            min_mod_size_kb: 0,
+            fast_hash_above_mb: (DEFAULT_FAST_HASH_ABOVE / (1024 * 1024)) as u32,
+            verify_fast_hash: false,
+            mo2_mods_dir: None,
+            show_all_modlist_versions: false,
+            keep_by: KeepBy::NewestUpload,
+            patch_size_ratio: DEFAULT_PATCH_SIZE_RATIO,
+            suspicious_size_ratio: DEFAULT_SUSPICIOUS_SIZE_RATIO,
+            log_panel_height: DEFAULT_LOG_PANEL_HEIGHT,
+            log_collapsed: false,
+            density: UiDensity::Comfortable,
+            result_row_limit: DEFAULT_RESULT_ROW_LIMIT,
+            version: SETTINGS_VERSION,
+        }
+    }
+}
+
+impl Settings {
+    /// Load settings, falling back to defaults if missing or unreadable
+    pub fn load(path: &Path) -> Self {
+        let content = match fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(_) => return Self::default(),
+        };
+        let mut settings: Self = serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::warn!("Ignoring invalid settings file {:?}: {}", path, e);
+            Self::default()
+        });
+        settings.prune_missing_recent();
+        settings.migrate();
+        settings
+    }
+
+    /// Bring settings saved by an older version up to the current defaults
+    fn migrate(&mut self) {
+        if self.version < 1 {
+            // `.bsa`/`.ba2` became scannable; keep them out of cleanup like new installs do
+            for ext in [".bsa", ".ba2"] {
+                if !self.protected_extensions.iter().any(|e| e == ext) {
+                    self.protected_extensions.push(ext.to_string());
+                }
+            }
+        }
+        self.version = SETTINGS_VERSION;
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {:?}", parent))?;
+        }
+        let json = serde_json::to_string_pretty(self).context("Failed to serialize settings")?;
+        fs::write(path, json).with_context(|| format!("Failed to write settings: {:?}", path))
+    }
+
+    /// Drop remembered folders that no longer exist
+    pub fn prune_missing_recent(&mut self) {
+        self.recent_wabbajack_dirs.retain(|p| p.exists());
+        self.recent_downloads_dirs.retain(|p| p.exists());
+    }
+}
+
+/// Accept either a single path or a list of paths
+fn one_or_many_paths<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<PathBuf>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(PathBuf),
+        Many(Vec<PathBuf>),
+    }
+    Ok(match Option::<OneOrMany>::deserialize(deserializer)? {
+        Some(OneOrMany::One(path)) => vec![path],
+        Some(OneOrMany::Many(paths)) => paths,
+        None => Vec::new(),
+    })
+}
+
+/// Default settings file location in the config directory
+pub fn settings_path() -> Option<PathBuf> {
+    config_file(SETTINGS_FILE)
+}
+
+/// Parse a comma or space separated extension list into lowercase `.ext` entries
+pub fn parse_extension_list(text: &str) -> Vec<String> {
+    text.split(|c: char| c == ',' || c.is_whitespace())
+        .map(|s| s.trim().trim_start_matches('.').to_lowercase())
+        .filter(|s| !s.is_empty())
+        .map(|s| format!(".{}", s))
+        .collect()
+}
+
+/// Split a comma-separated list of folder patterns
+pub fn parse_pattern_list(text: &str) -> Vec<String> {
+    text.split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Move a folder to the front of a recent list, removing duplicates and capping the length
+pub fn push_recent_folder(list: &mut Vec<PathBuf>, path: &Path) {
+    list.retain(|p| p != path);
+    list.insert(0, path.to_path_buf());
+    list.truncate(MAX_RECENT_FOLDERS);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_push_recent_folder() {
+        let mut list = Vec::new();
+        for i in 0..7 {
+            push_recent_folder(&mut list, Path::new(&format!("/dir{}", i)));
+        }
+        assert_eq!(list.len(), MAX_RECENT_FOLDERS);
+        assert_eq!(list[0], PathBuf::from("/dir6"));
+
+        // Re-adding moves the entry to the front without duplicating it
+        push_recent_folder(&mut list, Path::new("/dir4"));
+        assert_eq!(list.len(), MAX_RECENT_FOLDERS);
+        assert_eq!(list[0], PathBuf::from("/dir4"));
+        assert_eq!(list.iter().filter(|p| **p == Path::new("/dir4")).count(), 1);
+    }
+
+    #[test]
+    fn test_parse_extension_list() {
+        assert_eq!(
+            parse_extension_list(".exe, RAR  zip,,"),
+            vec![".exe", ".rar", ".zip"]
+        );
+        assert!(parse_extension_list("  ").is_empty());
+    }
+
+    #[test]
+    fn test_settings_round_trip_prunes_missing() {
+        let dir = tempdir().unwrap();
+        let existing = dir.path().join("downloads");
+        fs::create_dir(&existing).unwrap();
+
+        let mut settings = Settings::default();
+        push_recent_folder(&mut settings.recent_downloads_dirs, &existing);
+        push_recent_folder(
+            &mut settings.recent_downloads_dirs,
+            &dir.path().join("gone"),
+        );
+
+        let path = dir.path().join("config").join(SETTINGS_FILE);
+        settings.save(&path).unwrap();
+
+        let loaded = Settings::load(&path);
+        assert_eq!(loaded.recent_downloads_dirs, vec![existing]);
+    }
+
+    #[test]
+    fn test_safe_mode_defaults_on() {
+        assert!(Settings::default().safe_mode);
+
+        // Settings files written before safe mode existed pick up the default
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(SETTINGS_FILE);
+        fs::write(&path, r#"{"recent_downloads_dirs": []}"#).unwrap();
+        assert!(Settings::load(&path).safe_mode);
+    }
+
+    #[test]
+    fn test_bethesda_archives_protected_after_upgrade() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(SETTINGS_FILE);
+        fs::write(&path, r#"{"protected_extensions": [".exe"]}"#).unwrap();
+        assert_eq!(
+            Settings::load(&path).protected_extensions,
+            vec![".exe", ".bsa", ".ba2"]
+        );
+
+        // Once migrated, a user's choice to unprotect them sticks
+        let settings = Settings {
+            protected_extensions: vec![".exe".to_string()],
+            ..Default::default()
+        };
+        settings.save(&path).unwrap();
+        assert_eq!(Settings::load(&path).protected_extensions, vec![".exe"]);
+    }
+
+    #[test]
+    fn test_single_downloads_dir_migrates_to_list() {
+        let dir = tempdir().unwrap();
+        let downloads = dir.path().join("downloads");
+        fs::create_dir(&downloads).unwrap();
+        let path = dir.path().join(SETTINGS_FILE);
+
+        let legacy = serde_json::json!({ "downloads_dir": downloads });
+        fs::write(&path, legacy.to_string()).unwrap();
+        assert_eq!(
+            Settings::load(&path).downloads_dirs,
+            vec![downloads.clone()]
+        );
+
+        let settings = Settings {
+            downloads_dirs: vec![downloads.clone(), dir.path().join("gone")],
+            ..Default::default()
+        };
+        settings.save(&path).unwrap();
+        // A missing root may be an unplugged drive; it stays configured
+        assert_eq!(
+            Settings::load(&path).downloads_dirs,
+            vec![downloads, dir.path().join("gone")]
+        );
+    }
+}
@@ -0,0 +1,471 @@
+// Copyright (C) 2025 Berkay Yetgin
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::core::hash::{hash_files_with_options, HashOptions};
+use crate::core::platform::hardlink_identity;
+use crate::core::scanner::{distinct_size, scan_mod_files_in_folder};
+use crate::core::types::{CancelToken, ModFile, ModGroup};
+
+/// Byte-identical re-downloads found next to their original
+#[derive(Debug, Clone, Default)]
+pub struct IdenticalCopiesResult {
+    /// One group per original; the original is kept (`newest_idx`), the copies before it go
+    pub groups: Vec<ModGroup>,
+    pub total_files: usize,
+    pub total_space: u64,
+}
+
+/// One Nexus file, by ModID and FileID, downloaded into several game folders
+#[derive(Debug, Clone)]
+pub struct CrossGameGroup {
+    pub mod_id: String,
+    /// FileID, or version and upload timestamp for names that carry none
+    pub file_key: String,
+    /// Every copy, sorted by path; they may be named or compressed differently
+    pub copies: Vec<ModFile>,
+}
+
+impl CrossGameGroup {
+    /// Number of game folders holding a copy
+    pub fn game_count(&self) -> usize {
+        self.copies
+            .iter()
+            .filter_map(|f| f.full_path.parent())
+            .collect::<HashSet<&Path>>()
+            .len()
+    }
+
+    /// Space freed by keeping only the largest copy; hard links count once
+    pub fn redundant_size(&self) -> u64 {
+        let largest = self.copies.iter().map(|f| f.size).max().unwrap_or(0);
+        distinct_size(&self.copies).saturating_sub(largest)
+    }
+}
+
+/// Group files by ModID and FileID across game folders, regardless of name or content,
+/// keeping groups found in at least two folders. Files without a ModID, including generic
+/// archives the scanner gives ModID "0", are left out.
+pub fn find_cross_game_copies(files: &[ModFile]) -> Vec<CrossGameGroup> {
+    let mut by_key: BTreeMap<(String, String), Vec<ModFile>> = BTreeMap::new();
+    for file in files
+        .iter()
+        .filter(|f| !f.is_unparsed() && !matches!(f.mod_id.as_str(), "" | "0"))
+    {
+        let file_key = match &file.file_id {
+            Some(file_id) => file_id.clone(),
+            None => format!("{}-{}", file.version, file.timestamp),
+        };
+        by_key
+            .entry((file.mod_id.clone(), file_key))
+            .or_default()
+            .push(file.clone());
+    }
+    let mut groups: Vec<CrossGameGroup> = by_key
+        .into_iter()
+        .map(|((mod_id, file_key), mut copies)| {
+            copies.sort_by(|a, b| a.full_path.cmp(&b.full_path));
+            CrossGameGroup {
+                mod_id,
+                file_key,
+                copies,
+            }
+        })
+        .filter(|g| g.game_count() > 1)
+        .collect();
+    groups.sort_by_key(|g| std::cmp::Reverse(g.redundant_size()));
+    groups
+}
+
+/// Original name of a file saved under a copy name like `Mod (1).7z` or `Mod - Copy.7z`
+pub fn strip_copy_suffix(file_name: &str) -> Option<String> {
+    let (stem, ext) = match file_name.rfind('.') {
+        Some(i) if i > 0 => file_name.split_at(i),
+        _ => (file_name, ""),
+    };
+    let mut original = stem.trim_end();
+
+    // Browser style " (1)", also after a Windows " - Copy"
+    if let Some(inner) = original
+        .strip_suffix(')')
+        .and_then(|s| s.rsplit_once(" ("))
+        .filter(|(_, n)| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+        .map(|(base, _)| base)
+    {
+        original = inner;
+    }
+    // Explorer style " - Copy"
+    if original.len() > 7 && original.is_char_boundary(original.len() - 7) {
+        let (base, suffix) = original.split_at(original.len() - 7);
+        if suffix.eq_ignore_ascii_case(" - Copy") {
+            original = base;
+        }
+    }
+
+    (original.len() < stem.len() && !original.is_empty()).then(|| format!("{}{}", original, ext))
+}
+
+/// Find files in each folder that are copies of another file in the same folder: named
+/// like a re-download of it, same size, and the same hash. Only copies are reported, so
+/// the name a modlist expects always stays. Returns `None` if cancelled while hashing.
+pub fn find_identical_copies(
+    folders: &[PathBuf],
+    cancel: &CancelToken,
+    on_progress: &mut dyn FnMut(u64, u64),
+) -> Option<IdenticalCopiesResult> {
+    find_identical_copies_with_options(folders, cancel, on_progress, &HashOptions::default())
+}
+
+/// Like `find_identical_copies`, with large files fingerprinted as set in `options` and
+/// fingerprint matches optionally confirmed with a full hash
+pub fn find_identical_copies_with_options(
+    folders: &[PathBuf],
+    cancel: &CancelToken,
+    on_progress: &mut dyn FnMut(u64, u64),
+    options: &HashOptions,
+) -> Option<IdenticalCopiesResult> {
+    // (original, copies) with matching names and sizes
+    let mut candidates: Vec<(ModFile, Vec<ModFile>)> = Vec::new();
+    for folder in folders {
+        let files = scan_mod_files_in_folder(folder);
+        let by_name: HashMap<String, &ModFile> = files
+            .iter()
+            .map(|f| (f.file_name.to_lowercase(), f))
+            .collect();
+        let mut copies_of: HashMap<PathBuf, (ModFile, Vec<ModFile>)> = HashMap::new();
+        for file in &files {
+            let Some(original) = strip_copy_suffix(&file.file_name)
+                .and_then(|name| by_name.get(&name.to_lowercase()))
+                .filter(|original| original.size == file.size)
+            else {
+                continue;
+            };
+            // A hard link to the original takes no extra space
+            if hardlink_identity(&file.full_path)
+                .is_some_and(|id| hardlink_identity(&original.full_path) == Some(id))
+            {
+                continue;
+            }
+            copies_of
+                .entry(original.full_path.clone())
+                .or_insert_with(|| ((*original).clone(), Vec::new()))
+                .1
+                .push(file.clone());
+        }
+        candidates.extend(copies_of.into_values());
+    }
+    if candidates.is_empty() {
+        return Some(IdenticalCopiesResult::default());
+    }
+
+    let paths: Vec<PathBuf> = candidates
+        .iter()
+        .flat_map(|(original, copies)| {
+            std::iter::once(original)
+                .chain(copies)
+                .map(|f| f.full_path.clone())
+        })
+        .collect();
+    let mut hashes = hash_map(&paths, cancel, on_progress, options)?;
+
+    // Fingerprinted copies that match their original get hashed in full
+    if options.verify {
+        let to_verify: Vec<PathBuf> = candidates
+            .iter()
+            .filter(|(original, _)| options.is_fast(original.size))
+            .flat_map(|(original, copies)| {
+                let original_hash = hashes.get(&original.full_path).copied();
+                let matching: Vec<PathBuf> = copies
+                    .iter()
+                    .filter(|c| {
+                        original_hash.is_some()
+                            && hashes.get(&c.full_path).copied() == original_hash
+                    })
+                    .map(|c| c.full_path.clone())
+                    .collect();
+                let original = (!matching.is_empty()).then(|| original.full_path.clone());
+                original.into_iter().chain(matching)
+            })
+            .collect();
+        if !to_verify.is_empty() {
+            log::info!(
+                "Verifying {} fingerprint matches with a full hash",
+                to_verify.len()
+            );
+            for path in &to_verify {
+                hashes.remove(path);
+            }
+            hashes.extend(hash_map(
+                &to_verify,
+                cancel,
+                on_progress,
+                &HashOptions::full(),
+            )?);
+        }
+    }
+
+    let mut result = IdenticalCopiesResult::default();
+    for (original, copies) in candidates {
+        let Some(original_hash) = hashes.get(&original.full_path) else {
+            continue;
+        };
+        let mut files: Vec<ModFile> = copies
+            .into_iter()
+            .filter(|c| hashes.get(&c.full_path) == Some(original_hash))
+            .collect();
+        if files.is_empty() {
+            continue;
+        }
+        files.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+        let space_to_free = files.iter().map(|f| f.size).sum();
+        result.total_files += files.len();
+        result.total_space += space_to_free;
+        let newest_idx = files.len();
+        files.push(original.clone());
+        result.groups.push(ModGroup {
+            mod_key: original.file_name.clone(),
+            files,
+            newest_idx,
+            space_to_free,
+            review_recommended: false,
+        });
+    }
+    result
+        .groups
+        .sort_by_key(|g| std::cmp::Reverse(g.space_to_free));
+    Some(result)
+}
+
+/// Hashes by path, leaving out files that can't be read
+pub(crate) fn hash_map(
+    paths: &[PathBuf],
+    cancel: &CancelToken,
+    on_progress: &mut dyn FnMut(u64, u64),
+    options: &HashOptions,
+) -> Option<HashMap<PathBuf, u64>> {
+    let hashes = hash_files_with_options(paths, cancel, on_progress, options)?;
+    Some(
+        paths
+            .iter()
+            .cloned()
+            .zip(hashes)
+            .filter_map(|(path, hash)| match hash {
+                Ok(h) => Some((path, h)),
+                Err(e) => {
+                    log::warn!("Skipping unreadable file: {}", e);
+                    None
+                }
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_strip_copy_suffix() {
+        let cases = [
+            ("Mod-123-1-0-123 (1).7z", Some("Mod-123-1-0-123.7z")),
+            ("Mod-123-1-0-123 (12).zip", Some("Mod-123-1-0-123.zip")),
+            ("Mod-123-1-0-123 - Copy.7z", Some("Mod-123-1-0-123.7z")),
+            (
+                "Mod-123-1-0-123 - copy (2).rar",
+                Some("Mod-123-1-0-123.rar"),
+            ),
+            ("Mod-123-1-0-123.7z", None),
+            ("Mod (Lite)-123-1-0-123.7z", None),
+            (" (1).7z", None),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(strip_copy_suffix(name).as_deref(), expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_identical_redownload_flagged() {
+        let dir = tempdir().unwrap();
+        let game = dir.path().join("Skyrim");
+        fs::create_dir(&game).unwrap();
+        fs::write(game.join("Mod-123-1-0-123.7z"), b"same bytes").unwrap();
+        fs::write(game.join("Mod-123-1-0-123 (1).7z"), b"same bytes").unwrap();
+        // Same size but different content is not a copy
+        fs::write(game.join("Other-456-1-0-456.7z"), b"0123456789").unwrap();
+        fs::write(game.join("Other-456-1-0-456 - Copy.7z"), b"9876543210").unwrap();
+        // Identical content under an unrelated name is left alone
+        fs::write(game.join("Renamed-789-1-0-789.7z"), b"same bytes").unwrap();
+
+        let result = find_identical_copies(
+            std::slice::from_ref(&game),
+            &CancelToken::new(),
+            &mut |_, _| {},
+        )
+        .unwrap();
+        assert_eq!(result.groups.len(), 1);
+        assert_eq!(result.total_files, 1);
+        assert_eq!(result.total_space, 10);
+        let group = &result.groups[0];
+        assert_eq!(group.mod_key, "Mod-123-1-0-123.7z");
+        assert_eq!(group.newest_idx, 1);
+        assert_eq!(
+            group.files[0].full_path,
+            game.join("Mod-123-1-0-123 (1).7z")
+        );
+        assert_eq!(group.files[1].full_path, game.join("Mod-123-1-0-123.7z"));
+    }
+
+    #[cfg(any(unix, windows))]
+    #[test]
+    fn test_hardlinked_copy_not_flagged() {
+        let dir = tempdir().unwrap();
+        let game = dir.path().join("Skyrim");
+        fs::create_dir(&game).unwrap();
+        fs::write(game.join("Mod-123-1-0-123.7z"), b"same bytes").unwrap();
+        fs::hard_link(
+            game.join("Mod-123-1-0-123.7z"),
+            game.join("Mod-123-1-0-123 (1).7z"),
+        )
+        .unwrap();
+
+        let result = find_identical_copies(
+            std::slice::from_ref(&game),
+            &CancelToken::new(),
+            &mut |_, _| {},
+        )
+        .unwrap();
+        assert!(result.groups.is_empty());
+    }
+
+    #[test]
+    fn test_same_file_id_grouped_across_games() {
+        let file = |path: &str, mod_id: &str, file_id: Option<&str>, size: u64| ModFile {
+            file_name: Path::new(path)
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .to_string(),
+            full_path: PathBuf::from(path),
+            mod_name: "Mod".to_string(),
+            mod_id: mod_id.to_string(),
+            file_id: file_id.map(str::to_string),
+            version: "1".to_string(),
+            timestamp: "1600000000".to_string(),
+            size,
+            is_patch: false,
+            mtime: 0,
+        };
+        let files = [
+            file(
+                "/dl/Skyrim/SkyUI-12604-35407-5-2-1600000000.7z",
+                "12604",
+                Some("35407"),
+                100,
+            ),
+            file(
+                "/dl/Enderal/SkyUI renamed-12604-35407-5-2-1600000000.7z",
+                "12604",
+                Some("35407"),
+                90,
+            ),
+            // Another file of the same mod, and one copied within a single game
+            file(
+                "/dl/Enderal/SkyUI-12604-35408-5-2-1600000000.7z",
+                "12604",
+                Some("35408"),
+                50,
+            ),
+            file(
+                "/dl/Skyrim/Other-222-333-1-0-1600000000.7z",
+                "222",
+                Some("333"),
+                10,
+            ),
+            file(
+                "/dl/Skyrim/Other (1)-222-333-1-0-1600000000.7z",
+                "222",
+                Some("333"),
+                10,
+            ),
+        ];
+        // Unrelated generic archives, indexed as ModID "0" with placeholder versions
+        let generic = |path: &str| {
+            let name = Path::new(path).file_name().unwrap().to_string_lossy();
+            ModFile {
+                mod_name: name.to_string(),
+                version: "0.0".to_string(),
+                timestamp: "0".to_string(),
+                ..file(path, "0", None, 40)
+            }
+        };
+        let files: Vec<ModFile> = files
+            .into_iter()
+            .chain([
+                generic("/dl/Skyrim/ENB Preset.zip"),
+                generic("/dl/Fallout4/Texture Pack.7z"),
+            ])
+            .collect();
+
+        let groups = find_cross_game_copies(&files);
+        assert_eq!(groups.len(), 1);
+        let group = &groups[0];
+        assert_eq!(
+            (group.mod_id.as_str(), group.file_key.as_str()),
+            ("12604", "35407")
+        );
+        assert_eq!(group.game_count(), 2);
+        assert_eq!(group.redundant_size(), 90);
+        let folders: Vec<&Path> = group
+            .copies
+            .iter()
+            .map(|f| f.full_path.parent().unwrap())
+            .collect();
+        assert_eq!(folders, [Path::new("/dl/Enderal"), Path::new("/dl/Skyrim")]);
+    }
+
+    #[test]
+    fn test_fingerprint_matches_verified_in_full() {
+        let dir = tempdir().unwrap();
+        let game = dir.path().join("Skyrim");
+        fs::create_dir(&game).unwrap();
+        // Same ends, different middle: only a full hash tells them apart
+        let original = vec![7u8; 4096];
+        let mut copy = original.clone();
+        copy[2048] = 8;
+        fs::write(game.join("Mod-123-1-0-123.7z"), &original).unwrap();
+        fs::write(game.join("Mod-123-1-0-123 (1).7z"), &copy).unwrap();
+
+        let folders = std::slice::from_ref(&game);
+        let mut options = HashOptions {
+            fast_above: Some(1024),
+            sample_bytes: 512,
+            verify: false,
+        };
+        let fast = find_identical_copies_with_options(
+            folders,
+            &CancelToken::new(),
+            &mut |_, _| {},
+            &options,
+        )
+        .unwrap();
+        assert_eq!(fast.total_files, 1);
+
+        options.verify = true;
+        let verified = find_identical_copies_with_options(
+            folders,
+            &CancelToken::new(),
+            &mut |_, _| {},
+            &options,
+        )
+        .unwrap();
+        assert!(verified.groups.is_empty());
+    }
+}
@@ -0,0 +1,489 @@
+// Copyright (C) 2025 Berkay Yetgin
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::core::platform::long_path;
+use crate::core::types::CancelToken;
+
+/// Bytes read per chunk; progress and cancellation are checked once per chunk
+const HASH_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Bytes read from each end of a file for a fast fingerprint
+pub const DEFAULT_SAMPLE_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Files from this size on get a fast fingerprint unless configured otherwise
+pub const DEFAULT_FAST_HASH_ABOVE: u64 = 1024 * 1024 * 1024;
+
+const PRIME64_1: u64 = 0x9E37_79B1_85EB_CA87;
+const PRIME64_2: u64 = 0xC2B2_AE3D_27D4_EB4F;
+const PRIME64_3: u64 = 0x1656_67B1_9E37_79F9;
+const PRIME64_4: u64 = 0x85EB_CA77_C2B2_AE63;
+const PRIME64_5: u64 = 0x27D4_EB2F_1656_67C5;
+
+/// Streaming xxHash64, the archive hash Wabbajack stores in modlists
+#[derive(Debug, Clone)]
+pub struct Xxh64 {
+    seed: u64,
+    acc: [u64; 4],
+    buf: [u8; 32],
+    buf_len: usize,
+    total_len: u64,
+}
+
+fn round(acc: u64, input: u64) -> u64 {
+    acc.wrapping_add(input.wrapping_mul(PRIME64_2))
+        .rotate_left(31)
+        .wrapping_mul(PRIME64_1)
+}
+
+fn merge_round(acc: u64, val: u64) -> u64 {
+    (acc ^ round(0, val))
+        .wrapping_mul(PRIME64_1)
+        .wrapping_add(PRIME64_4)
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    u64::from_le_bytes(bytes[..8].try_into().unwrap())
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes(bytes[..4].try_into().unwrap())
+}
+
+impl Xxh64 {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            acc: [
+                seed.wrapping_add(PRIME64_1).wrapping_add(PRIME64_2),
+                seed.wrapping_add(PRIME64_2),
+                seed,
+                seed.wrapping_sub(PRIME64_1),
+            ],
+            buf: [0; 32],
+            buf_len: 0,
+            total_len: 0,
+        }
+    }
+
+    fn consume_stripe(acc: &mut [u64; 4], stripe: &[u8]) {
+        for (i, lane) in acc.iter_mut().enumerate() {
+            *lane = round(*lane, read_u64(&stripe[i * 8..]));
+        }
+    }
+
+    pub fn update(&mut self, mut data: &[u8]) {
+        self.total_len += data.len() as u64;
+
+        if self.buf_len > 0 {
+            let take = (32 - self.buf_len).min(data.len());
+            self.buf[self.buf_len..self.buf_len + take].copy_from_slice(&data[..take]);
+            self.buf_len += take;
+            data = &data[take..];
+            if self.buf_len < 32 {
+                return;
+            }
+            Self::consume_stripe(&mut self.acc, &self.buf);
+            self.buf_len = 0;
+        }
+
+        let mut stripes = data.chunks_exact(32);
+        for stripe in &mut stripes {
+            Self::consume_stripe(&mut self.acc, stripe);
+        }
+        let rest = stripes.remainder();
+        self.buf[..rest.len()].copy_from_slice(rest);
+        self.buf_len = rest.len();
+    }
+
+    pub fn digest(&self) -> u64 {
+        let mut h = if self.total_len >= 32 {
+            let [v1, v2, v3, v4] = self.acc;
+            let mut h = v1
+                .rotate_left(1)
+                .wrapping_add(v2.rotate_left(7))
+                .wrapping_add(v3.rotate_left(12))
+                .wrapping_add(v4.rotate_left(18));
+            for v in self.acc {
+                h = merge_round(h, v);
+            }
+            h
+        } else {
+            self.seed.wrapping_add(PRIME64_5)
+        };
+        h = h.wrapping_add(self.total_len);
+
+        let mut tail = &self.buf[..self.buf_len];
+        while tail.len() >= 8 {
+            h ^= round(0, read_u64(tail));
+            h = h
+                .rotate_left(27)
+                .wrapping_mul(PRIME64_1)
+                .wrapping_add(PRIME64_4);
+            tail = &tail[8..];
+        }
+        if tail.len() >= 4 {
+            h ^= (read_u32(tail) as u64).wrapping_mul(PRIME64_1);
+            h = h
+                .rotate_left(23)
+                .wrapping_mul(PRIME64_2)
+                .wrapping_add(PRIME64_3);
+            tail = &tail[4..];
+        }
+        for &byte in tail {
+            h ^= (byte as u64).wrapping_mul(PRIME64_5);
+            h = h.rotate_left(11).wrapping_mul(PRIME64_1);
+        }
+
+        h ^= h >> 33;
+        h = h.wrapping_mul(PRIME64_2);
+        h ^= h >> 29;
+        h = h.wrapping_mul(PRIME64_3);
+        h ^ (h >> 32)
+    }
+}
+
+/// Decode a modlist archive `Hash`: the xxHash64 as 8 little-endian bytes in base64
+pub fn parse_wabbajack_hash(encoded: &str) -> Option<u64> {
+    let mut bits = 0u128;
+    let mut len = 0;
+    for c in encoded.trim_end_matches('=').bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        bits = (bits << 6) | value as u128;
+        len += 1;
+    }
+    // 11 characters carry 66 bits: the 64 of the hash and 2 bits of padding
+    if len != 11 {
+        return None;
+    }
+    Some(((bits >> 2) as u64).swap_bytes())
+}
+
+/// How files are hashed for hash-based checks
+#[derive(Debug, Clone)]
+pub struct HashOptions {
+    /// Fingerprint files of at least this many bytes instead of hashing them whole
+    /// (`None` = always hash in full)
+    pub fast_above: Option<u64>,
+    /// Bytes read from each end of a file for a fingerprint
+    pub sample_bytes: u64,
+    /// Hash files whose fingerprints match in full before treating them as identical
+    pub verify: bool,
+}
+
+impl Default for HashOptions {
+    fn default() -> Self {
+        Self {
+            fast_above: Some(DEFAULT_FAST_HASH_ABOVE),
+            sample_bytes: DEFAULT_SAMPLE_BYTES,
+            verify: false,
+        }
+    }
+}
+
+impl HashOptions {
+    /// Options that always hash whole files
+    pub fn full() -> Self {
+        Self {
+            fast_above: None,
+            ..Self::default()
+        }
+    }
+
+    /// A file of `size` bytes gets a fast fingerprint
+    pub fn is_fast(&self, size: u64) -> bool {
+        self.fast_above.is_some_and(|min| size >= min)
+    }
+
+    /// Bytes read to hash a file of `size` bytes
+    fn bytes_read(&self, size: u64) -> u64 {
+        if self.is_fast(size) {
+            size.min(self.sample_bytes.saturating_mul(2))
+        } else {
+            size
+        }
+    }
+}
+
+/// Hash of the file size plus its first and last `sample_bytes`, a cheap stand-in for a
+/// full hash of multi-GB archives. Files that differ only in the middle get the same
+/// fingerprint; that is rare for re-downloads, but a match is not proof the files are
+/// identical.
+pub fn fast_fingerprint(path: &Path, sample_bytes: u64) -> Result<u64> {
+    let mut file = File::open(long_path(path))
+        .with_context(|| format!("Failed to open file for hashing: {:?}", path))?;
+    let size = file
+        .metadata()
+        .with_context(|| format!("Failed to read file size: {:?}", path))?
+        .len();
+
+    let mut hasher = Xxh64::new(0);
+    hasher.update(&size.to_le_bytes());
+    let mut buf = Vec::new();
+    let mut read_sample = |file: &mut File, len: u64| -> Result<()> {
+        buf.clear();
+        file.take(len)
+            .read_to_end(&mut buf)
+            .with_context(|| format!("Failed to read file for hashing: {:?}", path))?;
+        hasher.update(&buf);
+        Ok(())
+    };
+    if size <= sample_bytes.saturating_mul(2) {
+        read_sample(&mut file, size)?;
+    } else {
+        read_sample(&mut file, sample_bytes)?;
+        file.seek(SeekFrom::End(-(sample_bytes as i64)))
+            .with_context(|| format!("Failed to seek in file for hashing: {:?}", path))?;
+        read_sample(&mut file, sample_bytes)?;
+    }
+    Ok(hasher.digest())
+}
+
+/// Hash a file in chunks, calling `on_progress(bytes_hashed, total_bytes)` after each one.
+/// Returns `Ok(None)` if `cancel` fires before the file is finished.
+pub fn hash_file_with_progress(
+    path: &Path,
+    cancel: &CancelToken,
+    on_progress: &mut dyn FnMut(u64, u64),
+) -> Result<Option<u64>> {
+    let mut file = File::open(long_path(path))
+        .with_context(|| format!("Failed to open file for hashing: {:?}", path))?;
+    let total = file.metadata().map(|m| m.len()).unwrap_or(0);
+
+    let mut hasher = Xxh64::new(0);
+    let mut buf = vec![0u8; HASH_CHUNK_SIZE];
+    let mut done = 0u64;
+    loop {
+        if cancel.is_cancelled() {
+            return Ok(None);
+        }
+        let read = file
+            .read(&mut buf)
+            .with_context(|| format!("Failed to read file for hashing: {:?}", path))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buf[..read]);
+        done += read as u64;
+        on_progress(done, total.max(done));
+    }
+
+    Ok(Some(hasher.digest()))
+}
+
+/// Hash several files with progress counted in bytes across the whole batch.
+/// Unreadable files get an error entry; returns `None` if cancelled.
+pub fn hash_files_with_progress(
+    paths: &[PathBuf],
+    cancel: &CancelToken,
+    on_progress: &mut dyn FnMut(u64, u64),
+) -> Option<Vec<Result<u64, String>>> {
+    hash_files_with_options(paths, cancel, on_progress, &HashOptions::default())
+}
+
+/// Like `hash_files_with_progress`, with large files fingerprinted as set in `options`.
+/// Fingerprints and full hashes never match each other, but files of the same size are
+/// always hashed the same way.
+pub fn hash_files_with_options(
+    paths: &[PathBuf],
+    cancel: &CancelToken,
+    on_progress: &mut dyn FnMut(u64, u64),
+    options: &HashOptions,
+) -> Option<Vec<Result<u64, String>>> {
+    let sizes: Vec<u64> = paths
+        .iter()
+        .map(|p| {
+            std::fs::metadata(long_path(p))
+                .map(|m| m.len())
+                .unwrap_or(0)
+        })
+        .collect();
+    let total: u64 = sizes.iter().map(|&s| options.bytes_read(s)).sum();
+
+    let mut hashes = Vec::with_capacity(paths.len());
+    let mut finished = 0u64;
+    for (path, &size) in paths.iter().zip(&sizes) {
+        if options.is_fast(size) {
+            if cancel.is_cancelled() {
+                return None;
+            }
+            hashes
+                .push(fast_fingerprint(path, options.sample_bytes).map_err(|e| format!("{:#}", e)));
+            finished += options.bytes_read(size);
+            on_progress(finished, total.max(finished));
+            continue;
+        }
+        let result = hash_file_with_progress(path, cancel, &mut |done, _| {
+            on_progress(finished + done, total.max(finished + done))
+        });
+        match result {
+            Ok(Some(hash)) => hashes.push(Ok(hash)),
+            Ok(None) => return None,
+            Err(e) => hashes.push(Err(format!("{:#}", e))),
+        }
+        finished += size;
+    }
+    Some(hashes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn xxh64(data: &[u8]) -> u64 {
+        let mut hasher = Xxh64::new(0);
+        hasher.update(data);
+        hasher.digest()
+    }
+
+    #[test]
+    fn test_xxh64_known_values() {
+        assert_eq!(xxh64(b""), 0xEF46_DB37_51D8_E999);
+        assert_eq!(xxh64(b"a"), 0xD24E_C4F1_A98C_6E5B);
+        assert_eq!(xxh64(b"abc"), 0x44BC_2CF5_AD77_0999);
+        assert_eq!(
+            xxh64(b"Nobody inspects the spammish repetition"),
+            0xFBCE_A83C_8A37_8BF1
+        );
+
+        // Split updates hash the same as one buffer
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+        let mut hasher = Xxh64::new(0);
+        for chunk in data.chunks(13) {
+            hasher.update(chunk);
+        }
+        assert_eq!(hasher.digest(), xxh64(&data));
+    }
+
+    #[test]
+    fn test_parse_wabbajack_hash() {
+        assert_eq!(
+            parse_wabbajack_hash("menYUTfbRu8="),
+            Some(0xEF46_DB37_51D8_E999)
+        );
+        assert_eq!(parse_wabbajack_hash("menYUTfbRu8"), Some(xxh64(b"")));
+        assert_eq!(parse_wabbajack_hash("menYUTfb"), None);
+        assert_eq!(parse_wabbajack_hash("menYUTfb!u8="), None);
+    }
+
+    #[test]
+    fn test_hash_file_reports_byte_progress() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("big.7z");
+        let data = vec![42u8; HASH_CHUNK_SIZE * 2 + 10];
+        std::fs::write(&path, &data).unwrap();
+
+        let mut ticks = Vec::new();
+        let hash = hash_file_with_progress(&path, &CancelToken::new(), &mut |done, total| {
+            ticks.push((done, total))
+        })
+        .unwrap();
+        assert_eq!(hash, Some(xxh64(&data)));
+        let total = data.len() as u64;
+        assert_eq!(
+            ticks,
+            vec![
+                (HASH_CHUNK_SIZE as u64, total),
+                (HASH_CHUNK_SIZE as u64 * 2, total),
+                (total, total)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fast_fingerprint_of_large_files() {
+        let dir = tempdir().unwrap();
+        let sample = 1024;
+        let data: Vec<u8> = (0..10 * 1024u32).map(|i| (i % 251) as u8).collect();
+        let write = |name: &str, data: &[u8]| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, data).unwrap();
+            fast_fingerprint(&path, sample).unwrap()
+        };
+
+        let original = write("a.7z", &data);
+        assert_eq!(write("a (1).7z", &data), original);
+
+        // A change in either sampled end or in the size shows
+        let mut head = data.clone();
+        head[10] ^= 1;
+        assert_ne!(write("head.7z", &head), original);
+        let mut tail = data.clone();
+        *tail.last_mut().unwrap() ^= 1;
+        assert_ne!(write("tail.7z", &tail), original);
+        assert_ne!(write("short.7z", &data[..data.len() - 1]), original);
+
+        // The known blind spot: a change between the samples
+        let mut middle = data.clone();
+        middle[5000] ^= 1;
+        assert_eq!(write("middle.7z", &middle), original);
+
+        // Files no larger than both samples are read whole
+        let small = write("small.7z", &data[..2 * sample as usize]);
+        let mut changed = data[..2 * sample as usize].to_vec();
+        changed[sample as usize] ^= 1;
+        assert_ne!(write("small2.7z", &changed), small);
+
+        // Only files from the size cap on are fingerprinted
+        let paths = [dir.path().join("a.7z"), dir.path().join("middle.7z")];
+        let options = HashOptions {
+            fast_above: Some(data.len() as u64),
+            sample_bytes: sample,
+            verify: false,
+        };
+        let hashes =
+            hash_files_with_options(&paths, &CancelToken::new(), &mut |_, _| {}, &options).unwrap();
+        assert_eq!(hashes[0], hashes[1]);
+        let hashes = hash_files_with_options(
+            &paths,
+            &CancelToken::new(),
+            &mut |_, _| {},
+            &HashOptions::full(),
+        )
+        .unwrap();
+        assert_ne!(hashes[0], hashes[1]);
+        assert_eq!(hashes[0], Ok(xxh64(&data)));
+    }
+
+    #[test]
+    fn test_cancel_mid_hash_aborts_promptly() {
+        let dir = tempdir().unwrap();
+        let first = dir.path().join("first.7z");
+        let second = dir.path().join("second.7z");
+        std::fs::write(&first, vec![1u8; HASH_CHUNK_SIZE * 8]).unwrap();
+        std::fs::write(&second, b"small").unwrap();
+
+        let cancel = CancelToken::new();
+        let mut ticks = 0;
+        let result = hash_files_with_progress(&[first.clone(), second], &cancel, &mut |_, _| {
+            ticks += 1;
+            cancel.cancel();
+        });
+
+        // Stopped after the first chunk, without touching the files
+        assert!(result.is_none());
+        assert_eq!(ticks, 1);
+        assert_eq!(
+            std::fs::metadata(&first).unwrap().len(),
+            HASH_CHUNK_SIZE as u64 * 8
+        );
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+}
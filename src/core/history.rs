@@ -0,0 +1,219 @@
+// Copyright (C) 2025 Berkay Yetgin
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::core::config::config_file;
+use crate::core::types::ScanResult;
+
+const SCAN_HISTORY_FILE: &str = "scan_history.json";
+
+/// File paths and sizes from an orphaned scan, kept to compare against the next one
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScanSnapshot {
+    /// RFC 3339 time the scan finished
+    pub created: String,
+    pub orphaned: BTreeMap<PathBuf, u64>,
+    pub used: BTreeMap<PathBuf, u64>,
+}
+
+impl ScanSnapshot {
+    pub fn from_result(result: &ScanResult) -> Self {
+        Self {
+            created: chrono::Local::now().to_rfc3339(),
+            orphaned: result
+                .orphaned_mods
+                .iter()
+                .map(|m| (m.file.full_path.clone(), m.file.size))
+                .collect(),
+            used: result
+                .used_mods
+                .iter()
+                .map(|m| (m.file.full_path.clone(), m.file.size))
+                .collect(),
+        }
+    }
+}
+
+/// Changes in the orphaned set between two scans
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScanDelta {
+    /// When the previous scan ran
+    pub previous: String,
+    /// Orphaned now but not in the previous scan
+    pub new_orphans: Vec<(PathBuf, u64)>,
+    /// Orphaned before but no longer (deleted or now used)
+    pub resolved: Vec<(PathBuf, u64)>,
+    /// Resolved orphans that an active modlist uses again
+    pub now_used: Vec<(PathBuf, u64)>,
+}
+
+impl ScanDelta {
+    pub fn is_empty(&self) -> bool {
+        self.new_orphans.is_empty() && self.resolved.is_empty()
+    }
+
+    pub fn new_orphan_size(&self) -> u64 {
+        self.new_orphans.iter().map(|(_, size)| size).sum()
+    }
+
+    pub fn resolved_size(&self) -> u64 {
+        self.resolved.iter().map(|(_, size)| size).sum()
+    }
+}
+
+/// Compare two scans of the same downloads folder
+pub fn diff_scan_results(old: &ScanSnapshot, new: &ScanSnapshot) -> ScanDelta {
+    let new_orphans = new
+        .orphaned
+        .iter()
+        .filter(|(path, _)| !old.orphaned.contains_key(*path))
+        .map(|(path, size)| (path.clone(), *size))
+        .collect();
+    let resolved: Vec<(PathBuf, u64)> = old
+        .orphaned
+        .iter()
+        .filter(|(path, _)| !new.orphaned.contains_key(*path))
+        .map(|(path, size)| (path.clone(), *size))
+        .collect();
+    let now_used = resolved
+        .iter()
+        .filter(|(path, _)| new.used.contains_key(path))
+        .cloned()
+        .collect();
+
+    ScanDelta {
+        previous: old.created.clone(),
+        new_orphans,
+        resolved,
+        now_used,
+    }
+}
+
+/// Last orphaned scan per downloads folder
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScanHistory {
+    pub folders: HashMap<PathBuf, ScanSnapshot>,
+}
+
+impl ScanHistory {
+    /// Load the history file, falling back to an empty history if missing or unreadable
+    pub fn load(path: &Path) -> Self {
+        let content = match fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(_) => return Self::default(),
+        };
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::warn!("Ignoring invalid scan history {:?}: {}", path, e);
+            Self::default()
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {:?}", parent))?;
+        }
+        let json = serde_json::to_string(self).context("Failed to serialize scan history")?;
+        fs::write(path, json).with_context(|| format!("Failed to write scan history: {:?}", path))
+    }
+
+    /// Store `snapshot` for `downloads_dir` and return the diff against the previous one, if any
+    pub fn record(&mut self, downloads_dir: &Path, snapshot: ScanSnapshot) -> Option<ScanDelta> {
+        let delta = self
+            .folders
+            .get(downloads_dir)
+            .map(|old| diff_scan_results(old, &snapshot));
+        self.folders.insert(downloads_dir.to_path_buf(), snapshot);
+        delta
+    }
+}
+
+/// History key for a set of downloads roots. A single root keeps the key it had before
+/// several roots could be scanned together.
+pub fn history_key(roots: &[PathBuf]) -> Option<PathBuf> {
+    match roots {
+        [] => None,
+        [root] => Some(root.clone()),
+        _ => {
+            let mut names: Vec<String> = roots.iter().map(|r| r.display().to_string()).collect();
+            names.sort();
+            Some(PathBuf::from(names.join(";")))
+        }
+    }
+}
+
+/// Default scan history location in the config directory
+pub fn scan_history_path() -> Option<PathBuf> {
+    config_file(SCAN_HISTORY_FILE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(orphaned: &[(&str, u64)], used: &[(&str, u64)]) -> ScanSnapshot {
+        let map = |entries: &[(&str, u64)]| {
+            entries
+                .iter()
+                .map(|(p, s)| (PathBuf::from(p), *s))
+                .collect()
+        };
+        ScanSnapshot {
+            created: "2025-01-01T00:00:00+00:00".to_string(),
+            orphaned: map(orphaned),
+            used: map(used),
+        }
+    }
+
+    #[test]
+    fn test_diff_scan_results() {
+        let old = snapshot(&[("a.7z", 10), ("b.7z", 20), ("c.7z", 30)], &[("d.7z", 5)]);
+        let new = snapshot(&[("c.7z", 30), ("e.7z", 40)], &[("b.7z", 20), ("d.7z", 5)]);
+
+        let delta = diff_scan_results(&old, &new);
+        assert_eq!(delta.new_orphans, vec![(PathBuf::from("e.7z"), 40)]);
+        assert_eq!(
+            delta.resolved,
+            vec![(PathBuf::from("a.7z"), 10), (PathBuf::from("b.7z"), 20)]
+        );
+        assert_eq!(delta.now_used, vec![(PathBuf::from("b.7z"), 20)]);
+        assert_eq!(delta.new_orphan_size(), 40);
+        assert_eq!(delta.resolved_size(), 30);
+        assert_eq!(delta.previous, old.created);
+
+        assert!(diff_scan_results(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn test_history_record_keyed_by_downloads_folder() {
+        let mut history = ScanHistory::default();
+        let first = snapshot(&[("a.7z", 10)], &[]);
+        assert!(history.record(Path::new("D:/One"), first.clone()).is_none());
+        assert!(history.record(Path::new("D:/Two"), first).is_none());
+
+        let delta = history
+            .record(Path::new("D:/One"), snapshot(&[], &[]))
+            .unwrap();
+        assert_eq!(delta.resolved.len(), 1);
+        assert_eq!(history.folders[Path::new("D:/Two")].orphaned.len(), 1);
+
+        let one = PathBuf::from("D:/One");
+        let two = PathBuf::from("D:/Two");
+        assert_eq!(history_key(std::slice::from_ref(&one)), Some(one.clone()));
+        assert_eq!(
+            history_key(&[two.clone(), one.clone()]),
+            history_key(&[one, two])
+        );
+        assert_eq!(history_key(&[]), None);
+    }
+}
@@ -0,0 +1,159 @@
+// Copyright (C) 2025 Berkay Yetgin
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::core::config::config_file;
+use crate::core::types::ModFile;
+
+const DELETION_JOURNAL_FILE: &str = "deletion_journal.json";
+
+/// One file a cleanup run set out to remove
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub file: ModFile,
+    /// Recycle bin the file is moved to; `None` deletes it permanently
+    pub recycle_bin: Option<PathBuf>,
+}
+
+/// Files of a cleanup run that had not been removed when the app last stopped
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeletionJournal {
+    /// RFC 3339 time the run started
+    pub started: String,
+    pub pending: Vec<JournalEntry>,
+}
+
+impl DeletionJournal {
+    pub fn pending_size(&self) -> u64 {
+        self.pending.iter().map(|e| e.file.size).sum()
+    }
+}
+
+/// Completed files are appended here, one path per line, so each file costs one write
+fn done_log_path(journal: &Path) -> PathBuf {
+    PathBuf::from(format!("{}.done", journal.display()))
+}
+
+/// Record every file a run is about to remove, replacing any earlier journal
+pub fn begin_deletion_journal(journal: &Path, entries: Vec<JournalEntry>) -> Result<()> {
+    if let Some(parent) = journal.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {:?}", parent))?;
+    }
+    let content = DeletionJournal {
+        started: chrono::Local::now().to_rfc3339(),
+        pending: entries,
+    };
+    let json = serde_json::to_string(&content).context("Failed to serialize deletion journal")?;
+    fs::write(journal, json)
+        .with_context(|| format!("Failed to write deletion journal: {:?}", journal))?;
+    fs::write(done_log_path(journal), "")
+        .with_context(|| format!("Failed to reset deletion journal: {:?}", journal))
+}
+
+/// Mark one file of the journal as removed
+pub fn complete_deletion_entry(journal: &Path, file: &Path) -> Result<()> {
+    let mut log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(done_log_path(journal))
+        .with_context(|| format!("Failed to open deletion journal: {:?}", journal))?;
+    writeln!(log, "{}", file.display())
+        .with_context(|| format!("Failed to update deletion journal: {:?}", journal))
+}
+
+/// Remove the journal once a run has ended, however it ended
+pub fn finish_deletion_journal(journal: &Path) {
+    let _ = fs::remove_file(journal);
+    let _ = fs::remove_file(done_log_path(journal));
+}
+
+/// Files left over from a run that never finished, or `None` if there is nothing to resume
+pub fn pending_deletion(journal: &Path) -> Option<DeletionJournal> {
+    let content = fs::read_to_string(journal).ok()?;
+    let mut loaded: DeletionJournal = serde_json::from_str(&content)
+        .map_err(|e| log::warn!("Ignoring invalid deletion journal {:?}: {}", journal, e))
+        .ok()?;
+    let done = fs::read_to_string(done_log_path(journal)).unwrap_or_default();
+    let done: HashSet<&Path> = done.lines().map(Path::new).collect();
+    loaded
+        .pending
+        .retain(|e| !done.contains(e.file.full_path.as_path()));
+    (!loaded.pending.is_empty()).then_some(loaded)
+}
+
+/// Default deletion journal location in the config directory
+pub fn deletion_journal_path() -> Option<PathBuf> {
+    config_file(DELETION_JOURNAL_FILE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::cleaner::{resume_pending_deletion, DeleteOptions};
+    use crate::core::parser::parse_mod_filename;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_resume_partial_deletion_journal() {
+        let dir = tempdir().unwrap();
+        let journal = dir.path().join("config").join(DELETION_JOURNAL_FILE);
+        let game = dir.path().join("Skyrim");
+        let bin = dir.path().join("WLC_RecycleBin");
+        fs::create_dir(&game).unwrap();
+        let names = [
+            "First-11111-1-0-1600000000.7z",
+            "Second-22222-1-0-1600000000.7z",
+            "Third-33333-1-0-1600000000.7z",
+        ];
+        let entries: Vec<JournalEntry> = names
+            .iter()
+            .map(|name| {
+                let path = game.join(name);
+                fs::write(&path, b"data").unwrap();
+                let mut file = parse_mod_filename(name).unwrap();
+                file.full_path = path;
+                file.size = 4;
+                JournalEntry {
+                    file,
+                    recycle_bin: Some(bin.clone()),
+                }
+            })
+            .collect();
+        assert!(pending_deletion(&journal).is_none());
+        begin_deletion_journal(&journal, entries).unwrap();
+
+        // The first file went before the crash; the second was removed but not recorded
+        fs::create_dir(&bin).unwrap();
+        fs::rename(game.join(names[0]), bin.join(names[0])).unwrap();
+        complete_deletion_entry(&journal, &game.join(names[0])).unwrap();
+        fs::remove_file(game.join(names[1])).unwrap();
+
+        let pending = pending_deletion(&journal).unwrap();
+        assert_eq!(pending.pending.len(), 2);
+        assert_eq!(pending.pending_size(), 8);
+
+        let result = resume_pending_deletion(
+            &journal,
+            None,
+            &DeleteOptions::new(vec![dir.path().to_path_buf()]),
+        );
+        assert_eq!(result.deleted_count, 1);
+        assert!(result.errors.is_empty());
+        assert!(!game.join(names[2]).exists());
+        assert!(bin.join(names[2]).exists());
+        assert!(pending_deletion(&journal).is_none());
+        assert!(!journal.exists());
+    }
+}
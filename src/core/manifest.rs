@@ -0,0 +1,121 @@
+// Copyright (C) 2025 Berkay Yetgin
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::core::types::ModFile;
+
+/// Bumped when the manifest layout changes incompatibly
+const MANIFEST_FORMAT: u32 = 1;
+
+/// Parsed library metadata without the archives themselves, for offline analysis and bug reports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryManifest {
+    pub format: u32,
+    pub app_version: String,
+    pub created: String,
+    pub files: Vec<ModFile>,
+}
+
+/// Write the parsed metadata of `files` to a JSON manifest
+pub fn export_library_manifest(files: &[ModFile], out: &Path) -> Result<()> {
+    let manifest = LibraryManifest {
+        format: MANIFEST_FORMAT,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created: chrono::Local::now().to_rfc3339(),
+        files: files.to_vec(),
+    };
+    if let Some(parent) = out.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {:?}", parent))?;
+    }
+    let json =
+        serde_json::to_string_pretty(&manifest).context("Failed to serialize library manifest")?;
+    fs::write(out, json).with_context(|| format!("Failed to write library manifest: {:?}", out))
+}
+
+/// Read the files of a manifest written by `export_library_manifest`
+pub fn import_library_manifest(path: &Path) -> Result<Vec<ModFile>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read library manifest: {:?}", path))?;
+    let manifest: LibraryManifest = serde_json::from_str(&content)
+        .with_context(|| format!("Invalid library manifest: {:?}", path))?;
+    if manifest.format > MANIFEST_FORMAT {
+        bail!(
+            "Library manifest was written by a newer version ({}); please update",
+            manifest.app_version
+        );
+    }
+    Ok(manifest.files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::scanner::{find_old_versions_in_files, get_all_mod_files};
+    use std::fs::File;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_manifest_round_trip_supports_old_version_analysis() {
+        let dir = tempdir().unwrap();
+        let game_dir = dir.path().join("Skyrim");
+        fs::create_dir_all(&game_dir).unwrap();
+        for name in [
+            "SkyUI-12604-5-1-1600000000.7z",
+            "SkyUI-12604-5-2-1610000000.7z",
+            "Loose Archive.zip",
+        ] {
+            File::create(game_dir.join(name))
+                .unwrap()
+                .write_all(b"data")
+                .unwrap();
+        }
+        let files = get_all_mod_files(std::slice::from_ref(&game_dir)).unwrap();
+        assert_eq!(files.len(), 3);
+
+        let out = dir.path().join("reports").join("manifest.json");
+        export_library_manifest(&files, &out).unwrap();
+        let imported = import_library_manifest(&out).unwrap();
+        assert_eq!(imported.len(), files.len());
+        for (a, b) in files.iter().zip(&imported) {
+            assert_eq!(a.file_name, b.file_name);
+            assert_eq!(a.full_path, b.full_path);
+            assert_eq!(a.mod_id, b.mod_id);
+            assert_eq!(a.version, b.version);
+            assert_eq!(a.size, b.size);
+        }
+
+        // The archives are gone; analysis runs on the manifest alone
+        fs::remove_dir_all(&game_dir).unwrap();
+        let result = find_old_versions_in_files(&imported, &Default::default());
+        assert_eq!(result.duplicates.len(), 1);
+        assert_eq!(result.total_files, 1);
+        let group = &result.duplicates[0];
+        assert_eq!(
+            group.files[group.newest_idx].file_name,
+            "SkyUI-12604-5-2-1610000000.7z"
+        );
+    }
+
+    #[test]
+    fn test_manifest_from_newer_format_is_rejected() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("manifest.json");
+        fs::write(
+            &path,
+            r#"{"format": 99, "app_version": "9.0.0", "created": "", "files": []}"#,
+        )
+        .unwrap();
+        assert!(import_library_manifest(&path).is_err());
+    }
+}
@@ -0,0 +1,200 @@
+// Copyright (C) 2025 Berkay Yetgin
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::core::config::config_file;
+use crate::core::types::ModFile;
+
+const MANUAL_MAPPING_FILE: &str = "manual_ids.json";
+
+/// Nexus IDs assigned to a file by hand
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManualIds {
+    pub mod_id: String,
+    pub file_id: Option<String>,
+}
+
+/// User-provided file name to Nexus ID mapping for archives whose names don't parse
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ManualMapping {
+    /// Keyed by lowercase file name
+    pub entries: HashMap<String, ManualIds>,
+}
+
+impl ManualMapping {
+    /// Parse `filename -> modID,fileID` or `filename,modID,fileID` lines.
+    /// Blank lines and lines starting with `#` are ignored; the FileID is optional.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut mapping = Self::default();
+
+        for (line_no, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (file_name, ids): (String, Vec<&str>) = match line.split_once("->") {
+                Some((name, ids)) => (name.trim().to_string(), ids.split(',').collect()),
+                None => {
+                    // CSV: file names may contain commas, so take the IDs from the right
+                    let mut fields: Vec<&str> = line.rsplitn(3, ',').collect();
+                    fields.reverse();
+                    if fields.len() < 2 {
+                        bail!("Line {}: expected `filename -> modID,fileID`", line_no + 1);
+                    }
+                    // `name,modID` has no FileID; `name,modID,fileID` has both
+                    let split = if fields.len() == 3 && is_id(fields[1].trim()) {
+                        1
+                    } else {
+                        fields.len() - 1
+                    };
+                    (
+                        fields[..split].join(",").trim().to_string(),
+                        fields[split..].to_vec(),
+                    )
+                }
+            };
+
+            let ids: Vec<&str> = ids.iter().map(|s| s.trim()).collect();
+            let mod_id = ids.first().copied().unwrap_or_default();
+            let file_id = ids.get(1).copied().filter(|s| !s.is_empty());
+            if file_name.is_empty() || !is_id(mod_id) || file_id.is_some_and(|f| !is_id(f)) {
+                bail!("Line {}: invalid mapping `{}`", line_no + 1, line);
+            }
+
+            mapping.entries.insert(
+                file_name.to_lowercase(),
+                ManualIds {
+                    mod_id: mod_id.to_string(),
+                    file_id: file_id.map(str::to_string),
+                },
+            );
+        }
+
+        Ok(mapping)
+    }
+
+    /// Load a stored mapping, falling back to an empty one if missing or unreadable
+    pub fn load(path: &Path) -> Self {
+        let content = match fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(_) => return Self::default(),
+        };
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::warn!("Ignoring invalid manual ID mapping {:?}: {}", path, e);
+            Self::default()
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {:?}", parent))?;
+        }
+        let json = serde_json::to_string_pretty(self).context("Failed to serialize ID mapping")?;
+        fs::write(path, json).with_context(|| format!("Failed to write ID mapping: {:?}", path))
+    }
+
+    /// Merge another mapping into this one, replacing existing entries
+    pub fn merge(&mut self, other: ManualMapping) {
+        self.entries.extend(other.entries);
+    }
+
+    /// Assign mapped IDs to files whose names could not be parsed. Returns how many changed.
+    pub fn apply(&self, files: &mut [ModFile]) -> usize {
+        if self.entries.is_empty() {
+            return 0;
+        }
+        let mut applied = 0;
+        for file in files.iter_mut().filter(|f| f.mod_id == "0") {
+            if let Some(ids) = self.entries.get(&file.file_name.to_lowercase()) {
+                file.mod_id = ids.mod_id.clone();
+                file.file_id = ids.file_id.clone();
+                applied += 1;
+            }
+        }
+        applied
+    }
+}
+
+fn is_id(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Default manual ID mapping location in the config directory
+pub fn manual_mapping_path() -> Option<PathBuf> {
+    config_file(MANUAL_MAPPING_FILE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::scanner::{detect_orphaned_mods, get_all_mod_files};
+    use crate::core::types::{MatchReason, ModlistInfo};
+    use std::collections::HashSet;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_parse_mapping_formats() {
+        let mapping = ManualMapping::parse(
+            "# exported from Nexus\n\
+             Renamed SkyUI.7z -> 12604,52344\n\
+             \n\
+             Some, Mod.zip,266,1000\n\
+             NoFileId.rar,3863\n",
+        )
+        .unwrap();
+
+        assert_eq!(mapping.entries.len(), 3);
+        assert_eq!(
+            mapping.entries["renamed skyui.7z"],
+            ManualIds {
+                mod_id: "12604".to_string(),
+                file_id: Some("52344".to_string()),
+            }
+        );
+        assert_eq!(mapping.entries["some, mod.zip"].mod_id, "266");
+        assert_eq!(mapping.entries["nofileid.rar"].file_id, None);
+
+        assert!(ManualMapping::parse("broken.7z -> abc").is_err());
+    }
+
+    #[test]
+    fn test_apply_mapping_to_unparseable_file() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("Renamed SkyUI.7z"), b"data").unwrap();
+
+        let mut files = get_all_mod_files(&[dir.path().to_path_buf()]).unwrap();
+        assert_eq!(files[0].mod_id, "0");
+
+        let mapping = ManualMapping::parse("renamed skyui.7z -> 12604,52344").unwrap();
+        assert_eq!(mapping.apply(&mut files), 1);
+        assert_eq!(files[0].mod_id, "12604");
+        assert_eq!(files[0].file_id.as_deref(), Some("52344"));
+
+        // The mapped IDs let the renamed file match the modlist archive
+        let modlist = ModlistInfo {
+            file_path: PathBuf::new(),
+            name: "Test".to_string(),
+            mod_count: 1,
+            used_mod_keys: HashSet::from(["12604".to_string()]),
+            used_mod_file_ids: HashSet::from(["12604-52344".to_string()]),
+            used_file_names: HashSet::from(["SkyUI_5_2_SE-12604-5-2SE-1615410779.7z".to_string()]),
+            game: None,
+            archive_hashes: HashMap::new(),
+        };
+        let result = detect_orphaned_mods(&files, &[modlist]);
+        assert_eq!(result.used_mods.len(), 1);
+        assert_eq!(result.used_mods[0].reason, MatchReason::FileId);
+    }
+}
@@ -0,0 +1,251 @@
+// Copyright (C) 2025 Berkay Yetgin
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+use crate::core::scanner::OrphanScanOptions;
+use crate::core::types::{MatchReason, ModFile, ModlistInfo};
+
+/// One way of telling that a downloaded file is used by the active modlists
+pub trait Matcher: Send + Sync {
+    /// How the file is matched, or `None` if this strategy doesn't consider it used
+    fn matches(&self, file: &ModFile, modlists: &[ModlistInfo]) -> Option<MatchReason>;
+}
+
+/// Exact file name listed in a modlist (most reliable)
+pub struct FileNameMatcher;
+
+/// Same ModID and FileID as a modlist archive
+pub struct FileIdMatcher;
+
+/// Same ModID as any modlist archive; loose, keeps every version of a used mod
+pub struct ModIdMatcher;
+
+/// Files with one of these extensions are never orphaned
+pub struct ProtectedExtensionMatcher {
+    pub extensions: Vec<String>,
+}
+
+/// Whether `modlist` accounts for a file matched by `reason`
+pub(crate) fn modlist_references(
+    modlist: &ModlistInfo,
+    file: &ModFile,
+    reason: MatchReason,
+) -> bool {
+    match reason {
+        MatchReason::FileName => modlist.used_file_names.contains(&file.file_name),
+        // A file named by its FileID alone matches that FileID under any ModID
+        MatchReason::FileId => file.file_id.as_ref().is_some_and(|file_id| {
+            if file.mod_id.is_empty() {
+                modlist
+                    .used_mod_file_ids
+                    .iter()
+                    .any(|key| key.rsplit_once('-').is_some_and(|(_, id)| id == file_id))
+            } else {
+                modlist
+                    .used_mod_file_ids
+                    .contains(&format!("{}-{}", file.mod_id, file_id))
+            }
+        }),
+        MatchReason::ModId => modlist.used_mod_keys.contains(&file.mod_id),
+        MatchReason::ProtectedExtension | MatchReason::SiblingPart => false,
+    }
+}
+
+fn referenced_by_any(
+    file: &ModFile,
+    modlists: &[ModlistInfo],
+    reason: MatchReason,
+) -> Option<MatchReason> {
+    modlists
+        .iter()
+        .any(|ml| modlist_references(ml, file, reason))
+        .then_some(reason)
+}
+
+/// Case-insensitive check of a file name against extensions like ".exe"
+pub(crate) fn has_extension(file_name: &str, extensions: &[String]) -> bool {
+    let lower = file_name.to_lowercase();
+    extensions
+        .iter()
+        .any(|ext| lower.ends_with(&ext.to_lowercase()))
+}
+
+impl Matcher for FileNameMatcher {
+    fn matches(&self, file: &ModFile, modlists: &[ModlistInfo]) -> Option<MatchReason> {
+        referenced_by_any(file, modlists, MatchReason::FileName)
+    }
+}
+
+impl Matcher for FileIdMatcher {
+    fn matches(&self, file: &ModFile, modlists: &[ModlistInfo]) -> Option<MatchReason> {
+        referenced_by_any(file, modlists, MatchReason::FileId)
+    }
+}
+
+impl Matcher for ModIdMatcher {
+    fn matches(&self, file: &ModFile, modlists: &[ModlistInfo]) -> Option<MatchReason> {
+        referenced_by_any(file, modlists, MatchReason::ModId)
+    }
+}
+
+impl Matcher for ProtectedExtensionMatcher {
+    fn matches(&self, file: &ModFile, _modlists: &[ModlistInfo]) -> Option<MatchReason> {
+        has_extension(&file.file_name, &self.extensions).then_some(MatchReason::ProtectedExtension)
+    }
+}
+
+/// The matchers enabled by `options`, in the order they are tried: file name, FileID,
+/// the ModID fallback, then protected extensions
+pub fn default_matchers(options: &OrphanScanOptions) -> Vec<Box<dyn Matcher>> {
+    let mut matchers: Vec<Box<dyn Matcher>> =
+        vec![Box::new(FileNameMatcher), Box::new(FileIdMatcher)];
+    if options.mod_id_fallback {
+        matchers.push(Box::new(ModIdMatcher));
+    }
+    if !options.protected_extensions.is_empty() {
+        matchers.push(Box::new(ProtectedExtensionMatcher {
+            extensions: options.protected_extensions.clone(),
+        }));
+    }
+    matchers
+}
+
+/// Reason of the first matcher in the chain that considers the file used
+pub fn first_match(
+    matchers: &[Box<dyn Matcher>],
+    file: &ModFile,
+    modlists: &[ModlistInfo],
+) -> Option<MatchReason> {
+    matchers.iter().find_map(|m| m.matches(file, modlists))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::parser::parse_mod_filename;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    fn modlist(file_names: &[&str]) -> ModlistInfo {
+        let files: Vec<ModFile> = file_names
+            .iter()
+            .map(|n| parse_mod_filename(n).unwrap())
+            .collect();
+        ModlistInfo {
+            file_path: PathBuf::new(),
+            name: "Test".to_string(),
+            mod_count: files.len(),
+            used_mod_keys: files.iter().map(|f| f.mod_id.clone()).collect(),
+            used_mod_file_ids: files
+                .iter()
+                .filter_map(|f| Some(format!("{}-{}", f.mod_id, f.file_id.as_ref()?)))
+                .collect(),
+            used_file_names: file_names.iter().map(|n| n.to_string()).collect(),
+            game: None,
+            archive_hashes: HashMap::new(),
+        }
+    }
+
+    fn file(name: &str) -> ModFile {
+        parse_mod_filename(name).unwrap()
+    }
+
+    #[test]
+    fn test_each_matcher_in_isolation() {
+        let modlists = [modlist(&["SkyUI_5_2SE-12604-5-2SE-52344-1615410779.7z"])];
+        let listed = file("SkyUI_5_2SE-12604-5-2SE-52344-1615410779.7z");
+        let renamed = file("SkyUI Renamed-12604-5-2SE-52344-1615410779.7z");
+        let other_version = file("SkyUI_5_1SE-12604-5-1SE-40000-1600000000.7z");
+        let unrelated = file("Unrelated-99999-1-0-1600000000.exe");
+        let cases: [(&dyn Matcher, [Option<MatchReason>; 4]); 4] = [
+            (
+                &FileNameMatcher,
+                [Some(MatchReason::FileName), None, None, None],
+            ),
+            (
+                &FileIdMatcher,
+                [
+                    Some(MatchReason::FileId),
+                    Some(MatchReason::FileId),
+                    None,
+                    None,
+                ],
+            ),
+            (
+                &ModIdMatcher,
+                [
+                    Some(MatchReason::ModId),
+                    Some(MatchReason::ModId),
+                    Some(MatchReason::ModId),
+                    None,
+                ],
+            ),
+            (
+                &ProtectedExtensionMatcher {
+                    extensions: vec![".EXE".to_string()],
+                },
+                [None, None, None, Some(MatchReason::ProtectedExtension)],
+            ),
+        ];
+        for (matcher, expected) in cases {
+            let got = [&listed, &renamed, &other_version, &unrelated]
+                .map(|f| matcher.matches(f, &modlists));
+            assert_eq!(got, expected);
+        }
+        assert_eq!(FileNameMatcher.matches(&listed, &[]), None);
+    }
+
+    #[test]
+    fn test_matcher_chain_order() {
+        let modlists = [modlist(&["SkyUI_5_2SE-12604-5-2SE-52344-1615410779.7z"])];
+        let listed = file("SkyUI_5_2SE-12604-5-2SE-52344-1615410779.7z");
+        let renamed = file("SkyUI Renamed-12604-5-2SE-52344-1615410779.7z");
+        let other_version = file("SkyUI_5_1SE-12604-5-1SE-40000-1600000000.7z");
+
+        // Loose by default; strict drops the ModID fallback
+        assert_eq!(default_matchers(&OrphanScanOptions::default()).len(), 3);
+        let strict = default_matchers(&OrphanScanOptions {
+            mod_id_fallback: false,
+            ..Default::default()
+        });
+        assert_eq!(strict.len(), 2);
+        assert_eq!(
+            first_match(&strict, &listed, &modlists),
+            Some(MatchReason::FileName)
+        );
+        assert_eq!(
+            first_match(&strict, &renamed, &modlists),
+            Some(MatchReason::FileId)
+        );
+        assert_eq!(first_match(&strict, &other_version, &modlists), None);
+
+        let options = OrphanScanOptions {
+            protected_extensions: vec![".7z".to_string()],
+            ..Default::default()
+        };
+        let loose = default_matchers(&options);
+        assert_eq!(
+            first_match(&loose, &renamed, &modlists),
+            Some(MatchReason::FileId)
+        );
+        assert_eq!(
+            first_match(&loose, &other_version, &modlists),
+            Some(MatchReason::ModId)
+        );
+        assert_eq!(
+            first_match(&loose, &file("Other-55555-1-0-1600000000.7z"), &modlists),
+            Some(MatchReason::ProtectedExtension)
+        );
+
+        // A custom chain tries its own order
+        let reversed: Vec<Box<dyn Matcher>> =
+            vec![Box::new(ModIdMatcher), Box::new(FileNameMatcher)];
+        assert_eq!(
+            first_match(&reversed, &listed, &modlists),
+            Some(MatchReason::ModId)
+        );
+    }
+}
@@ -0,0 +1,317 @@
+// Copyright (C) 2025 Berkay Yetgin
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::core::types::ModlistInfo;
+
+const MO2_INI_FILE: &str = "ModOrganizer.ini";
+
+/// Per-mod INI that MO2 writes into each folder of its `mods` directory
+const MO2_META_FILE: &str = "meta.ini";
+
+/// Name shown for files kept because an MO2 mod installed from them
+pub const MO2_INSTALLED_MODS_NAME: &str = "MO2 installed mods";
+
+/// `key=value` pairs of one INI section (`section` like "Settings"), unquoted and
+/// unescaped. Qt writes paths as `@ByteArray(...)` with doubled backslashes.
+fn ini_section<'a>(content: &'a str, section: &'a str) -> impl Iterator<Item = (&'a str, String)> {
+    let mut in_section = false;
+    content.lines().filter_map(move |line| {
+        let line = line.trim();
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            in_section = name.eq_ignore_ascii_case(section);
+            return None;
+        }
+        if !in_section {
+            return None;
+        }
+        let (name, value) = line.split_once('=')?;
+        let mut value = value.trim();
+        if let Some(inner) = value
+            .strip_prefix("@ByteArray(")
+            .and_then(|v| v.strip_suffix(')'))
+        {
+            value = inner;
+        }
+        Some((name.trim(), value.trim_matches('"').replace("\\\\", "\\")))
+    })
+}
+
+/// Value of `key` in the `[Settings]` section of an MO2 INI
+fn ini_setting(content: &str, key: &str) -> Option<String> {
+    ini_section(content, "Settings")
+        .find(|(name, _)| name.eq_ignore_ascii_case(key))
+        .map(|(_, value)| value)
+        .filter(|value| !value.is_empty())
+}
+
+/// Expand `%BASE_DIR%` and environment variables like `%USERPROFILE%`. Unknown
+/// variables are left as they are. Relative results are taken from `base_dir`.
+fn expand_mo2_path(value: &str, base_dir: &Path) -> PathBuf {
+    let mut expanded = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find('%') {
+        let Some(len) = rest[start + 1..].find('%') else {
+            break;
+        };
+        let name = &rest[start + 1..start + 1 + len];
+        expanded.push_str(&rest[..start]);
+        if name.eq_ignore_ascii_case("BASE_DIR") {
+            expanded.push_str(&base_dir.to_string_lossy());
+        } else if let Some(var) = std::env::var_os(name).filter(|_| !name.is_empty()) {
+            expanded.push_str(&var.to_string_lossy());
+        } else {
+            expanded.push_str(&rest[start..start + len + 2]);
+        }
+        rest = &rest[start + len + 2..];
+    }
+    expanded.push_str(rest);
+
+    let path = PathBuf::from(expanded);
+    if path.is_absolute() {
+        path
+    } else {
+        base_dir.join(path)
+    }
+}
+
+/// Downloads folder configured in an MO2 instance (the folder holding `ModOrganizer.ini`,
+/// or the INI itself). Falls back to MO2's default `%BASE_DIR%/downloads`. Returns `None`
+/// if the INI is missing or the folder does not exist.
+pub fn read_mo2_downloads(instance_dir: &Path) -> Option<PathBuf> {
+    let instance_dir = if instance_dir
+        .file_name()
+        .is_some_and(|n| n.eq_ignore_ascii_case(MO2_INI_FILE))
+    {
+        instance_dir.parent()?
+    } else {
+        instance_dir
+    };
+    let content = fs::read_to_string(instance_dir.join(MO2_INI_FILE)).ok()?;
+
+    let base_dir = ini_setting(&content, "base_directory")
+        .map(|b| expand_mo2_path(&b, instance_dir))
+        .unwrap_or_else(|| instance_dir.to_path_buf());
+    let downloads = ini_setting(&content, "download_directory")
+        .unwrap_or_else(|| "%BASE_DIR%/downloads".to_string());
+    let path = expand_mo2_path(&downloads, &base_dir);
+
+    if path.is_dir() {
+        Some(path)
+    } else {
+        log::warn!("MO2 downloads folder does not exist: {:?}", path);
+        None
+    }
+}
+
+/// Archives the mods in an MO2 `mods` folder were installed from, read from each mod's
+/// `meta.ini`, as a modlist so their downloads count as used. `None` if the folder can't
+/// be read.
+pub fn collect_installed_mods(mods_dir: &Path) -> Option<ModlistInfo> {
+    let entries = fs::read_dir(mods_dir)
+        .map_err(|e| log::warn!("Cannot read MO2 mods folder {:?}: {}", mods_dir, e))
+        .ok()?;
+
+    let mut mod_count = 0;
+    let mut used_mod_keys = HashSet::new();
+    let mut used_mod_file_ids = HashSet::new();
+    let mut used_file_names = HashSet::new();
+    for entry in entries.filter_map(|e| e.ok()) {
+        let Ok(content) = fs::read_to_string(entry.path().join(MO2_META_FILE)) else {
+            continue;
+        };
+        mod_count += 1;
+
+        let general: Vec<(&str, String)> = ini_section(&content, "General").collect();
+        let general_value = |key: &str| {
+            general
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(key))
+                .map(|(_, value)| value.as_str())
+        };
+        // Older MO2 versions store the full path of the archive
+        if let Some(file) = general_value("installationFile")
+            .and_then(|f| f.rsplit(['/', '\\']).next())
+            .filter(|f| !f.is_empty())
+        {
+            used_file_names.insert(file.to_string());
+        }
+        let mod_id = general_value("modid")
+            .and_then(|id| id.parse::<i64>().ok())
+            .filter(|&id| id > 0);
+        if let Some(mod_id) = mod_id {
+            used_mod_keys.insert(mod_id.to_string());
+        }
+
+        // `[installedFiles]` lists `N\modid` and `N\fileid` per installed file
+        let installed: Vec<(&str, String)> = ini_section(&content, "installedFiles").collect();
+        for (name, file_id) in &installed {
+            let Some(index) = name.strip_suffix("\\fileid") else {
+                continue;
+            };
+            let file_mod_id = installed
+                .iter()
+                .find(|(n, _)| *n == format!("{}\\modid", index))
+                .and_then(|(_, id)| id.parse::<i64>().ok())
+                .or(mod_id);
+            if let (Some(m), Ok(f)) = (file_mod_id, file_id.parse::<i64>()) {
+                if m > 0 && f > 0 {
+                    used_mod_file_ids.insert(format!("{}-{}", m, f));
+                }
+            }
+        }
+    }
+
+    log::info!(
+        "Read {} installed MO2 mods from {:?}: {} ModIDs, {} file names",
+        mod_count,
+        mods_dir,
+        used_mod_keys.len(),
+        used_file_names.len()
+    );
+    Some(ModlistInfo {
+        file_path: mods_dir.to_path_buf(),
+        name: MO2_INSTALLED_MODS_NAME.to_string(),
+        mod_count,
+        used_mod_keys,
+        used_mod_file_ids,
+        used_file_names,
+        game: None,
+        archive_hashes: HashMap::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_ini_setting_unescapes_qt_values() {
+        let ini = "[General]\ndownload_directory=wrong\n\n[Settings]\n\
+                   base_directory=\"C:\\\\Modding\"\n\
+                   download_directory=@ByteArray(D:\\\\Wabbajack\\\\downloads)\n";
+        assert_eq!(
+            ini_setting(ini, "download_directory").as_deref(),
+            Some("D:\\Wabbajack\\downloads")
+        );
+        assert_eq!(
+            ini_setting(ini, "base_directory").as_deref(),
+            Some("C:\\Modding")
+        );
+        assert_eq!(ini_setting(ini, "profiles_directory"), None);
+    }
+
+    #[test]
+    fn test_installed_mods_protect_their_archives() {
+        let dir = tempdir().unwrap();
+        let mods = dir.path().join("mods");
+        let skyui = mods.join("SkyUI");
+        fs::create_dir_all(&skyui).unwrap();
+        fs::write(
+            skyui.join(MO2_META_FILE),
+            "[General]\ngameName=SkyrimSE\nmodid=12604\nversion=5.2\n\
+             installationFile=C:/Modding/downloads/SkyUI_5_2_SE-12604-5-2SE.7z\n\
+             repository=Nexus\n\n[installedFiles]\n1\\modid=12604\n1\\fileid=35407\nsize=1\n",
+        )
+        .unwrap();
+        // A manual install without Nexus info and a folder without meta.ini
+        fs::create_dir_all(mods.join("My Patch")).unwrap();
+        fs::write(
+            mods.join("My Patch").join(MO2_META_FILE),
+            "[General]\nmodid=0\n",
+        )
+        .unwrap();
+        fs::create_dir_all(mods.join("Overwrite")).unwrap();
+
+        let installed = collect_installed_mods(&mods).unwrap();
+        assert_eq!(installed.name, MO2_INSTALLED_MODS_NAME);
+        assert_eq!(installed.mod_count, 2);
+        assert_eq!(
+            installed.used_mod_keys,
+            HashSet::from(["12604".to_string()])
+        );
+        assert!(installed.used_mod_file_ids.contains("12604-35407"));
+        assert!(installed
+            .used_file_names
+            .contains("SkyUI_5_2_SE-12604-5-2SE.7z"));
+
+        // Its archive counts as used; an unrelated one stays orphaned
+        let downloads = dir.path().join("downloads");
+        fs::create_dir_all(&downloads).unwrap();
+        fs::write(downloads.join("SkyUI_5_2_SE-12604-5-2SE.7z"), b"x").unwrap();
+        fs::write(downloads.join("Other-22222-1-0-1600000000.7z"), b"x").unwrap();
+        let files = crate::core::scanner::scan_mod_files_in_folder(&downloads);
+        let result = crate::core::scanner::detect_orphaned_mods(&files, &[installed]);
+        let orphaned: Vec<&str> = result
+            .orphaned_mods
+            .iter()
+            .map(|m| m.file.file_name.as_str())
+            .collect();
+        assert_eq!(orphaned, ["Other-22222-1-0-1600000000.7z"]);
+
+        assert!(collect_installed_mods(&dir.path().join("missing")).is_none());
+    }
+
+    #[test]
+    fn test_read_mo2_downloads() {
+        let dir = tempdir().unwrap();
+        let instance = dir.path().join("Instance");
+        let storage = dir.path().join("Storage");
+        fs::create_dir_all(storage.join("downloads")).unwrap();
+        fs::create_dir_all(instance.join("downloads")).unwrap();
+        let ini = instance.join(MO2_INI_FILE);
+
+        // No setting: MO2's default inside the instance
+        fs::write(&ini, "[General]\ngameName=Skyrim Special Edition\n").unwrap();
+        assert_eq!(
+            read_mo2_downloads(&instance),
+            Some(instance.join("downloads"))
+        );
+
+        // %BASE_DIR% follows base_directory, and the INI path itself is accepted
+        let content = format!(
+            "[Settings]\nbase_directory={}\ndownload_directory=%BASE_DIR%/downloads\n",
+            storage.display()
+        );
+        fs::write(&ini, content).unwrap();
+        assert_eq!(read_mo2_downloads(&ini), Some(storage.join("downloads")));
+
+        // Relative paths are taken from the base directory
+        fs::write(
+            &ini,
+            "[Settings]\ndownload_directory=../Storage/downloads\n",
+        )
+        .unwrap();
+        assert_eq!(
+            read_mo2_downloads(&instance),
+            Some(instance.join("../Storage/downloads"))
+        );
+
+        // Environment variables are expanded; unknown ones leave an invalid path
+        std::env::set_var("WLC_TEST_MO2_ROOT", dir.path());
+        fs::write(
+            &ini,
+            "[Settings]\ndownload_directory=%WLC_TEST_MO2_ROOT%/Storage/downloads\n",
+        )
+        .unwrap();
+        assert_eq!(
+            read_mo2_downloads(&instance),
+            Some(dir.path().join("Storage/downloads"))
+        );
+        fs::write(&ini, "[Settings]\ndownload_directory=%WLC_TEST_UNSET%/x\n").unwrap();
+        assert_eq!(read_mo2_downloads(&instance), None);
+
+        // Missing folders and missing INI files are rejected
+        fs::write(&ini, "[Settings]\ndownload_directory=gone\n").unwrap();
+        assert_eq!(read_mo2_downloads(&instance), None);
+        assert_eq!(read_mo2_downloads(&storage), None);
+    }
+}
@@ -5,12 +5,48 @@
 // the Free Software Foundation, either version 3 of the License, or
 // (at your option) any later version.
 
+pub mod archive;
+pub mod backup;
+pub mod cache;
 pub mod cleaner;
+pub mod config;
+pub mod dedup;
+pub mod hash;
+pub mod history;
+pub mod journal;
+pub mod manifest;
+pub mod mapping;
+pub mod matcher;
+pub mod mo2;
 pub mod parser;
+pub mod platform;
+pub mod reconcile;
+pub mod report;
 pub mod scanner;
+pub mod script;
 pub mod types;
+pub mod update;
+pub mod wabbajack;
 
+pub use archive::*;
+pub use backup::*;
+pub use cache::*;
 pub use cleaner::*;
+pub use config::*;
+pub use dedup::*;
+pub use hash::*;
+pub use history::*;
+pub use journal::*;
+pub use manifest::*;
+pub use mapping::*;
+pub use matcher::*;
+pub use mo2::*;
 pub use parser::*;
+pub use platform::*;
+pub use reconcile::*;
+pub use report::*;
 pub use scanner::*;
+pub use script::*;
 pub use types::*;
+pub use update::*;
+pub use wabbajack::*;
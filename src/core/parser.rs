@@ -5,15 +5,17 @@
 // the Free Software Foundation, either version 3 of the License, or
 // (at your option) any later version.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use serde::Deserialize;
+use serde_json::Value;
 use zip::ZipArchive;
 
+use crate::core::hash::parse_wabbajack_hash;
 use crate::core::types::{ModFile, ModlistInfo, ARCHIVE_EXTENSIONS};
 
 /// JSON structures for parsing .wabbajack files
@@ -34,7 +36,6 @@ struct Modlist {
 #[derive(Debug, Deserialize)]
 struct ModlistArchive {
     #[serde(rename = "Hash")]
-    #[allow(dead_code)]
     hash: Option<String>,
     #[serde(rename = "Name")]
     #[allow(dead_code)]
@@ -56,7 +57,6 @@ struct ModlistModState {
     #[serde(rename = "FileID")]
     file_id: Option<i64>,
     #[serde(rename = "GameName")]
-    #[allow(dead_code)]
     game_name: Option<String>,
     #[serde(rename = "Name")]
     #[allow(dead_code)]
@@ -66,6 +66,18 @@ struct ModlistModState {
     version: Option<String>,
 }
 
+/// Numeric parts of a version like "1-2-3", "v2.1" or "1_0"; `None` if any part isn't a number
+pub fn parse_version_numbers(version: &str) -> Option<Vec<u64>> {
+    let trimmed = version.trim().trim_start_matches(['v', 'V']);
+    if trimmed.is_empty() {
+        return None;
+    }
+    trimmed
+        .split(['-', '.', '_'])
+        .map(|p| p.parse().ok())
+        .collect()
+}
+
 /// Check if a string contains only digits (optionally with leading minus)
 pub fn is_numeric(s: &str) -> bool {
     if s.is_empty() {
@@ -178,39 +190,76 @@ pub fn extract_part_indicator(filename: &str) -> Option<String> {
     None
 }
 
+/// Split a "Part N"/"PtN" marker off a mod name, e.g. "Big Textures Part 2" gives
+/// ("big textures", 2), so the archives of a multi-part mod can be tied together
+pub fn split_part_indicator(mod_name: &str) -> Option<(String, u32)> {
+    let words: Vec<String> = mod_name
+        .split(|c: char| c.is_whitespace() || c == '_')
+        .map(|w| {
+            w.trim_matches(|c: char| matches!(c, '(' | ')' | '[' | ']' | '-'))
+                .to_lowercase()
+        })
+        .filter(|w| !w.is_empty())
+        .collect();
+    let mut base = Vec::new();
+    let mut part = None;
+    let mut i = 0;
+    while i < words.len() {
+        let word = &words[i];
+        let rest = word
+            .strip_prefix("part")
+            .or_else(|| word.strip_prefix("pt"))
+            .filter(|_| part.is_none());
+        match rest {
+            Some("") if words.get(i + 1).is_some_and(|n| n.parse::<u32>().is_ok()) => {
+                part = words[i + 1].parse().ok();
+                i += 2;
+                continue;
+            }
+            Some(n) if n.parse::<u32>().is_ok() => part = n.parse().ok(),
+            _ => base.push(word.as_str()),
+        }
+        i += 1;
+    }
+    part.map(|p| (base.join(" "), p))
+}
+
+/// Split a file name into its stem and archive extension, ignoring ASCII case.
+/// Compares the original bytes so the stem slice always matches the extension found.
+fn split_archive_extension(filename: &str) -> Option<(&str, &'static str)> {
+    ARCHIVE_EXTENSIONS.iter().find_map(|ext| {
+        let stem_len = filename.len().checked_sub(ext.len())?;
+        (filename.is_char_boundary(stem_len) && filename[stem_len..].eq_ignore_ascii_case(ext))
+            .then(|| (&filename[..stem_len], *ext))
+    })
+}
+
 /// Check if a file has a valid archive extension
 pub fn has_valid_archive_extension(filename: &str) -> bool {
-    let lower = filename.to_lowercase();
-    ARCHIVE_EXTENSIONS.iter().any(|ext| lower.ends_with(ext))
+    split_archive_extension(filename).is_some()
 }
 
-/// Check if a file is a valid Wabbajack mod file
-pub fn is_wabbajack_file(filename: &str) -> bool {
-    if !has_valid_archive_extension(filename) {
-        return false;
-    }
-
+/// Markers of unfinished downloads and temp files, in any letter case
+pub fn is_partial_download(filename: &str) -> bool {
     let lower = filename.to_lowercase();
-    if lower.contains(".part")
+    lower.contains(".part")
         || lower.contains(".tmp")
         || lower.contains(".download")
         || lower.starts_with('~')
-    {
-        return false;
-    }
+}
 
-    true
+/// Check if a file is a valid Wabbajack mod file
+pub fn is_wabbajack_file(filename: &str) -> bool {
+    has_valid_archive_extension(filename) && !is_partial_download(filename)
 }
 
 /// Parse a mod filename into its components
 pub fn parse_mod_filename(filename: &str) -> Option<ModFile> {
-    // Check extension
-    let ext = ARCHIVE_EXTENSIONS
-        .iter()
-        .find(|ext| filename.to_lowercase().ends_with(*ext))?;
-
-    // Remove extension
-    let name_without_ext = &filename[..filename.len() - ext.len()];
+    // Check and remove extension; unfinished downloads never parse as archives
+    if is_partial_download(filename) {
+        return None;
+    }
+    let (name_without_ext, _) = split_archive_extension(filename)?;
 
     // Split by dash
     let parts: Vec<&str> = name_without_ext.split('-').collect();
@@ -239,24 +288,31 @@ pub fn parse_mod_filename(filename: &str) -> Option<ModFile> {
     let mod_id = mod_id?;
     let mod_id_index = mod_id_index?;
 
-    // Try to find FileID (numeric part after ModID, typically 4-7 digits)
-    let mut file_id = None;
-    let mut file_id_index = None;
-
-    if mod_id_index + 1 < parts.len() - 1 {
-        let next_part = parts[mod_id_index + 1];
-        if is_numeric(next_part) && next_part.len() >= 4 {
-            file_id = Some(next_part.to_string());
-            file_id_index = Some(mod_id_index + 1);
-        }
-    }
+    // Find FileID: prefer the last long (5+ digit) numeric token before the timestamp.
+    // Short numeric tokens between ModID and timestamp are version parts.
+    let middle = mod_id_index + 1..parts.len() - 1;
+    let file_id_index = middle
+        .clone()
+        .rev()
+        .find(|&i| is_numeric(parts[i]) && parts[i].len() >= 5)
+        .or_else(|| {
+            // Older short FileIDs: a 4-digit token right after the ModID
+            middle
+                .clone()
+                .next()
+                .filter(|&i| is_numeric(parts[i]) && parts[i].len() == 4)
+        });
+    let file_id = file_id_index.map(|i| parts[i].to_string());
 
     // ModName = parts[0:mod_id_index]
     let mod_name = parts[..mod_id_index].join("-");
 
-    // Version = parts after ModID (and FileID if present) until timestamp
-    let version_start = file_id_index.map(|i| i + 1).unwrap_or(mod_id_index + 1);
-    let version = parts[version_start..parts.len() - 1].join("-");
+    // Version = parts after ModID until timestamp, excluding the FileID
+    let version = middle
+        .filter(|&i| Some(i) != file_id_index)
+        .map(|i| parts[i])
+        .collect::<Vec<_>>()
+        .join("-");
 
     Some(ModFile {
         file_name: filename.to_string(),
@@ -268,9 +324,207 @@ pub fn parse_mod_filename(filename: &str) -> Option<ModFile> {
         timestamp: timestamp.to_string(),
         size: 0,
         is_patch: is_patch_or_hotfix(filename),
+        mtime: 0,
+    })
+}
+
+/// Parse a timestamped archive name that has no ModID (format: `ModName-version-timestamp.ext`).
+/// The returned file has ModID "0"; the name is everything before the first numeric part.
+pub fn parse_unidentified_filename(filename: &str) -> Option<ModFile> {
+    if is_partial_download(filename) {
+        return None;
+    }
+    let (name_without_ext, _) = split_archive_extension(filename)?;
+
+    let parts: Vec<&str> = name_without_ext.split('-').collect();
+    let timestamp = *parts.last()?;
+    if parts.len() < 2 || !is_numeric(timestamp) || timestamp.len() < 10 {
+        return None;
+    }
+
+    let name_end = parts[..parts.len() - 1]
+        .iter()
+        .position(|p| is_numeric(p))
+        .unwrap_or(parts.len() - 1);
+    if name_end == 0 {
+        return None;
+    }
+
+    Some(ModFile {
+        file_name: filename.to_string(),
+        full_path: std::path::PathBuf::new(),
+        mod_name: parts[..name_end].join("-"),
+        mod_id: "0".to_string(),
+        file_id: None,
+        version: parts[name_end..parts.len() - 1].join("-"),
+        timestamp: timestamp.to_string(),
+        size: 0,
+        is_patch: is_patch_or_hotfix(filename),
+        mtime: 0,
     })
 }
 
+/// Parse a timestamped archive name whose only ID is a FileID too long to be a ModID
+/// (format: `ModName-FileID-timestamp.ext`). The returned file has an empty ModID, so it
+/// can only be matched by FileID.
+pub fn parse_file_id_only_filename(filename: &str) -> Option<ModFile> {
+    if is_partial_download(filename) {
+        return None;
+    }
+    let (name_without_ext, _) = split_archive_extension(filename)?;
+
+    let parts: Vec<&str> = name_without_ext.split('-').collect();
+    let [name @ .., file_id, timestamp] = parts.as_slice() else {
+        return None;
+    };
+    let mod_name = name.join("-");
+    if mod_name.is_empty()
+        || !is_numeric(timestamp)
+        || timestamp.len() < 10
+        || !is_numeric(file_id)
+        || !(7..10).contains(&file_id.len())
+    {
+        return None;
+    }
+
+    Some(ModFile {
+        file_name: filename.to_string(),
+        full_path: std::path::PathBuf::new(),
+        mod_name,
+        mod_id: String::new(),
+        file_id: Some(file_id.to_string()),
+        version: String::new(),
+        timestamp: timestamp.to_string(),
+        size: 0,
+        is_patch: is_patch_or_hotfix(filename),
+        mtime: 0,
+    })
+}
+
+/// Entry names the modlist JSON is stored under, in probing order
+const MODLIST_ENTRY_NAMES: &[&str] = &["modlist", "modlist.json"];
+
+/// Read the modlist entry, trying each known name case-insensitively (any compression
+/// method). Returns the content and the name of the entry found.
+fn read_modlist_entry<R: Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+) -> Result<(String, String)> {
+    let index = MODLIST_ENTRY_NAMES
+        .iter()
+        .find_map(|name| {
+            archive.index_for_name(name).or_else(|| {
+                (0..archive.len()).find(|&i| {
+                    archive
+                        .name_for_index(i)
+                        .is_some_and(|n| n.eq_ignore_ascii_case(name))
+                })
+            })
+        })
+        .with_context(|| {
+            let names: Vec<&str> = archive.file_names().take(10).collect();
+            format!(
+                "modlist entry not found in archive (entries: {})",
+                names.join(", ")
+            )
+        })?;
+    let entry_name = archive
+        .name_for_index(index)
+        .unwrap_or_default()
+        .to_string();
+
+    let mut content = String::new();
+    archive
+        .by_index(index)
+        .with_context(|| "Failed to open modlist entry")?
+        .read_to_string(&mut content)
+        .with_context(|| "Failed to read modlist file")?;
+    Ok((content, entry_name))
+}
+
+/// Where the modlist object with `Archives` sits in the JSON
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ModlistLayout {
+    /// `Archives` at the top level (every format seen so far)
+    TopLevel,
+    /// `Archives` inside the object under this key
+    Nested(String),
+}
+
+/// Find the object holding `Archives`: the top level, or one level down. Fails with the
+/// format version, if the JSON has one, when neither matches.
+fn detect_modlist_layout(json: &Value) -> Result<ModlistLayout> {
+    let has_archives = |v: &Value| v.get("Archives").is_some_and(Value::is_array);
+    if has_archives(json) {
+        return Ok(ModlistLayout::TopLevel);
+    }
+    if let Some((key, _)) = json
+        .as_object()
+        .and_then(|o| o.iter().find(|(_, v)| has_archives(v)))
+    {
+        return Ok(ModlistLayout::Nested(key.clone()));
+    }
+
+    let version = ["WabbajackVersion", "Version"]
+        .iter()
+        .find_map(|k| json.get(k)?.as_str())
+        .unwrap_or("unknown");
+    bail!(
+        "Unsupported modlist format (version {}): no Archives list found",
+        version
+    )
+}
+
+/// Modlist JSON in any supported layout
+fn parse_modlist_json(content: &str, entry_name: &str) -> Result<Modlist> {
+    let mut json: Value =
+        serde_json::from_str(content).with_context(|| "Failed to parse modlist JSON")?;
+    let layout = detect_modlist_layout(&json)?;
+    log::info!("Modlist layout: {:?} in entry '{}'", layout, entry_name);
+
+    let modlist = match layout {
+        ModlistLayout::TopLevel => json,
+        ModlistLayout::Nested(key) => {
+            let name = json.get("Name").cloned();
+            let mut inner = json[&key].take();
+            // The name may stay on the outer object
+            if let (Some(name), Some(obj)) = (name, inner.as_object_mut()) {
+                obj.entry("Name").or_insert(name);
+            }
+            inner
+        }
+    };
+    serde_json::from_value(modlist).with_context(|| "Failed to parse modlist JSON")
+}
+
+/// Game most archives were downloaded for, from their `GameName`s. Ties go to the name
+/// that sorts first so the result doesn't depend on archive order.
+pub fn majority_game<'a>(game_names: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for name in game_names.into_iter().filter(|n| !n.is_empty()) {
+        *counts.entry(name).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .max_by(|(a, a_count), (b, b_count)| a_count.cmp(b_count).then(b.cmp(a)))
+        .map(|(name, _)| name.to_string())
+}
+
+/// Readable name for a Wabbajack `GameName`, e.g. "Fallout 4" for `Fallout4`
+pub fn game_display_name(game: &str) -> &str {
+    match game {
+        "SkyrimSpecialEdition" => "Skyrim SE",
+        "SkyrimVR" => "Skyrim VR",
+        "Fallout4" => "Fallout 4",
+        "Fallout4VR" => "Fallout 4 VR",
+        "FalloutNewVegas" => "Fallout New Vegas",
+        "Fallout3" => "Fallout 3",
+        "EnderalSpecialEdition" => "Enderal SE",
+        "Cyberpunk2077" => "Cyberpunk 2077",
+        "BaldursGate3" => "Baldur's Gate 3",
+        other => other,
+    }
+}
+
 /// Parse a .wabbajack file and extract modlist information
 pub fn parse_wabbajack_file(file_path: &Path) -> Result<ModlistInfo> {
     log::info!("Parsing wabbajack file: {:?}", file_path);
@@ -281,24 +535,15 @@ pub fn parse_wabbajack_file(file_path: &Path) -> Result<ModlistInfo> {
     let mut archive =
         ZipArchive::new(file).with_context(|| "Failed to read wabbajack file as ZIP")?;
 
-    // Find and read the "modlist" file
-    let mut modlist_content = String::new();
-    {
-        let mut modlist_file = archive
-            .by_name("modlist")
-            .with_context(|| "modlist file not found in archive")?;
-        modlist_file
-            .read_to_string(&mut modlist_content)
-            .with_context(|| "Failed to read modlist file")?;
-    }
-
-    let modlist: Modlist =
-        serde_json::from_str(&modlist_content).with_context(|| "Failed to parse modlist JSON")?;
+    // Find and read the modlist entry
+    let (modlist_content, entry_name) = read_modlist_entry(&mut archive)?;
+    let modlist = parse_modlist_json(&modlist_content, &entry_name)?;
 
     // Build sets for used mods
     let mut used_mod_keys = HashSet::new();
     let mut used_mod_file_ids = HashSet::new();
     let mut used_file_names = HashSet::new();
+    let mut archive_hashes = HashMap::new();
 
     for arch in &modlist.archives {
         // Collect exact file names for precise matching
@@ -307,6 +552,9 @@ pub fn parse_wabbajack_file(file_path: &Path) -> Result<ModlistInfo> {
                 used_file_names.insert(name.clone());
             }
         }
+        if let Some(hash) = arch.hash.as_deref().and_then(parse_wabbajack_hash) {
+            archive_hashes.insert(hash, arch.name.clone().unwrap_or_default());
+        }
 
         if let Some(mod_id) = arch.state.mod_id {
             if mod_id > 0 {
@@ -323,9 +571,17 @@ pub fn parse_wabbajack_file(file_path: &Path) -> Result<ModlistInfo> {
         }
     }
 
+    let game = majority_game(
+        modlist
+            .archives
+            .iter()
+            .filter_map(|a| a.state.game_name.as_deref()),
+    );
+
     log::info!(
-        "Parsed modlist '{}': {} archives, {} unique ModIDs, {} file names",
+        "Parsed modlist '{}' ({}): {} archives, {} unique ModIDs, {} file names",
         modlist.name,
+        game.as_deref().unwrap_or("unknown game"),
         modlist.archives.len(),
         used_mod_keys.len(),
         used_file_names.len()
@@ -338,12 +594,214 @@ pub fn parse_wabbajack_file(file_path: &Path) -> Result<ModlistInfo> {
         used_mod_keys,
         used_mod_file_ids,
         used_file_names,
+        game,
+        archive_hashes,
     })
 }
 
+/// How a file name is interpreted by the scanner, for troubleshooting
+#[derive(Debug, Clone)]
+pub struct FilenameDiagnosis {
+    pub file_name: String,
+    pub is_wabbajack_file: bool,
+    pub is_partial_download: bool,
+    /// Components from the ModID pattern, or from the fallback for names without one
+    pub parsed: Option<ModFile>,
+    /// Only the fallback for names without a ModID matched
+    pub unidentified: bool,
+}
+
+impl FilenameDiagnosis {
+    /// One-line breakdown in English, for pasting into bug reports
+    pub fn describe(&self) -> String {
+        let verdict = match &self.parsed {
+            _ if self.is_partial_download => "partial download, ignored".to_string(),
+            _ if !self.is_wabbajack_file => "not an archive, ignored".to_string(),
+            None => "not parsed, matched by file name only".to_string(),
+            Some(f) => format!(
+                "{}name=\"{}\" mod_id={} file_id={} version={} timestamp={} patch={}",
+                if self.unidentified { "no ModID: " } else { "" },
+                f.mod_name,
+                f.mod_id,
+                f.file_id.as_deref().unwrap_or("-"),
+                f.version,
+                f.timestamp,
+                f.is_patch
+            ),
+        };
+        format!("{} -> {}", self.file_name, verdict)
+    }
+}
+
+/// Run the scanner's checks and parsers against one file name
+pub fn diagnose_filename(filename: &str) -> FilenameDiagnosis {
+    let mut unidentified = false;
+    let parsed = parse_mod_filename(filename).or_else(|| {
+        unidentified = true;
+        parse_unidentified_filename(filename)
+    });
+    FilenameDiagnosis {
+        file_name: filename.to_string(),
+        is_wabbajack_file: is_wabbajack_file(filename),
+        is_partial_download: is_partial_download(filename),
+        unidentified: unidentified && parsed.is_some(),
+        parsed,
+    }
+}
+
+/// Diagnose each non-empty line of pasted text. Quotes and leading folders are
+/// stripped, so copied paths work as well as bare names.
+pub fn diagnose_filenames(text: &str) -> Vec<FilenameDiagnosis> {
+    text.lines()
+        .map(|line| line.trim().trim_matches('"').trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let name = line.rsplit(['/', '\\']).next().unwrap_or(line);
+            diagnose_filename(name)
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::types::ArchiveKind;
+    use std::io::Write;
+    use tempfile::tempdir;
+    use zip::write::SimpleFileOptions;
+    use zip::{CompressionMethod, ZipWriter};
+
+    const TEST_MODLIST_JSON: &str = r#"{"Name": "Test List", "Archives": [
+        {"Name": "SkyUI-12604-5-2-1615410779.7z", "Hash": "menYUTfbRu8=",
+         "State": {"ModID": 12604, "FileID": 52344, "GameName": "SkyrimSpecialEdition"}}
+    ]}"#;
+
+    fn write_wabbajack_json(path: &Path, entry_name: &str, method: CompressionMethod, json: &str) {
+        let mut zip = ZipWriter::new(File::create(path).unwrap());
+        zip.start_file(
+            entry_name,
+            SimpleFileOptions::default().compression_method(method),
+        )
+        .unwrap();
+        zip.write_all(json.as_bytes()).unwrap();
+        zip.finish().unwrap();
+    }
+
+    fn write_wabbajack(path: &Path, entry_name: &str, method: CompressionMethod) {
+        write_wabbajack_json(path, entry_name, method, TEST_MODLIST_JSON);
+    }
+
+    #[test]
+    fn test_archive_kind_from_extension() {
+        let template = parse_mod_filename("SkyUI-12604-5-2-1615410779.7z").unwrap();
+        let kind = |name: &str| {
+            ModFile {
+                file_name: name.to_string(),
+                ..template.clone()
+            }
+            .archive_kind()
+        };
+        assert_eq!(kind("SkyUI_5_2_SE.7z"), ArchiveKind::SevenZip);
+        assert_eq!(kind("Tool.ZIP"), ArchiveKind::Zip);
+        assert_eq!(kind("Patch.rar"), ArchiveKind::Rar);
+        assert_eq!(kind("Fonts.tar"), ArchiveKind::Tar);
+        assert_eq!(kind("Fonts.tar.gz"), ArchiveKind::Tar);
+        assert_eq!(kind("BethINI Installer.exe"), ArchiveKind::Exe);
+        assert_eq!(ArchiveKind::Exe.label(), "EXE");
+        assert_eq!(kind("Textures.ba2"), ArchiveKind::Bethesda);
+    }
+
+    #[test]
+    fn test_parse_wabbajack_deflate_modlist() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Test.wabbajack");
+        write_wabbajack(&path, "modlist", CompressionMethod::Deflated);
+
+        let info = parse_wabbajack_file(&path).unwrap();
+        assert_eq!(info.name, "Test List");
+        assert_eq!(info.mod_count, 1);
+        assert!(info.used_mod_file_ids.contains("12604-52344"));
+        assert_eq!(
+            info.archive_hashes
+                .get(&0xEF46_DB37_51D8_E999)
+                .map(String::as_str),
+            Some("SkyUI-12604-5-2-1615410779.7z")
+        );
+    }
+
+    #[test]
+    fn test_parse_wabbajack_modlist_name_case_insensitive() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Test.wabbajack");
+        write_wabbajack(&path, "ModList", CompressionMethod::Deflated);
+
+        let info = parse_wabbajack_file(&path).unwrap();
+        assert_eq!(info.name, "Test List");
+    }
+
+    #[test]
+    fn test_parse_wabbajack_alternative_layouts() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Test.wabbajack");
+
+        // Same JSON under a `modlist.json` entry
+        write_wabbajack(&path, "modlist.json", CompressionMethod::Deflated);
+        let info = parse_wabbajack_file(&path).unwrap();
+        assert_eq!(info.name, "Test List");
+        assert!(info.used_mod_file_ids.contains("12604-52344"));
+
+        // Archives nested one level down, name kept on the outer object
+        let nested = format!(
+            r#"{{"Name": "Outer", "WabbajackVersion": "9.0", "ModList": {}}}"#,
+            TEST_MODLIST_JSON.replace(r#""Name": "Test List", "#, "")
+        );
+        write_wabbajack_json(&path, "modlist", CompressionMethod::Deflated, &nested);
+        let info = parse_wabbajack_file(&path).unwrap();
+        assert_eq!(info.name, "Outer");
+        assert_eq!(info.mod_count, 1);
+
+        // Nothing recognisable names the format version
+        write_wabbajack_json(
+            &path,
+            "modlist",
+            CompressionMethod::Deflated,
+            r#"{"Name": "X", "WabbajackVersion": "9.0", "Files": []}"#,
+        );
+        let err = parse_wabbajack_file(&path).unwrap_err();
+        assert!(format!("{:#}", err).contains("Unsupported modlist format (version 9.0)"));
+    }
+
+    #[test]
+    fn test_modlist_game_from_majority_of_archives() {
+        assert_eq!(
+            majority_game(["Fallout4", "SkyrimSpecialEdition", "Fallout4", ""]).as_deref(),
+            Some("Fallout4")
+        );
+        // A tie is broken by name, whatever the archive order
+        assert_eq!(
+            majority_game(["Starfield", "Fallout4"]).as_deref(),
+            Some("Fallout4")
+        );
+        assert_eq!(majority_game([]), None);
+        assert_eq!(game_display_name("SkyrimSpecialEdition"), "Skyrim SE");
+        assert_eq!(game_display_name("Starfield"), "Starfield");
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Test.wabbajack");
+        write_wabbajack(&path, "modlist", CompressionMethod::Deflated);
+        let info = parse_wabbajack_file(&path).unwrap();
+        assert_eq!(info.game.as_deref(), Some("SkyrimSpecialEdition"));
+    }
+
+    #[test]
+    fn test_parse_wabbajack_missing_modlist() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Test.wabbajack");
+        write_wabbajack(&path, "something-else", CompressionMethod::Stored);
+
+        let err = parse_wabbajack_file(&path).unwrap_err();
+        assert!(format!("{:#}", err).contains("modlist entry not found"));
+    }
 
     #[test]
     fn test_is_numeric() {
@@ -375,6 +833,24 @@ mod tests {
         assert_eq!(normalize_mod_name("Mod 0.18"), "Mod");
     }
 
+    #[test]
+    fn test_split_part_indicator() {
+        assert_eq!(
+            split_part_indicator("Big Textures Part 2"),
+            Some(("big textures".to_string(), 2))
+        );
+        assert_eq!(
+            split_part_indicator("Big_Textures_(Part1)"),
+            Some(("big textures".to_string(), 1))
+        );
+        assert_eq!(
+            split_part_indicator("Landscape PT3 4K"),
+            Some(("landscape 4k".to_string(), 3))
+        );
+        assert_eq!(split_part_indicator("Particle Effects"), None);
+        assert_eq!(split_part_indicator("Script1 Fixes"), None);
+    }
+
     #[test]
     fn test_is_patch_or_hotfix() {
         assert!(is_patch_or_hotfix("SkyUI-Patch.7z"));
@@ -408,6 +884,100 @@ mod tests {
         assert!(parse_mod_filename("Mod-123-1-0-1234567890.txt").is_none());
     }
 
+    #[test]
+    fn test_parse_mod_filename_file_id_heuristics() {
+        // (filename, mod_id, file_id, version)
+        let cases: &[(&str, &str, Option<&str>, &str)] = &[
+            ("Mod-12345-1-0-1234567890.7z", "12345", None, "1-0"),
+            (
+                "SkyUI_5_2SE-12604-5-2SE-52344-1615410779.7z",
+                "12604",
+                Some("52344"),
+                "5-2SE",
+            ),
+            (
+                "Skyrim 2020-12345-67890-1-0-1234567890.7z",
+                "12345",
+                Some("67890"),
+                "1-0",
+            ),
+            (
+                "1) Point That Somewhere Else - Main File-73938-2-22-2-1766239208.zip",
+                "73938",
+                None,
+                "2-22-2",
+            ),
+            ("BHYSYS-71112-13-02-1766329383.rar", "71112", None, "13-02"),
+            (
+                "Pip-Boy UI Tweaks-85343-5-0-1-1766262984.zip",
+                "85343",
+                None,
+                "5-0-1",
+            ),
+            (
+                "SKSE64-30379-111593-2-0-20-1622656000.7z",
+                "30379",
+                Some("111593"),
+                "2-0-20",
+            ),
+            (
+                "Address Library-32444-11-39858-1596574734.7z",
+                "32444",
+                Some("39858"),
+                "11",
+            ),
+            (
+                "USSEP-266-4-2-5a-1234567-1630000000.7z",
+                "266",
+                Some("1234567"),
+                "4-2-5a",
+            ),
+            (
+                "TestMod-1000-2000-1-0-1500000000.7z",
+                "1000",
+                Some("2000"),
+                "1-0",
+            ),
+            ("Simple Mod-123-1-0-1234567890.rar", "123", None, "1-0"),
+            ("Mod-12345-2-1234567890.zip", "12345", None, "2"),
+            (
+                "Mod-12345-1-0-99999-1234567890.7z",
+                "12345",
+                Some("99999"),
+                "1-0",
+            ),
+        ];
+
+        for (filename, mod_id, file_id, version) in cases {
+            let parsed = parse_mod_filename(filename)
+                .unwrap_or_else(|| panic!("Failed to parse {}", filename));
+            assert_eq!(parsed.mod_id, *mod_id, "ModID for {}", filename);
+            assert_eq!(
+                parsed.file_id.as_deref(),
+                *file_id,
+                "FileID for {}",
+                filename
+            );
+            assert_eq!(parsed.version, *version, "Version for {}", filename);
+        }
+    }
+
+    #[test]
+    fn test_parse_file_id_only_filename() {
+        let file = parse_file_id_only_filename("Some Mod-Lite-1234567-1700000000.7z").unwrap();
+        assert_eq!(file.mod_name, "Some Mod-Lite");
+        assert_eq!(file.mod_id, "");
+        assert_eq!(file.file_id.as_deref(), Some("1234567"));
+        assert_eq!(file.timestamp, "1700000000");
+        assert!(!file.is_unparsed());
+
+        // Short enough to be a ModID, a version in between, or no name at all
+        assert!(parse_file_id_only_filename("Some Mod-123456-1700000000.7z").is_none());
+        assert!(parse_file_id_only_filename("Some Mod-1234567-1-0-1700000000.7z").is_none());
+        assert!(parse_file_id_only_filename("1234567-1700000000.7z").is_none());
+        assert!(parse_file_id_only_filename("Some Mod-1234567-1700000000.7z.part").is_none());
+    }
+
     #[test]
     fn test_is_wabbajack_file() {
         assert!(is_wabbajack_file("Mod-123-1-0-1234567890.7z"));
@@ -416,4 +986,75 @@ mod tests {
         assert!(!is_wabbajack_file("mod.part.7z"));
         assert!(!is_wabbajack_file("~temp.zip"));
     }
+
+    #[test]
+    fn test_mixed_case_extensions_and_partial_downloads() {
+        let complete = "SkyUI-12345-5-0-1234567890.7Z";
+        assert!(has_valid_archive_extension(complete));
+        assert!(is_wabbajack_file(complete));
+        let parsed = parse_mod_filename(complete).unwrap();
+        assert_eq!(parsed.mod_name, "SkyUI");
+        assert_eq!(parsed.version, "5-0");
+        assert!(parse_mod_filename("SKSE-54321-2-1-1234567890.ZiP").is_some());
+        assert!(parse_unidentified_filename("Patch-1-0-1234567890.RAR").is_some());
+
+        // Partial downloads in any case are rejected by every check
+        for partial in [
+            "SkyUI-12345-5-0-1234567890.7Z.PART",
+            "SkyUI-12345-5-0-1234567890.7z.Part",
+            "SkyUI-12345-5-0-1234567890.ZIP.TMP",
+            "SkyUI-12345-5-0-1234567890.7z.DOWNLOAD",
+            "SkyUI.PART-12345-5-0-1234567890.7Z",
+            "SkyUI.Tmp-12345-5-0-1234567890.zip",
+        ] {
+            assert!(is_partial_download(partial), "{}", partial);
+            assert!(!is_wabbajack_file(partial), "{}", partial);
+            assert!(parse_mod_filename(partial).is_none(), "{}", partial);
+            assert!(
+                parse_unidentified_filename(partial).is_none(),
+                "{}",
+                partial
+            );
+        }
+        assert!(!has_valid_archive_extension("FILE.7Z.PART"));
+
+        // Non-ASCII names keep their stem intact and never split inside a character
+        let parsed = parse_mod_filename("Ünïcödé-12345-1-0-1234567890.ZIP").unwrap();
+        assert_eq!(parsed.mod_name, "Ünïcödé");
+        assert!(!has_valid_archive_extension("Mod.7zİ"));
+        assert!(!has_valid_archive_extension("ö"));
+    }
+
+    #[test]
+    fn test_diagnose_filenames() {
+        let text = "\
+            \"D:\\Downloads\\Skyrim\\SkyUI-12604-5-2-1615410779.7z\"
+
+            /mnt/mods/Some Patch-1-0-1600000000.zip
+            SkyUI-12604-5-2-1615410779.7z.part
+            readme.txt
+            Random Archive.7z
+        ";
+        let results = diagnose_filenames(text);
+        assert_eq!(results.len(), 5);
+
+        assert_eq!(results[0].file_name, "SkyUI-12604-5-2-1615410779.7z");
+        let parsed = results[0].parsed.as_ref().unwrap();
+        assert_eq!(
+            (parsed.mod_id.as_str(), parsed.version.as_str()),
+            ("12604", "5-2")
+        );
+        assert!(!results[0].unidentified);
+
+        assert!(results[1].unidentified);
+        assert_eq!(results[1].parsed.as_ref().unwrap().mod_id, "0");
+
+        assert!(results[2].is_partial_download && results[2].parsed.is_none());
+        assert!(results[2].describe().ends_with("partial download, ignored"));
+        assert!(!results[3].is_wabbajack_file);
+        assert!(results[4].is_wabbajack_file && results[4].parsed.is_none());
+        assert!(results[4]
+            .describe()
+            .ends_with("not parsed, matched by file name only"));
+    }
 }
@@ -0,0 +1,180 @@
+// Copyright (C) 2025 Berkay Yetgin
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+use std::borrow::Cow;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+#[cfg(target_os = "windows")]
+mod file_identity;
+#[cfg(target_os = "windows")]
+mod restart_manager;
+
+/// Paths this long get the extended-length prefix. Kept below MAX_PATH (260) so the
+/// `.meta` and ` (n)` names derived from a path are covered too.
+const LONG_PATH_THRESHOLD: usize = 240;
+
+/// Extended-length (`\\?\`) form of a long absolute path on Windows, so file operations
+/// work past MAX_PATH. Short, relative or already prefixed paths (and all paths on other
+/// platforms) are returned unchanged.
+pub fn long_path(path: &Path) -> Cow<'_, Path> {
+    #[cfg(target_os = "windows")]
+    if let Some(extended) = path.to_str().and_then(extended_length_path) {
+        return Cow::Owned(std::path::PathBuf::from(extended));
+    }
+    Cow::Borrowed(path)
+}
+
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn extended_length_path(path: &str) -> Option<String> {
+    if path.encode_utf16().count() < LONG_PATH_THRESHOLD || path.starts_with(r"\\?\") {
+        return None;
+    }
+    let path = path.replace('/', "\\");
+    if let Some(unc) = path.strip_prefix(r"\\") {
+        return Some(format!(r"\\?\UNC\{}", unc));
+    }
+    let bytes = path.as_bytes();
+    let is_drive_path =
+        bytes.len() > 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && bytes[2] == b'\\';
+    is_drive_path.then(|| format!(r"\\?\{}", path))
+}
+
+/// Open the OS file manager at the file's parent folder, selecting the file where supported
+pub fn reveal_in_file_manager(path: &Path) -> io::Result<()> {
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("File no longer exists: {:?}", path),
+        ));
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        // explorer expects the path quoted inside the /select argument
+        Command::new("explorer")
+            .raw_arg(format!("/select,\"{}\"", path.display()))
+            .spawn()?;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        Command::new("open").arg("-R").arg(path).spawn()?;
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        // xdg-open cannot select a file, so open the containing folder
+        let dir = path.parent().unwrap_or(path);
+        Command::new("xdg-open").arg(dir).spawn()?;
+    }
+
+    Ok(())
+}
+
+/// The data behind a path: the same for every hard link to one file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FileIdentity {
+    volume: u64,
+    index: u64,
+}
+
+/// Identity of a file with more than one hard link; `None` for files with a single link
+/// and where the file system can't tell
+pub fn hardlink_identity(path: &Path) -> Option<FileIdentity> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let meta = std::fs::metadata(long_path(path)).ok()?;
+        (meta.nlink() > 1).then(|| FileIdentity {
+            volume: meta.dev(),
+            index: meta.ino(),
+        })
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let (volume, index, links) = file_identity::file_index(&long_path(path))?;
+        (links > 1).then_some(FileIdentity { volume, index })
+    }
+
+    #[cfg(not(any(unix, target_os = "windows")))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+/// Names of processes holding the file open (Windows Restart Manager; empty elsewhere)
+pub fn file_lock_holders(path: &Path) -> Vec<String> {
+    #[cfg(target_os = "windows")]
+    {
+        restart_manager::processes_using_file(path)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = path;
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_extended_length_path() {
+        let deep = format!(r"C:\Modding\{}\Mod-123-1-0-1234567890.7z", "a".repeat(250));
+        assert_eq!(extended_length_path(&deep), Some(format!(r"\\?\{}", deep)));
+        let unc = format!(r"\\nas\share\{}", "b".repeat(250));
+        assert_eq!(
+            extended_length_path(&unc),
+            Some(format!(r"\\?\UNC\nas\share\{}", "b".repeat(250)))
+        );
+        let mixed = format!("C:/Modding/{}", "c".repeat(250));
+        assert_eq!(
+            extended_length_path(&mixed),
+            Some(format!(r"\\?\C:\Modding\{}", "c".repeat(250)))
+        );
+
+        assert_eq!(extended_length_path(r"C:\Modding\Mod.7z"), None);
+        assert_eq!(extended_length_path(&format!(r"\\?\{}", deep)), None);
+        assert_eq!(
+            extended_length_path(&format!("relative\\{}", "d".repeat(250))),
+            None
+        );
+    }
+
+    #[cfg(any(unix, target_os = "windows"))]
+    #[test]
+    fn test_hardlink_identity() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Mod-123-1-0-1234567890.7z");
+        std::fs::write(&path, b"data").unwrap();
+        assert_eq!(hardlink_identity(&path), None);
+
+        let link = dir.path().join("Linked-123-1-0-1234567890.7z");
+        std::fs::hard_link(&path, &link).unwrap();
+        let identity = hardlink_identity(&path);
+        assert!(identity.is_some());
+        assert_eq!(hardlink_identity(&link), identity);
+    }
+
+    #[test]
+    fn test_file_lock_holders_unused_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("Mod-123-1-0-1234567890.7z");
+        File::create(&path).unwrap();
+
+        assert!(file_lock_holders(&path).is_empty());
+    }
+}
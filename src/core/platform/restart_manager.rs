@@ -0,0 +1,107 @@
+// Copyright (C) 2025 Berkay Yetgin
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+//! Windows Restart Manager lookup of processes holding a file open
+
+use std::os::windows::ffi::OsStrExt;
+use std::path::Path;
+use std::ptr;
+
+use windows_sys::Win32::Foundation::{CloseHandle, ERROR_MORE_DATA, ERROR_SUCCESS};
+use windows_sys::Win32::System::RestartManager::{
+    RmEndSession, RmGetList, RmRegisterResources, RmStartSession, CCH_RM_SESSION_KEY,
+    RM_PROCESS_INFO,
+};
+use windows_sys::Win32::System::Threading::{
+    OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+};
+
+/// Convert a NUL-terminated UTF-16 buffer to a String
+fn from_wide(buf: &[u16]) -> String {
+    let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+    String::from_utf16_lossy(&buf[..len])
+}
+
+/// Executable file name of a process, if it can be queried
+fn process_exe_name(pid: u32) -> Option<String> {
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return None;
+        }
+        let mut buf = [0u16; 1024];
+        let mut size = buf.len() as u32;
+        let ok =
+            QueryFullProcessImageNameW(handle, PROCESS_NAME_WIN32, buf.as_mut_ptr(), &mut size);
+        CloseHandle(handle);
+        if ok == 0 {
+            return None;
+        }
+        Path::new(&String::from_utf16_lossy(&buf[..size as usize]))
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+    }
+}
+
+/// Names of processes that currently have the file open
+pub fn processes_using_file(path: &Path) -> Vec<String> {
+    let wide: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    let mut names = Vec::new();
+
+    unsafe {
+        let mut session = 0u32;
+        let mut key = [0u16; CCH_RM_SESSION_KEY as usize + 1];
+        if RmStartSession(&mut session, 0, key.as_mut_ptr()) != ERROR_SUCCESS {
+            return names;
+        }
+
+        let files = [wide.as_ptr()];
+        if RmRegisterResources(session, 1, files.as_ptr(), 0, ptr::null(), 0, ptr::null())
+            == ERROR_SUCCESS
+        {
+            let mut needed = 0u32;
+            let mut count = 0u32;
+            let mut reasons = 0u32;
+            let status = RmGetList(
+                session,
+                &mut needed,
+                &mut count,
+                ptr::null_mut(),
+                &mut reasons,
+            );
+
+            if status == ERROR_MORE_DATA && needed > 0 {
+                let mut infos: Vec<RM_PROCESS_INFO> = vec![std::mem::zeroed(); needed as usize];
+                count = needed;
+                if RmGetList(
+                    session,
+                    &mut needed,
+                    &mut count,
+                    infos.as_mut_ptr(),
+                    &mut reasons,
+                ) == ERROR_SUCCESS
+                {
+                    for info in &infos[..count as usize] {
+                        let name = process_exe_name(info.Process.dwProcessId)
+                            .unwrap_or_else(|| from_wide(&info.strAppName));
+                        if !names.contains(&name) {
+                            names.push(name);
+                        }
+                    }
+                }
+            }
+        }
+
+        RmEndSession(session);
+    }
+
+    names
+}
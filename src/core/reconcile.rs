@@ -0,0 +1,271 @@
+// Copyright (C) 2025 Berkay Yetgin
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use crate::core::dedup::hash_map;
+use crate::core::hash::HashOptions;
+use crate::core::types::{CancelToken, MatchReason, ModFile, ModlistInfo, ScanResult};
+
+/// How name and ID matching and the modlist hashes disagree about one file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Disagreement {
+    /// Matched by name or ID, but a modlist archive of that name has another hash: the
+    /// download is corrupt or was replaced by a different upload
+    HashMismatch,
+    /// Matched by ID only, and no modlist archive has its hash
+    NoMatchingHash,
+    /// Not matched, yet its hash is that of a modlist archive, saved under another name
+    RenamedArchive { archive: String },
+}
+
+impl Disagreement {
+    /// Short tag shown next to the file
+    pub fn label(&self) -> &'static str {
+        match self {
+            Disagreement::HashMismatch => "hash mismatch",
+            Disagreement::NoMatchingHash => "no matching hash",
+            Disagreement::RenamedArchive { .. } => "renamed archive",
+        }
+    }
+}
+
+/// One file the two classifications disagree on
+#[derive(Debug, Clone)]
+pub struct ReconciledFile {
+    pub file: ModFile,
+    /// How the orphan scan matched the file; `None` if it counted as orphaned
+    pub reason: Option<MatchReason>,
+    pub disagreement: Disagreement,
+}
+
+/// Where the orphan scan and the modlist hashes disagree
+#[derive(Debug, Clone, Default)]
+pub struct ReconciliationReport {
+    /// Kept by name or ID, but the hash doesn't back it up
+    pub over_protected: Vec<ReconciledFile>,
+    /// Reported as orphaned, but the hash says a modlist uses it
+    pub falsely_orphaned: Vec<ReconciledFile>,
+    /// Files both classifications agree on
+    pub agreed: usize,
+    /// Files that could not be checked: unreadable, or kept only by modlists without hashes
+    pub unchecked: usize,
+}
+
+impl ReconciliationReport {
+    pub fn disagreements(&self) -> usize {
+        self.over_protected.len() + self.falsely_orphaned.len()
+    }
+}
+
+/// Files of a scan result that hashes can say something about: those matched by name or
+/// ID, and those reported as orphaned. Protected extensions and sibling parts are kept
+/// for other reasons and left out.
+fn files_to_check(scan: &ScanResult) -> Vec<(&ModFile, Option<MatchReason>, &[String])> {
+    let used = scan
+        .used_mods
+        .iter()
+        .filter(|m| {
+            matches!(
+                m.reason,
+                MatchReason::FileName | MatchReason::FileId | MatchReason::ModId
+            )
+        })
+        .map(|m| (&m.file, Some(m.reason), m.modlists.as_slice()));
+    let orphaned = scan.orphaned_mods.iter().map(|m| (&m.file, None, &[][..]));
+    used.chain(orphaned).collect()
+}
+
+/// Compare the orphan scan with the archive hashes of the modlists, given the hash of
+/// each file by path. Files without a hash count as unchecked.
+pub fn reconcile_with_hashes(
+    scan: &ScanResult,
+    modlists: &[ModlistInfo],
+    hashes: &HashMap<PathBuf, u64>,
+) -> ReconciliationReport {
+    let archive_hashes: HashMap<u64, &str> = modlists
+        .iter()
+        .flat_map(|ml| ml.archive_hashes.iter().map(|(h, n)| (*h, n.as_str())))
+        .collect();
+    let hashed_names: HashSet<&str> = archive_hashes.values().copied().collect();
+    let hashed_modlists: HashSet<&str> = modlists
+        .iter()
+        .filter(|ml| !ml.archive_hashes.is_empty())
+        .map(|ml| ml.name.as_str())
+        .collect();
+
+    let mut report = ReconciliationReport::default();
+    for (file, reason, used_by) in files_to_check(scan) {
+        let Some(hash) = hashes.get(&file.full_path) else {
+            report.unchecked += 1;
+            continue;
+        };
+        let known = archive_hashes.get(hash);
+        let disagreement = match (reason, known) {
+            (Some(_), Some(_)) | (None, None) => None,
+            (Some(_), None) if !used_by.iter().any(|n| hashed_modlists.contains(n.as_str())) => {
+                report.unchecked += 1;
+                continue;
+            }
+            (Some(_), None) if hashed_names.contains(file.file_name.as_str()) => {
+                Some(Disagreement::HashMismatch)
+            }
+            (Some(_), None) => Some(Disagreement::NoMatchingHash),
+            (None, Some(archive)) => Some(Disagreement::RenamedArchive {
+                archive: archive.to_string(),
+            }),
+        };
+        let Some(disagreement) = disagreement else {
+            report.agreed += 1;
+            continue;
+        };
+        let entry = ReconciledFile {
+            file: file.clone(),
+            reason,
+            disagreement,
+        };
+        if reason.is_some() {
+            report.over_protected.push(entry);
+        } else {
+            report.falsely_orphaned.push(entry);
+        }
+    }
+    for list in [&mut report.over_protected, &mut report.falsely_orphaned] {
+        list.sort_by(|a, b| a.file.file_name.cmp(&b.file.file_name));
+    }
+    report
+}
+
+/// Hash the files of an orphan scan in full and compare both classifications. Nothing is
+/// changed on disk. Returns `None` if cancelled while hashing.
+pub fn reconcile_matching(
+    scan: &ScanResult,
+    modlists: &[ModlistInfo],
+    cancel: &CancelToken,
+    on_progress: &mut dyn FnMut(u64, u64),
+) -> Option<ReconciliationReport> {
+    // Wabbajack hashes whole archives, so fingerprints can't be compared with them
+    let paths: Vec<PathBuf> = files_to_check(scan)
+        .iter()
+        .map(|(file, _, _)| file.full_path.clone())
+        .collect();
+    let hashes = hash_map(&paths, cancel, on_progress, &HashOptions::full())?;
+    Some(reconcile_with_hashes(scan, modlists, &hashes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::hash::Xxh64;
+    use crate::core::scanner::{
+        detect_orphaned_mods_with_options, scan_mod_files_in_folder, OrphanScanOptions,
+    };
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn xxh64(data: &[u8]) -> u64 {
+        let mut hasher = Xxh64::new(0);
+        hasher.update(data);
+        hasher.digest()
+    }
+
+    #[test]
+    fn test_reconcile_flags_disagreements() {
+        let dir = tempdir().unwrap();
+        let game = dir.path().join("Skyrim");
+        fs::create_dir(&game).unwrap();
+        let files: [(&str, &[u8]); 5] = [
+            // Listed and intact
+            ("Good-11111-1-0-1600000000.7z", b"good"),
+            // Listed by name, but the bytes differ from the modlist's
+            ("Corrupt-22222-1-0-1600000000.7z", b"truncated"),
+            // Another version of a listed mod, kept by the ModID fallback
+            ("Fallback-33333-2-0-1700000000.7z", b"newer upload"),
+            // The listed archive saved under a different name
+            ("Renamed Download.7z", b"renamed"),
+            // Unlisted and unknown
+            ("Stale-44444-1-0-1600000000.7z", b"stale"),
+        ];
+        for (name, data) in files {
+            fs::write(game.join(name), data).unwrap();
+        }
+
+        let archives = [
+            ("Good-11111-1-0-1600000000.7z", xxh64(b"good")),
+            ("Corrupt-22222-1-0-1600000000.7z", xxh64(b"complete")),
+            ("Fallback-33333-1-0-1600000000.7z", xxh64(b"older upload")),
+            ("Original-55555-1-0-1600000000.7z", xxh64(b"renamed")),
+        ];
+        let modlist = ModlistInfo {
+            file_path: PathBuf::new(),
+            name: "Test".to_string(),
+            mod_count: archives.len(),
+            used_mod_keys: ["11111", "22222", "33333", "55555"]
+                .map(String::from)
+                .into(),
+            used_mod_file_ids: HashSet::new(),
+            used_file_names: archives.iter().map(|(n, _)| n.to_string()).collect(),
+            game: None,
+            archive_hashes: archives.iter().map(|(n, h)| (*h, n.to_string())).collect(),
+        };
+        let options = OrphanScanOptions {
+            mod_id_fallback: true,
+            ..Default::default()
+        };
+        let modlists = [modlist];
+        let scan = detect_orphaned_mods_with_options(
+            &scan_mod_files_in_folder(&game),
+            &modlists,
+            &options,
+        );
+
+        let report =
+            reconcile_matching(&scan, &modlists, &CancelToken::new(), &mut |_, _| {}).unwrap();
+        let summary = |list: &[ReconciledFile]| -> Vec<(String, Disagreement)> {
+            list.iter()
+                .map(|r| (r.file.file_name.clone(), r.disagreement.clone()))
+                .collect()
+        };
+        assert_eq!(
+            summary(&report.over_protected),
+            [
+                (
+                    "Corrupt-22222-1-0-1600000000.7z".to_string(),
+                    Disagreement::HashMismatch
+                ),
+                (
+                    "Fallback-33333-2-0-1700000000.7z".to_string(),
+                    Disagreement::NoMatchingHash
+                ),
+            ]
+        );
+        assert_eq!(
+            summary(&report.falsely_orphaned),
+            [(
+                "Renamed Download.7z".to_string(),
+                Disagreement::RenamedArchive {
+                    archive: "Original-55555-1-0-1600000000.7z".to_string()
+                }
+            )]
+        );
+        assert_eq!(report.over_protected[1].reason, Some(MatchReason::ModId));
+        assert_eq!((report.agreed, report.unchecked), (2, 0));
+        assert_eq!(report.disagreements(), 3);
+
+        // Without modlist hashes nothing kept can be checked
+        let mut unhashed = modlists[0].clone();
+        unhashed.archive_hashes.clear();
+        let hashes: HashMap<PathBuf, u64> = files
+            .iter()
+            .map(|(name, data)| (game.join(name), xxh64(data)))
+            .collect();
+        let report = reconcile_with_hashes(&scan, &[unhashed], &hashes);
+        assert_eq!(report.disagreements(), 0);
+        assert_eq!((report.agreed, report.unchecked), (2, 3));
+    }
+}
@@ -0,0 +1,294 @@
+// Copyright (C) 2025 Berkay Yetgin
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+use std::cmp::Reverse;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use crate::core::cleaner::format_size;
+use crate::core::types::{LibraryStats, OldVersionScanResult, ScanResult};
+
+/// Games listed in the summary before the rest are folded into one line
+const MAX_GAMES: usize = 15;
+/// Largest orphaned files listed in the summary
+const MAX_ORPHANED_FILES: usize = 10;
+
+/// GitHub page for filing a new issue
+pub const NEW_ISSUE_URL: &str = "https://github.com/Yakrel/wabbajack-library-cleaner/issues/new";
+/// Log lines in a copied report; pre-filled issue URLs keep fewer to stay under URL length limits
+pub const REPORT_LOG_LINES: usize = 200;
+pub const ISSUE_URL_LOG_LINES: usize = 40;
+
+/// Markdown summary of the current scan results, for sharing in help channels
+pub fn summary_markdown(
+    stats: Option<&LibraryStats>,
+    orphaned: Option<&ScanResult>,
+    old_versions: Option<&OldVersionScanResult>,
+) -> String {
+    let mut out = format!(
+        "## Wabbajack Library Cleaner v{} summary\n",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    if let Some(stats) = stats {
+        let _ = writeln!(
+            out,
+            "\n**Library:** {} files, {}",
+            stats.total_files,
+            format_size(stats.total_size)
+        );
+        let mut games: Vec<_> = stats.by_game.iter().collect();
+        games.sort_by_key(|g| Reverse(g.size));
+        for game in games.iter().take(MAX_GAMES) {
+            let _ = writeln!(
+                out,
+                "- {}: {} files, {} (largest: `{}`, {})",
+                game.name,
+                game.files,
+                format_size(game.size),
+                game.largest_name,
+                format_size(game.largest_size)
+            );
+        }
+        if games.len() > MAX_GAMES {
+            let rest = &games[MAX_GAMES..];
+            let _ = writeln!(
+                out,
+                "- ...and {} more games ({})",
+                rest.len(),
+                format_size(rest.iter().map(|g| g.size).sum())
+            );
+        }
+    }
+
+    if let Some(res) = orphaned {
+        let _ = writeln!(
+            out,
+            "\n**Orphaned:** {} files, {} (in use: {} files, {})",
+            res.orphaned_mods.len(),
+            format_size(res.orphaned_size),
+            res.used_mods.len(),
+            format_size(res.used_size)
+        );
+        let mut largest: Vec<_> = res.orphaned_mods.iter().map(|m| &m.file).collect();
+        largest.sort_by_key(|f| Reverse(f.size));
+        for file in largest.iter().take(MAX_ORPHANED_FILES) {
+            let _ = writeln!(out, "- `{}` ({})", file.file_name, format_size(file.size));
+        }
+        if largest.len() > MAX_ORPHANED_FILES {
+            let _ = writeln!(out, "- ...and {} more", largest.len() - MAX_ORPHANED_FILES);
+        }
+    }
+
+    if let Some(res) = old_versions {
+        let _ = writeln!(
+            out,
+            "\n**Old versions:** {} files in {} mods, {}",
+            res.total_files,
+            res.duplicates.len(),
+            format_size(res.total_space)
+        );
+    }
+
+    if stats.is_none() && orphaned.is_none() && old_versions.is_none() {
+        out.push_str("\nNo scan results yet.\n");
+    }
+    out
+}
+
+/// Replace each folder (as shown in logs, plain or debug-escaped) with its placeholder.
+/// Longer folders are replaced first so nested ones keep their own placeholder.
+pub fn redact_paths(text: &str, folders: &[(PathBuf, &str)]) -> String {
+    let mut folders: Vec<_> = folders
+        .iter()
+        .filter(|(path, _)| !path.as_os_str().is_empty())
+        .collect();
+    folders.sort_by_key(|(path, _)| Reverse(path.as_os_str().len()));
+
+    let mut out = text.to_string();
+    for (path, placeholder) in folders {
+        let plain = path.display().to_string();
+        let debug = format!("{:?}", path);
+        let escaped = debug.trim_matches('"');
+        if escaped != plain {
+            out = out.replace(escaped, placeholder);
+        }
+        out = out.replace(&plain, placeholder);
+    }
+    out
+}
+
+/// Plain-text bug report: app version, OS, game folder names (never full paths) and the
+/// last `max_log_lines` log lines, with `redactions` applied to the log
+pub fn issue_report(
+    log: &[String],
+    game_folders: &[PathBuf],
+    redactions: &[(PathBuf, &str)],
+    max_log_lines: usize,
+) -> String {
+    let mut out = format!(
+        "**Version:** {}\n**OS:** {} ({})\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+
+    let names: Vec<_> = game_folders
+        .iter()
+        .map(|f| f.file_name().map(Path::new).unwrap_or(f).display())
+        .map(|name| name.to_string())
+        .collect();
+    let _ = writeln!(
+        out,
+        "**Game folders ({}):** {}",
+        names.len(),
+        if names.is_empty() {
+            "none".to_string()
+        } else {
+            names.join(", ")
+        }
+    );
+
+    let skip = log.len().saturating_sub(max_log_lines);
+    out.push_str("\n**Log:**\n```\n");
+    if skip > 0 {
+        let _ = writeln!(out, "... {} earlier lines omitted", skip);
+    }
+    for line in &log[skip..] {
+        out.push_str(&redact_paths(line, redactions));
+        out.push('\n');
+    }
+    out.push_str("```\n");
+    out
+}
+
+/// Pre-filled GitHub issue URL
+pub fn issue_url(title: &str, body: &str) -> String {
+    format!(
+        "{}?title={}&body={}",
+        NEW_ISSUE_URL,
+        percent_encode(title),
+        percent_encode(body)
+    )
+}
+
+fn percent_encode(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+            out.push(byte as char);
+        } else {
+            let _ = write!(out, "%{:02X}", byte);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{GameStats, ModFile, OrphanedMod};
+
+    fn orphaned_file(name: &str, size: u64) -> OrphanedMod {
+        OrphanedMod {
+            file: ModFile {
+                file_name: name.to_string(),
+                full_path: std::path::PathBuf::new(),
+                mod_name: name.to_string(),
+                mod_id: "0".to_string(),
+                file_id: None,
+                version: String::new(),
+                timestamp: "0".to_string(),
+                size,
+                is_patch: false,
+                mtime: 0,
+            },
+        }
+    }
+
+    #[test]
+    fn test_summary_lists_largest_orphans_first_and_truncates() {
+        let orphaned_mods: Vec<OrphanedMod> = (1..=12)
+            .map(|i| orphaned_file(&format!("file{}.7z", i), i * 1024))
+            .collect();
+        let res = ScanResult {
+            used_mods: Vec::new(),
+            orphaned_mods,
+            used_size: 0,
+            orphaned_size: 78 * 1024,
+            recent_mods: Vec::new(),
+            recent_size: 0,
+            small_mods: Vec::new(),
+            small_size: 0,
+        };
+        let stats = LibraryStats {
+            total_files: 12,
+            total_size: 78 * 1024,
+            by_game: vec![GameStats {
+                name: "Skyrim".to_string(),
+                files: 12,
+                size: 78 * 1024,
+                largest_name: "file12.7z".to_string(),
+                largest_size: 12 * 1024,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let md = summary_markdown(Some(&stats), Some(&res), None);
+        assert!(md.contains("**Library:** 12 files"));
+        assert!(md.contains("- Skyrim: 12 files"));
+        assert!(md.contains("**Orphaned:** 12 files"));
+        assert!(md.find("`file12.7z`").unwrap() < md.find("`file11.7z`").unwrap());
+        assert!(!md.contains("`file2.7z`"));
+        assert!(md.contains("...and 2 more"));
+        assert!(!md.contains("Old versions"));
+    }
+
+    #[test]
+    fn test_issue_report_redacts_paths() {
+        let downloads = PathBuf::from("/home/alice/Games/Downloads");
+        let home = PathBuf::from("/home/alice");
+        let log = vec![
+            "[10:00:00] Indexing downloads folder...".to_string(),
+            format!(
+                "[10:00:01] Failed to delete file: {:?}",
+                downloads.join("Skyrim/a.7z")
+            ),
+            "[10:00:02] Config saved to /home/alice/.config/app".to_string(),
+        ];
+        let folders = vec![downloads.join("Skyrim"), downloads.join("Fallout 4")];
+        let redactions = [(downloads.clone(), "<downloads>"), (home, "~")];
+
+        let report = issue_report(&log, &folders, &redactions, 2);
+        assert!(report.contains(&format!("**Version:** {}", env!("CARGO_PKG_VERSION"))));
+        assert!(report.contains("**Game folders (2):** Skyrim, Fallout 4"));
+        assert!(report.contains("<downloads>/Skyrim/a.7z"));
+        assert!(report.contains("~/.config/app"));
+        assert!(!report.contains("alice"));
+        // Only the newest lines are kept
+        assert!(report.contains("... 1 earlier lines omitted"));
+        assert!(!report.contains("Indexing"));
+
+        // Without redactions the log is kept verbatim, but folders are still names only
+        let report = issue_report(&log, &folders, &[], 10);
+        assert!(report.contains("/home/alice/.config/app"));
+        assert!(report.contains("**Game folders (2):** Skyrim, Fallout 4"));
+    }
+
+    #[test]
+    fn test_issue_url_encodes_body() {
+        let url = issue_url("Bug report", "a b&c\n```");
+        assert_eq!(
+            url,
+            format!(
+                "{}?title=Bug%20report&body=a%20b%26c%0A%60%60%60",
+                NEW_ISSUE_URL
+            )
+        );
+    }
+}
@@ -5,37 +5,90 @@
 // the Free Software Foundation, either version 3 of the License, or
 // (at your option) any later version.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use rayon::prelude::*;
 
+use crate::core::matcher::{
+    default_matchers, first_match, has_extension, modlist_references, Matcher,
+};
 use crate::core::parser::{
     extract_part_indicator, is_full_or_main_file, is_wabbajack_file, normalize_mod_name,
-    parse_mod_filename,
+    parse_file_id_only_filename, parse_mod_filename, parse_unidentified_filename,
+    parse_version_numbers, split_part_indicator,
 };
+use crate::core::platform::{hardlink_identity, long_path};
 use crate::core::types::{
-    LibraryStats, ModFile, ModGroup, ModlistInfo, OldVersionScanResult, OrphanedMod, ScanResult,
+    GameStats, KeepBy, LibraryStats, MatchReason, ModFile, ModGroup, ModlistInfo, MonthActivity,
+    OldVersionScanResult, OrphanedMod, ScanResult, SkipReason, UsedMod, APP_FOLDER_NAMES,
+    DEFAULT_EXCLUDED_FOLDERS, DEFAULT_PATCH_SIZE_RATIO, DEFAULT_REVIEW_SIZE_RATIO,
+    DEFAULT_SUSPICIOUS_SIZE_RATIO,
 };
 
-/// Get game folders from a base directory
+/// Check if a directory directly contains at least one mod archive
+fn folder_contains_mod_files(dir: &Path) -> bool {
+    match fs::read_dir(dir) {
+        Ok(entries) => entries.flatten().any(|entry| {
+            !entry.file_type().map(|t| t.is_dir()).unwrap_or(true)
+                && is_wabbajack_file(&entry.file_name().to_string_lossy())
+        }),
+        Err(_) => false,
+    }
+}
+
+/// Case-insensitive match of a folder name against a pattern where `*` matches any text
+pub fn matches_folder_pattern(name: &str, pattern: &str) -> bool {
+    let name = name.to_lowercase();
+    let pattern = pattern.to_lowercase();
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        // No `*`: the whole name must match
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// The built-in folder exclusion patterns
+pub fn default_excluded_folders() -> Vec<String> {
+    DEFAULT_EXCLUDED_FOLDERS
+        .iter()
+        .map(|p| p.to_string())
+        .collect()
+}
+
+/// Get game folders from a base directory, skipping the default excluded folders
 pub fn get_game_folders(base_dir: &Path) -> Result<Vec<std::path::PathBuf>> {
+    get_game_folders_excluding(base_dir, &default_excluded_folders())
+}
+
+/// Get game folders from a base directory, skipping subfolders whose name matches one of
+/// `excluded` (see `matches_folder_pattern`). The app's own folders are always skipped.
+pub fn get_game_folders_excluding(
+    base_dir: &Path,
+    excluded: &[String],
+) -> Result<Vec<std::path::PathBuf>> {
     let mut folders = Vec::new();
 
     let entries = fs::read_dir(base_dir)
         .with_context(|| format!("Failed to read directory: {:?}", base_dir))?;
 
     // Check if this directory itself contains mod files
-    let mut has_mod_files = false;
-    for entry in fs::read_dir(base_dir)? {
-        let entry = entry?;
-        if !entry.file_type()?.is_dir() && is_wabbajack_file(&entry.file_name().to_string_lossy()) {
-            has_mod_files = true;
-            break;
-        }
-    }
+    let has_mod_files = folder_contains_mod_files(base_dir);
 
     // If the selected directory contains mod files, include it
     if has_mod_files {
@@ -52,16 +105,119 @@ pub fn get_game_folders(base_dir: &Path) -> Result<Vec<std::path::PathBuf>> {
         let name = entry.file_name();
         let name_str = name.to_string_lossy();
 
-        if entry.file_type()?.is_dir() && !name_str.starts_with('.') && !name_str.starts_with("__")
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+
+        if APP_FOLDER_NAMES
+            .iter()
+            .any(|app| name_str.eq_ignore_ascii_case(app))
+        {
+            continue;
+        }
+
+        if let Some(pattern) = excluded
+            .iter()
+            .find(|p| matches_folder_pattern(&name_str, p))
         {
-            folders.push(entry.path());
+            log::debug!("Skipping excluded folder {:?} ({})", entry.path(), pattern);
+            continue;
+        }
+
+        // A flat downloads folder may hold unrelated subfolders; only count ones with archives
+        if has_mod_files && !folder_contains_mod_files(&entry.path()) {
+            log::debug!("Skipping subfolder without mod files: {:?}", entry.path());
+            continue;
+        }
+
+        folders.push(entry.path());
+    }
+
+    folders.sort();
+    Ok(folders)
+}
+
+/// Game folders from several downloads roots, merged and deduplicated
+pub fn get_game_folders_in_roots(roots: &[std::path::PathBuf]) -> Result<Vec<std::path::PathBuf>> {
+    get_game_folders_in_roots_excluding(roots, &default_excluded_folders())
+}
+
+/// Like `get_game_folders_in_roots`, with the folder exclusion patterns given. Roots that
+/// are unavailable, like an unplugged drive, are skipped unless none is available.
+pub fn get_game_folders_in_roots_excluding(
+    roots: &[std::path::PathBuf],
+    excluded: &[String],
+) -> Result<Vec<std::path::PathBuf>> {
+    let (available, missing): (Vec<_>, Vec<_>) = roots.iter().partition(|r| r.is_dir());
+    if available.is_empty() {
+        if let Some(root) = missing.first() {
+            bail!("Downloads folder not available: {:?}", root);
         }
     }
+    for root in &missing {
+        log::warn!("Skipping unavailable downloads folder {:?}", root);
+    }
 
+    let mut folders = Vec::new();
+    for root in available {
+        folders.extend(get_game_folders_excluding(root, excluded)?);
+    }
     folders.sort();
+    folders.dedup();
     Ok(folders)
 }
 
+/// Path with symlinks and `..` resolved where possible, for comparing folders
+fn comparable_path(path: &Path) -> std::path::PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Two folders are the same or one is inside the other
+pub fn dirs_overlap(a: &Path, b: &Path) -> bool {
+    let (a, b) = (comparable_path(a), comparable_path(b));
+    a.starts_with(&b) || b.starts_with(&a)
+}
+
+/// `folder` belongs to the Wabbajack install at `wabbajack_dir`: the install itself, its
+/// `downloaded_mod_lists`, or a version folder like `4.0.0.0`
+pub fn is_wabbajack_internal_dir(folder: &Path, wabbajack_dir: &Path) -> bool {
+    let (folder, wabbajack_dir) = (comparable_path(folder), comparable_path(wabbajack_dir));
+    if folder == wabbajack_dir {
+        return true;
+    }
+    if folder.parent() != Some(wabbajack_dir.as_path()) {
+        return false;
+    }
+    let name = folder
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    name.eq_ignore_ascii_case("downloaded_mod_lists")
+        || parse_version_numbers(&name).is_some_and(|v| v.len() > 1)
+        || folder.join("downloaded_mod_lists").is_dir()
+}
+
+/// Split game folders into those to scan and those that belong to the Wabbajack install,
+/// for downloads roots that overlap it
+pub fn exclude_wabbajack_dirs(
+    folders: Vec<std::path::PathBuf>,
+    wabbajack_dir: &Path,
+) -> (Vec<std::path::PathBuf>, Vec<std::path::PathBuf>) {
+    folders
+        .into_iter()
+        .partition(|f| !is_wabbajack_internal_dir(f, wabbajack_dir))
+}
+
+/// Index of the root that contains `path`, preferring the deepest one if roots are nested
+pub fn root_index_for(path: &Path, roots: &[std::path::PathBuf]) -> Option<usize> {
+    roots
+        .iter()
+        .enumerate()
+        .filter(|(_, root)| path.starts_with(root))
+        .max_by_key(|(_, root)| root.components().count())
+        .map(|(i, _)| i)
+}
+
 /// Find all .wabbajack files in a directory
 pub fn find_wabbajack_files(base_dir: &Path) -> Result<Vec<std::path::PathBuf>> {
     let mut wabbajack_files = Vec::new();
@@ -88,64 +244,181 @@ pub fn find_wabbajack_files(base_dir: &Path) -> Result<Vec<std::path::PathBuf>>
     Ok(wabbajack_files)
 }
 
-/// Collect all mod files from game folders
-pub fn get_all_mod_files(game_folders: &[std::path::PathBuf]) -> Result<Vec<ModFile>> {
-    // Process game folders in parallel
-    let all_files: Vec<ModFile> = game_folders
+/// Result of checking a folder picked as the Wabbajack installation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WabbajackDirStatus {
+    /// Contains `.wabbajack` files directly or in a `downloaded_mod_lists` folder
+    Valid,
+    NotFound,
+    Empty,
+    /// Has `Wabbajack.exe` but no version folder with downloaded modlists yet
+    NoVersionFolders,
+    /// Looks like a game install (game executable plus a `Data` folder)
+    LooksLikeGameFolder,
+    NoModlists,
+}
+
+impl WabbajackDirStatus {
+    /// Actionable explanation shown when the folder can't be used
+    pub fn message(&self) -> &'static str {
+        match self {
+            WabbajackDirStatus::Valid => "Wabbajack folder looks good.",
+            WabbajackDirStatus::NotFound => "The selected folder does not exist.",
+            WabbajackDirStatus::Empty => {
+                "The selected folder is empty. Select the folder that contains Wabbajack.exe."
+            }
+            WabbajackDirStatus::NoVersionFolders => {
+                "Wabbajack.exe found, but no version folder contains downloaded_mod_lists. Install a modlist in Wabbajack first."
+            }
+            WabbajackDirStatus::LooksLikeGameFolder => {
+                "This looks like a game install folder. Select the Wabbajack folder (the one with Wabbajack.exe) instead."
+            }
+            WabbajackDirStatus::NoModlists => {
+                "No .wabbajack files found. Select the folder that contains Wabbajack.exe or a downloaded_mod_lists folder."
+            }
+        }
+    }
+}
+
+fn has_wabbajack_files(dir: &Path) -> bool {
+    find_wabbajack_files(dir).is_ok_and(|files| !files.is_empty())
+}
+
+/// Check that a folder looks like a Wabbajack installation before scanning it
+pub fn validate_wabbajack_dir(path: &Path) -> WabbajackDirStatus {
+    let entries: Vec<fs::DirEntry> = match fs::read_dir(path) {
+        Ok(e) => e.flatten().collect(),
+        Err(_) => return WabbajackDirStatus::NotFound,
+    };
+    if entries.is_empty() {
+        return WabbajackDirStatus::Empty;
+    }
+
+    // Same layouts scan_wabbajack_dir accepts: direct files, downloaded_mod_lists, version folders
+    let subdirs: Vec<_> = entries
+        .iter()
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .map(|e| e.path())
+        .collect();
+    if has_wabbajack_files(path)
+        || has_wabbajack_files(&path.join("downloaded_mod_lists"))
+        || subdirs
+            .iter()
+            .any(|d| has_wabbajack_files(&d.join("downloaded_mod_lists")))
+    {
+        return WabbajackDirStatus::Valid;
+    }
+
+    let file_names: Vec<String> = entries
+        .iter()
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_file()))
+        .map(|e| e.file_name().to_string_lossy().to_lowercase())
+        .collect();
+    if file_names.iter().any(|n| n == "wabbajack.exe") {
+        return WabbajackDirStatus::NoVersionFolders;
+    }
+
+    let has_data_dir = subdirs.iter().any(|d| {
+        d.file_name()
+            .is_some_and(|n| n.eq_ignore_ascii_case("data"))
+    });
+    if has_data_dir && file_names.iter().any(|n| n.ends_with(".exe")) {
+        return WabbajackDirStatus::LooksLikeGameFolder;
+    }
+
+    WabbajackDirStatus::NoModlists
+}
+
+/// Collect all mod files from a single game folder
+pub fn scan_mod_files_in_folder(folder: &Path) -> Vec<ModFile> {
+    let entries = match fs::read_dir(long_path(folder)) {
+        Ok(e) => e,
+        Err(e) => {
+            log::warn!("Failed to read folder {:?}: {}", folder, e);
+            return Vec::new();
+        }
+    };
+
+    // Collect valid entries first to avoid holding I/O locks
+    let valid_entries: Vec<_> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| !e.file_type().map(|t| t.is_dir()).unwrap_or(true))
+        .collect();
+
+    // Process entries in parallel within each folder
+    valid_entries
         .par_iter()
-        .flat_map(|folder| {
-            let entries = match fs::read_dir(folder) {
-                Ok(e) => e,
-                Err(e) => {
-                    log::warn!("Failed to read folder {:?}: {}", folder, e);
-                    return Vec::new();
+        .filter_map(|entry| {
+            let filename = entry.file_name().to_string_lossy().to_string();
+
+            // Check if it is an archive file
+            if !is_wabbajack_file(&filename) {
+                return None;
+            }
+
+            // Try to parse as Nexus mod, otherwise treat as generic archive
+            let mut mod_file = parse_mod_filename(&filename).unwrap_or_else(|| {
+                // Generic archive file (e.g. from GitHub/Direct URL)
+                // We track it so we can detect if it is Orphaned (unused)
+                ModFile {
+                    file_name: filename.clone(),
+                    full_path: std::path::PathBuf::new(),
+                    mod_name: filename.clone(), // Use full filename as name
+                    mod_id: "0".to_string(),    // Default ID for unknown
+                    file_id: None,
+                    version: "0.0".to_string(),
+                    timestamp: "0".to_string(),
+                    size: 0,
+                    is_patch: false,
+                    mtime: 0,
                 }
-            };
+            });
 
-            // Collect valid entries first to avoid holding I/O locks
-            let valid_entries: Vec<_> = entries
-                .filter_map(|e| e.ok())
-                .filter(|e| !e.file_type().map(|t| t.is_dir()).unwrap_or(true))
-                .collect();
-
-            // Process entries in parallel within each folder
-            valid_entries
-                .par_iter()
-                .filter_map(|entry| {
-                    let filename = entry.file_name().to_string_lossy().to_string();
-
-                    // Check if it is an archive file
-                    if !is_wabbajack_file(&filename) {
-                        return None;
-                    }
+            let full_path = entry.path();
+            if let Ok(metadata) = fs::metadata(long_path(&full_path)) {
+                mod_file.full_path = full_path;
+                mod_file.size = metadata.len();
+                mod_file.mtime = unix_secs(metadata.modified());
+                return Some(mod_file);
+            }
+            None
+        })
+        .collect()
+}
 
-                    // Try to parse as Nexus mod, otherwise treat as generic archive
-                    let mut mod_file = parse_mod_filename(&filename).unwrap_or_else(|| {
-                        // Generic archive file (e.g. from GitHub/Direct URL)
-                        // We track it so we can detect if it is Orphaned (unused)
-                        ModFile {
-                            file_name: filename.clone(),
-                            full_path: std::path::PathBuf::new(),
-                            mod_name: filename.clone(), // Use full filename as name
-                            mod_id: "0".to_string(),    // Default ID for unknown
-                            file_id: None,
-                            version: "0.0".to_string(),
-                            timestamp: "0".to_string(),
-                            size: 0,
-                            is_patch: false,
-                        }
-                    });
-
-                    let full_path = entry.path();
-                    if let Ok(metadata) = fs::metadata(&full_path) {
-                        mod_file.full_path = full_path;
-                        mod_file.size = metadata.len();
-                        return Some(mod_file);
-                    }
-                    None
-                })
-                .collect::<Vec<ModFile>>()
+/// Total size of `files`, counting hard links to the same data once
+pub fn distinct_size<'a>(files: impl IntoIterator<Item = &'a ModFile>) -> u64 {
+    let files: Vec<&ModFile> = files.into_iter().collect();
+    // Links always share a size, so only files of a repeated size need a look
+    let mut size_counts: HashMap<u64, usize> = HashMap::new();
+    for file in &files {
+        *size_counts.entry(file.size).or_default() += 1;
+    }
+    let mut seen = HashSet::new();
+    files
+        .iter()
+        .filter(|f| {
+            size_counts[&f.size] < 2
+                || hardlink_identity(&f.full_path).is_none_or(|id| seen.insert(id))
         })
+        .map(|f| f.size)
+        .sum()
+}
+
+/// Seconds since epoch for a file time (0 if unavailable)
+pub(crate) fn unix_secs(time: std::io::Result<SystemTime>) -> u64 {
+    time.ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Collect all mod files from game folders
+pub fn get_all_mod_files(game_folders: &[std::path::PathBuf]) -> Result<Vec<ModFile>> {
+    // Process game folders in parallel
+    let all_files: Vec<ModFile> = game_folders
+        .par_iter()
+        .flat_map(|folder| scan_mod_files_in_folder(folder))
         .collect();
 
     Ok(all_files)
@@ -153,19 +426,155 @@ pub fn get_all_mod_files(game_folders: &[std::path::PathBuf]) -> Result<Vec<ModF
 
 /// Detect orphaned mods by comparing mod files with active modlists
 pub fn detect_orphaned_mods(mod_files: &[ModFile], active_modlists: &[ModlistInfo]) -> ScanResult {
-    // Build combined sets for matching
-    let mut used_file_names = std::collections::HashSet::new();
-    let mut used_mod_ids = std::collections::HashSet::new();
+    detect_orphaned_mods_with_options(mod_files, active_modlists, &OrphanScanOptions::default())
+}
+
+/// Collapse modlists with the same archives into the first of them, so per-modlist
+/// numbers don't count one list twice. Returns the remaining modlists and
+/// `(duplicate, kept)` file paths of each collapsed one.
+pub fn merge_identical_modlists(
+    modlists: &[ModlistInfo],
+) -> (
+    Vec<ModlistInfo>,
+    Vec<(std::path::PathBuf, std::path::PathBuf)>,
+) {
+    let mut unique: Vec<ModlistInfo> = Vec::new();
+    let mut merged = Vec::new();
+    for modlist in modlists {
+        match unique.iter().find(|kept| kept.same_archives(modlist)) {
+            Some(kept) => {
+                log::warn!(
+                    "Modlist {:?} has the same archives as {:?}; counting it once",
+                    modlist.file_path,
+                    kept.file_path
+                );
+                merged.push((modlist.file_path.clone(), kept.file_path.clone()));
+            }
+            None => unique.push(modlist.clone()),
+        }
+    }
+    (unique, merged)
+}
 
-    for modlist in active_modlists {
-        for file_name in &modlist.used_file_names {
-            used_file_names.insert(file_name.clone());
+/// Bytes of files that only one of `modlists` uses, per modlist in the same order: what
+/// deselecting it would free. Shared and protected files count towards none of them.
+pub fn exclusive_modlist_sizes(
+    mod_files: &[ModFile],
+    modlists: &[ModlistInfo],
+    options: &OrphanScanOptions,
+) -> Vec<u64> {
+    let mut sizes = vec![0; modlists.len()];
+    for file in mod_files {
+        if options.is_protected(&file.file_name) {
+            continue;
+        }
+        let mut users = modlists.iter().enumerate().filter(|(_, ml)| {
+            modlist_references(ml, file, MatchReason::FileName)
+                || modlist_references(ml, file, MatchReason::FileId)
+                || (options.mod_id_fallback && modlist_references(ml, file, MatchReason::ModId))
+        });
+        if let (Some((i, _)), None) = (users.next(), users.next()) {
+            sizes[i] += file.size;
         }
-        for mod_key in &modlist.used_mod_keys {
-            used_mod_ids.insert(mod_key.clone());
+    }
+    sizes
+}
+
+/// Options controlling how downloaded files are matched to modlists
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrphanScanOptions {
+    /// Treat any file sharing a ModID with a modlist archive as used
+    pub mod_id_fallback: bool,
+    /// Extensions (e.g. ".exe") that are counted but never reported as orphaned
+    pub protected_extensions: Vec<String>,
+    /// Hold back unreferenced files modified within this many days (`None` = off)
+    pub ignore_newer_than_days: Option<u32>,
+    /// Hold back unreferenced files smaller than this many bytes (0 = off)
+    pub min_mod_size: u64,
+    /// Read names like `ModName-FileID-timestamp` that carry no ModID, and match them by
+    /// FileID alone
+    pub file_id_only_names: bool,
+}
+
+impl Default for OrphanScanOptions {
+    /// Loose matching: a file sharing a ModID with a modlist archive counts as used
+    fn default() -> Self {
+        Self {
+            mod_id_fallback: true,
+            protected_extensions: Vec::new(),
+            ignore_newer_than_days: None,
+            min_mod_size: 0,
+            file_id_only_names: false,
         }
     }
+}
+
+impl OrphanScanOptions {
+    /// Files with an mtime at or after this (seconds since epoch) are too recent to report
+    fn recent_cutoff(&self) -> Option<u64> {
+        let days = self.ignore_newer_than_days.filter(|&d| d > 0)?;
+        let now = unix_secs(Ok(SystemTime::now()));
+        Some(now.saturating_sub(days as u64 * 86_400))
+    }
+
+    fn is_protected(&self, file_name: &str) -> bool {
+        has_extension(file_name, &self.protected_extensions)
+    }
+}
+
+/// Detect orphaned mods, recording how each used file was matched
+pub fn detect_orphaned_mods_with_options(
+    mod_files: &[ModFile],
+    active_modlists: &[ModlistInfo],
+    options: &OrphanScanOptions,
+) -> ScanResult {
+    let relaxed: Vec<ModFile>;
+    let mod_files = if options.file_id_only_names {
+        relaxed = mod_files.iter().map(with_file_id_only_name).collect();
+        &relaxed
+    } else {
+        mod_files
+    };
+    detect_orphaned_mods_with_matchers(
+        mod_files,
+        active_modlists,
+        &default_matchers(options),
+        options,
+    )
+}
+
+/// Copy of an unparsed file, re-read as `ModName-FileID-timestamp` if its name fits
+fn with_file_id_only_name(file: &ModFile) -> ModFile {
+    if file.mod_id != "0" || file.file_id.is_some() {
+        return file.clone();
+    }
+    match parse_file_id_only_filename(&file.file_name) {
+        Some(parsed) => ModFile {
+            full_path: file.full_path.clone(),
+            size: file.size,
+            mtime: file.mtime,
+            ..parsed
+        },
+        None => file.clone(),
+    }
+}
 
+/// Like `detect_orphaned_mods_with_options`, classifying each file as used on the first
+/// hit of `matchers`, tried in order. The matching fields of `options` are ignored.
+pub fn detect_orphaned_mods_with_matchers(
+    mod_files: &[ModFile],
+    active_modlists: &[ModlistInfo],
+    matchers: &[Box<dyn Matcher>],
+    options: &OrphanScanOptions,
+) -> ScanResult {
+    let used_file_names: HashSet<&String> = active_modlists
+        .iter()
+        .flat_map(|ml| &ml.used_file_names)
+        .collect();
+    let used_mod_ids: HashSet<&String> = active_modlists
+        .iter()
+        .flat_map(|ml| &ml.used_mod_keys)
+        .collect();
     log::info!(
         "Total unique file names in active modlists: {}",
         used_file_names.len()
@@ -175,27 +584,96 @@ pub fn detect_orphaned_mods(mod_files: &[ModFile], active_modlists: &[ModlistInf
         used_mod_ids.len()
     );
 
-    let (used_mods, orphaned_mods): (Vec<ModFile>, Vec<OrphanedMod>) =
+    let (mut used_mods, mut orphaned_mods): (Vec<UsedMod>, Vec<OrphanedMod>) =
         mod_files.par_iter().partition_map(|mod_file| {
-            // Primary matching: exact file name match (most reliable)
-            let is_used = used_file_names.contains(&mod_file.file_name);
-
-            if is_used {
-                rayon::iter::Either::Left(mod_file.clone())
-            } else {
-                rayon::iter::Either::Right(OrphanedMod {
+            match first_match(matchers, mod_file, active_modlists) {
+                Some(reason) => rayon::iter::Either::Left(UsedMod {
                     file: mod_file.clone(),
-                })
+                    reason,
+                    modlists: active_modlists
+                        .iter()
+                        .filter(|ml| modlist_references(ml, mod_file, reason))
+                        .map(|ml| ml.name.clone())
+                        .collect(),
+                }),
+                None => rayon::iter::Either::Right(OrphanedMod {
+                    file: mod_file.clone(),
+                }),
             }
         });
 
-    let used_size: u64 = used_mods.par_iter().map(|m| m.size).sum();
-    let orphaned_size: u64 = orphaned_mods.par_iter().map(|m| m.file.size).sum();
+    // A modlist referencing only some parts of a multi-part mod must not lose the others
+    let part_key = |file: &ModFile| {
+        split_part_indicator(&file.mod_name)
+            .map(|(base, part)| (format!("{}:{}", file.mod_id, base), part))
+    };
+    let mut used_parts: HashMap<String, (HashSet<u32>, Vec<String>)> = HashMap::new();
+    for used in &used_mods {
+        if let Some((key, part)) = part_key(&used.file) {
+            let (parts, modlists) = used_parts.entry(key).or_default();
+            parts.insert(part);
+            for name in &used.modlists {
+                if !modlists.contains(name) {
+                    modlists.push(name.clone());
+                }
+            }
+        }
+    }
+    if !used_parts.is_empty() {
+        let (siblings, still_orphaned): (Vec<OrphanedMod>, Vec<OrphanedMod>) =
+            orphaned_mods.into_iter().partition(|m| {
+                part_key(&m.file).is_some_and(|(key, part)| {
+                    used_parts
+                        .get(&key)
+                        .is_some_and(|(parts, _)| !parts.contains(&part))
+                })
+            });
+        for sibling in &siblings {
+            log::info!(
+                "Keeping {}: another part of the same mod is used",
+                sibling.file.file_name
+            );
+        }
+        used_mods.extend(siblings.into_iter().map(|m| {
+            let modlists = part_key(&m.file)
+                .and_then(|(key, _)| used_parts.get(&key))
+                .map(|(_, modlists)| modlists.clone())
+                .unwrap_or_default();
+            UsedMod {
+                file: m.file,
+                reason: MatchReason::SiblingPart,
+                modlists,
+            }
+        }));
+        orphaned_mods = still_orphaned;
+    }
+
+    // Tiny archives are mostly config snippets or FOMOD selectors, not worth cleaning
+    let (small_mods, mut orphaned_mods): (Vec<OrphanedMod>, Vec<OrphanedMod>) = orphaned_mods
+        .into_iter()
+        .partition(|m| m.file.is_trivial(options.min_mod_size));
+
+    // Fresh downloads may belong to an install in progress; files with unknown mtime count as old
+    let mut recent_mods = Vec::new();
+    if let Some(cutoff) = options.recent_cutoff() {
+        let (recent, older): (Vec<OrphanedMod>, Vec<OrphanedMod>) = orphaned_mods
+            .into_iter()
+            .partition(|m| m.file.mtime >= cutoff);
+        recent_mods = recent;
+        orphaned_mods = older;
+    }
+
+    let used_size = distinct_size(used_mods.iter().map(|m| &m.file));
+    let orphaned_size = distinct_size(orphaned_mods.iter().map(|m| &m.file));
+    let recent_size = distinct_size(recent_mods.iter().map(|m| &m.file));
+    let small_size = distinct_size(small_mods.iter().map(|m| &m.file));
 
     log::info!(
-        "Classification complete: {} used, {} orphaned",
+        "Classification complete: {} used, {} orphaned, {} recent, {} small",
         used_mods.len(),
-        orphaned_mods.len()
+        orphaned_mods.len(),
+        recent_mods.len(),
+        small_mods.len()
     );
 
     ScanResult {
@@ -203,6 +681,10 @@ pub fn detect_orphaned_mods(mod_files: &[ModFile], active_modlists: &[ModlistInf
         orphaned_mods,
         used_size,
         orphaned_size,
+        recent_mods,
+        recent_size,
+        small_mods,
+        small_size,
     }
 }
 
@@ -307,7 +789,7 @@ fn has_conflicting_descriptors(filename1: &str, filename2: &str) -> bool {
 }
 
 /// Check if a mod group has suspicious version patterns
-fn has_suspicious_version_pattern(group: &ModGroup) -> bool {
+fn has_suspicious_version_pattern(group: &ModGroup, max_size_ratio: f64) -> bool {
     if group.files.len() < 2 {
         return false;
     }
@@ -319,13 +801,14 @@ fn has_suspicious_version_pattern(group: &ModGroup) -> bool {
 
             // If versions are identical
             if file1.version == file2.version {
-                // Check size ratio (>10x difference)
+                // Check size ratio (more than `max_size_ratio` times apart)
                 let size_ratio = file1.size as f64 / file2.size as f64;
-                if !(0.1..=10.0).contains(&size_ratio) {
+                if !(1.0 / max_size_ratio..=max_size_ratio).contains(&size_ratio) {
                     log::warn!(
-                        "Group {}: Same version '{}' but size diff >10x",
+                        "Group {}: Same version '{}' but size diff >{}x",
                         group.mod_key,
-                        file1.version
+                        file1.version,
+                        max_size_ratio
                     );
                     return true;
                 }
@@ -363,12 +846,67 @@ fn has_suspicious_version_pattern(group: &ModGroup) -> bool {
 
 /// Scan folder for old versions (duplicates)
 pub fn scan_folder_for_duplicates(folder_path: &Path) -> Result<OldVersionScanResult> {
+    scan_folder_for_duplicates_with_options(folder_path, &OldVersionScanOptions::default())
+}
+
+/// Options controlling how old versions are detected
+#[derive(Debug, Clone, PartialEq)]
+pub struct OldVersionScanOptions {
+    /// Which file of each group is kept
+    pub keep_by: KeepBy,
+    /// Skip a group when its newest file is a patch smaller than this fraction of an older file
+    pub patch_size_ratio: f64,
+    /// Skip a group when two files of the same version differ in size by more than this factor
+    pub suspicious_size_ratio: f64,
+    /// Recommend review of a group when its kept file is smaller than this fraction of a
+    /// file it would delete
+    pub review_size_ratio: f64,
+}
+
+impl Default for OldVersionScanOptions {
+    fn default() -> Self {
+        Self {
+            keep_by: KeepBy::NewestUpload,
+            patch_size_ratio: DEFAULT_PATCH_SIZE_RATIO,
+            suspicious_size_ratio: DEFAULT_SUSPICIOUS_SIZE_RATIO,
+            review_size_ratio: DEFAULT_REVIEW_SIZE_RATIO,
+        }
+    }
+}
+
+/// Order files oldest to newest, so the keeper ends up last
+fn compare_for_keep(a: &ModFile, b: &ModFile, keep_by: KeepBy) -> std::cmp::Ordering {
+    let by_upload = || {
+        a.timestamp
+            .cmp(&b.timestamp)
+            .then_with(|| a.version.cmp(&b.version))
+    };
+    match keep_by {
+        KeepBy::NewestUpload => by_upload(),
+        // Re-uploads of an older version get a newer timestamp, so compare versions first
+        KeepBy::HighestVersion => {
+            match (
+                parse_version_numbers(&a.version),
+                parse_version_numbers(&b.version),
+            ) {
+                (Some(va), Some(vb)) if va != vb => va.cmp(&vb),
+                _ => by_upload(),
+            }
+        }
+    }
+}
+
+/// Scan a folder for old versions, choosing the keeper per `options`
+pub fn scan_folder_for_duplicates_with_options(
+    folder_path: &Path,
+    options: &OldVersionScanOptions,
+) -> Result<OldVersionScanResult> {
     log::info!("Scanning folder: {:?}", folder_path);
 
-    let mut mod_groups: HashMap<String, ModGroup> = HashMap::new();
+    let mut files = Vec::new();
     let mut skipped = 0;
 
-    let entries = fs::read_dir(folder_path)
+    let entries = fs::read_dir(long_path(folder_path))
         .with_context(|| format!("Failed to read directory: {:?}", folder_path))?;
 
     for entry in entries {
@@ -385,7 +923,10 @@ pub fn scan_folder_for_duplicates(folder_path: &Path) -> Result<OldVersionScanRe
             continue;
         }
 
-        let mut mod_file = match parse_mod_filename(&filename) {
+        // Files without a ModID still have a version history if they carry a timestamp
+        let mut mod_file = match parse_mod_filename(&filename)
+            .or_else(|| parse_unidentified_filename(&filename))
+        {
             Some(mf) => mf,
             None => {
                 skipped += 1;
@@ -393,24 +934,45 @@ pub fn scan_folder_for_duplicates(folder_path: &Path) -> Result<OldVersionScanRe
             }
         };
 
-        // Skip generic files that don't have a valid ModID/Timestamp parsed
-        // We can't determine version history for these.
-        if mod_file.mod_id == "0" || mod_file.timestamp == "0" {
+        // Skip files without a timestamp; we can't determine version history for these.
+        if mod_file.timestamp == "0" {
             skipped += 1;
             continue;
         }
 
         let full_path = entry.path();
-        let metadata = fs::metadata(&full_path)?;
+        let metadata = fs::metadata(long_path(&full_path))?;
         mod_file.full_path = full_path;
         mod_file.size = metadata.len();
 
+        files.push(mod_file);
+    }
+
+    if skipped > 0 {
+        log::info!("Skipped {} files in {:?}", skipped, folder_path);
+    }
+
+    Ok(group_old_versions(files, options))
+}
+
+/// Group parsed files of one folder by mod and pick the file to keep in each group
+fn group_old_versions(
+    files: Vec<ModFile>,
+    options: &OldVersionScanOptions,
+) -> OldVersionScanResult {
+    let mut mod_groups: HashMap<String, ModGroup> = HashMap::new();
+    for mod_file in files {
         // Create mod key: ModID + normalized ModName + part indicator
         let normalized_name = normalize_mod_name(&mod_file.mod_name);
         let part_indicator = extract_part_indicator(&mod_file.file_name)
             .or_else(|| extract_part_indicator(&mod_file.mod_name))
             .unwrap_or_default();
-        let mod_key = format!("{}:{}{}", mod_file.mod_id, normalized_name, part_indicator);
+        // Without a ModID only identical normalized names may group together
+        let mod_key = if mod_file.mod_id == "0" {
+            format!("name:{}{}", normalized_name, part_indicator)
+        } else {
+            format!("{}:{}{}", mod_file.mod_id, normalized_name, part_indicator)
+        };
 
         mod_groups
             .entry(mod_key.clone())
@@ -419,17 +981,15 @@ pub fn scan_folder_for_duplicates(folder_path: &Path) -> Result<OldVersionScanRe
                 files: Vec::new(),
                 newest_idx: 0,
                 space_to_free: 0,
+                review_recommended: false,
             })
             .files
             .push(mod_file);
     }
 
-    if skipped > 0 {
-        log::info!("Skipped {} files in {:?}", skipped, folder_path);
-    }
-
     // Find duplicates and calculate space
     let mut duplicates = Vec::new();
+    let mut skipped_groups = Vec::new();
 
     for (_, mut group) in mod_groups {
         if group.files.len() <= 1 {
@@ -445,23 +1005,22 @@ pub fn scan_folder_for_duplicates(folder_path: &Path) -> Result<OldVersionScanRe
                 "Skipped group {}: all files have same timestamp",
                 group.mod_key
             );
+            skipped_groups.push((group.mod_key, SkipReason::SameTimestamp));
             continue;
         }
 
-        // Sort by timestamp, then version
+        // Sort so the file to keep is last
         group
             .files
-            .sort_by(|a, b| match a.timestamp.cmp(&b.timestamp) {
-                std::cmp::Ordering::Equal => a.version.cmp(&b.version),
-                other => other,
-            });
+            .sort_by(|a, b| compare_for_keep(a, b, options.keep_by));
 
         // Check for suspicious patterns
-        if has_suspicious_version_pattern(&group) {
+        if has_suspicious_version_pattern(&group, options.suspicious_size_ratio) {
             log::warn!(
                 "Skipped group {}: suspicious version pattern",
                 group.mod_key
             );
+            skipped_groups.push((group.mod_key, SkipReason::SuspiciousPattern));
             continue;
         }
 
@@ -477,6 +1036,7 @@ pub fn scan_folder_for_duplicates(folder_path: &Path) -> Result<OldVersionScanRe
                 "Skipped group {}: contains both PATCH and MAIN files",
                 group.mod_key
             );
+            skipped_groups.push((group.mod_key, SkipReason::PatchAndMain));
             continue;
         }
 
@@ -487,7 +1047,7 @@ pub fn scan_folder_for_duplicates(folder_path: &Path) -> Result<OldVersionScanRe
             for i in 0..group.files.len() - 1 {
                 let old_file = &group.files[i];
                 let size_ratio = newest.size as f64 / old_file.size as f64;
-                if size_ratio < 0.1 {
+                if size_ratio < options.patch_size_ratio {
                     log::warn!(
                         "Skipped group {}: newest file is likely a patch",
                         group.mod_key
@@ -499,6 +1059,7 @@ pub fn scan_folder_for_duplicates(folder_path: &Path) -> Result<OldVersionScanRe
         }
 
         if skip_patch {
+            skipped_groups.push((group.mod_key, SkipReason::NewestIsPatch));
             continue;
         }
 
@@ -506,34 +1067,206 @@ pub fn scan_folder_for_duplicates(folder_path: &Path) -> Result<OldVersionScanRe
         group.newest_idx = group.files.len() - 1;
         group.space_to_free = group.files[..group.newest_idx].iter().map(|f| f.size).sum();
 
+        // Between the patch and suspicious thresholds: a much smaller keeper may be a hotfix
+        let largest_deleted = group.deletable_files().iter().map(|f| f.size).max();
+        let keeper_size = group.files[group.newest_idx].size as f64;
+        if largest_deleted.is_some_and(|s| keeper_size < s as f64 * options.review_size_ratio) {
+            log::warn!(
+                "Group {}: kept file is much smaller than an older one, review recommended",
+                group.mod_key
+            );
+            group.review_recommended = true;
+        }
+
         duplicates.push(group);
     }
 
+    sort_by_age_gap(&mut duplicates);
+    skipped_groups.sort_by(|a, b| a.0.cmp(&b.0));
+
     let total_files: usize = duplicates.iter().map(|g| g.files.len() - 1).sum();
     let total_space: u64 = duplicates.iter().map(|g| g.space_to_free).sum();
 
     log::info!("Found {} mod groups with duplicates", duplicates.len());
 
-    Ok(OldVersionScanResult {
+    OldVersionScanResult {
         duplicates,
         total_files,
         total_space,
-    })
+        skipped_groups,
+    }
+}
+
+/// Old-version analysis of already parsed files (e.g. from a library manifest).
+/// Files are grouped per parent folder, like a scan of each game folder.
+pub fn find_old_versions_in_files(
+    files: &[ModFile],
+    options: &OldVersionScanOptions,
+) -> OldVersionScanResult {
+    let mut by_folder: HashMap<std::path::PathBuf, Vec<ModFile>> = HashMap::new();
+    for file in files {
+        // Indexing stores unrecognized names with placeholder fields; retry them as unidentified
+        let mod_file = if file.timestamp == "0" {
+            match parse_unidentified_filename(&file.file_name) {
+                Some(mut parsed) => {
+                    parsed.full_path = file.full_path.clone();
+                    parsed.size = file.size;
+                    parsed
+                }
+                None => continue,
+            }
+        } else {
+            file.clone()
+        };
+        let folder = mod_file
+            .full_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        by_folder.entry(folder).or_default().push(mod_file);
+    }
+
+    let mut merged = OldVersionScanResult {
+        duplicates: Vec::new(),
+        total_files: 0,
+        total_space: 0,
+        skipped_groups: Vec::new(),
+    };
+    for (_, folder_files) in by_folder {
+        let result = group_old_versions(folder_files, options);
+        merged.duplicates.extend(result.duplicates);
+        merged.total_files += result.total_files;
+        merged.total_space += result.total_space;
+        merged.skipped_groups.extend(result.skipped_groups);
+    }
+    sort_by_age_gap(&mut merged.duplicates);
+    merged.skipped_groups.sort_by(|a, b| a.0.cmp(&b.0));
+    merged
+}
+
+/// Scan several folders for old versions and merge the results
+pub fn scan_folders_for_duplicates(
+    folders: &[std::path::PathBuf],
+    progress_callback: Option<&dyn Fn(usize, usize)>,
+) -> Result<OldVersionScanResult> {
+    scan_folders_for_duplicates_with_options(
+        folders,
+        progress_callback,
+        &OldVersionScanOptions::default(),
+    )
+}
+
+/// Scan several folders for old versions, choosing the keeper per `options`
+pub fn scan_folders_for_duplicates_with_options(
+    folders: &[std::path::PathBuf],
+    progress_callback: Option<&dyn Fn(usize, usize)>,
+    options: &OldVersionScanOptions,
+) -> Result<OldVersionScanResult> {
+    let mut merged = OldVersionScanResult {
+        duplicates: Vec::new(),
+        total_files: 0,
+        total_space: 0,
+        skipped_groups: Vec::new(),
+    };
+
+    for (i, folder) in folders.iter().enumerate() {
+        if let Some(cb) = progress_callback {
+            cb(i + 1, folders.len());
+        }
+        let result = scan_folder_for_duplicates_with_options(folder, options)?;
+        merged.duplicates.extend(result.duplicates);
+        merged.total_files += result.total_files;
+        merged.total_space += result.total_space;
+        merged.skipped_groups.extend(result.skipped_groups);
+    }
+    sort_by_age_gap(&mut merged.duplicates);
+    merged.skipped_groups.sort_by(|a, b| a.0.cmp(&b.0));
+
+    Ok(merged)
+}
+
+/// Largest gap between kept and deletable versions first (safest to clean)
+fn sort_by_age_gap(groups: &mut [ModGroup]) {
+    groups.sort_by(|a, b| {
+        b.age_gap_secs()
+            .cmp(&a.age_gap_secs())
+            .then_with(|| a.mod_key.cmp(&b.mod_key))
+    });
+}
+
+/// Files and bytes per (year, month)
+type MonthBuckets = BTreeMap<(i32, u32), (usize, u64)>;
+
+/// Bucket files by the month in their name's timestamp. Timestamps that don't parse, or
+/// fall before 2000 or in the future, are left out.
+fn month_buckets(files: &[ModFile]) -> MonthBuckets {
+    use chrono::Datelike;
+
+    let now = chrono::Utc::now();
+    let mut buckets = MonthBuckets::new();
+    for file in files {
+        let Some(date) = file
+            .timestamp
+            .parse::<i64>()
+            .ok()
+            .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+            .filter(|date| date.year() >= 2000 && *date <= now)
+        else {
+            continue;
+        };
+        let bucket = buckets.entry((date.year(), date.month())).or_default();
+        bucket.0 += 1;
+        bucket.1 += file.size;
+    }
+    buckets
+}
+
+/// Months from the first bucket to the last, with the empty ones in between
+fn monthly_activity(buckets: &MonthBuckets) -> Vec<MonthActivity> {
+    let (Some(&first), Some(&last)) = (buckets.keys().next(), buckets.keys().next_back()) else {
+        return Vec::new();
+    };
+    let mut activity = Vec::new();
+    let (mut year, mut month) = first;
+    while (year, month) <= last {
+        let (files, size) = buckets.get(&(year, month)).copied().unwrap_or_default();
+        activity.push(MonthActivity {
+            year,
+            month,
+            files,
+            size,
+        });
+        (year, month) = if month == 12 {
+            (year + 1, 1)
+        } else {
+            (year, month + 1)
+        };
+    }
+    activity
 }
 
 /// Calculate library statistics
 pub fn calculate_library_stats(game_folders: &[std::path::PathBuf]) -> LibraryStats {
-    let results: Vec<(String, usize, u64)> = game_folders
+    let mut results: Vec<_> = game_folders
         .par_iter()
         .map(|folder| {
-            let entries = match fs::read_dir(folder) {
-                Ok(e) => e,
-                Err(_) => return ("Unknown".to_string(), 0, 0),
+            let game_name = folder
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "Unknown".to_string());
+            let mut game = GameStats {
+                name: game_name,
+                ..Default::default()
             };
 
-            let mut game_files = 0;
-            let mut game_size = 0u64;
+            let entries = match fs::read_dir(long_path(folder)) {
+                Ok(e) => e,
+                Err(_) => return (game, Vec::new(), MonthBuckets::new()),
+            };
 
+            // Files with a version history, for the old-version estimate
+            let mut versioned = Vec::new();
+            let mut sizes = Vec::new();
             for entry in entries {
                 let entry = match entry {
                     Ok(e) => e,
@@ -550,32 +1283,85 @@ pub fn calculate_library_stats(game_folders: &[std::path::PathBuf]) -> LibrarySt
                 }
 
                 if let Ok(metadata) = entry.metadata() {
-                    game_files += 1;
-                    game_size += metadata.len();
+                    let size = metadata.len();
+                    game.files += 1;
+                    game.size += size;
+                    sizes.push((entry.path(), size));
+                    if let Ok(modified) = metadata.modified() {
+                        game.newest_modified = game.newest_modified.max(Some(modified));
+                    }
+                    if let Some(mut mod_file) = parse_mod_filename(&filename)
+                        .or_else(|| parse_unidentified_filename(&filename))
+                        .filter(|f| f.timestamp != "0")
+                    {
+                        mod_file.full_path = entry.path();
+                        mod_file.size = size;
+                        versioned.push(mod_file);
+                    }
+                    if size > game.largest_size || game.largest_name.is_empty() {
+                        game.largest_size = size;
+                        game.largest_name = filename;
+                    }
                 }
             }
 
-            let game_name = folder
-                .file_name()
-                .map(|n| n.to_string_lossy().to_string())
-                .unwrap_or_else(|| "Unknown".to_string());
-
-            (game_name, game_files, game_size)
+            let months = month_buckets(&versioned);
+            let estimate = group_old_versions(versioned, &OldVersionScanOptions::default());
+            game.old_version_files = estimate.total_files;
+            game.old_version_space = estimate.total_space;
+            (game, sizes, months)
         })
         .collect();
 
-    let mut stats = LibraryStats::default();
-    for (name, files, size) in results {
-        if files > 0 {
-            stats.by_game.push((name, files, size));
-            stats.total_files += files;
-            stats.total_size += size;
+    // Hard links share their data: count it once per game, and once in the total
+    let mut size_counts: HashMap<u64, usize> = HashMap::new();
+    for (_, sizes, _) in &results {
+        for (_, size) in sizes {
+            *size_counts.entry(*size).or_default() += 1;
+        }
+    }
+    let mut seen_in_library = HashSet::new();
+    let mut linked_across_games = 0;
+    for (game, sizes, _) in &mut results {
+        let mut seen_in_game = HashSet::new();
+        for (path, size) in sizes.iter() {
+            if size_counts[size] < 2 {
+                continue;
+            }
+            let Some(identity) = hardlink_identity(path) else {
+                continue;
+            };
+            if !seen_in_game.insert(identity) {
+                game.size -= size;
+            } else if !seen_in_library.insert(identity) {
+                linked_across_games += size;
+            }
         }
     }
 
-    // Sort by game name for consistent display
-    stats.by_game.sort_by(|a, b| a.0.cmp(&b.0));
-
+    let mut stats = LibraryStats::default();
+    let mut months = MonthBuckets::new();
+    for (game, _, game_months) in results {
+        for (key, (files, size)) in game_months {
+            let bucket = months.entry(key).or_default();
+            bucket.0 += files;
+            bucket.1 += size;
+        }
+        if game.files > 0 {
+            stats.total_files += game.files;
+            stats.total_size += game.size;
+            stats.old_version_files += game.old_version_files;
+            stats.old_version_space += game.old_version_space;
+            stats.by_game.push(game);
+        }
+    }
+
+    stats.total_size -= linked_across_games;
+    stats.activity = monthly_activity(&months);
+
+    // Sort by game name for consistent display
+    stats.by_game.sort_by(|a, b| a.name.cmp(&b.name));
+
     stats
 }
 
@@ -599,6 +1385,7 @@ mod tests {
                 timestamp: "1234567890".to_string(),
                 size: 1000,
                 is_patch: false,
+                mtime: 0,
             },
             ModFile {
                 file_name: "mod2.7z".to_string(),
@@ -610,6 +1397,7 @@ mod tests {
                 timestamp: "1234567891".to_string(),
                 size: 2000,
                 is_patch: false,
+                mtime: 0,
             },
             ModFile {
                 file_name: "mod3.7z".to_string(),
@@ -621,6 +1409,7 @@ mod tests {
                 timestamp: "1234567892".to_string(),
                 size: 3000,
                 is_patch: false,
+                mtime: 0,
             },
             ModFile {
                 file_name: "mod4.7z".to_string(),
@@ -632,6 +1421,7 @@ mod tests {
                 timestamp: "1234567893".to_string(),
                 size: 4000,
                 is_patch: false,
+                mtime: 0,
             },
         ];
 
@@ -655,6 +1445,8 @@ mod tests {
             used_mod_keys,
             used_mod_file_ids,
             used_file_names,
+            game: None,
+            archive_hashes: HashMap::new(),
         };
 
         let result = detect_orphaned_mods(&mod_files, &[modlist]);
@@ -666,6 +1458,798 @@ mod tests {
         assert_eq!(result.orphaned_mods[0].file.file_name, "mod4.7z");
     }
 
+    fn match_reason_fixture() -> (Vec<ModFile>, ModlistInfo) {
+        let mod_file = |file_name: &str, mod_id: &str, file_id: &str| ModFile {
+            file_name: file_name.to_string(),
+            full_path: std::path::PathBuf::new(),
+            mod_name: "Mod".to_string(),
+            mod_id: mod_id.to_string(),
+            file_id: Some(file_id.to_string()),
+            version: "1.0".to_string(),
+            timestamp: "1234567890".to_string(),
+            size: 100,
+            is_patch: false,
+            mtime: 0,
+        };
+        let files = vec![
+            mod_file("exact.7z", "100", "1000"),
+            mod_file("renamed.7z", "200", "2000"),
+            mod_file("other-file.7z", "300", "3999"),
+        ];
+        let modlist = ModlistInfo {
+            file_path: std::path::PathBuf::new(),
+            name: "Test Modlist".to_string(),
+            mod_count: 3,
+            used_mod_keys: ["100", "200", "300"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            used_mod_file_ids: ["100-1000", "200-2000", "300-3000"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            used_file_names: ["exact.7z"].iter().map(|s| s.to_string()).collect(),
+            game: None,
+            archive_hashes: HashMap::new(),
+        };
+        (files, modlist)
+    }
+
+    fn reason_for(result: &ScanResult, file_name: &str) -> Option<MatchReason> {
+        result
+            .used_mods
+            .iter()
+            .find(|m| m.file.file_name == file_name)
+            .map(|m| m.reason)
+    }
+
+    #[test]
+    fn test_match_reasons() {
+        let (files, modlist) = match_reason_fixture();
+
+        let result = detect_orphaned_mods(&files, std::slice::from_ref(&modlist));
+        assert_eq!(reason_for(&result, "exact.7z"), Some(MatchReason::FileName));
+        assert_eq!(reason_for(&result, "renamed.7z"), Some(MatchReason::FileId));
+        assert_eq!(
+            reason_for(&result, "other-file.7z"),
+            Some(MatchReason::ModId)
+        );
+        assert!(result.orphaned_mods.is_empty());
+
+        // ModID-only matches stay orphaned when strict
+        let strict = OrphanScanOptions {
+            mod_id_fallback: false,
+            ..Default::default()
+        };
+        let result = detect_orphaned_mods_with_options(&files, &[modlist], &strict);
+        assert_eq!(reason_for(&result, "renamed.7z"), Some(MatchReason::FileId));
+        assert_eq!(reason_for(&result, "other-file.7z"), None);
+        assert_eq!(result.orphaned_mods.len(), 1);
+    }
+
+    #[test]
+    fn test_strict_and_loose_matching_of_mod_versions() {
+        let version = |file_id: &str, timestamp: &str| {
+            let name = format!("Big Mod-12345-1-{}-{}-{}.7z", file_id, file_id, timestamp);
+            let mut file = crate::core::parser::parse_mod_filename(&name).unwrap();
+            file.size = 100;
+            file
+        };
+        let files = vec![
+            version("10001", "1600000000"),
+            version("10002", "1650000000"),
+            version("10003", "1700000000"),
+        ];
+        // The modlist uses the middle version only
+        let modlist = ModlistInfo {
+            file_path: std::path::PathBuf::new(),
+            name: "List".to_string(),
+            mod_count: 1,
+            used_mod_keys: HashSet::from(["12345".to_string()]),
+            used_mod_file_ids: HashSet::from(["12345-10002".to_string()]),
+            used_file_names: HashSet::new(),
+            game: None,
+            archive_hashes: HashMap::new(),
+        };
+        let modlists = std::slice::from_ref(&modlist);
+        let orphaned = |result: &ScanResult| -> Vec<Option<String>> {
+            result
+                .orphaned_mods
+                .iter()
+                .map(|m| m.file.file_id.clone())
+                .collect()
+        };
+
+        let strict = detect_orphaned_mods_with_options(
+            &files,
+            modlists,
+            &OrphanScanOptions {
+                mod_id_fallback: false,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            reason_for(&strict, &files[1].file_name),
+            Some(MatchReason::FileId)
+        );
+        let mut cleanable = orphaned(&strict);
+        cleanable.sort();
+        assert_eq!(
+            cleanable,
+            [Some("10001".to_string()), Some("10003".to_string())]
+        );
+
+        let loose = detect_orphaned_mods_with_options(&files, modlists, &Default::default());
+        assert!(orphaned(&loose).is_empty());
+        assert_eq!(
+            reason_for(&loose, &files[0].file_name),
+            Some(MatchReason::ModId)
+        );
+        assert_eq!(
+            reason_for(&loose, &files[1].file_name),
+            Some(MatchReason::FileId)
+        );
+    }
+
+    #[test]
+    fn test_file_id_only_name_matched_when_enabled() {
+        let dir = tempdir().unwrap();
+        let name = "Standalone Patch-7654321-1700000000.7z";
+        fs::write(dir.path().join(name), b"patch").unwrap();
+        fs::write(dir.path().join("Unrelated-7777777-1700000000.7z"), b"other").unwrap();
+        let files = scan_mod_files_in_folder(dir.path());
+        // Without a ModID the name doesn't parse at index time
+        assert!(files.iter().all(|f| f.mod_id == "0" && f.file_id.is_none()));
+        // The modlist knows the ModID, which the file name leaves out
+        let modlist = ModlistInfo {
+            file_path: std::path::PathBuf::new(),
+            name: "List".to_string(),
+            mod_count: 1,
+            used_mod_keys: HashSet::from(["4321".to_string()]),
+            used_mod_file_ids: HashSet::from(["4321-7654321".to_string()]),
+            used_file_names: HashSet::new(),
+            game: None,
+            archive_hashes: HashMap::new(),
+        };
+        let modlists = std::slice::from_ref(&modlist);
+
+        let off = detect_orphaned_mods_with_options(&files, modlists, &Default::default());
+        assert_eq!(off.orphaned_mods.len(), 2);
+
+        let options = OrphanScanOptions {
+            file_id_only_names: true,
+            ..Default::default()
+        };
+        let on = detect_orphaned_mods_with_options(&files, modlists, &options);
+        assert_eq!(reason_for(&on, name), Some(MatchReason::FileId));
+        assert_eq!(on.used_mods[0].modlists, ["List".to_string()]);
+        assert_eq!(on.used_mods[0].file.file_id.as_deref(), Some("7654321"));
+        assert_eq!(on.used_mods[0].file.size, 5);
+        assert_eq!(on.orphaned_mods.len(), 1);
+    }
+
+    #[test]
+    fn test_exclusive_modlist_sizes() {
+        let mod_file = |file_name: &str, mod_id: &str, size: u64| ModFile {
+            file_name: file_name.to_string(),
+            full_path: std::path::PathBuf::new(),
+            mod_name: file_name.to_string(),
+            mod_id: mod_id.to_string(),
+            file_id: None,
+            version: "1.0".to_string(),
+            timestamp: "0".to_string(),
+            size,
+            is_patch: false,
+            mtime: 0,
+        };
+        let modlist = |name: &str, files: &[&str], mod_ids: &[&str]| ModlistInfo {
+            file_path: std::path::PathBuf::from(format!("{}.wabbajack", name)),
+            name: name.to_string(),
+            mod_count: files.len(),
+            used_mod_keys: mod_ids.iter().map(|s| s.to_string()).collect(),
+            used_mod_file_ids: HashSet::new(),
+            used_file_names: files.iter().map(|s| s.to_string()).collect(),
+            game: None,
+            archive_hashes: HashMap::new(),
+        };
+        let files = vec![
+            mod_file("only-a.7z", "1", 100),
+            mod_file("only-b.7z", "2", 20),
+            mod_file("shared.7z", "3", 5000),
+            mod_file("unused.7z", "2", 7),
+            mod_file("Tool.exe", "4", 900),
+        ];
+        let modlists = [
+            modlist("A", &["only-a.7z", "shared.7z", "Tool.exe"], &["1", "3"]),
+            modlist("B", &["only-b.7z", "shared.7z"], &["2", "3"]),
+        ];
+        let options = OrphanScanOptions {
+            mod_id_fallback: false,
+            protected_extensions: vec![".exe".to_string()],
+            ..Default::default()
+        };
+
+        // The shared file and the protected tool belong to neither total
+        assert_eq!(
+            exclusive_modlist_sizes(&files, &modlists, &options),
+            [100, 20]
+        );
+
+        // With the ModID fallback, B also keeps its other file of mod 2
+        let fallback = OrphanScanOptions {
+            mod_id_fallback: true,
+            ..options
+        };
+        assert_eq!(
+            exclusive_modlist_sizes(&files, &modlists, &fallback),
+            [100, 27]
+        );
+    }
+
+    #[test]
+    fn test_identical_modlists_counted_once() {
+        let mod_file = |file_name: &str, mod_id: &str, size: u64| ModFile {
+            file_name: file_name.to_string(),
+            full_path: std::path::PathBuf::from(file_name),
+            mod_name: "Mod".to_string(),
+            mod_id: mod_id.to_string(),
+            file_id: None,
+            version: "1".to_string(),
+            timestamp: "1600000000".to_string(),
+            size,
+            is_patch: false,
+            mtime: 0,
+        };
+        let modlist = |path: &str, files: &[&str]| ModlistInfo {
+            file_path: std::path::PathBuf::from(path),
+            name: "List".to_string(),
+            mod_count: files.len(),
+            used_mod_keys: HashSet::new(),
+            used_mod_file_ids: ["11111-1".to_string()].into_iter().collect(),
+            used_file_names: files.iter().map(|s| s.to_string()).collect(),
+            game: None,
+            archive_hashes: HashMap::new(),
+        };
+        let files = vec![
+            mod_file("a.7z", "11111", 100),
+            mod_file("b.7z", "22222", 20),
+            mod_file("unused.7z", "33333", 7),
+        ];
+        let modlists = [
+            modlist("4.0.0.0/List.wabbajack", &["a.7z"]),
+            modlist("4.0.1.0/List.wabbajack", &["a.7z"]),
+            modlist("Other.wabbajack", &["b.7z"]),
+        ];
+
+        // Unmerged, the two copies of List share a.7z and neither gets it
+        let options = OrphanScanOptions::default();
+        assert_eq!(
+            exclusive_modlist_sizes(&files, &modlists, &options),
+            [0, 0, 20]
+        );
+
+        let (unique, merged) = merge_identical_modlists(&modlists);
+        assert_eq!(unique.len(), 2);
+        assert_eq!(
+            merged,
+            [(
+                std::path::PathBuf::from("4.0.1.0/List.wabbajack"),
+                std::path::PathBuf::from("4.0.0.0/List.wabbajack")
+            )]
+        );
+        assert_eq!(
+            exclusive_modlist_sizes(&files, &unique, &options),
+            [100, 20]
+        );
+
+        let result = detect_orphaned_mods(&files, &unique);
+        assert_eq!(result.used_mods.len(), 2);
+        assert_eq!(result.orphaned_mods.len(), 1);
+        assert_eq!(result.orphaned_mods[0].file.file_name, "unused.7z");
+        let used_a = result
+            .used_mods
+            .iter()
+            .find(|m| m.file.file_name == "a.7z")
+            .unwrap();
+        assert_eq!(used_a.modlists, ["List"]);
+    }
+
+    #[test]
+    fn test_protected_extension_never_orphaned() {
+        let mod_file = |file_name: &str| ModFile {
+            file_name: file_name.to_string(),
+            full_path: std::path::PathBuf::new(),
+            mod_name: file_name.to_string(),
+            mod_id: "0".to_string(),
+            file_id: None,
+            version: "0.0".to_string(),
+            timestamp: "0".to_string(),
+            size: 100,
+            is_patch: false,
+            mtime: 0,
+        };
+        let files = vec![mod_file("ToolInstaller.EXE"), mod_file("Leftover.7z")];
+        let (_, modlist) = match_reason_fixture();
+        let options = OrphanScanOptions {
+            protected_extensions: vec![".exe".to_string()],
+            ..Default::default()
+        };
+
+        let result = detect_orphaned_mods_with_options(&files, &[modlist], &options);
+        assert_eq!(result.orphaned_mods.len(), 1);
+        assert_eq!(result.orphaned_mods[0].file.file_name, "Leftover.7z");
+        assert_eq!(
+            reason_for(&result, "ToolInstaller.EXE"),
+            Some(MatchReason::ProtectedExtension)
+        );
+        // Protected files still count towards the library totals
+        assert_eq!(result.used_size + result.orphaned_size, 200);
+    }
+
+    #[test]
+    fn test_small_archives_held_back_from_orphans() {
+        let dir = tempdir().unwrap();
+        let small = dir.path().join("Selector-11111-1-0-1234567890.7z");
+        let large = dir.path().join("Textures-22222-1-0-1234567890.7z");
+        fs::write(&small, vec![0u8; 2 * 1024]).unwrap();
+        File::create(&large)
+            .unwrap()
+            .set_len(50 * 1024 * 1024)
+            .unwrap();
+
+        let files = get_all_mod_files(&[dir.path().to_path_buf()]).unwrap();
+        assert_eq!(files.len(), 2);
+        let min_mod_size = 64 * 1024;
+        let by_name = |name: &str| {
+            files
+                .iter()
+                .find(|f| f.file_name.starts_with(name))
+                .unwrap()
+        };
+        assert!(by_name("Selector").is_trivial(min_mod_size));
+        assert!(!by_name("Textures").is_trivial(min_mod_size));
+        assert!(!by_name("Selector").is_trivial(0));
+
+        let (_, modlist) = match_reason_fixture();
+        let options = OrphanScanOptions {
+            min_mod_size,
+            ..Default::default()
+        };
+        let result = detect_orphaned_mods_with_options(&files, &[modlist], &options);
+        assert_eq!(result.orphaned_mods.len(), 1);
+        assert_eq!(result.orphaned_size, 50 * 1024 * 1024);
+        assert_eq!(result.small_mods.len(), 1);
+        assert_eq!(result.small_size, 2 * 1024);
+    }
+
+    #[test]
+    fn test_recent_files_held_back_from_orphans() {
+        let dir = tempdir().unwrap();
+        let recent = dir.path().join("Fresh-11111-1-0-1234567890.7z");
+        let old = dir.path().join("Stale-22222-1-0-1234567890.7z");
+        fs::write(&recent, b"recent").unwrap();
+        fs::write(&old, b"old").unwrap();
+        let thirty_days_ago = SystemTime::now() - std::time::Duration::from_secs(30 * 86_400);
+        File::options()
+            .write(true)
+            .open(&old)
+            .unwrap()
+            .set_modified(thirty_days_ago)
+            .unwrap();
+
+        let files = scan_mod_files_in_folder(dir.path());
+        let (_, modlist) = match_reason_fixture();
+
+        // Off by default: both files are orphaned
+        let result = detect_orphaned_mods_with_options(
+            &files,
+            std::slice::from_ref(&modlist),
+            &Default::default(),
+        );
+        assert_eq!(result.orphaned_mods.len(), 2);
+        assert!(result.recent_mods.is_empty());
+
+        let options = OrphanScanOptions {
+            ignore_newer_than_days: Some(7),
+            ..Default::default()
+        };
+        let result = detect_orphaned_mods_with_options(&files, &[modlist], &options);
+        assert_eq!(result.orphaned_mods.len(), 1);
+        assert_eq!(
+            result.orphaned_mods[0].file.file_name,
+            old.file_name().unwrap().to_str().unwrap()
+        );
+        assert_eq!(result.recent_mods.len(), 1);
+        assert_eq!(result.recent_size, 6);
+        // Held-back files are neither used nor deletable
+        assert!(reason_for(&result, "Fresh-11111-1-0-1234567890.7z").is_none());
+        assert_eq!(result.orphaned_size, 3);
+    }
+
+    #[test]
+    fn test_all_parts_protected_when_one_part_is_used() {
+        let part = |file_name: &str, mod_name: &str, timestamp: &str| ModFile {
+            file_name: file_name.to_string(),
+            full_path: std::path::PathBuf::new(),
+            mod_name: mod_name.to_string(),
+            mod_id: "5555".to_string(),
+            file_id: None,
+            version: "1-0".to_string(),
+            timestamp: timestamp.to_string(),
+            size: 100,
+            is_patch: false,
+            mtime: 0,
+        };
+        let files = vec![
+            part(
+                "Big Textures Part 1-5555-1-0-1600000000.7z",
+                "Big Textures Part 1",
+                "1600000000",
+            ),
+            part(
+                "Big Textures Part 2-5555-1-0-1600000000.7z",
+                "Big Textures Part 2",
+                "1600000000",
+            ),
+            // An outdated copy of the referenced part is still a normal orphan
+            part(
+                "Big Textures Part 1-5555-0-9-1500000000.7z",
+                "Big Textures Part 1",
+                "1500000000",
+            ),
+        ];
+        let modlist = ModlistInfo {
+            file_path: std::path::PathBuf::new(),
+            name: "Test Modlist".to_string(),
+            mod_count: 1,
+            used_mod_keys: ["5555".to_string()].into_iter().collect(),
+            used_mod_file_ids: HashSet::new(),
+            used_file_names: ["Big Textures Part 1-5555-1-0-1600000000.7z".to_string()]
+                .into_iter()
+                .collect(),
+            game: None,
+            archive_hashes: HashMap::new(),
+        };
+
+        let strict = OrphanScanOptions {
+            mod_id_fallback: false,
+            ..Default::default()
+        };
+        let result = detect_orphaned_mods_with_options(&files, &[modlist], &strict);
+        assert_eq!(
+            reason_for(&result, "Big Textures Part 2-5555-1-0-1600000000.7z"),
+            Some(MatchReason::SiblingPart)
+        );
+        assert!(result
+            .used_mods
+            .iter()
+            .all(|m| m.modlists == vec!["Test Modlist".to_string()]));
+        assert_eq!(result.used_mods.len(), 2);
+        assert_eq!(result.orphaned_mods.len(), 1);
+        assert_eq!(
+            result.orphaned_mods[0].file.file_name,
+            "Big Textures Part 1-5555-0-9-1500000000.7z"
+        );
+    }
+
+    #[test]
+    fn test_duplicates_without_mod_id_group_by_name() {
+        let dir = tempdir().unwrap();
+        for name in [
+            "Alpha Textures-1-0-1600000000.7z",
+            "Alpha Textures-1-1-1610000000.7z",
+            "Beta Meshes-1-0-1600000000.7z",
+            "Gamma Sounds-2-0-1610000000.7z",
+        ] {
+            File::create(dir.path().join(name))
+                .unwrap()
+                .write_all(b"data")
+                .unwrap();
+        }
+
+        let result = scan_folder_for_duplicates(dir.path()).unwrap();
+        assert_eq!(result.duplicates.len(), 1, "Only same-named files group");
+        let group = &result.duplicates[0];
+        assert_eq!(group.mod_key, "name:Alpha Textures");
+        assert_eq!(group.files.len(), 2);
+        assert_eq!(
+            group.files[group.newest_idx].file_name,
+            "Alpha Textures-1-1-1610000000.7z"
+        );
+    }
+
+    #[test]
+    fn test_keep_by_highest_version_handles_reverted_upload() {
+        let dir = tempdir().unwrap();
+        // 2.0 was pulled and 1.5 re-uploaded later as a revert
+        for (name, size) in [
+            ("Revert Mod-1234-1-0-1600000000.7z", 100),
+            ("Revert Mod-1234-2-0-1610000000.7z", 120),
+            ("Revert Mod-1234-1-5-1620000000.7z", 110),
+        ] {
+            File::create(dir.path().join(name))
+                .unwrap()
+                .write_all(&vec![0u8; size])
+                .unwrap();
+        }
+
+        let newest = scan_folder_for_duplicates(dir.path()).unwrap();
+        let group = &newest.duplicates[0];
+        assert_eq!(
+            group.files[group.newest_idx].file_name,
+            "Revert Mod-1234-1-5-1620000000.7z"
+        );
+
+        let options = OldVersionScanOptions {
+            keep_by: KeepBy::HighestVersion,
+            ..Default::default()
+        };
+        let highest = scan_folder_for_duplicates_with_options(dir.path(), &options).unwrap();
+        let group = &highest.duplicates[0];
+        assert_eq!(
+            group.files[group.newest_idx].file_name,
+            "Revert Mod-1234-2-0-1610000000.7z"
+        );
+        assert_eq!(highest.total_files, 2);
+    }
+
+    #[test]
+    fn test_keep_by_highest_version_falls_back_to_timestamp() {
+        let dir = tempdir().unwrap();
+        for name in [
+            "Odd Mod-4321-beta-1600000000.7z",
+            "Odd Mod-4321-1-0-1610000000.7z",
+        ] {
+            File::create(dir.path().join(name))
+                .unwrap()
+                .write_all(b"data")
+                .unwrap();
+        }
+
+        let options = OldVersionScanOptions {
+            keep_by: KeepBy::HighestVersion,
+            ..Default::default()
+        };
+        let result = scan_folder_for_duplicates_with_options(dir.path(), &options).unwrap();
+        let group = &result.duplicates[0];
+        assert_eq!(
+            group.files[group.newest_idx].file_name,
+            "Odd Mod-4321-1-0-1610000000.7z"
+        );
+    }
+
+    fn write_sized(dir: &Path, name: &str, size: usize) {
+        File::create(dir.join(name))
+            .unwrap()
+            .write_all(&vec![0u8; size])
+            .unwrap();
+    }
+
+    #[test]
+    fn test_patch_size_ratio_threshold() {
+        let dir = tempdir().unwrap();
+        write_sized(dir.path(), "Cool Mod Patch-1234-1-0-1600000000.7z", 100);
+        // Newest is exactly 0.1x the older file: not below the default threshold
+        write_sized(dir.path(), "Cool Mod Patch-1234-1-1-1610000000.7z", 10);
+
+        let at_default = scan_folder_for_duplicates(dir.path()).unwrap();
+        assert_eq!(at_default.duplicates.len(), 1);
+
+        let stricter = OldVersionScanOptions {
+            patch_size_ratio: 0.11,
+            ..Default::default()
+        };
+        let result = scan_folder_for_duplicates_with_options(dir.path(), &stricter).unwrap();
+        assert!(result.duplicates.is_empty());
+    }
+
+    #[test]
+    fn test_smaller_hotfix_keeper_needs_review() {
+        let dir = tempdir().unwrap();
+        // Full mod, then a hotfix upload at 30% of its size
+        write_sized(dir.path(), "Hot Mod-5678-1-0-1600000000.7z", 1000);
+        write_sized(dir.path(), "Hot Mod-5678-1-1-1610000000.7z", 300);
+        // A regular update of similar size
+        write_sized(dir.path(), "Calm Mod-8765-1-0-1600000000.7z", 1000);
+        write_sized(dir.path(), "Calm Mod-8765-1-1-1610000000.7z", 900);
+
+        let result = scan_folder_for_duplicates(dir.path()).unwrap();
+        let flagged: Vec<(&str, bool)> = result
+            .duplicates
+            .iter()
+            .map(|g| {
+                (
+                    g.files[g.newest_idx].file_name.as_str(),
+                    g.review_recommended,
+                )
+            })
+            .collect();
+        assert_eq!(flagged.len(), 2);
+        assert!(flagged.contains(&("Hot Mod-5678-1-1-1610000000.7z", true)));
+        assert!(flagged.contains(&("Calm Mod-8765-1-1-1610000000.7z", false)));
+
+        let lenient = OldVersionScanOptions {
+            review_size_ratio: 0.25,
+            ..Default::default()
+        };
+        let result = scan_folder_for_duplicates_with_options(dir.path(), &lenient).unwrap();
+        assert!(result.duplicates.iter().all(|g| !g.review_recommended));
+    }
+
+    #[test]
+    fn test_keeper_override_changes_deletion_set() {
+        let dir = tempdir().unwrap();
+        write_sized(dir.path(), "Hot Mod-5678-1-0-1600000000.7z", 1000);
+        write_sized(dir.path(), "Hot Mod-5678-1-1-1610000000.7z", 300);
+        write_sized(dir.path(), "Hot Mod-5678-1-2-1620000000.7z", 200);
+        let result = scan_folder_for_duplicates(dir.path()).unwrap();
+        let group = &result.duplicates[0];
+        let names = |files: &[ModFile]| -> Vec<String> {
+            files.iter().map(|f| f.version.clone()).collect()
+        };
+        assert_eq!(names(group.deletable_files()), ["1-0", "1-1"]);
+
+        // Keep the full upload instead of the latest hotfix
+        let full = group.files.iter().position(|f| f.version == "1-0").unwrap();
+        let overridden = group.with_keeper(full).unwrap();
+        assert_eq!(names(overridden.deletable_files()), ["1-1", "1-2"]);
+        assert_eq!(overridden.files[overridden.newest_idx].version, "1-0");
+        assert_eq!(overridden.space_to_free, 500);
+        assert_eq!(overridden.files.len(), 3);
+        assert!(group.review_recommended && overridden.review_recommended);
+
+        // Keeping the automatic choice changes nothing; there is always one keeper
+        let same = group.with_keeper(group.newest_idx).unwrap();
+        assert_eq!(names(same.deletable_files()), ["1-0", "1-1"]);
+        assert_eq!(same.space_to_free, group.space_to_free);
+        assert!(group.with_keeper(group.files.len()).is_none());
+    }
+
+    #[test]
+    fn test_suspicious_size_ratio_threshold() {
+        let dir = tempdir().unwrap();
+        // Same version re-uploaded a day later at exactly 10x the size
+        write_sized(dir.path(), "Big Mod-4321-2-0-1600000000.7z", 100);
+        write_sized(dir.path(), "Big Mod-4321-2-0-1600086400.7z", 1000);
+
+        let at_default = scan_folder_for_duplicates(dir.path()).unwrap();
+        assert_eq!(at_default.duplicates.len(), 1);
+
+        let stricter = OldVersionScanOptions {
+            suspicious_size_ratio: 9.9,
+            ..Default::default()
+        };
+        let result = scan_folder_for_duplicates_with_options(dir.path(), &stricter).unwrap();
+        assert!(result.duplicates.is_empty());
+    }
+
+    #[test]
+    fn test_skipped_groups_report_reason() {
+        let file =
+            |name: &str, mod_id: &str, version: &str, ts: &str, size: u64, patch: bool| ModFile {
+                file_name: name.to_string(),
+                full_path: std::path::PathBuf::from(name),
+                mod_name: "Mod".to_string(),
+                mod_id: mod_id.to_string(),
+                file_id: None,
+                version: version.to_string(),
+                timestamp: ts.to_string(),
+                size,
+                is_patch: patch,
+                mtime: 0,
+            };
+        let files = vec![
+            // Same timestamp on both: no way to tell which is newer
+            file("a.7z", "100", "1.0", "1600000000", 10, false),
+            file("a.zip", "100", "1.1", "1600000000", 10, false),
+            // Same version uploaded ten minutes apart
+            file("b1.7z", "200", "2.0", "1600000000", 10, false),
+            file("b2.7z", "200", "2.0", "1600000600", 10, false),
+            // Patch next to a main file
+            file("Main File.7z", "300", "1.0", "1600000000", 100, false),
+            file("Hotfix.7z", "300", "1.1", "1610000000", 90, true),
+            // Newest is a patch far smaller than the old full file
+            file("c1.7z", "400", "1.0", "1600000000", 100, true),
+            file("c2.7z", "400", "1.1", "1610000000", 5, true),
+            // A normal update is still offered
+            file("d1.7z", "500", "1.0", "1600000000", 10, false),
+            file("d2.7z", "500", "1.1", "1610000000", 10, false),
+        ];
+
+        let result = group_old_versions(files, &OldVersionScanOptions::default());
+        assert_eq!(
+            result.skipped_groups,
+            vec![
+                ("100:Mod".to_string(), SkipReason::SameTimestamp),
+                ("200:Mod".to_string(), SkipReason::SuspiciousPattern),
+                ("300:Mod".to_string(), SkipReason::PatchAndMain),
+                ("400:Mod".to_string(), SkipReason::NewestIsPatch),
+            ]
+        );
+        assert_eq!(result.duplicates.len(), 1);
+        assert_eq!(result.duplicates[0].mod_key, "500:Mod");
+    }
+
+    #[test]
+    fn test_validate_wabbajack_dir() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        assert_eq!(
+            validate_wabbajack_dir(&path.join("missing")),
+            WabbajackDirStatus::NotFound
+        );
+        assert_eq!(validate_wabbajack_dir(path), WabbajackDirStatus::Empty);
+
+        File::create(path.join("Wabbajack.exe")).unwrap();
+        assert_eq!(
+            validate_wabbajack_dir(path),
+            WabbajackDirStatus::NoVersionFolders
+        );
+
+        let modlists = path.join("4.0.0.0").join("downloaded_mod_lists");
+        fs::create_dir_all(&modlists).unwrap();
+        File::create(modlists.join("List@@Game.wabbajack")).unwrap();
+        assert_eq!(validate_wabbajack_dir(path), WabbajackDirStatus::Valid);
+    }
+
+    #[test]
+    fn test_downloads_overlapping_wabbajack_dir() {
+        let dir = tempdir().unwrap();
+        let wabbajack = dir.path();
+        let modlists = wabbajack.join("4.0.0.0").join("downloaded_mod_lists");
+        fs::create_dir_all(&modlists).unwrap();
+        File::create(modlists.join("List@@Game.wabbajack")).unwrap();
+        fs::create_dir(wabbajack.join("downloaded_mod_lists")).unwrap();
+        let skyrim = wabbajack.join("Skyrim");
+        fs::create_dir(&skyrim).unwrap();
+        File::create(skyrim.join("Mod-12345-1-0-1600000000.7z")).unwrap();
+
+        // Same folder picked for both
+        let roots = vec![wabbajack.to_path_buf()];
+        assert!(dirs_overlap(wabbajack, &skyrim));
+        assert!(!dirs_overlap(&skyrim, &wabbajack.join("4.0.0.0")));
+        let folders = get_game_folders_in_roots(&roots).unwrap();
+        assert_eq!(folders.len(), 3);
+        let (kept, excluded) = exclude_wabbajack_dirs(folders, wabbajack);
+        assert_eq!(kept, vec![skyrim.clone()]);
+        assert_eq!(
+            excluded,
+            vec![
+                wabbajack.join("4.0.0.0"),
+                wabbajack.join("downloaded_mod_lists")
+            ]
+        );
+
+        // `Wabbajack.exe` makes the install folder itself look like a game folder
+        File::create(wabbajack.join("Wabbajack.exe")).unwrap();
+        let folders = get_game_folders_in_roots(&roots).unwrap();
+        let (kept, excluded) = exclude_wabbajack_dirs(folders, wabbajack);
+        assert_eq!(kept, vec![skyrim.clone()]);
+        assert_eq!(excluded, roots);
+
+        // Wabbajack installed inside the downloads folder
+        let downloads = tempdir().unwrap();
+        let nested = downloads.path().join("Wabbajack");
+        fs::create_dir(&nested).unwrap();
+        fs::create_dir(downloads.path().join("Fallout4")).unwrap();
+        let roots = vec![downloads.path().to_path_buf()];
+        let folders = get_game_folders_in_roots(&roots).unwrap();
+        let (kept, excluded) = exclude_wabbajack_dirs(folders, &nested);
+        assert_eq!(kept, vec![downloads.path().join("Fallout4")]);
+        assert_eq!(excluded, vec![nested]);
+    }
+
+    #[test]
+    fn test_validate_wabbajack_dir_game_folder() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("Data")).unwrap();
+        File::create(dir.path().join("SkyrimSE.exe")).unwrap();
+        assert_eq!(
+            validate_wabbajack_dir(dir.path()),
+            WabbajackDirStatus::LooksLikeGameFolder
+        );
+    }
+
     #[test]
     fn test_find_wabbajack_files() {
         let dir = tempdir().unwrap();
@@ -698,4 +2282,305 @@ mod tests {
         let files = get_all_mod_files(&[game_dir]).unwrap();
         assert_eq!(files.len(), 2);
     }
+
+    #[test]
+    fn test_flat_downloads_folder_is_single_game() {
+        let dir = tempdir().unwrap();
+        File::create(dir.path().join("SkyUI-12345-5-0-1234567890.7z")).unwrap();
+        File::create(dir.path().join("SKSE-54321-1-0-9876543210.zip")).unwrap();
+
+        let folders = get_game_folders(dir.path()).unwrap();
+        assert_eq!(folders, vec![dir.path().to_path_buf()]);
+
+        let stats = calculate_library_stats(&folders);
+        assert_eq!(stats.by_game.len(), 1);
+        assert_eq!(stats.total_files, 2);
+    }
+
+    #[test]
+    fn test_library_stats_estimate_old_versions() {
+        let dir = tempdir().unwrap();
+        let skyrim = dir.path().join("Skyrim");
+        fs::create_dir_all(&skyrim).unwrap();
+        for (name, size) in [
+            ("SkyUI-12604-5-1-1600000000.7z", 100),
+            ("SkyUI-12604-5-2-1700000000.7z", 110),
+            ("USSEP-266-4-2-1600000000.7z", 70),
+            ("USSEP-266-4-3-1650000000.7z", 72),
+            ("USSEP-266-4-4-1700000000.7z", 75),
+            ("Installer.exe", 500),
+        ] {
+            fs::write(skyrim.join(name), vec![0u8; size]).unwrap();
+        }
+
+        let stats = calculate_library_stats(std::slice::from_ref(&skyrim));
+        assert_eq!(stats.total_files, 6);
+        assert_eq!(stats.old_version_files, 3);
+        assert_eq!(stats.old_version_space, 100 + 70 + 72);
+        assert_eq!(stats.by_game[0].old_version_space, stats.old_version_space);
+    }
+
+    #[test]
+    fn test_library_stats_newest_modified_per_game() {
+        let dir = tempdir().unwrap();
+        let day = std::time::Duration::from_secs(86_400);
+        let now = SystemTime::now();
+        let mut expected = Vec::new();
+        for (game, ages) in [("Fallout4", [700, 400]), ("Skyrim", [30, 2])] {
+            let folder = dir.path().join(game);
+            fs::create_dir(&folder).unwrap();
+            for (i, days) in ages.into_iter().enumerate() {
+                let file =
+                    File::create(folder.join(format!("Mod-1234{}-1-0-1600000000.7z", i))).unwrap();
+                file.set_modified(now - day * days).unwrap();
+            }
+            expected.push(Some(now - day * ages[1]));
+        }
+        // Not an archive, so it doesn't count
+        let readme = File::create(dir.path().join("Fallout4").join("readme.txt")).unwrap();
+        readme.set_modified(now).unwrap();
+
+        let folders = get_game_folders(dir.path()).unwrap();
+        let stats = calculate_library_stats(&folders);
+        let newest: Vec<_> = stats.by_game.iter().map(|g| g.newest_modified).collect();
+        assert_eq!(newest, expected);
+    }
+
+    #[cfg(any(unix, windows))]
+    #[test]
+    fn test_hardlinked_files_counted_once() {
+        let dir = tempdir().unwrap();
+        let skyrim = dir.path().join("Skyrim");
+        let enderal = dir.path().join("Enderal");
+        fs::create_dir(&skyrim).unwrap();
+        fs::create_dir(&enderal).unwrap();
+        let shared = skyrim.join("Shared-12345-1-0-1600000000.7z");
+        fs::write(&shared, vec![1u8; 100]).unwrap();
+        fs::hard_link(&shared, skyrim.join("Shared Link-12345-1-0-1600000000.7z")).unwrap();
+        fs::hard_link(&shared, enderal.join("Shared-12345-1-0-1600000000.7z")).unwrap();
+        // Same size, separate data
+        fs::write(
+            enderal.join("Other-22222-1-0-1600000000.7z"),
+            vec![2u8; 100],
+        )
+        .unwrap();
+
+        let stats = calculate_library_stats(&[enderal.clone(), skyrim.clone()]);
+        assert_eq!(stats.total_files, 4);
+        assert_eq!(stats.total_size, 200);
+        let sizes: Vec<u64> = stats.by_game.iter().map(|g| g.size).collect();
+        assert_eq!(sizes, [200, 100]);
+
+        let files = get_all_mod_files(&[enderal, skyrim]).unwrap();
+        let result = detect_orphaned_mods(&files, &[]);
+        assert_eq!(result.orphaned_mods.len(), 4);
+        assert_eq!(result.orphaned_size, 200);
+    }
+
+    #[test]
+    fn test_loose_bsa_counted_but_not_orphaned() {
+        let dir = tempdir().unwrap();
+        let skyrim = dir.path().join("Skyrim");
+        fs::create_dir(&skyrim).unwrap();
+        fs::write(skyrim.join("Textures.bsa"), vec![0u8; 300]).unwrap();
+        fs::write(
+            skyrim.join("Unused-12345-1-0-1600000000.7z"),
+            vec![0u8; 100],
+        )
+        .unwrap();
+
+        let stats = calculate_library_stats(std::slice::from_ref(&skyrim));
+        assert_eq!(stats.total_files, 2);
+        assert_eq!(stats.total_size, 400);
+
+        let files = get_all_mod_files(&[skyrim]).unwrap();
+        let options = OrphanScanOptions {
+            protected_extensions: crate::core::config::Settings::default().protected_extensions,
+            ..Default::default()
+        };
+        let result = detect_orphaned_mods_with_options(&files, &[], &options);
+        let orphaned: Vec<&str> = result
+            .orphaned_mods
+            .iter()
+            .map(|m| m.file.file_name.as_str())
+            .collect();
+        assert_eq!(orphaned, ["Unused-12345-1-0-1600000000.7z"]);
+        assert!(result
+            .used_mods
+            .iter()
+            .any(|m| m.file.file_name == "Textures.bsa"));
+    }
+
+    #[test]
+    fn test_library_activity_by_month() {
+        let dir = tempdir().unwrap();
+        let skyrim = dir.path().join("Skyrim");
+        let fallout = dir.path().join("Fallout4");
+        fs::create_dir(&skyrim).unwrap();
+        fs::create_dir(&fallout).unwrap();
+        // 2020-11-15, 2020-11-20, 2021-01-10; the last name's timestamp does not parse
+        for (folder, name, size) in [
+            (&skyrim, "ModA-111-1-0-1605398400.7z", 10),
+            (&fallout, "ModB-222-1-0-1605830400.7z", 20),
+            (&skyrim, "ModC-333-1-0-1610236800.7z", 5),
+            (&skyrim, "Loose.bsa", 7),
+        ] {
+            fs::write(folder.join(name), vec![0u8; size]).unwrap();
+        }
+
+        let stats = calculate_library_stats(&[skyrim, fallout]);
+        assert_eq!(stats.total_files, 4);
+        let months: Vec<(i32, u32, usize, u64)> = stats
+            .activity
+            .iter()
+            .map(|m| (m.year, m.month, m.files, m.size))
+            .collect();
+        assert_eq!(
+            months,
+            [(2020, 11, 2, 30), (2020, 12, 0, 0), (2021, 1, 1, 5)]
+        );
+
+        assert!(calculate_library_stats(&[]).activity.is_empty());
+    }
+
+    #[test]
+    fn test_library_stats_track_largest_file_per_game() {
+        let dir = tempdir().unwrap();
+        let skyrim = dir.path().join("Skyrim");
+        let fallout = dir.path().join("Fallout4");
+        fs::create_dir_all(&skyrim).unwrap();
+        fs::create_dir_all(&fallout).unwrap();
+        for (folder, name, size) in [
+            (&skyrim, "Small-111-1-0-1600000000.7z", 10),
+            (&skyrim, "Huge-222-1-0-1600000000.7z", 300),
+            (&skyrim, "Medium-333-1-0-1600000000.7z", 50),
+            (&fallout, "Only-444-1-0-1600000000.zip", 40),
+        ] {
+            File::create(folder.join(name))
+                .unwrap()
+                .write_all(&vec![0u8; size])
+                .unwrap();
+        }
+
+        let stats = calculate_library_stats(&[skyrim, fallout]);
+        assert_eq!(stats.by_game.len(), 2);
+        let fallout = &stats.by_game[0];
+        assert_eq!(fallout.name, "Fallout4");
+        assert_eq!(fallout.largest_name, "Only-444-1-0-1600000000.zip");
+        let skyrim = &stats.by_game[1];
+        assert_eq!(skyrim.files, 3);
+        assert_eq!(skyrim.largest_name, "Huge-222-1-0-1600000000.7z");
+        assert_eq!(skyrim.largest_size, 300);
+        assert_eq!(skyrim.average_size(), 120);
+    }
+
+    #[test]
+    fn test_mixed_downloads_folder_skips_empty_subfolders() {
+        let dir = tempdir().unwrap();
+        File::create(dir.path().join("SkyUI-12345-5-0-1234567890.7z")).unwrap();
+
+        // App folder with archives inside must never become a game
+        let recycle_bin = dir
+            .path()
+            .join("WLC_RecycleBin")
+            .join("2025-01-01_00-00-00");
+        fs::create_dir_all(&recycle_bin).unwrap();
+        File::create(recycle_bin.join("Old-111-1-0-1234567890.7z")).unwrap();
+        File::create(
+            dir.path()
+                .join("WLC_RecycleBin")
+                .join("Old-222-1-0-1234567890.7z"),
+        )
+        .unwrap();
+
+        // Unrelated subfolder without archives
+        let misc = dir.path().join("Misc");
+        fs::create_dir(&misc).unwrap();
+        File::create(misc.join("notes.txt")).unwrap();
+
+        // Real game subfolder with archives
+        let fallout = dir.path().join("Fallout4");
+        fs::create_dir(&fallout).unwrap();
+        File::create(fallout.join("F4SE-42147-1-0-1234567890.7z")).unwrap();
+
+        let folders = get_game_folders(dir.path()).unwrap();
+        assert_eq!(folders.len(), 2);
+        assert!(folders.contains(&dir.path().to_path_buf()));
+        assert!(folders.contains(&fallout));
+    }
+
+    #[test]
+    fn test_excluded_folder_patterns_skipped() {
+        let dir = tempdir().unwrap();
+        for folder in [
+            "Skyrim",
+            "_tools",
+            ".cache",
+            "wlc_deleted",
+            "Ignore Me",
+            "Fallout4",
+        ] {
+            let path = dir.path().join(folder);
+            fs::create_dir(&path).unwrap();
+            File::create(path.join("SkyUI-12345-5-0-1234567890.7z")).unwrap();
+        }
+        let folders = get_game_folders(dir.path()).unwrap();
+        assert_eq!(
+            folders,
+            vec![
+                dir.path().join("Fallout4"),
+                dir.path().join("Ignore Me"),
+                dir.path().join("Skyrim")
+            ]
+        );
+
+        let excluded = ["ignore*".to_string(), "*out*".to_string()];
+        let folders = get_game_folders_excluding(dir.path(), &excluded).unwrap();
+        let names: Vec<_> = folders
+            .iter()
+            .map(|f| f.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, [".cache", "Skyrim", "_tools", "wlc_deleted"]);
+
+        assert!(matches_folder_pattern("WLC_Backup", "wlc_backup"));
+        assert!(!matches_folder_pattern("WLC_Backup_Old", "WLC_Backup"));
+        assert!(matches_folder_pattern("a.b.c", "a*c"));
+        assert!(!matches_folder_pattern("ab", "a*b*b"));
+    }
+
+    #[test]
+    fn test_game_folders_from_two_roots() {
+        let dir = tempdir().unwrap();
+        let ssd = dir.path().join("ssd");
+        let hdd = dir.path().join("hdd");
+        for game in [ssd.join("Skyrim"), hdd.join("Skyrim"), hdd.join("Fallout4")] {
+            fs::create_dir_all(&game).unwrap();
+            File::create(game.join("SkyUI-12345-5-0-1234567890.7z")).unwrap();
+        }
+
+        // Listing a root twice does not duplicate its folders
+        let roots = vec![ssd.clone(), hdd.clone(), ssd.clone()];
+        let folders = get_game_folders_in_roots(&roots).unwrap();
+        assert_eq!(
+            folders,
+            vec![hdd.join("Fallout4"), hdd.join("Skyrim"), ssd.join("Skyrim")]
+        );
+        assert_eq!(get_all_mod_files(&folders).unwrap().len(), 3);
+
+        assert_eq!(
+            root_index_for(&hdd.join("Skyrim").join("a.7z"), &roots),
+            Some(1)
+        );
+        let nested = vec![dir.path().to_path_buf(), hdd.clone()];
+        assert_eq!(root_index_for(&hdd.join("Skyrim"), &nested), Some(1));
+        assert_eq!(root_index_for(Path::new("/elsewhere"), &nested), None);
+
+        assert!(get_game_folders_in_roots(&[dir.path().join("missing")]).is_err());
+        // An unplugged drive next to an available one is skipped
+        let unplugged = vec![dir.path().join("missing"), ssd.clone()];
+        assert_eq!(
+            get_game_folders_in_roots(&unplugged).unwrap(),
+            vec![ssd.join("Skyrim")]
+        );
+    }
 }
@@ -0,0 +1,214 @@
+// Copyright (C) 2025 Berkay Yetgin
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::core::cleaner::format_size;
+use crate::core::types::ModFile;
+
+/// Shell a deletion script is written for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptShell {
+    /// Windows `cmd` batch file (`.bat`)
+    Batch,
+    /// PowerShell script (`.ps1`)
+    PowerShell,
+    /// POSIX shell script (`.sh`)
+    Sh,
+}
+
+impl ScriptShell {
+    /// Shell for a script file name, by its extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_string_lossy().to_lowercase();
+        match ext.as_str() {
+            "bat" | "cmd" => Some(ScriptShell::Batch),
+            "ps1" => Some(ScriptShell::PowerShell),
+            "sh" => Some(ScriptShell::Sh),
+            _ => None,
+        }
+    }
+
+    /// Usual shell of the platform the app runs on
+    pub fn native() -> Self {
+        if cfg!(target_os = "windows") {
+            ScriptShell::Batch
+        } else {
+            ScriptShell::Sh
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ScriptShell::Batch => "bat",
+            ScriptShell::PowerShell => "ps1",
+            ScriptShell::Sh => "sh",
+        }
+    }
+
+    fn comment(&self) -> &'static str {
+        match self {
+            ScriptShell::Batch => "REM",
+            ScriptShell::PowerShell | ScriptShell::Sh => "#",
+        }
+    }
+
+    /// Command deleting one file, with the path quoted for this shell
+    fn delete_command(&self, path: &Path) -> String {
+        let path = path.display().to_string();
+        match self {
+            // Paths can't contain `"` on Windows; `%` would expand even inside quotes
+            ScriptShell::Batch => format!("del /f \"{}\"", path.replace('%', "%%")),
+            // PowerShell also ends a single-quoted string at typographic single quotes
+            ScriptShell::PowerShell => {
+                let mut quoted = String::with_capacity(path.len());
+                for c in path.chars() {
+                    if matches!(c, '\'' | '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}') {
+                        quoted.push(c);
+                    }
+                    quoted.push(c);
+                }
+                format!("Remove-Item -LiteralPath '{}'", quoted)
+            }
+            ScriptShell::Sh => format!("rm -f -- '{}'", path.replace('\'', "'\\''")),
+        }
+    }
+}
+
+/// Script deleting `files`, one command per file, with a commented header of totals
+pub fn deletion_script(files: &[ModFile], shell: ScriptShell) -> String {
+    let mut out = String::new();
+    match shell {
+        ScriptShell::Batch => out.push_str("@echo off\r\nchcp 65001 >nul\r\n"),
+        // Windows PowerShell 5.1 reads UTF-8 without a BOM as ANSI
+        ScriptShell::PowerShell => out.push('\u{FEFF}'),
+        ScriptShell::Sh => out.push_str("#!/bin/sh\n"),
+    }
+    let total: u64 = files.iter().map(|f| f.size).sum();
+    let header = [
+        format!(
+            "Generated by Wabbajack Library Cleaner v{} on {}",
+            env!("CARGO_PKG_VERSION"),
+            chrono::Local::now().format("%Y-%m-%d %H:%M")
+        ),
+        format!(
+            "Deletes {} files, {}. Review before running.",
+            files.len(),
+            format_size(total)
+        ),
+    ];
+    let newline = if shell == ScriptShell::Batch {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    for line in header {
+        let _ = write!(out, "{} {}{}", shell.comment(), line, newline);
+    }
+    out.push_str(newline);
+    for file in files {
+        let _ = write!(out, "{}{}", shell.delete_command(&file.full_path), newline);
+    }
+    out
+}
+
+/// Write a deletion script for `files`; the shell follows the extension of `out`, or the
+/// platform's own if it has none that is known
+pub fn export_deletion_script(files: &[ModFile], out: &Path) -> Result<()> {
+    let shell = ScriptShell::from_path(out).unwrap_or_else(ScriptShell::native);
+    fs::write(out, deletion_script(files, shell))
+        .with_context(|| format!("Failed to write deletion script: {:?}", out))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn file(path: &str, size: u64) -> ModFile {
+        ModFile {
+            file_name: Path::new(path)
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .to_string(),
+            full_path: PathBuf::from(path),
+            mod_name: "Mod".to_string(),
+            mod_id: "12345".to_string(),
+            file_id: None,
+            version: "1".to_string(),
+            timestamp: "1600000000".to_string(),
+            size,
+            is_patch: false,
+            mtime: 0,
+        }
+    }
+
+    #[test]
+    fn test_deletion_script_quotes_paths_with_spaces() {
+        let files = [
+            file("/mods/Skyrim Special/Sky UI-12604-5-2-1600000000.7z", 1024),
+            file(
+                "/mods/Skyrim Special/Bob's 100% Mod-2222-1-0-1600000000.7z",
+                1024,
+            ),
+        ];
+
+        let sh = deletion_script(&files, ScriptShell::Sh);
+        assert!(sh.starts_with("#!/bin/sh\n# Generated by"));
+        assert!(sh.contains("# Deletes 2 files, 2.00 KB."));
+        assert!(sh.contains("rm -f -- '/mods/Skyrim Special/Sky UI-12604-5-2-1600000000.7z'\n"));
+        assert!(sh.contains("rm -f -- '/mods/Skyrim Special/Bob'\\''s 100% Mod-2222"));
+
+        let ps = deletion_script(&files, ScriptShell::PowerShell);
+        assert!(ps.contains("Remove-Item -LiteralPath '/mods/Skyrim Special/Bob''s 100% Mod"));
+        let curly = [file(
+            "/mods/Bob\u{2019}s \u{2018}Best\u{201B} Mod-3333-1-0-1600000000.7z",
+            1,
+        )];
+        assert!(deletion_script(&curly, ScriptShell::PowerShell).contains(
+            "-LiteralPath '/mods/Bob\u{2019}\u{2019}s \u{2018}\u{2018}Best\u{201B}\u{201B} Mod"
+        ));
+
+        let bat = deletion_script(&files, ScriptShell::Batch);
+        assert!(bat.contains("REM Deletes 2 files"));
+        assert!(bat.contains("del /f \"/mods/Skyrim Special/Sky UI-12604-5-2-1600000000.7z\"\r\n"));
+        assert!(bat.contains("Bob's 100%% Mod"));
+    }
+
+    #[test]
+    fn test_powershell_script_written_with_bom() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = [file("/mods/Sk\u{fc}rim/Mod-1-1-0-1600000000.7z", 1)];
+        let ps1 = dir.path().join("delete.ps1");
+        export_deletion_script(&files, &ps1).unwrap();
+        let bytes = fs::read(&ps1).unwrap();
+        assert!(bytes.starts_with(&[0xEF, 0xBB, 0xBF]));
+        assert!(String::from_utf8(bytes).unwrap().contains("Sk\u{fc}rim"));
+
+        let sh = dir.path().join("delete.sh");
+        export_deletion_script(&files, &sh).unwrap();
+        assert!(fs::read(&sh).unwrap().starts_with(b"#!"));
+    }
+
+    #[test]
+    fn test_script_shell_from_extension() {
+        assert_eq!(
+            ScriptShell::from_path(Path::new("a/delete.PS1")),
+            Some(ScriptShell::PowerShell)
+        );
+        assert_eq!(
+            ScriptShell::from_path(Path::new("delete.cmd")),
+            Some(ScriptShell::Batch)
+        );
+        assert_eq!(ScriptShell::from_path(Path::new("delete.txt")), None);
+    }
+}
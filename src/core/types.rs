@@ -5,11 +5,16 @@
 // the Free Software Foundation, either version 3 of the License, or
 // (at your option) any later version.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
 
 /// Represents a parsed mod file from the downloads folder
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModFile {
     pub file_name: String,
     pub full_path: PathBuf,
@@ -20,6 +25,84 @@ pub struct ModFile {
     pub timestamp: String,
     pub size: u64,
     pub is_patch: bool,
+    /// Filesystem modification time in seconds since epoch (0 if unknown)
+    #[serde(default)]
+    pub mtime: u64,
+}
+
+impl ModFile {
+    /// Name didn't follow a known download pattern, so name/ID/version are placeholders
+    pub fn is_unparsed(&self) -> bool {
+        self.mod_name.is_empty()
+            || (self.mod_id.is_empty() && self.file_id.is_none())
+            || self.mod_name == self.file_name
+    }
+
+    /// Smaller than `min_mod_size` bytes: likely a config snippet or FOMOD selector
+    /// rather than a real mod (0 = off)
+    pub fn is_trivial(&self, min_mod_size: u64) -> bool {
+        self.size < min_mod_size
+    }
+
+    /// Archive type from the file extension
+    pub fn archive_kind(&self) -> ArchiveKind {
+        ArchiveKind::from_file_name(&self.file_name)
+    }
+}
+
+/// Archive type of a download, shown as a short tag in the results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    SevenZip,
+    Zip,
+    Rar,
+    /// `.tar` and `.gz`
+    Tar,
+    /// Installers and tools rather than archives
+    Exe,
+    /// Bethesda game archives, `.bsa` and `.ba2`
+    Bethesda,
+    Other,
+}
+
+impl ArchiveKind {
+    /// Archive type a file name's extension claims
+    pub fn from_file_name(file_name: &str) -> Self {
+        let ext = file_name
+            .rsplit_once('.')
+            .map(|(_, ext)| ext.to_ascii_lowercase());
+        match ext.as_deref() {
+            Some("7z") => ArchiveKind::SevenZip,
+            Some("zip") => ArchiveKind::Zip,
+            Some("rar") => ArchiveKind::Rar,
+            Some("tar" | "gz") => ArchiveKind::Tar,
+            Some("exe") => ArchiveKind::Exe,
+            Some("bsa" | "ba2") => ArchiveKind::Bethesda,
+            _ => ArchiveKind::Other,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ArchiveKind::SevenZip => "7Z",
+            ArchiveKind::Zip => "ZIP",
+            ArchiveKind::Rar => "RAR",
+            ArchiveKind::Tar => "TAR",
+            ArchiveKind::Exe => "EXE",
+            ArchiveKind::Bethesda => "BSA",
+            ArchiveKind::Other => "?",
+        }
+    }
+}
+
+/// Which file of an old-version group is kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum KeepBy {
+    /// Most recent upload timestamp
+    #[default]
+    NewestUpload,
+    /// Highest parsed version number, falling back to the upload timestamp
+    HighestVersion,
 }
 
 /// Represents a group of mod versions (same mod, different versions)
@@ -29,12 +112,70 @@ pub struct ModGroup {
     pub files: Vec<ModFile>,
     pub newest_idx: usize,
     pub space_to_free: u64,
+    /// The kept file is much smaller than a file that would go, e.g. a hotfix after the full
+    /// mod; left out of cleanup unless the user includes it
+    pub review_recommended: bool,
+}
+
+impl ModGroup {
+    /// Timestamp of the file that is kept
+    pub fn newest_timestamp(&self) -> &str {
+        &self.files[self.newest_idx].timestamp
+    }
+
+    /// Files that would be deleted, oldest first; everything before the kept file
+    pub fn deletable_files(&self) -> &[ModFile] {
+        &self.files[..self.newest_idx]
+    }
+
+    /// Timestamp of the oldest file that would be deleted
+    pub fn oldest_deletable_timestamp(&self) -> Option<&str> {
+        (self.newest_idx > 0).then(|| self.files[0].timestamp.as_str())
+    }
+
+    /// The group with `files[idx]` kept instead, every other file up to the current keeper
+    /// deletable and `space_to_free` to match. Files kept after the keeper stay kept, and
+    /// so does the review flag. `None` if `idx` is out of range.
+    pub fn with_keeper(&self, idx: usize) -> Option<ModGroup> {
+        let keeper = self.files.get(idx)?.clone();
+        let mut files: Vec<ModFile> = self.files[..=self.newest_idx]
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != idx)
+            .map(|(_, f)| f.clone())
+            .collect();
+        let newest_idx = files.len();
+        let space_to_free = files.iter().map(|f| f.size).sum();
+        files.push(keeper);
+        files.extend(
+            self.files
+                .iter()
+                .enumerate()
+                .skip(self.newest_idx + 1)
+                .filter(|(i, _)| *i != idx)
+                .map(|(_, f)| f.clone()),
+        );
+        Some(ModGroup {
+            mod_key: self.mod_key.clone(),
+            files,
+            newest_idx,
+            space_to_free,
+            review_recommended: self.review_recommended,
+        })
+    }
+
+    /// Seconds between the oldest deletable file and the kept file
+    pub fn age_gap_secs(&self) -> i64 {
+        let newest = self.newest_timestamp().parse::<i64>().unwrap_or(0);
+        self.oldest_deletable_timestamp()
+            .and_then(|ts| ts.parse::<i64>().ok())
+            .map_or(0, |oldest| newest - oldest)
+    }
 }
 
 /// Information about a parsed .wabbajack modlist file
 #[derive(Debug, Clone)]
 pub struct ModlistInfo {
-    #[allow(dead_code)]
     pub file_path: PathBuf,
     pub name: String,
     pub mod_count: usize,
@@ -44,6 +185,26 @@ pub struct ModlistInfo {
     pub used_mod_file_ids: HashSet<String>,
     /// Exact file names from the modlist for precise matching
     pub used_file_names: HashSet<String>,
+    /// Wabbajack `GameName` most of its archives are for, if any archive names one
+    pub game: Option<String>,
+    /// xxHash64 of each archive as stored in the modlist, with the archive's name
+    pub archive_hashes: HashMap<u64, String>,
+}
+
+impl ModlistInfo {
+    /// No archive carries a Nexus ModID: either every source is off Nexus or the file
+    /// parsed wrong. Protection then rests on exact file names alone.
+    pub fn has_no_mod_ids(&self) -> bool {
+        self.used_mod_keys.is_empty()
+    }
+
+    /// Both modlists reference exactly the same archives, e.g. one list picked from two
+    /// Wabbajack version folders
+    pub fn same_archives(&self, other: &ModlistInfo) -> bool {
+        self.used_mod_file_ids == other.used_mod_file_ids
+            && self.used_file_names == other.used_file_names
+            && self.used_mod_keys == other.used_mod_keys
+    }
 }
 
 /// Represents a mod file that's not used by any active modlist
@@ -52,16 +213,98 @@ pub struct OrphanedMod {
     pub file: ModFile,
 }
 
-/// Archive extensions supported by Wabbajack
-pub const ARCHIVE_EXTENSIONS: &[&str] = &[".7z", ".zip", ".rar", ".tar", ".gz", ".exe"];
+/// How a downloaded file was matched to an active modlist
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchReason {
+    /// Exact file name listed in the modlist
+    FileName,
+    /// Same ModID and FileID as a modlist archive
+    FileId,
+    /// Same ModID only (loose fallback, may over-protect)
+    ModId,
+    /// Extension is protected from orphan cleanup
+    ProtectedExtension,
+    /// Another part of the same multi-part mod is used
+    SiblingPart,
+}
+
+impl MatchReason {
+    /// Short tag shown next to used files
+    pub fn label(&self) -> &'static str {
+        match self {
+            MatchReason::FileName => "name",
+            MatchReason::FileId => "file id",
+            MatchReason::ModId => "mod id",
+            MatchReason::ProtectedExtension => "protected",
+            MatchReason::SiblingPart => "other part used",
+        }
+    }
+}
+
+/// Represents a mod file used by an active modlist
+#[derive(Debug, Clone)]
+pub struct UsedMod {
+    pub file: ModFile,
+    pub reason: MatchReason,
+    /// Active modlists that reference the file (empty for protected extensions)
+    pub modlists: Vec<String>,
+}
+
+/// Archive extensions supported by Wabbajack, plus bare Bethesda archives some users
+/// download directly
+pub const ARCHIVE_EXTENSIONS: &[&str] =
+    &[".7z", ".zip", ".rar", ".tar", ".gz", ".exe", ".bsa", ".ba2"];
+
+/// Extensions counted in stats but kept out of orphan cleanup until the user allows it.
+/// Bare `.bsa`/`.ba2` files rarely follow the Nexus naming scheme, so matching them to
+/// modlists is unreliable.
+pub const DEFAULT_PROTECTED_EXTENSIONS: &[&str] = &[".exe", ".bsa", ".ba2"];
+
+/// Default fraction of an older file's size below which a newest patch file skips its group
+pub const DEFAULT_PATCH_SIZE_RATIO: f64 = 0.1;
+
+/// Default fraction of the largest deletable file below which a smaller keeper asks for review
+pub const DEFAULT_REVIEW_SIZE_RATIO: f64 = 0.5;
+
+/// Default size factor between same-version files that marks a group as suspicious
+pub const DEFAULT_SUSPICIOUS_SIZE_RATIO: f64 = 10.0;
+
+/// Folder inside the downloads directory that receives moved (recycled) files
+pub const RECYCLE_BIN_DIR_NAME: &str = "WLC_RecycleBin";
+
+/// Folders created by this app (current and legacy names) that are never game folders
+pub const APP_FOLDER_NAMES: &[&str] = &[RECYCLE_BIN_DIR_NAME, "WLC_Backup"];
+
+/// Folder name patterns skipped when looking for game folders unless configured otherwise;
+/// `*` matches any text
+pub const DEFAULT_EXCLUDED_FOLDERS: &[&str] = &["WLC_Backup", "WLC_Deleted", ".*", "_*"];
 
 /// Result of a scan operation
 #[derive(Debug, Clone)]
 pub struct ScanResult {
-    pub used_mods: Vec<ModFile>,
+    pub used_mods: Vec<UsedMod>,
     pub orphaned_mods: Vec<OrphanedMod>,
     pub used_size: u64,
     pub orphaned_size: u64,
+    /// Unreferenced files too recent to report as orphaned yet
+    pub recent_mods: Vec<OrphanedMod>,
+    pub recent_size: u64,
+    /// Unreferenced files below the minimum mod size, not offered for cleanup
+    pub small_mods: Vec<OrphanedMod>,
+    pub small_size: u64,
+}
+
+/// Why a group of versions was left alone by the old-version scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    /// Every file has the same upload timestamp, so there is no newest one
+    SameTimestamp,
+    /// Same version with very different sizes, close uploads, or conflicting descriptors
+    SuspiciousPattern,
+    /// Contains both a patch and a main file, which are usually needed together
+    PatchAndMain,
+    /// The newest file is a small patch on top of an older full file
+    NewestIsPatch,
 }
 
 /// Result of old version scan
@@ -70,6 +313,8 @@ pub struct OldVersionScanResult {
     pub duplicates: Vec<ModGroup>,
     pub total_files: usize,
     pub total_space: u64,
+    /// Groups with several versions that were not offered for cleanup, by mod key
+    pub skipped_groups: Vec<(String, SkipReason)>,
 }
 
 /// Deletion result
@@ -81,6 +326,70 @@ pub struct DeletionResult {
     pub errors: Vec<String>,
     /// Path to the recycle bin folder used, if files were moved instead of deleted
     pub recycle_bin_path: Option<PathBuf>,
+    /// The run was stopped before every file was processed
+    pub cancelled: bool,
+    /// Time the whole run took, set by whoever started it
+    pub elapsed: Duration,
+}
+
+impl DeletionResult {
+    /// Add the totals of a batch run against another recycle bin. The first recycle
+    /// bin path is kept.
+    pub fn merge(&mut self, other: DeletionResult) {
+        self.deleted_count += other.deleted_count;
+        self.space_freed += other.space_freed;
+        self.skipped.extend(other.skipped);
+        self.errors.extend(other.errors);
+        if self.recycle_bin_path.is_none() {
+            self.recycle_bin_path = other.recycle_bin_path;
+        }
+        self.cancelled |= other.cancelled;
+        self.elapsed += other.elapsed;
+    }
+}
+
+/// Shared flag that stops a running cleanup or hash pass at the next file or chunk
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Download statistics for one game folder
+#[derive(Debug, Clone, Default)]
+pub struct GameStats {
+    pub name: String,
+    pub files: usize,
+    pub size: u64,
+    /// File name of the biggest archive in the folder
+    pub largest_name: String,
+    pub largest_size: u64,
+    /// Estimated old versions from file names alone, with the default old-version options
+    pub old_version_files: usize,
+    pub old_version_space: u64,
+    /// Modification time of the newest archive; an old one hints at a retired modlist
+    pub newest_modified: Option<SystemTime>,
+}
+
+impl GameStats {
+    pub fn average_size(&self) -> u64 {
+        if self.files == 0 {
+            0
+        } else {
+            self.size / self.files as u64
+        }
+    }
 }
 
 /// Statistics about the mod library
@@ -88,5 +397,20 @@ pub struct DeletionResult {
 pub struct LibraryStats {
     pub total_files: usize,
     pub total_size: u64,
-    pub by_game: Vec<(String, usize, u64)>,
+    pub by_game: Vec<GameStats>,
+    /// Rough reclaimable space from old versions, before any real scan
+    pub old_version_files: usize,
+    pub old_version_space: u64,
+    /// Downloads per month from the first to the last, empty months included
+    pub activity: Vec<MonthActivity>,
+}
+
+/// Files downloaded in one calendar month, going by the timestamps in their names
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MonthActivity {
+    pub year: i32,
+    /// 1 to 12
+    pub month: u32,
+    pub files: usize,
+    pub size: u64,
 }
@@ -0,0 +1,66 @@
+// Copyright (C) 2025 Berkay Yetgin
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+const LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/Yakrel/wabbajack-library-cleaner/releases/latest";
+
+/// Latest published release on GitHub
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseInfo {
+    #[serde(rename = "tag_name")]
+    pub tag: String,
+    #[serde(rename = "html_url")]
+    pub url: String,
+}
+
+/// Query the GitHub Releases API for the latest release
+pub fn fetch_latest_release() -> Result<ReleaseInfo> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(10))
+        .build();
+    let body = agent
+        .get(LATEST_RELEASE_URL)
+        .set("User-Agent", "wabbajack-library-cleaner")
+        .set("Accept", "application/vnd.github+json")
+        .call()
+        .context("Failed to query latest release")?
+        .into_string()
+        .context("Failed to read release response")?;
+    serde_json::from_str(&body).context("Failed to parse release response")
+}
+
+/// Whether a release tag like "v2.2.0" is newer than the running version
+pub fn is_newer_version(tag: &str, current: &str) -> bool {
+    let parse = |v: &str| -> Vec<u64> {
+        v.trim()
+            .trim_start_matches(['v', 'V'])
+            .split(['.', '-'])
+            .map_while(|p| p.parse().ok())
+            .collect()
+    };
+    parse(tag) > parse(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_newer_version() {
+        assert!(is_newer_version("v2.2.0", "2.1.3"));
+        assert!(is_newer_version("2.10.0", "2.9.9"));
+        assert!(is_newer_version("v3.0", "2.1.3"));
+        assert!(!is_newer_version("v2.1.3", "2.1.3"));
+        assert!(!is_newer_version("v2.1.2", "2.1.3"));
+        assert!(!is_newer_version("nightly", "2.1.3"));
+    }
+}
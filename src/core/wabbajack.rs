@@ -0,0 +1,194 @@
+// Copyright (C) 2025 Berkay Yetgin
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+use std::cmp::Ordering;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+use crate::core::backup::dir_usage;
+use crate::core::parser::parse_version_numbers;
+use crate::core::platform::long_path;
+use crate::core::scanner::find_wabbajack_files;
+
+const DOWNLOADED_MODLISTS_DIR: &str = "downloaded_mod_lists";
+
+/// Compare Wabbajack version folder names numerically per dot-separated part
+/// (so "3.10.0.0" is newer than "3.9.0.0"); non-numeric parts compare as text
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let mut a_parts = a.split('.');
+    let mut b_parts = b.split('.');
+    loop {
+        match (a_parts.next(), b_parts.next()) {
+            (None, None) => return Ordering::Equal,
+            (Some(_), None) => return Ordering::Greater,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(x), Some(y)) => {
+                let ord = match (x.parse::<u64>(), y.parse::<u64>()) {
+                    (Ok(x), Ok(y)) => x.cmp(&y),
+                    _ => x.cmp(y),
+                };
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+            }
+        }
+    }
+}
+
+/// A version folder like `3.6.0.0` inside the Wabbajack install
+#[derive(Debug, Clone)]
+pub struct VersionFolderInfo {
+    pub path: PathBuf,
+    pub version: String,
+    /// Everything in the folder: the Wabbajack binaries plus its downloaded modlists
+    pub size: u64,
+    /// `.wabbajack` files in its `downloaded_mod_lists`
+    pub modlists: usize,
+    /// Modlists found only here and not in the newest version folder; cleaning moves them
+    /// there so their archives stay protected
+    pub unique_modlists: Vec<String>,
+    /// The newest version, which Wabbajack runs; it is never cleaned
+    pub is_newest: bool,
+}
+
+/// `.wabbajack` file names in a version folder's `downloaded_mod_lists`
+fn modlist_names(version_dir: &Path) -> Vec<String> {
+    find_wabbajack_files(&version_dir.join(DOWNLOADED_MODLISTS_DIR))
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+        .collect()
+}
+
+/// Version folders of the Wabbajack install at `dir`, oldest first
+pub fn scan_wabbajack_version_folders(dir: &Path) -> Vec<VersionFolderInfo> {
+    let Ok(entries) = fs::read_dir(long_path(dir)) else {
+        return Vec::new();
+    };
+    let mut folders: Vec<(PathBuf, String)> = entries
+        .flatten()
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .filter_map(|e| {
+            let version = e.file_name().to_string_lossy().to_string();
+            parse_version_numbers(&version)
+                .is_some_and(|v| v.len() > 1)
+                .then(|| (e.path(), version))
+        })
+        .collect();
+    folders.sort_by(|(_, a), (_, b)| compare_versions(a, b));
+
+    let newest_modlists = folders
+        .last()
+        .map(|(path, _)| modlist_names(path))
+        .unwrap_or_default();
+    let newest = folders.len().saturating_sub(1);
+    folders
+        .into_iter()
+        .enumerate()
+        .map(|(i, (path, version))| {
+            let (_, size, _) = dir_usage(&path);
+            let modlists = modlist_names(&path);
+            VersionFolderInfo {
+                unique_modlists: modlists
+                    .iter()
+                    .filter(|name| !newest_modlists.contains(name))
+                    .cloned()
+                    .collect(),
+                modlists: modlists.len(),
+                path,
+                version,
+                size,
+                is_newest: i == newest,
+            }
+        })
+        .collect()
+}
+
+/// Permanently delete an old version folder. Modlists only it has are moved into the
+/// newest version's `downloaded_mod_lists` first. Returns the bytes freed.
+pub fn purge_version_folder(folder: &VersionFolderInfo, newest: &VersionFolderInfo) -> Result<u64> {
+    if folder.is_newest
+        || folder.path == newest.path
+        || folder.path.parent() != newest.path.parent()
+    {
+        bail!("Not an old Wabbajack version folder: {:?}", folder.path);
+    }
+
+    if !folder.unique_modlists.is_empty() {
+        let dest = newest.path.join(DOWNLOADED_MODLISTS_DIR);
+        fs::create_dir_all(long_path(&dest))
+            .with_context(|| format!("Failed to create folder: {:?}", dest))?;
+        for name in &folder.unique_modlists {
+            let src = folder.path.join(DOWNLOADED_MODLISTS_DIR).join(name);
+            fs::rename(long_path(&src), long_path(&dest.join(name)))
+                .with_context(|| format!("Failed to move modlist {:?} to {:?}", src, dest))?;
+            log::info!("Moved modlist {} to {:?}", name, dest);
+        }
+    }
+
+    // Measured after the move, so moved modlists don't count as freed
+    let (_, size, _) = dir_usage(&folder.path);
+    fs::remove_dir_all(long_path(&folder.path))
+        .with_context(|| format!("Failed to delete version folder: {:?}", folder.path))?;
+    log::info!("Deleted Wabbajack version folder {:?}", folder.path);
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("3.10.0.0", "3.9.0.0"), Ordering::Greater);
+        assert_eq!(compare_versions("3.6.0.0", "3.6.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("3.6", "3.6.0.1"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_old_version_folders_cleaned_keeping_their_modlists() {
+        let dir = tempdir().unwrap();
+        let write = |version: &str, name: &str, len: usize| {
+            let lists = dir.path().join(version).join(DOWNLOADED_MODLISTS_DIR);
+            fs::create_dir_all(&lists).unwrap();
+            fs::write(lists.join(name), vec![0u8; len]).unwrap();
+        };
+        write("3.9.0.0", "Shared.wabbajack", 100);
+        write("3.9.0.0", "OnlyOld.wabbajack", 30);
+        write("3.10.0.0", "Shared.wabbajack", 100);
+        write("3.5.0.0", "Shared.wabbajack", 100);
+        fs::write(dir.path().join("3.9.0.0").join("Wabbajack.exe"), [0u8; 20]).unwrap();
+        fs::create_dir(dir.path().join("downloaded_mod_lists")).unwrap();
+        fs::write(dir.path().join("Wabbajack.exe"), b"x").unwrap();
+
+        let folders = scan_wabbajack_version_folders(dir.path());
+        let versions: Vec<&str> = folders.iter().map(|f| f.version.as_str()).collect();
+        assert_eq!(versions, ["3.5.0.0", "3.9.0.0", "3.10.0.0"]);
+        assert_eq!(
+            folders.iter().map(|f| f.is_newest).collect::<Vec<_>>(),
+            [false, false, true]
+        );
+        let old = &folders[1];
+        assert_eq!((old.size, old.modlists), (150, 2));
+        assert_eq!(old.unique_modlists, ["OnlyOld.wabbajack"]);
+
+        let newest = &folders[2];
+        assert!(purge_version_folder(newest, newest).is_err());
+        assert_eq!(purge_version_folder(old, newest).unwrap(), 120);
+        assert!(!old.path.exists());
+        assert!(newest
+            .path
+            .join(DOWNLOADED_MODLISTS_DIR)
+            .join("OnlyOld.wabbajack")
+            .exists());
+        assert_eq!(purge_version_folder(&folders[0], newest).unwrap(), 100);
+        assert_eq!(scan_wabbajack_version_folders(dir.path()).len(), 1);
+    }
+}
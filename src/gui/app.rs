@@ -3,22 +3,55 @@
 
 //! Single-page GUI for Wabbajack Library Cleaner
 
-use std::path::PathBuf;
-use std::sync::mpsc::{channel, Receiver, Sender};
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::mpsc::{channel, Receiver, SendError, Sender};
+use std::sync::Arc;
 use std::thread;
+use std::time::{Duration, Instant, SystemTime};
 
 use eframe::egui;
 use egui::{Color32, RichText, Rounding, Vec2};
+use rayon::prelude::*;
 
 use crate::core::{
-    calculate_library_stats, delete_old_versions, delete_orphaned_mods, detect_orphaned_mods,
-    find_wabbajack_files, format_size, get_all_mod_files, get_game_folders, parse_wabbajack_file,
-    scan_folder_for_duplicates, DeletionResult, LibraryStats, ModlistInfo, OldVersionScanResult,
-    ScanResult,
+    backup_root, backups_older_than, begin_deletion_journal, calculate_library_stats,
+    check_archives_health, coarse_age, collect_installed_mods, compare_versions, config_dir,
+    delete_old_versions_with_options, delete_orphaned_mods_with_options, deletion_journal_path,
+    detect_orphaned_mods_with_options, diagnose_filenames, dirs_overlap, exclude_wabbajack_dirs,
+    exclusive_modlist_sizes, export_deletion_script, export_library_manifest, fetch_latest_release,
+    find_cross_game_copies, find_identical_copies_with_options, find_old_versions_in_files,
+    find_wabbajack_files, finish_deletion_journal, format_elapsed, format_size, game_display_name,
+    get_all_mod_files_cached, get_game_folders_in_roots_excluding, history_key,
+    import_library_manifest, is_newer_version, issue_report, issue_url, list_archive_entries,
+    list_backups, manual_mapping_path, merge_identical_modlists, parse_extension_list,
+    parse_pattern_list, parse_wabbajack_file, pending_deletion, purge_backup, purge_version_folder,
+    push_recent_folder, read_mo2_downloads, reconcile_matching, recycle_bin_for_run,
+    relocate_orphaned_mods_with_options, restore_backup_archive, resume_pending_deletion,
+    reveal_in_file_manager, root_index_for, scan_cache_path,
+    scan_folders_for_duplicates_with_options, scan_history_path, scan_wabbajack_version_folders,
+    settings_path, summary_markdown, timestamp_to_date, validate_wabbajack_dir, verify_backup,
+    AgeUnit, ArchiveHealthReport, ArchiveKind, BackupInfo, BackupVerification, CancelToken,
+    CopyProgress, CrossGameGroup, DeleteOptions, DeletionJournal, DeletionResult, Disagreement,
+    GameStats, HashOptions, IdenticalCopiesResult, JournalEntry, KeepBy, LibraryStats,
+    ManualMapping, MatchReason, ModFile, ModGroup, ModlistInfo, MonthActivity,
+    OldVersionScanOptions, OldVersionScanResult, OrphanScanOptions, OrphanedMod,
+    ReconciliationReport, ReleaseInfo, ScanCache, ScanDelta, ScanHistory, ScanResult, ScanSnapshot,
+    ScriptShell, Settings, SkipReason, UiDensity, VersionFolderInfo, WabbajackDirStatus,
+    ISSUE_URL_LOG_LINES, REPORT_LOG_LINES,
 };
 
+use super::i18n::{self, tr, tr_args, LANGUAGES};
+use super::tray::{self, Tray};
+
 const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Height limits of the resizable log panel
+const LOG_PANEL_MIN_HEIGHT: f32 = 60.0;
+const LOG_PANEL_MAX_HEIGHT: f32 = 600.0;
+
 // Colors
 const COLOR_BG_MAIN: Color32 = Color32::from_rgb(30, 30, 35);
 const COLOR_BG_CARD: Color32 = Color32::from_rgb(42, 42, 50);
@@ -37,16 +70,198 @@ enum AsyncMessage {
     GameFoldersFound(Vec<PathBuf>),
     OrphanedScanComplete(ScanResult),
     OldVersionScanComplete(OldVersionScanResult),
+    /// Orphaned and old-version results of a partial scan, labeled with what was scanned
+    QuickScanComplete(String, ScanResult, OldVersionScanResult),
+    /// Identical copies found by hashing; `None` if the scan was stopped
+    IdenticalCopiesFound(Option<IdenticalCopiesResult>),
+    /// Mod files found in more than one game folder, by ModID and FileID
+    CrossGameCopiesFound(Vec<CrossGameGroup>),
+    /// Hash cross-check of the orphaned result; `None` if it was stopped
+    ReconciliationComplete(Option<ReconciliationReport>),
+    /// Quick zip check of the selected game folders; `None` if it was stopped
+    ArchiveHealthChecked(Option<ArchiveHealthReport>),
+    /// Top-level entries of an archive for its row tooltip
+    ArchivePreviewListed(PathBuf, Result<Vec<String>, String>),
+    ManifestExported(PathBuf, usize),
     DeletionComplete(DeletionResult),
+    /// Orphaned files moved to another library folder
+    RelocationComplete(PathBuf, DeletionResult),
+    /// Orphaned and old-version parts of a "clean all detected" run
+    CleanAllComplete(DeletionResult, DeletionResult),
     StatsComplete(LibraryStats),
+    UpdateAvailable(ReleaseInfo),
+    BackupsListed(Vec<BackupInfo>),
+    /// Bytes freed and errors of a backup purge
+    BackupsPurged(u64, Vec<String>),
+    /// Files restored from a backup archive, and errors
+    BackupRestored(usize, Vec<String>),
+    /// A backup checked for a restore, by name
+    BackupVerified(String, BackupVerification),
+    VersionFoldersListed(Vec<VersionFolderInfo>),
+    /// Bytes freed and errors of a version folder cleanup
+    VersionFoldersPurged(u64, Vec<String>),
     Progress(String, Option<(usize, usize)>),
     Error(String),
 }
 
+/// Order of the per-game bars in the library stats
+#[derive(PartialEq, Clone, Copy)]
+enum StatsSort {
+    Name,
+    Size,
+    /// Least recently changed first
+    LastModified,
+}
+
+/// Worker side of the message channel. Every message carries the generation of the
+/// operation that sent it, so results of a superseded operation can be dropped.
+#[derive(Clone)]
+struct OpSender {
+    generation: u64,
+    tx: Sender<(u64, AsyncMessage)>,
+}
+
+impl OpSender {
+    /// Fails only once the app has closed; the unsent message is dropped
+    fn send(&self, msg: AsyncMessage) -> Result<(), SendError<()>> {
+        self.tx
+            .send((self.generation, msg))
+            .map_err(|_| SendError(()))
+    }
+}
+
+/// Generation of messages not tied to an operation, like the update check; never stale
+const BACKGROUND_GENERATION: u64 = 0;
+
+/// The message comes from an operation older than the current one
+fn is_stale(generation: u64, current: u64) -> bool {
+    generation != BACKGROUND_GENERATION && generation < current
+}
+
+/// Work started by the app itself that waits while another operation runs
+#[derive(Debug, Clone, PartialEq)]
+enum QueuedOperation {
+    ScanWabbajackDir(PathBuf),
+    Analysis,
+    ListBackups,
+    OrphanedScan,
+    OldVersionScan,
+}
+
 #[derive(PartialEq, Clone, Copy)]
 enum DeleteAction {
     Orphaned,
     OldVersions,
+    IdenticalCopies,
+}
+
+/// Inputs a displayed result was computed from. Clean reuses the result only while
+/// these still match, so it never deletes a different set than the one shown.
+#[derive(Debug, Clone, PartialEq)]
+struct ResultScope {
+    downloads_dirs: Vec<PathBuf>,
+    folders: Vec<PathBuf>,
+    modlists: Vec<PathBuf>,
+    options: ScopeOptions,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ScopeOptions {
+    Orphaned(OrphanScanOptions),
+    OldVersions(OldVersionScanOptions),
+}
+
+impl ResultScope {
+    fn new(
+        downloads_dirs: Vec<PathBuf>,
+        mut folders: Vec<PathBuf>,
+        mut modlists: Vec<PathBuf>,
+        options: ScopeOptions,
+    ) -> Self {
+        folders.sort();
+        modlists.sort();
+        Self {
+            downloads_dirs,
+            folders,
+            modlists,
+            options,
+        }
+    }
+}
+
+/// Scan notifications arriving within this time of each other share one toast
+const NOTIFY_COALESCE: Duration = Duration::from_secs(3);
+
+/// Scan results kept for the results history dropdown
+const RESULT_HISTORY_LIMIT: usize = 5;
+
+/// A finished scan kept so it can be viewed again after newer scans replace it
+#[derive(Debug, Clone)]
+struct ResultHistoryEntry {
+    finished: chrono::DateTime<chrono::Local>,
+    /// Folders the scan covered
+    folders: String,
+    orphaned: Option<ScanResult>,
+    old_versions: Option<OldVersionScanResult>,
+}
+
+impl ResultHistoryEntry {
+    fn label(&self) -> String {
+        let kind = match (&self.orphaned, &self.old_versions) {
+            (Some(_), Some(_)) => tr("quick_scan"),
+            (Some(_), None) => tr("orphaned_mods"),
+            _ => tr("old_versions"),
+        };
+        format!(
+            "{} - {} ({})",
+            self.finished.format("%H:%M:%S"),
+            self.folders,
+            kind
+        )
+    }
+}
+
+/// Short list of folder names, e.g. "Skyrim, Fallout4, +2"
+fn folder_names_label(folders: &[PathBuf]) -> String {
+    let mut names: Vec<String> = folders
+        .iter()
+        .take(2)
+        .map(|f| {
+            f.file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string()
+        })
+        .collect();
+    if folders.len() > 2 {
+        names.push(format!("+{}", folders.len() - 2));
+    }
+    names.join(", ")
+}
+
+/// How long ago a time was, e.g. "2 years ago"
+fn format_age(time: SystemTime) -> String {
+    let age = SystemTime::now().duration_since(time).unwrap_or_default();
+    let (n, unit) = coarse_age(age);
+    let key = match (unit, n == 1) {
+        (AgeUnit::Minutes, _) if n == 0 => return tr("age_just_now").to_string(),
+        (AgeUnit::Minutes, true) => "age_minute",
+        (AgeUnit::Minutes, false) => "age_minutes",
+        (AgeUnit::Hours, true) => "age_hour",
+        (AgeUnit::Hours, false) => "age_hours",
+        (AgeUnit::Days, true) => "age_day",
+        (AgeUnit::Days, false) => "age_days",
+        (AgeUnit::Months, true) => "age_month",
+        (AgeUnit::Months, false) => "age_months",
+        (AgeUnit::Years, true) => "age_year",
+        (AgeUnit::Years, false) => "age_years",
+    };
+    tr_args(key, &[&n])
+}
+
+/// Whether Clean can act on the shown result instead of scanning again
+fn can_reuse_result(shown: Option<&ResultScope>, current: &ResultScope) -> bool {
+    shown == Some(current)
 }
 
 #[derive(PartialEq, Clone, Copy)]
@@ -55,6 +270,13 @@ enum Modal {
     About,
     FolderSelect,
     ConfirmDelete(DeleteAction),
+    ConfirmCleanAll,
+    ReviewOldVersions,
+    ReportIssue,
+    Backups,
+    VersionFolders,
+    Diagnostics,
+    ResumeDeletion,
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -64,25 +286,112 @@ enum LogLevel {
     Error,
 }
 
+impl LogLevel {
+    const ALL: [LogLevel; 3] = [LogLevel::Info, LogLevel::Warning, LogLevel::Error];
+
+    fn color(self) -> Color32 {
+        match self {
+            LogLevel::Info => COLOR_TEXT_SECONDARY,
+            LogLevel::Warning => COLOR_WARNING,
+            LogLevel::Error => COLOR_DANGER,
+        }
+    }
+}
+
 pub struct WabbajackCleanerApp {
     wabbajack_dir: Option<PathBuf>,
-    downloads_dir: Option<PathBuf>,
+    /// Downloads roots scanned together, usually just one
+    downloads_dirs: Vec<PathBuf>,
     modlists: Vec<ModlistInfo>,
     modlist_selected: Vec<bool>,
+    /// Bytes only this modlist uses, by modlist file, from the last orphaned scan
+    modlist_exclusive_sizes: HashMap<PathBuf, u64>,
     game_folders: Vec<PathBuf>,
-    selected_game_folder: Option<usize>,
+    game_folder_selected: Vec<bool>,
+    /// Game folders indexed by the orphaned scan and library stats
+    orphan_folder_selected: Vec<bool>,
     move_to_recycle_bin: bool,
     pending_delete_mode: bool,
-    tx: Sender<AsyncMessage>,
-    rx: Receiver<AsyncMessage>,
+    tx: Sender<(u64, AsyncMessage)>,
+    rx: Receiver<(u64, AsyncMessage)>,
+    /// Generation of the current operation; messages from older ones are ignored
+    generation: u64,
+    /// Operations waiting for the current one to finish, in order
+    queued_operations: VecDeque<QueuedOperation>,
     is_loading: bool,
     current_operation: String,
     progress: Option<(usize, usize)>,
     stats: Option<LibraryStats>,
     orphaned_result: Option<ScanResult>,
+    /// Selection the shown orphaned result was scanned with
+    orphaned_scope: Option<ResultScope>,
+    /// Selection the shown old-version result was scanned with
+    old_version_scope: Option<ResultScope>,
+    /// Changes since the previous full scan of the same downloads folder
+    scan_delta: Option<ScanDelta>,
+    /// A scan just finished; the next frame scrolls to the results
+    scroll_to_results: bool,
+    /// Short "scan complete" note shown in the header, with when it appeared
+    results_badge: Option<(String, Instant)>,
     old_version_result: Option<OldVersionScanResult>,
+    identical_result: Option<IdenticalCopiesResult>,
+    cross_game_result: Option<Vec<CrossGameGroup>>,
+    /// Recent scan results, newest first
+    result_history: Vec<ResultHistoryEntry>,
+    stats_sort: StatsSort,
+    /// Old-version results list only the files that would be deleted
+    only_cleanable: bool,
+    /// Old-version groups flagged for review that the user chose to clean anyway, by mod key
+    review_included: HashSet<String>,
+    /// Keeper picked by hand per old-version group, by the path of the automatic keeper
+    keeper_overrides: HashMap<PathBuf, PathBuf>,
+    /// Rows revealed with "Show more" per results list; lists not in here show the limit
+    shown_rows: HashMap<&'static str, usize>,
+    /// Files a cleanup interrupted by a crash or a closed app had not removed yet
+    pending_journal: Option<DeletionJournal>,
+    /// History entry shown in the results section instead of the current results
+    viewed_history: Option<usize>,
     log_messages: Vec<(String, LogLevel)>,
+    /// Levels filtered out of the log panel
+    hidden_log_levels: Vec<LogLevel>,
     modal: Modal,
+    settings: Settings,
+    /// Archive listings shown in old-version row tooltips, cached by path; `None` while
+    /// the listing is still running
+    archive_previews: HashMap<PathBuf, Option<Result<Vec<String>, String>>>,
+    /// Result files an open-folder click found gone, until the next scan
+    missing_files: HashSet<PathBuf>,
+    tray: Option<Tray>,
+    window_minimized: bool,
+    window_focused: bool,
+    /// Scan summaries waiting to be shown together in one notification
+    pending_notifications: Vec<String>,
+    /// When the pending notifications are shown
+    notify_at: Option<Instant>,
+    /// Edit buffer for the protected extensions field
+    protected_extensions_text: String,
+    excluded_folders_text: String,
+    /// Newer release found by the update check
+    update_available: Option<ReleaseInfo>,
+    /// The update check runs at most once per session
+    update_checked: bool,
+    /// Stops the cleanup currently running, if any
+    cancel_token: Option<CancelToken>,
+    /// Replace folder paths in bug reports with placeholders
+    report_redact: bool,
+    /// File names pasted into the diagnostics dialog, one per line
+    diagnostics_input: String,
+    /// Recycle bin runs shown in the backups dialog, with their selection
+    backups: Vec<BackupInfo>,
+    backup_selected: Vec<bool>,
+    backup_older_than_days: u32,
+    /// The backups dialog is asking to confirm a purge
+    backup_confirm: bool,
+    /// Wabbajack version folders shown in their dialog, with their selection
+    version_folders: Vec<VersionFolderInfo>,
+    version_folder_selected: Vec<bool>,
+    /// The version folders dialog is asking to confirm a cleanup
+    version_folder_confirm: bool,
 }
 
 impl Default for WabbajackCleanerApp {
@@ -90,23 +399,65 @@ impl Default for WabbajackCleanerApp {
         let (tx, rx) = channel();
         Self {
             wabbajack_dir: None,
-            downloads_dir: None,
+            downloads_dirs: Vec::new(),
             modlists: Vec::new(),
             modlist_selected: Vec::new(),
+            modlist_exclusive_sizes: HashMap::new(),
             game_folders: Vec::new(),
-            selected_game_folder: None,
+            game_folder_selected: Vec::new(),
+            orphan_folder_selected: Vec::new(),
             move_to_recycle_bin: true,
             pending_delete_mode: false,
             tx,
             rx,
+            generation: BACKGROUND_GENERATION,
+            queued_operations: VecDeque::new(),
             is_loading: false,
             current_operation: String::new(),
             progress: None,
             stats: None,
             orphaned_result: None,
+            scan_delta: None,
+            scroll_to_results: false,
+            results_badge: None,
+            orphaned_scope: None,
+            old_version_scope: None,
             old_version_result: None,
+            identical_result: None,
+            cross_game_result: None,
+            result_history: Vec::new(),
+            stats_sort: StatsSort::Name,
+            only_cleanable: false,
+            review_included: HashSet::new(),
+            keeper_overrides: HashMap::new(),
+            shown_rows: HashMap::new(),
+            pending_journal: None,
+            viewed_history: None,
             log_messages: Vec::new(),
+            hidden_log_levels: Vec::new(),
             modal: Modal::None,
+            settings: Settings::default(),
+            archive_previews: HashMap::new(),
+            missing_files: HashSet::new(),
+            tray: None,
+            window_minimized: false,
+            window_focused: true,
+            pending_notifications: Vec::new(),
+            notify_at: None,
+            protected_extensions_text: String::new(),
+            excluded_folders_text: String::new(),
+            update_available: None,
+            update_checked: false,
+            cancel_token: None,
+            report_redact: true,
+            diagnostics_input: String::new(),
+            backups: Vec::new(),
+            backup_selected: Vec::new(),
+            backup_older_than_days: 30,
+            backup_confirm: false,
+            version_folders: Vec::new(),
+            version_folder_selected: Vec::new(),
+            version_folder_confirm: false,
         }
     }
 }
@@ -122,10 +473,140 @@ impl WabbajackCleanerApp {
         style.visuals.widgets.active.rounding = Rounding::same(6.0);
         style.visuals.window_fill = COLOR_BG_MAIN;
         style.visuals.panel_fill = COLOR_BG_MAIN;
-        style.spacing.item_spacing = Vec2::new(8.0, 6.0);
-        style.spacing.button_padding = Vec2::new(12.0, 6.0);
         cc.egui_ctx.set_style(style);
-        Self::default()
+
+        let mut app = Self::default();
+        if let Some(path) = settings_path() {
+            app.settings = Settings::load(&path);
+        }
+        apply_density(&cc.egui_ctx, app.settings.density);
+        if app.settings.tray_icon {
+            app.tray = Tray::new();
+        }
+        i18n::set_language(&app.settings.language);
+        app.protected_extensions_text = app.settings.protected_extensions.join(", ");
+        app.excluded_folders_text = app.settings.excluded_folders.join(", ");
+        if !app.settings.downloads_dirs.is_empty() {
+            app.set_downloads_dirs(app.settings.downloads_dirs.clone());
+        }
+        if app.settings.check_for_updates {
+            app.check_for_updates();
+        }
+        app.pending_journal = deletion_journal_path().and_then(|p| pending_deletion(&p));
+        if app.pending_journal.is_some() {
+            app.modal = Modal::ResumeDeletion;
+        }
+        app
+    }
+
+    /// Query GitHub for a newer release in the background; failures are only logged
+    fn check_for_updates(&mut self) {
+        if self.update_checked {
+            return;
+        }
+        self.update_checked = true;
+        let tx = self.background_sender();
+        thread::spawn(move || match fetch_latest_release() {
+            Ok(release) if is_newer_version(&release.tag, APP_VERSION) => {
+                tx.send(AsyncMessage::UpdateAvailable(release)).ok();
+            }
+            Ok(_) => {}
+            Err(e) => log::debug!("Update check failed: {}", e),
+        });
+    }
+
+    fn save_settings(&mut self) {
+        if let Some(path) = settings_path() {
+            if let Err(e) = self.settings.save(&path) {
+                self.log(
+                    LogLevel::Warning,
+                    &format!("Failed to save settings: {}", e),
+                );
+            }
+        }
+    }
+
+    /// Toast a finished operation if the window is minimized and the tray is enabled
+    fn notify_done(&self, body: &str) {
+        if self.tray.is_some() && self.window_minimized {
+            tray::notify(tray::APP_NAME, body);
+        }
+    }
+
+    /// Notify with a scan category summary like "Orphaned scan: 42 files, 3.1 GB". With scan
+    /// notifications on and the window in the background, summaries are collected briefly
+    /// so a full analysis shows one toast.
+    fn notify_scan(&mut self, body: String) {
+        if self.settings.scan_notifications && !self.window_focused {
+            self.pending_notifications.push(body);
+            self.notify_at = Some(Instant::now() + NOTIFY_COALESCE);
+        } else {
+            self.notify_done(&body);
+        }
+    }
+
+    /// Show the collected scan summaries once no more arrived for a moment
+    fn flush_notifications(&mut self, ctx: &egui::Context) {
+        let Some(at) = self.notify_at else {
+            return;
+        };
+        let now = Instant::now();
+        if now < at {
+            ctx.request_repaint_after(at - now);
+            return;
+        }
+        self.notify_at = None;
+        let body = std::mem::take(&mut self.pending_notifications).join("\n");
+        tray::notify(tray::APP_NAME, &body);
+    }
+
+    /// Point the user at freshly completed results
+    fn announce_results(&mut self, badge: String) {
+        self.scroll_to_results = true;
+        self.results_badge = Some((badge, Instant::now()));
+    }
+
+    /// Log messages whose level is not filtered out
+    fn visible_log(&self) -> impl Iterator<Item = &(String, LogLevel)> {
+        self.log_messages
+            .iter()
+            .filter(|(_, level)| !self.hidden_log_levels.contains(level))
+    }
+
+    /// Toggle per level showing how many messages it has, e.g. "Warnings (3)"
+    fn render_log_filter(&mut self, ui: &mut egui::Ui) {
+        for level in LogLevel::ALL.into_iter().rev() {
+            let count = self
+                .log_messages
+                .iter()
+                .filter(|(_, l)| *l == level)
+                .count();
+            let key = match level {
+                LogLevel::Info => "log_info",
+                LogLevel::Warning => "log_warnings",
+                LogLevel::Error => "log_errors",
+            };
+            let shown = !self.hidden_log_levels.contains(&level);
+            let color = if count > 0 && level != LogLevel::Info {
+                level.color()
+            } else {
+                COLOR_TEXT_SECONDARY
+            };
+            let text = RichText::new(tr_args(key, &[&count]))
+                .size(11.0)
+                .color(color);
+            if ui
+                .selectable_label(shown, text)
+                .on_hover_text(tr("log_filter_hint"))
+                .clicked()
+            {
+                if shown {
+                    self.hidden_log_levels.push(level);
+                } else {
+                    self.hidden_log_levels.retain(|l| *l != level);
+                }
+            }
+        }
     }
 
     fn log(&mut self, level: LogLevel, msg: &str) {
@@ -138,21 +619,34 @@ impl WabbajackCleanerApp {
     }
 
     fn is_ready(&self) -> bool {
-        self.wabbajack_dir.is_some() && self.downloads_dir.is_some()
+        self.wabbajack_dir.is_some() && !self.downloads_dirs.is_empty()
     }
 
     fn selected_modlist_count(&self) -> usize {
         self.modlist_selected.iter().filter(|&&x| x).count()
     }
 
-    fn get_recycle_bin_path(&self) -> Option<PathBuf> {
-        if !self.move_to_recycle_bin {
-            return None;
-        }
-        self.downloads_dir.as_ref().map(|dir| {
-            let ts = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S");
-            dir.join("WLC_RecycleBin").join(ts.to_string())
-        })
+    /// Whether cleanups move files to the recycle bin folder
+    fn uses_recycle_bin(&self) -> bool {
+        self.move_to_recycle_bin || self.settings.safe_mode
+    }
+
+    /// Recycle bin for this run in each downloads root, paired with its root.
+    /// Empty when files are deleted permanently.
+    fn get_recycle_bin_paths(&self) -> Vec<(PathBuf, PathBuf)> {
+        self.downloads_dirs
+            .iter()
+            .filter_map(|dir| {
+                recycle_bin_for_run(
+                    dir,
+                    self.move_to_recycle_bin,
+                    self.settings.safe_mode,
+                    // Compressed backups need a run name for their archive
+                    self.settings.flat_recycle_bin && !self.settings.compress_backups,
+                )
+                .map(|bin| (dir.clone(), bin))
+            })
+            .collect()
     }
 
     fn select_wabbajack_dir(&mut self) {
@@ -160,125 +654,1350 @@ impl WabbajackCleanerApp {
             .set_title("Select Wabbajack Installation Folder")
             .pick_folder()
         {
-            self.wabbajack_dir = Some(path.clone());
-            self.log(LogLevel::Info, "Scanning Wabbajack folder...");
-            self.is_loading = true;
-            self.current_operation = "Scanning for modlists...".to_string();
-            let tx = self.tx.clone();
-            thread::spawn(move || scan_wabbajack_dir(path, tx));
+            self.set_wabbajack_dir(path);
         }
     }
 
-    fn select_downloads_dir(&mut self) {
-        if let Some(path) = rfd::FileDialog::new()
-            .set_title("Select Downloads Folder")
-            .pick_folder()
-        {
-            self.downloads_dir = Some(path.clone());
-            self.log(LogLevel::Info, "Indexing downloads folder...");
-            let tx = self.tx.clone();
-            thread::spawn(move || match get_game_folders(&path) {
-                Ok(folders) => {
-                    tx.send(AsyncMessage::GameFoldersFound(folders)).ok();
-                }
-                Err(e) => {
-                    tx.send(AsyncMessage::Error(e.to_string())).ok();
-                }
-            });
+    fn set_wabbajack_dir(&mut self, path: PathBuf) {
+        if self.defer(QueuedOperation::ScanWabbajackDir(path.clone())) {
+            return;
+        }
+        let status = validate_wabbajack_dir(&path);
+        if status != WabbajackDirStatus::Valid {
+            self.log(LogLevel::Error, status.message());
+            return;
         }
+        push_recent_folder(&mut self.settings.recent_wabbajack_dirs, &path);
+        self.save_settings();
+        self.wabbajack_dir = Some(path.clone());
+        self.drop_wabbajack_folders();
+        self.log(LogLevel::Info, "Scanning Wabbajack folder...");
+        self.begin_operation();
+        self.current_operation = "Scanning for modlists...".to_string();
+        let dedup = !self.settings.show_all_modlist_versions;
+        let tx = self.sender();
+        thread::spawn(move || scan_wabbajack_dir(path, dedup, tx));
     }
 
-    fn run_analysis(&mut self) {
-        if !self.is_ready() {
+    /// Save the parsed metadata of the indexed game folders for offline analysis
+    fn export_library_manifest(&mut self) {
+        let folders = self.orphan_scan_folders();
+        if folders.is_empty() {
+            self.log(LogLevel::Warning, "Please select at least one game folder!");
             return;
         }
-        self.is_loading = true;
-        self.current_operation = "Calculating statistics...".to_string();
-        let folders = self.game_folders.clone();
-        let tx = self.tx.clone();
+        let Some(out) = rfd::FileDialog::new()
+            .set_title("Export Library Manifest")
+            .set_file_name("library_manifest.json")
+            .add_filter("JSON", &["json"])
+            .save_file()
+        else {
+            return;
+        };
+        self.begin_operation();
+        self.current_operation = "Exporting library manifest...".to_string();
+        let tx = self.sender();
         thread::spawn(move || {
-            let stats = calculate_library_stats(&folders);
-            tx.send(AsyncMessage::StatsComplete(stats)).ok();
+            let Some(files) = index_mod_files(&folders, false, &tx) else {
+                return;
+            };
+            match export_library_manifest(&files, &out) {
+                Ok(()) => tx.send(AsyncMessage::ManifestExported(out, files.len())),
+                Err(e) => tx.send(AsyncMessage::Error(e.to_string())),
+            }
+            .ok();
         });
     }
 
-    fn run_orphaned_scan(&mut self, delete: bool) {
-        let selected: Vec<ModlistInfo> = self
-            .modlists
-            .iter()
-            .enumerate()
-            .filter(|(i, _)| self.modlist_selected.get(*i).copied().unwrap_or(false))
-            .map(|(_, ml)| ml.clone())
-            .collect();
+    /// Save the orphaned files as a script of delete commands to review and run by hand
+    fn export_deletion_script(&mut self, files: &[ModFile]) {
+        let native = ScriptShell::native();
+        let Some(out) = rfd::FileDialog::new()
+            .set_title("Export Deletion Script")
+            .set_file_name(format!("delete_orphaned.{}", native.extension()))
+            .add_filter("Batch", &["bat"])
+            .add_filter("PowerShell", &["ps1"])
+            .add_filter("Shell", &["sh"])
+            .save_file()
+        else {
+            return;
+        };
+        match export_deletion_script(files, &out) {
+            Ok(()) => self.log(
+                LogLevel::Info,
+                &format!(
+                    "Deletion script for {} files saved to {}",
+                    files.len(),
+                    out.display()
+                ),
+            ),
+            Err(e) => self.log(LogLevel::Error, &e.to_string()),
+        }
+    }
 
+    /// Run the orphaned and old-version analysis on a manifest instead of live folders
+    fn analyze_library_manifest(&mut self) {
+        let selected = self.selected_modlists();
         if selected.is_empty() {
             self.log(LogLevel::Warning, "Please select at least one modlist!");
             return;
         }
+        let Some(path) = rfd::FileDialog::new()
+            .set_title("Analyze Library Manifest")
+            .add_filter("JSON", &["json"])
+            .pick_file()
+        else {
+            return;
+        };
+        self.begin_operation();
+        self.current_operation = "Analyzing library manifest...".to_string();
+        let orphan_options = self.orphan_scan_options();
+        let old_options = self.old_version_options();
+        let tx = self.sender();
+        thread::spawn(move || {
+            let files = match import_library_manifest(&path) {
+                Ok(f) => f,
+                Err(e) => {
+                    tx.send(AsyncMessage::Error(e.to_string())).ok();
+                    return;
+                }
+            };
+            let orphaned = detect_orphaned_mods_with_options(&files, &selected, &orphan_options);
+            let old_versions = find_old_versions_in_files(&files, &old_options);
+            tx.send(AsyncMessage::QuickScanComplete(
+                format!("Manifest {}", path.display()),
+                orphaned,
+                old_versions,
+            ))
+            .ok();
+        });
+    }
 
-        self.is_loading = true;
-        self.current_operation = if delete {
-            "Cleaning orphaned mods..."
-        } else {
-            "Scanning for orphaned mods..."
+    fn import_id_mapping(&mut self) {
+        let Some(file) = rfd::FileDialog::new()
+            .set_title("Import File Name to Nexus ID Mapping")
+            .add_filter("Mapping", &["txt", "csv"])
+            .pick_file()
+        else {
+            return;
+        };
+        let Some(store) = manual_mapping_path() else {
+            self.log(
+                LogLevel::Error,
+                "No config directory to store the ID mapping.",
+            );
+            return;
+        };
+        let imported = match std::fs::read_to_string(&file)
+            .map_err(anyhow::Error::from)
+            .and_then(|content| ManualMapping::parse(&content))
+        {
+            Ok(m) => m,
+            Err(e) => {
+                self.log(
+                    LogLevel::Error,
+                    &format!("Failed to import ID mapping: {}", e),
+                );
+                return;
+            }
+        };
+        let count = imported.entries.len();
+        let mut mapping = ManualMapping::load(&store);
+        mapping.merge(imported);
+        match mapping.save(&store) {
+            Ok(()) => self.log(
+                LogLevel::Info,
+                &format!(
+                    "Imported {} ID mappings ({} stored). They apply on the next orphaned scan.",
+                    count,
+                    mapping.entries.len()
+                ),
+            ),
+            Err(e) => self.log(
+                LogLevel::Error,
+                &format!("Failed to save ID mapping: {}", e),
+            ),
         }
-        .to_string();
+    }
 
-        let path = match self.downloads_dir.clone() {
-            Some(p) => p,
-            None => {
-                self.log(LogLevel::Error, "Downloads directory not selected!");
-                self.is_loading = false;
+    fn select_downloads_dir(&mut self) {
+        if let Some(path) = rfd::FileDialog::new()
+            .set_title("Select Downloads Folder")
+            .pick_folder()
+        {
+            self.set_downloads_dirs(vec![path]);
+        }
+    }
+
+    /// Use the downloads folder configured in a Mod Organizer 2 instance
+    fn import_mo2_downloads(&mut self) {
+        let Some(instance) = rfd::FileDialog::new()
+            .set_title("Select Mod Organizer 2 Instance Folder")
+            .pick_folder()
+        else {
+            return;
+        };
+        match read_mo2_downloads(&instance) {
+            Some(path) => {
+                self.log(
+                    LogLevel::Info,
+                    &format!("Using MO2 downloads folder: {}", path.display()),
+                );
+                self.set_downloads_dirs(vec![path]);
+            }
+            None => self.log(
+                LogLevel::Warning,
+                &format!(
+                    "No existing downloads folder found in ModOrganizer.ini of {}",
+                    instance.display()
+                ),
+            ),
+        }
+    }
+
+    /// Scan another downloads root alongside the current ones
+    fn add_downloads_dir(&mut self) {
+        if let Some(path) = rfd::FileDialog::new()
+            .set_title("Add Downloads Folder")
+            .pick_folder()
+        {
+            if self.downloads_dirs.contains(&path) {
                 return;
             }
+            let mut dirs = self.downloads_dirs.clone();
+            dirs.push(path);
+            self.set_downloads_dirs(dirs);
+        }
+    }
+
+    fn remove_downloads_dir(&mut self, index: usize) {
+        let mut dirs = self.downloads_dirs.clone();
+        dirs.remove(index);
+        self.set_downloads_dirs(dirs);
+    }
+
+    /// Save this scan for the current downloads roots and diff it against the last one
+    fn record_scan_history(&mut self, result: &ScanResult) -> Option<ScanDelta> {
+        let key = history_key(&self.downloads_dirs)?;
+        let path = scan_history_path()?;
+        let mut history = ScanHistory::load(&path);
+        let delta = history.record(&key, ScanSnapshot::from_result(result));
+        if let Err(e) = history.save(&path) {
+            self.log(
+                LogLevel::Warning,
+                &format!("Failed to save scan history: {}", e),
+            );
+        }
+        if let Some(d) = &delta {
+            self.log(
+                LogLevel::Info,
+                &format!(
+                    "Since last scan: +{} new orphans, -{} resolved",
+                    d.new_orphans.len(),
+                    d.resolved.len()
+                ),
+            );
+        }
+        delta
+    }
+
+    /// Keep a finished scan in the results history and show the current results again
+    fn push_result_history(
+        &mut self,
+        folders: String,
+        orphaned: Option<ScanResult>,
+        old_versions: Option<OldVersionScanResult>,
+    ) {
+        self.result_history.insert(
+            0,
+            ResultHistoryEntry {
+                finished: chrono::Local::now(),
+                folders,
+                orphaned,
+                old_versions,
+            },
+        );
+        self.result_history.truncate(RESULT_HISTORY_LIMIT);
+        self.viewed_history = None;
+    }
+
+    fn set_downloads_dirs(&mut self, dirs: Vec<PathBuf>) {
+        for dir in dirs.iter().rev() {
+            push_recent_folder(&mut self.settings.recent_downloads_dirs, dir);
+        }
+        self.settings.downloads_dirs = dirs.clone();
+        self.save_settings();
+        self.downloads_dirs = dirs.clone();
+        if dirs.is_empty() {
+            self.game_folders.clear();
+            self.game_folder_selected.clear();
+            self.orphan_folder_selected.clear();
+            self.stats = None;
+            return;
+        }
+        self.log(
+            LogLevel::Info,
+            &format!("Indexing {} downloads folder(s)...", dirs.len()),
+        );
+        for dir in dirs.iter().filter(|d| !d.is_dir()) {
+            self.log(
+                LogLevel::Warning,
+                &format!(
+                    "Downloads folder {} is not available and is skipped.",
+                    dir.display()
+                ),
+            );
+        }
+        let tx = self.background_sender();
+        let excluded = self.settings.excluded_folders.clone();
+        thread::spawn(
+            move || match get_game_folders_in_roots_excluding(&dirs, &excluded) {
+                Ok(folders) => {
+                    tx.send(AsyncMessage::GameFoldersFound(folders)).ok();
+                }
+                Err(e) => {
+                    tx.send(AsyncMessage::Error(e.to_string())).ok();
+                }
+            },
+        );
+    }
+
+    /// Warn when a downloads folder overlaps the Wabbajack folder and stop treating its
+    /// version folders and `downloaded_mod_lists` as game folders
+    fn drop_wabbajack_folders(&mut self) {
+        let Some(wabbajack_dir) = self.wabbajack_dir.clone() else {
+            return;
         };
+        if !self
+            .downloads_dirs
+            .iter()
+            .any(|d| dirs_overlap(d, &wabbajack_dir))
+        {
+            return;
+        }
+        self.log(
+            LogLevel::Warning,
+            &format!(
+                "Downloads folder overlaps the Wabbajack folder '{}'. Wabbajack's own folders are not scanned; pick the downloads folder Wabbajack installs to instead.",
+                wabbajack_dir.display()
+            ),
+        );
 
-        let recycle_bin = if delete {
-            self.get_recycle_bin_path()
-        } else {
-            None
+        let (kept, excluded) = exclude_wabbajack_dirs(self.game_folders.clone(), &wabbajack_dir);
+        if excluded.is_empty() {
+            return;
+        }
+        for folder in &excluded {
+            self.log(
+                LogLevel::Info,
+                &format!("Skipping Wabbajack folder: {}", folder.display()),
+            );
+        }
+        let keep: Vec<bool> = self
+            .game_folders
+            .iter()
+            .map(|f| !excluded.contains(f))
+            .collect();
+        let filter = |selected: &[bool]| -> Vec<bool> {
+            selected
+                .iter()
+                .zip(&keep)
+                .filter(|(_, &k)| k)
+                .map(|(&s, _)| s)
+                .collect()
         };
-        let tx = self.tx.clone();
-        thread::spawn(move || scan_orphaned_mods_async(path, selected, delete, recycle_bin, tx));
+        self.game_folder_selected = filter(&self.game_folder_selected);
+        self.orphan_folder_selected = filter(&self.orphan_folder_selected);
+        self.game_folders = kept;
     }
 
-    fn run_old_version_scan(&mut self, delete: bool) {
+    /// Game folders ticked for the orphaned scan
+    fn orphan_scan_folders(&self) -> Vec<PathBuf> {
+        self.game_folders
+            .iter()
+            .zip(&self.orphan_folder_selected)
+            .filter(|(_, &selected)| selected)
+            .map(|(f, _)| f.clone())
+            .collect()
+    }
+
+    fn hash_options(&self) -> HashOptions {
+        let mb = self.settings.fast_hash_above_mb as u64;
+        HashOptions {
+            fast_above: (mb > 0).then_some(mb * 1024 * 1024),
+            verify: self.settings.verify_fast_hash,
+            ..HashOptions::default()
+        }
+    }
+
+    /// Hash re-downloaded copies in every game folder to find byte-identical ones
+    fn run_identical_scan(&mut self) {
         if self.game_folders.is_empty() {
             self.log(LogLevel::Warning, "No game folders found.");
             return;
         }
-        self.pending_delete_mode = delete;
-        self.modal = Modal::FolderSelect;
+        let folders = self.game_folders.clone();
+        let hash_options = self.hash_options();
+        let cancel = CancelToken::new();
+        self.cancel_token = Some(cancel.clone());
+        self.begin_operation();
+        self.current_operation = "Looking for identical copies...".to_string();
+        let tx = self.sender();
+        thread::spawn(move || {
+            let mut last_update = Instant::now();
+            let mut on_progress = |done: u64, total: u64| {
+                // Hashing reports every chunk; keep the UI channel quiet
+                if last_update.elapsed() < Duration::from_millis(100) && done < total {
+                    return;
+                }
+                last_update = Instant::now();
+                tx.send(AsyncMessage::Progress(
+                    format!("Hashing... {} / {}", format_size(done), format_size(total)),
+                    Some((done as usize, total as usize)),
+                ))
+                .ok();
+            };
+            let result = find_identical_copies_with_options(
+                &folders,
+                &cancel,
+                &mut on_progress,
+                &hash_options,
+            );
+            tx.send(AsyncMessage::IdenticalCopiesFound(result)).ok();
+        });
     }
 
-    fn start_old_version_scan(&mut self) {
-        if let Some(idx) = self.selected_game_folder {
-            let folder = self.game_folders[idx].clone();
-            let delete = self.pending_delete_mode;
-            let recycle_bin = if delete {
-                self.get_recycle_bin_path()
-            } else {
-                None
-            };
-            let tx = self.tx.clone();
-            self.modal = Modal::None;
-            self.is_loading = true;
-            self.current_operation = "Scanning for old versions...".to_string();
-            thread::spawn(move || scan_old_versions_async(folder, delete, recycle_bin, tx));
+    /// Group files sharing a ModID and FileID across game folders; names only, no hashing
+    fn run_cross_game_scan(&mut self) {
+        if self.game_folders.is_empty() {
+            self.log(LogLevel::Warning, "No game folders found.");
+            return;
         }
+        let folders = self.game_folders.clone();
+        self.begin_operation();
+        self.current_operation = "Looking for mods in several games...".to_string();
+        let tx = self.sender();
+        thread::spawn(move || {
+            let Some(files) = index_mod_files(&folders, false, &tx) else {
+                return;
+            };
+            tx.send(AsyncMessage::CrossGameCopiesFound(find_cross_game_copies(
+                &files,
+            )))
+            .ok();
+        });
     }
 
-    fn handle_messages(&mut self) {
-        while let Ok(msg) = self.rx.try_recv() {
-            match msg {
-                AsyncMessage::ModlistsParsed(list) => {
-                    self.log(LogLevel::Info, &format!("Found {} modlists", list.len()));
-                    self.modlist_selected = vec![true; list.len()];
-                    self.modlists = list;
-                    self.is_loading = false;
-                    self.progress = None;
-                    if self.downloads_dir.is_some() {
-                        self.run_analysis();
+    /// Hash the files of the orphaned result and log where the modlist hashes disagree
+    fn run_hash_cross_check(&mut self) {
+        let Some(scan) = self.orphaned_result.clone() else {
+            return;
+        };
+        let modlists = self.selected_modlists();
+        let cancel = CancelToken::new();
+        self.cancel_token = Some(cancel.clone());
+        self.begin_operation();
+        self.current_operation = "Cross-checking with modlist hashes...".to_string();
+        let tx = self.sender();
+        thread::spawn(move || {
+            let mut last_update = Instant::now();
+            let mut on_progress = |done: u64, total: u64| {
+                if last_update.elapsed() < Duration::from_millis(100) && done < total {
+                    return;
+                }
+                last_update = Instant::now();
+                tx.send(AsyncMessage::Progress(
+                    format!("Hashing... {} / {}", format_size(done), format_size(total)),
+                    Some((done as usize, total as usize)),
+                ))
+                .ok();
+            };
+            let report = reconcile_matching(&scan, &modlists, &cancel, &mut on_progress);
+            tx.send(AsyncMessage::ReconciliationComplete(report)).ok();
+        });
+    }
+
+    /// Quick structural check of the archives in the selected game folders
+    fn run_archive_health_check(&mut self) {
+        let folders = self.orphan_scan_folders();
+        if folders.is_empty() {
+            self.log(LogLevel::Warning, "Please select at least one game folder!");
+            return;
+        }
+        let cancel = CancelToken::new();
+        self.cancel_token = Some(cancel.clone());
+        self.begin_operation();
+        self.current_operation = "Checking archive health...".to_string();
+        let tx = self.sender();
+        thread::spawn(move || {
+            let Some(files) = index_mod_files(&folders, false, &tx) else {
+                return;
+            };
+            let mut last_update = Instant::now();
+            let mut on_progress = |done: usize, total: usize| {
+                if last_update.elapsed() < Duration::from_millis(100) && done < total {
+                    return;
+                }
+                last_update = Instant::now();
+                tx.send(AsyncMessage::Progress(
+                    format!("Checking archives... {} / {}", done, total),
+                    Some((done, total)),
+                ))
+                .ok();
+            };
+            let report = check_archives_health(&files, &cancel, &mut on_progress);
+            tx.send(AsyncMessage::ArchiveHealthChecked(report)).ok();
+        });
+    }
+
+    /// Remove the copies shown in the results, keeping each original
+    fn clean_identical_copies(&mut self) {
+        let groups = match &self.identical_result {
+            Some(res) if !res.groups.is_empty() => res.groups.clone(),
+            _ => return,
+        };
+        let recycle_bins = self.get_recycle_bin_paths();
+        self.begin_operation();
+        let options = self.delete_options();
+        let tx = self.sender();
+        self.current_operation = "Removing identical copies...".to_string();
+        thread::spawn(move || delete_old_versions_async(groups, recycle_bins, options, tx));
+    }
+
+    /// Library stats and the old-version estimate; only needs the downloads folder
+    fn run_analysis(&mut self) {
+        if self.downloads_dirs.is_empty() || self.game_folders.is_empty() {
+            return;
+        }
+        if self.defer(QueuedOperation::Analysis) {
+            return;
+        }
+        self.begin_operation();
+        self.current_operation = "Calculating statistics...".to_string();
+        let folders = self.orphan_scan_folders();
+        let tx = self.sender();
+        thread::spawn(move || {
+            let stats = calculate_library_stats(&folders);
+            tx.send(AsyncMessage::StatsComplete(stats)).ok();
+        });
+    }
+
+    fn orphan_scan_options(&self) -> OrphanScanOptions {
+        OrphanScanOptions {
+            protected_extensions: self.settings.protected_extensions.clone(),
+            ignore_newer_than_days: (self.settings.ignore_newer_than_days > 0)
+                .then_some(self.settings.ignore_newer_than_days),
+            min_mod_size: self.settings.min_mod_size_kb as u64 * 1024,
+            mod_id_fallback: !self.settings.strict_orphan_matching,
+            file_id_only_names: self.settings.file_id_only_names,
+        }
+    }
+
+    fn old_version_options(&self) -> OldVersionScanOptions {
+        OldVersionScanOptions {
+            keep_by: self.settings.keep_by,
+            patch_size_ratio: self.settings.patch_size_ratio,
+            suspicious_size_ratio: self.settings.suspicious_size_ratio,
+            ..Default::default()
+        }
+    }
+
+    /// Old-version groups a cleanup acts on, with keepers picked by hand; groups flagged for
+    /// review only when included
+    fn cleanable_old_versions(&self, groups: &[ModGroup]) -> Vec<ModGroup> {
+        groups
+            .iter()
+            .map(|g| with_keeper_override(g, &self.keeper_overrides).into_owned())
+            .filter(|g| !g.review_recommended || self.review_included.contains(&g.mod_key))
+            .collect()
+    }
+
+    fn current_orphaned_scope(&self) -> ResultScope {
+        ResultScope::new(
+            self.downloads_dirs.clone(),
+            self.orphan_scan_folders(),
+            self.selected_modlists()
+                .into_iter()
+                .map(|ml| ml.file_path)
+                .chain(self.settings.mo2_mods_dir.clone())
+                .collect(),
+            ScopeOptions::Orphaned(self.orphan_scan_options()),
+        )
+    }
+
+    fn current_old_version_scope(&self) -> ResultScope {
+        ResultScope::new(
+            self.downloads_dirs.clone(),
+            self.selected_game_folders(),
+            Vec::new(),
+            ScopeOptions::OldVersions(self.old_version_options()),
+        )
+    }
+
+    fn selected_game_folders(&self) -> Vec<PathBuf> {
+        self.game_folders
+            .iter()
+            .zip(&self.game_folder_selected)
+            .filter(|(_, &selected)| selected)
+            .map(|(f, _)| f.clone())
+            .collect()
+    }
+
+    /// Whether the shown orphaned result still matches the current selection
+    fn orphaned_result_fresh(&self) -> bool {
+        self.orphaned_result.is_some()
+            && can_reuse_result(self.orphaned_scope.as_ref(), &self.current_orphaned_scope())
+    }
+
+    /// Whether the shown old-version result still matches the current selection
+    fn old_version_result_fresh(&self) -> bool {
+        self.old_version_result.is_some()
+            && can_reuse_result(
+                self.old_version_scope.as_ref(),
+                &self.current_old_version_scope(),
+            )
+    }
+
+    /// Clean orphaned mods, reusing the shown result when fresh and scanning first otherwise
+    fn clean_orphaned(&mut self) {
+        let orphaned = match &self.orphaned_result {
+            Some(res) if self.orphaned_result_fresh() => res.orphaned_mods.clone(),
+            _ => return self.run_orphaned_scan(true, false),
+        };
+        if orphaned.is_empty() {
+            self.log(LogLevel::Info, "No orphaned mods to clean.");
+            return;
+        }
+        self.log(
+            LogLevel::Info,
+            "Cleaning the orphaned mods shown in the results.",
+        );
+        let recycle_bins = self.get_recycle_bin_paths();
+        self.begin_operation();
+        let options = self.delete_options();
+        let tx = self.sender();
+        self.current_operation = "Cleaning orphaned mods...".to_string();
+        thread::spawn(move || delete_orphaned_async(orphaned, recycle_bins, options, tx));
+    }
+
+    /// Clean old versions, going straight to the review when the shown result is fresh
+    fn clean_old_versions(&mut self) {
+        if !self.old_version_result_fresh() {
+            return self.run_old_version_scan(true);
+        }
+        if self
+            .old_version_result
+            .as_ref()
+            .is_some_and(|res| res.duplicates.is_empty())
+        {
+            self.modal = Modal::None;
+            self.log(LogLevel::Info, "No old versions to clean.");
+            return;
+        }
+        self.pending_delete_mode = true;
+        self.modal = Modal::ReviewOldVersions;
+    }
+
+    /// Both shown results are fresh and at least one has something to clean
+    fn can_clean_all(&self) -> bool {
+        let (Some(orphaned), Some(old_versions)) =
+            (&self.orphaned_result, &self.old_version_result)
+        else {
+            return false;
+        };
+        self.orphaned_result_fresh()
+            && self.old_version_result_fresh()
+            && (!orphaned.orphaned_mods.is_empty() || !old_versions.duplicates.is_empty())
+    }
+
+    /// Orphaned files and the old-version groups cleaned with them, when both are fresh
+    fn clean_all_plan(&self) -> Option<(&[OrphanedMod], Vec<ModGroup>, usize)> {
+        if !self.can_clean_all() {
+            return None;
+        }
+        let orphaned = &self.orphaned_result.as_ref()?.orphaned_mods;
+        let duplicates = self.cleanable_old_versions(&self.old_version_result.as_ref()?.duplicates);
+        let (duplicates, skipped) = plan_clean_all(orphaned, &duplicates);
+        Some((orphaned, duplicates, skipped))
+    }
+
+    /// Clean orphaned files and then old versions in one run
+    fn clean_all_detected(&mut self) {
+        self.modal = Modal::None;
+        let Some((orphaned, duplicates, skipped)) = self
+            .clean_all_plan()
+            .map(|(orphaned, duplicates, skipped)| (orphaned.to_vec(), duplicates, skipped))
+        else {
+            return;
+        };
+        if skipped > 0 {
+            self.log(
+                LogLevel::Info,
+                &format!(
+                    "Skipping {} old-version groups whose kept file is orphaned.",
+                    skipped
+                ),
+            );
+        }
+        let recycle_bins = self.get_recycle_bin_paths();
+        self.begin_operation();
+        let options = self.delete_options();
+        let tx = self.sender();
+        self.current_operation = "Cleaning everything detected...".to_string();
+        thread::spawn(move || {
+            delete_all_detected_async(orphaned, duplicates, recycle_bins, options, tx)
+        });
+    }
+
+    fn render_confirm_clean_all(&mut self, ctx: &egui::Context) {
+        let Some((orphaned, duplicates, skipped)) = self.clean_all_plan() else {
+            self.modal = Modal::None;
+            return;
+        };
+        let orphaned_size: u64 = orphaned.iter().map(|m| m.file.size).sum();
+        let old_files: usize = duplicates.iter().map(|g| g.newest_idx).sum();
+        let old_size: u64 = duplicates.iter().map(|g| g.space_to_free).sum();
+        let mut confirm = false;
+        let mut cancel = false;
+
+        egui::Window::new(tr("clean_all"))
+            .collapsible(false)
+            .resizable(false)
+            .default_width(380.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                egui::Grid::new("clean_all_grid")
+                    .num_columns(3)
+                    .spacing([16.0, 4.0])
+                    .show(ui, |ui| {
+                        ui.label(tr("orphaned_mods"));
+                        ui.label(tr_args("n_files", &[&orphaned.len()]));
+                        ui.label(RichText::new(format_size(orphaned_size)).color(COLOR_DANGER));
+                        ui.end_row();
+                        ui.label(tr("old_versions"));
+                        ui.label(tr_args("n_files", &[&old_files]));
+                        ui.label(RichText::new(format_size(old_size)).color(COLOR_WARNING));
+                        ui.end_row();
+                        ui.label(RichText::new(tr("total")).strong());
+                        ui.label(
+                            RichText::new(tr_args("n_files", &[&(orphaned.len() + old_files)]))
+                                .strong(),
+                        );
+                        ui.label(
+                            RichText::new(format_size(orphaned_size + old_size))
+                                .strong()
+                                .color(COLOR_TEXT_PRIMARY),
+                        );
+                        ui.end_row();
+                    });
+                if skipped > 0 {
+                    ui.label(
+                        RichText::new(tr_args("clean_all_skipped_groups", &[&skipped]))
+                            .size(11.0)
+                            .color(COLOR_TEXT_MUTED),
+                    );
+                }
+                ui.add_space(8.0);
+                ui.label(
+                    RichText::new(if self.uses_recycle_bin() {
+                        tr("clean_all_to_recycle_bin")
+                    } else {
+                        tr("clean_all_permanent")
+                    })
+                    .color(if self.uses_recycle_bin() {
+                        COLOR_TEXT_SECONDARY
+                    } else {
+                        COLOR_DANGER
+                    }),
+                );
+                ui.add_space(8.0);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .add(
+                            egui::Button::new(
+                                RichText::new(tr("confirm_clean")).color(COLOR_TEXT_PRIMARY),
+                            )
+                            .fill(COLOR_DANGER),
+                        )
+                        .clicked()
+                    {
+                        confirm = true;
+                    }
+                    if ui.button(tr("cancel")).clicked() {
+                        cancel = true;
+                    }
+                });
+            });
+
+        if confirm {
+            self.clean_all_detected();
+        } else if cancel {
+            self.modal = Modal::None;
+        }
+    }
+
+    fn selected_modlists(&self) -> Vec<ModlistInfo> {
+        self.modlists
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.modlist_selected.get(*i).copied().unwrap_or(false))
+            .map(|(_, ml)| ml.clone())
+            .collect()
+    }
+
+    /// Selected modlists for a scan, with copies of the same list counted once, plus the
+    /// installed MO2 mods when a mods folder is set
+    fn selected_modlists_for_scan(&mut self) -> Vec<ModlistInfo> {
+        let (mut modlists, merged) = merge_identical_modlists(&self.selected_modlists());
+        for (duplicate, kept) in merged {
+            self.log(
+                LogLevel::Warning,
+                &format!(
+                    "{} has the same archives as {}; counting it once.",
+                    duplicate.display(),
+                    kept.display()
+                ),
+            );
+        }
+        if !modlists.is_empty() {
+            modlists.extend(self.installed_mods());
+        }
+        modlists
+    }
+
+    /// Installed mods of the configured MO2 mods folder, read fresh for each scan
+    fn installed_mods(&mut self) -> Option<ModlistInfo> {
+        let dir = self.settings.mo2_mods_dir.clone()?;
+        let installed = collect_installed_mods(&dir);
+        match &installed {
+            Some(ml) => self.log(
+                LogLevel::Info,
+                &format!("Protecting {} installed MO2 mods", ml.mod_count),
+            ),
+            None => self.log(
+                LogLevel::Warning,
+                &format!("Cannot read MO2 mods folder {}", dir.display()),
+            ),
+        }
+        installed
+    }
+
+    /// Protect the downloads of mods installed in an MO2 `mods` folder
+    fn select_mo2_mods_dir(&mut self) {
+        if let Some(path) = rfd::FileDialog::new()
+            .set_title("Select Mod Organizer 2 mods Folder")
+            .pick_folder()
+        {
+            self.settings.mo2_mods_dir = Some(path);
+            self.save_settings();
+        }
+    }
+
+    /// What deselecting each scanned modlist would free. Orphaned files are used by no
+    /// modlist, so the used files of the scan are enough. Copies of one list share its size.
+    fn update_modlist_exclusive_sizes(&mut self, result: &ScanResult) {
+        let (mut modlists, merged) = merge_identical_modlists(&self.selected_modlists());
+        // Files an installed MO2 mod also needs are not freed by deselecting a modlist
+        modlists.extend(
+            self.settings
+                .mo2_mods_dir
+                .as_deref()
+                .and_then(collect_installed_mods),
+        );
+        let files: Vec<ModFile> = result.used_mods.iter().map(|m| m.file.clone()).collect();
+        let sizes = exclusive_modlist_sizes(&files, &modlists, &self.orphan_scan_options());
+        self.modlist_exclusive_sizes = modlists
+            .into_iter()
+            .map(|ml| ml.file_path)
+            .zip(sizes)
+            .collect();
+        for (duplicate, kept) in merged {
+            if let Some(&size) = self.modlist_exclusive_sizes.get(&kept) {
+                self.modlist_exclusive_sizes.insert(duplicate, size);
+            }
+        }
+    }
+
+    /// Orphaned and old-version scan of one picked folder, without touching the rest of the library
+    fn run_quick_scan(&mut self) {
+        let selected = self.selected_modlists_for_scan();
+        if selected.is_empty() {
+            self.log(LogLevel::Warning, "Please select at least one modlist!");
+            return;
+        }
+        let mut dialog = rfd::FileDialog::new();
+        if let Some(dir) = self.downloads_dirs.first() {
+            dialog = dialog.set_directory(dir);
+        }
+        let Some(folder) = dialog.pick_folder() else {
+            return;
+        };
+
+        self.begin_operation();
+        self.current_operation = format!("Quick scan of {}...", folder.display());
+        let orphan_options = self.orphan_scan_options();
+        let old_options = self.old_version_options();
+        let tx = self.sender();
+        thread::spawn(move || {
+            quick_scan_async(folder, selected, orphan_options, old_options, tx);
+        });
+    }
+
+    fn run_orphaned_scan(&mut self, delete: bool, force_rescan: bool) {
+        let selected = self.selected_modlists_for_scan();
+
+        if selected.is_empty() {
+            self.log(LogLevel::Warning, "Please select at least one modlist!");
+            return;
+        }
+
+        let folders = self.orphan_scan_folders();
+        if folders.is_empty() {
+            self.log(LogLevel::Warning, "Please select at least one game folder!");
+            return;
+        }
+
+        if !delete {
+            self.orphaned_scope = Some(self.current_orphaned_scope());
+        }
+        self.begin_operation();
+        self.current_operation = if delete {
+            "Cleaning orphaned mods..."
+        } else {
+            "Scanning for orphaned mods..."
+        }
+        .to_string();
+
+        let (recycle_bins, delete) = if delete {
+            (self.get_recycle_bin_paths(), Some(self.delete_options()))
+        } else {
+            (Vec::new(), None)
+        };
+        let options = self.orphan_scan_options();
+        let tx = self.sender();
+        thread::spawn(move || {
+            scan_orphaned_mods_async(
+                folders,
+                selected,
+                options,
+                delete,
+                force_rescan,
+                recycle_bins,
+                tx,
+            )
+        });
+    }
+
+    fn run_old_version_scan(&mut self, delete: bool) {
+        if self.game_folders.is_empty() {
+            self.log(LogLevel::Warning, "No game folders found.");
+            return;
+        }
+        self.pending_delete_mode = delete;
+        self.modal = Modal::FolderSelect;
+    }
+
+    fn start_old_version_scan(&mut self) {
+        let folders = self.selected_game_folders();
+        if folders.is_empty() {
+            return;
+        }
+        self.old_version_scope = Some(self.current_old_version_scope());
+        // Cleaning always scans first; deletion waits for the review modal
+        self.begin_operation();
+        let tx = self.sender();
+        self.modal = Modal::None;
+        self.current_operation = "Scanning for old versions...".to_string();
+        let options = self.old_version_options();
+        thread::spawn(move || scan_old_versions_async(folders, options, tx));
+    }
+
+    fn confirm_old_version_clean(&mut self) {
+        self.modal = Modal::None;
+        self.pending_delete_mode = false;
+        let duplicates = match &self.old_version_result {
+            Some(res) => self.cleanable_old_versions(&res.duplicates),
+            None => return,
+        };
+        if duplicates.is_empty() {
+            self.log(LogLevel::Info, "No old versions selected for cleanup.");
+            return;
+        }
+        let recycle_bins = self.get_recycle_bin_paths();
+        self.begin_operation();
+        let options = self.delete_options();
+        let tx = self.sender();
+        self.current_operation = "Cleaning old versions...".to_string();
+        thread::spawn(move || delete_old_versions_async(duplicates, recycle_bins, options, tx));
+    }
+
+    /// Throttle and a fresh cancel token for a cleanup run
+    fn delete_options(&mut self) -> DeleteOptions {
+        let cancel = CancelToken::new();
+        self.cancel_token = Some(cancel.clone());
+        let tx = self.sender();
+        DeleteOptions {
+            keep_meta: self.settings.keep_meta,
+            copy_delete: self.settings.copy_delete_moves,
+            copy_progress: Some(CopyProgress(Arc::new(move |name, done, total| {
+                tx.send(AsyncMessage::Progress(
+                    format!(
+                        "Copying {}... {} / {}",
+                        name,
+                        format_size(done),
+                        format_size(total)
+                    ),
+                    Some((done as usize, total as usize)),
+                ))
+                .ok();
+            }))),
+            journal: deletion_journal_path(),
+            compress_backups: self.settings.compress_backups,
+            ..DeleteOptions::with_throttle_ms(
+                self.settings.delete_throttle_ms,
+                cancel,
+                self.downloads_dirs.clone(),
+            )
+        }
+    }
+
+    /// Finish the cleanup an interrupted run left in the deletion journal
+    fn resume_deletion(&mut self) {
+        if self.settings.safe_mode
+            && self
+                .pending_journal
+                .as_ref()
+                .is_some_and(|p| p.pending.iter().any(|e| e.recycle_bin.is_none()))
+        {
+            self.log(
+                LogLevel::Warning,
+                "Safe mode is on: the interrupted cleanup would delete files permanently, so it was not resumed.",
+            );
+            return;
+        }
+        self.modal = Modal::None;
+        let (Some(pending), Some(journal)) = (self.pending_journal.take(), deletion_journal_path())
+        else {
+            return;
+        };
+        self.log(
+            LogLevel::Info,
+            &format!(
+                "Resuming the cleanup started {}: {} files left.",
+                pending.started,
+                pending.pending.len()
+            ),
+        );
+        self.begin_operation();
+        let options = self.delete_options();
+        let tx = self.sender();
+        self.current_operation = "Resuming cleanup...".to_string();
+        thread::spawn(move || {
+            let start = Instant::now();
+            let progress = batch_progress(&tx, 0, pending.pending.len());
+            let mut del = resume_pending_deletion(&journal, Some(&progress), &options);
+            del.elapsed = start.elapsed();
+            tx.send(AsyncMessage::DeletionComplete(del)).ok();
+        });
+    }
+
+    fn discard_pending_deletion(&mut self) {
+        self.modal = Modal::None;
+        if let (Some(pending), Some(journal)) =
+            (self.pending_journal.take(), deletion_journal_path())
+        {
+            finish_deletion_journal(&journal);
+            self.log(
+                LogLevel::Info,
+                &format!(
+                    "Discarded the interrupted cleanup; {} files were left in place.",
+                    pending.pending.len()
+                ),
+            );
+        }
+    }
+
+    fn render_resume_deletion(&mut self, ctx: &egui::Context) {
+        let Some(pending) = &self.pending_journal else {
+            self.modal = Modal::None;
+            return;
+        };
+        let started = chrono::DateTime::parse_from_rfc3339(&pending.started)
+            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|_| pending.started.clone());
+        let count = pending.pending.len();
+        let size = format_size(pending.pending_size());
+        let permanent = pending.pending.iter().any(|e| e.recycle_bin.is_none());
+        let locked = permanent && self.settings.safe_mode;
+        let mut resume = false;
+        let mut discard = false;
+        egui::Window::new(tr("resume_deletion"))
+            .collapsible(false)
+            .resizable(false)
+            .default_width(380.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(8.0);
+                    ui.label(tr_args("resume_deletion_body", &[&started, &count, &size]));
+                    if permanent {
+                        ui.add_space(4.0);
+                        ui.label(
+                            RichText::new(tr("resume_deletion_permanent"))
+                                .size(11.0)
+                                .color(COLOR_WARNING),
+                        );
+                    }
+                    ui.add_space(16.0);
+                    ui.horizontal(|ui| {
+                        resume = ui
+                            .add_enabled(
+                                !locked,
+                                egui::Button::new(RichText::new(tr("resume")).strong()),
+                            )
+                            .on_disabled_hover_text(tr("purge_locked_hint"))
+                            .clicked();
+                        discard = ui
+                            .button(tr("discard"))
+                            .on_hover_text(tr("discard_deletion_hint"))
+                            .clicked();
+                    });
+                });
+            });
+        if resume {
+            self.resume_deletion();
+        } else if discard {
+            self.discard_pending_deletion();
+        }
+    }
+
+    /// Log a finished cleanup and refresh the analysis
+    fn finish_deletion(&mut self, res: DeletionResult) {
+        if res.cancelled {
+            self.log(
+                LogLevel::Warning,
+                "Cleanup stopped before all files were processed.",
+            );
+        }
+        if let Some(ref path) = res.recycle_bin_path {
+            self.log(
+                LogLevel::Info,
+                &format!(
+                    "Cleanup complete! {} files ({}) moved to '{}' in {}. Verify your modlist in Wabbajack before permanently deleting this folder to free disk space.",
+                    res.deleted_count,
+                    format_size(res.space_freed),
+                    path.display(),
+                    format_elapsed(res.elapsed)
+                ),
+            );
+        } else {
+            self.log(
+                LogLevel::Info,
+                &format!(
+                    "Cleanup complete! {} files ({}) permanently deleted in {}.",
+                    res.deleted_count,
+                    format_size(res.space_freed),
+                    format_elapsed(res.elapsed)
+                ),
+            );
+        }
+        self.notify_done(&format!(
+            "Cleaned {} files, freed {} in {}",
+            res.deleted_count,
+            format_size(res.space_freed),
+            format_elapsed(res.elapsed)
+        ));
+        self.after_files_removed(&res);
+    }
+
+    /// Log a finished relocation and refresh the analysis
+    fn finish_relocation(&mut self, dest: &Path, res: DeletionResult) {
+        if res.cancelled {
+            self.log(
+                LogLevel::Warning,
+                "Relocation stopped before all files were processed.",
+            );
+        }
+        let summary = format!(
+            "Moved {} files ({}) to '{}' in {}",
+            res.deleted_count,
+            format_size(res.space_freed),
+            dest.display(),
+            format_elapsed(res.elapsed)
+        );
+        self.log(LogLevel::Info, &summary);
+        self.notify_done(&summary);
+        self.after_files_removed(&res);
+    }
+
+    /// Shared end of a cleanup or relocation: the shown results no longer match the disk, so
+    /// the scans behind them run again. Quick scan results have no scan to repeat and go.
+    fn after_files_removed(&mut self, res: &DeletionResult) {
+        self.cancel_token = None;
+        // Only results of the current selection are rescanned; others are dropped rather
+        // than replaced by a scan of something else
+        let mut rescans = Vec::new();
+        if self.orphaned_result_fresh() {
+            rescans.push(QueuedOperation::OrphanedScan);
+        } else {
+            self.orphaned_result = None;
+        }
+        if self.old_version_result_fresh() {
+            rescans.push(QueuedOperation::OldVersionScan);
+        } else {
+            self.old_version_result = None;
+        }
+        self.orphaned_scope = None;
+        self.old_version_scope = None;
+        self.identical_result = None;
+        self.cross_game_result = None;
+        if !res.errors.is_empty() {
+            self.log(
+                LogLevel::Warning,
+                &format!("{} error(s) occurred during cleanup.", res.errors.len()),
+            );
+        }
+        self.is_loading = false;
+        self.progress = None;
+        self.run_analysis();
+        for op in rescans {
+            if !self.defer(op.clone()) {
+                self.start_operation(op);
+            }
+        }
+    }
+
+    /// Move the orphaned files shown in the results to a folder the user picks,
+    /// keeping their game folders
+    fn relocate_orphaned(&mut self) {
+        let orphaned = match &self.orphaned_result {
+            Some(res) if self.orphaned_result_fresh() => res.orphaned_mods.clone(),
+            _ => return,
+        };
+        if orphaned.is_empty() {
+            self.log(LogLevel::Info, "No orphaned mods to move.");
+            return;
+        }
+        let Some(dest) = rfd::FileDialog::new()
+            .set_title(tr("relocate_pick_folder"))
+            .pick_folder()
+        else {
+            return;
+        };
+        if self.downloads_dirs.iter().any(|d| dest.starts_with(d)) {
+            self.log(
+                LogLevel::Error,
+                "Pick a folder outside the downloads folder to move orphaned mods to.",
+            );
+            return;
+        }
+        self.log(
+            LogLevel::Info,
+            &format!(
+                "Moving {} orphaned mods to {}",
+                orphaned.len(),
+                dest.display()
+            ),
+        );
+        self.begin_operation();
+        let options = self.delete_options();
+        let tx = self.sender();
+        self.current_operation = "Moving orphaned mods...".to_string();
+        thread::spawn(move || {
+            let start = Instant::now();
+            let progress = batch_progress(&tx, 0, orphaned.len());
+            let mut res =
+                relocate_orphaned_mods_with_options(&orphaned, &dest, Some(&progress), &options);
+            res.elapsed = start.elapsed();
+            tx.send(AsyncMessage::RelocationComplete(dest, res)).ok();
+        });
+    }
+
+    /// Start a background operation. Messages still arriving from an earlier one are
+    /// ignored from now on, so it can't overwrite the new one's state.
+    fn begin_operation(&mut self) {
+        if self.is_loading {
+            log::debug!("Superseding operation: {}", self.current_operation);
+        }
+        self.generation += 1;
+        self.is_loading = true;
+    }
+
+    /// Sender for the current operation's worker
+    fn sender(&self) -> OpSender {
+        OpSender {
+            generation: self.generation,
+            tx: self.tx.clone(),
+        }
+    }
+
+    /// Sender for work that runs alongside operations and is never superseded
+    fn background_sender(&self) -> OpSender {
+        OpSender {
+            generation: BACKGROUND_GENERATION,
+            tx: self.tx.clone(),
+        }
+    }
+
+    /// Queue `op` if another operation is running; returns whether it was queued
+    fn defer(&mut self, op: QueuedOperation) -> bool {
+        if !self.is_loading {
+            self.queued_operations.retain(|q| *q != op);
+            return false;
+        }
+        if !self.queued_operations.contains(&op) {
+            log::debug!("Queued {:?} until {} finishes", op, self.current_operation);
+            self.queued_operations.push_back(op);
+        }
+        true
+    }
+
+    /// Start the next queued operation once nothing is running
+    fn run_queued_operation(&mut self) {
+        if self.is_loading {
+            return;
+        }
+        if let Some(op) = self.queued_operations.pop_front() {
+            self.start_operation(op);
+        }
+    }
+
+    fn start_operation(&mut self, op: QueuedOperation) {
+        match op {
+            QueuedOperation::ScanWabbajackDir(path) => self.set_wabbajack_dir(path),
+            QueuedOperation::Analysis => self.run_analysis(),
+            QueuedOperation::ListBackups => self.list_backups(),
+            QueuedOperation::OrphanedScan => self.run_orphaned_scan(false, false),
+            QueuedOperation::OldVersionScan => self.start_old_version_scan(),
+        }
+    }
+
+    fn handle_messages(&mut self) {
+        while let Ok((generation, msg)) = self.rx.try_recv() {
+            if is_stale(generation, self.generation) {
+                log::debug!("Ignoring a message of superseded operation {}", generation);
+                continue;
+            }
+            match msg {
+                AsyncMessage::ModlistsParsed(list) => {
+                    self.log(LogLevel::Info, &format!("Found {} modlists", list.len()));
+                    for ml in list.iter().filter(|ml| ml.has_no_mod_ids()) {
+                        self.log(
+                            LogLevel::Warning,
+                            &format!(
+                                "Modlist '{}' has no Nexus ModIDs; it is not selected until you tick it",
+                                ml.name
+                            ),
+                        );
+                    }
+                    self.modlist_selected = default_modlist_selection(&list);
+                    self.modlist_exclusive_sizes.clear();
+                    self.modlists = list;
+                    self.is_loading = false;
+                    self.progress = None;
+                    if !self.downloads_dirs.is_empty() {
+                        self.run_analysis();
                     }
                 }
                 AsyncMessage::GameFoldersFound(folders) => {
@@ -286,11 +2005,12 @@ impl WabbajackCleanerApp {
                         LogLevel::Info,
                         &format!("Found {} game folders", folders.len()),
                     );
+                    self.game_folder_selected = vec![false; folders.len()];
+                    self.orphan_folder_selected = vec![true; folders.len()];
                     self.game_folders = folders;
+                    self.drop_wabbajack_folders();
                     self.progress = None;
-                    if self.wabbajack_dir.is_some() {
-                        self.run_analysis();
-                    }
+                    self.run_analysis();
                 }
                 AsyncMessage::StatsComplete(stats) => {
                     self.stats = Some(stats);
@@ -298,6 +2018,8 @@ impl WabbajackCleanerApp {
                     self.progress = None;
                 }
                 AsyncMessage::OrphanedScanComplete(res) => {
+                    self.missing_files.clear();
+                    self.cancel_token = None;
                     self.log(
                         LogLevel::Info,
                         &format!(
@@ -306,11 +2028,188 @@ impl WabbajackCleanerApp {
                             format_size(res.orphaned_size)
                         ),
                     );
+                    self.notify_scan(format!(
+                        "Orphaned scan: {} files, {}",
+                        res.orphaned_mods.len(),
+                        format_size(res.orphaned_size)
+                    ));
+                    self.scan_delta = self.record_scan_history(&res);
+                    self.update_modlist_exclusive_sizes(&res);
+                    self.announce_results(tr_args("badge_orphaned", &[&res.orphaned_mods.len()]));
+                    let folders = match &self.orphaned_scope {
+                        Some(scope) => scope.folders.clone(),
+                        None => self.orphan_scan_folders(),
+                    };
+                    self.push_result_history(folder_names_label(&folders), Some(res.clone()), None);
                     self.orphaned_result = Some(res);
+                    self.shown_rows.clear();
+                    self.is_loading = false;
+                    self.progress = None;
+                }
+                AsyncMessage::QuickScanComplete(source, orphaned, old_versions) => {
+                    self.missing_files.clear();
+                    let summary = format!(
+                        "{}: {} orphaned files ({}), {} old versions ({})",
+                        source,
+                        orphaned.orphaned_mods.len(),
+                        format_size(orphaned.orphaned_size),
+                        old_versions.total_files,
+                        format_size(old_versions.total_space)
+                    );
+                    self.log(LogLevel::Info, &summary);
+                    self.notify_scan(format!(
+                        "Orphaned scan: {} files, {}",
+                        orphaned.orphaned_mods.len(),
+                        format_size(orphaned.orphaned_size)
+                    ));
+                    self.notify_scan(format!(
+                        "Old versions: {} files, {}",
+                        old_versions.total_files,
+                        format_size(old_versions.total_space)
+                    ));
+                    self.announce_results(tr_args(
+                        "badge_quick_scan",
+                        &[&orphaned.orphaned_mods.len(), &old_versions.total_files],
+                    ));
+                    self.scan_delta = None;
+                    self.orphaned_scope = None;
+                    self.old_version_scope = None;
+                    self.push_result_history(
+                        source,
+                        Some(orphaned.clone()),
+                        Some(old_versions.clone()),
+                    );
+                    self.orphaned_result = Some(orphaned);
+                    self.old_version_result = Some(old_versions);
+                    self.review_included.clear();
+                    self.keeper_overrides.clear();
+                    self.shown_rows.clear();
+                    self.is_loading = false;
+                    self.progress = None;
+                }
+                AsyncMessage::IdenticalCopiesFound(res) => {
+                    self.cancel_token = None;
+                    self.is_loading = false;
+                    self.progress = None;
+                    let Some(res) = res else {
+                        self.log(LogLevel::Warning, "Identical copy scan stopped.");
+                        continue;
+                    };
+                    let summary = format!(
+                        "Found {} identical copies ({})",
+                        res.total_files,
+                        format_size(res.total_space)
+                    );
+                    self.log(LogLevel::Info, &summary);
+                    self.notify_scan(format!(
+                        "Identical copies: {} files, {}",
+                        res.total_files,
+                        format_size(res.total_space)
+                    ));
+                    self.announce_results(tr_args("badge_identical", &[&res.total_files]));
+                    self.identical_result = Some(res);
+                    self.shown_rows.clear();
+                }
+                AsyncMessage::CrossGameCopiesFound(groups) => {
+                    self.is_loading = false;
+                    self.progress = None;
+                    let redundant: u64 = groups.iter().map(|g| g.redundant_size()).sum();
+                    self.log(
+                        LogLevel::Info,
+                        &format!(
+                            "Found {} mods in more than one game folder ({} in extra copies)",
+                            groups.len(),
+                            format_size(redundant)
+                        ),
+                    );
+                    self.announce_results(tr_args("badge_cross_game", &[&groups.len()]));
+                    self.cross_game_result = Some(groups);
+                    self.shown_rows.clear();
+                }
+                AsyncMessage::ReconciliationComplete(report) => {
+                    self.cancel_token = None;
+                    self.is_loading = false;
+                    self.progress = None;
+                    let Some(report) = report else {
+                        self.log(LogLevel::Warning, "Hash cross-check stopped.");
+                        continue;
+                    };
+                    for entry in &report.over_protected {
+                        self.log(
+                            LogLevel::Warning,
+                            &format!(
+                                "Kept but {}: {} (matched by {})",
+                                entry.disagreement.label(),
+                                entry.file.file_name,
+                                entry.reason.map_or("-", |r| r.label())
+                            ),
+                        );
+                    }
+                    for entry in &report.falsely_orphaned {
+                        if let Disagreement::RenamedArchive { archive } = &entry.disagreement {
+                            self.log(
+                                LogLevel::Warning,
+                                &format!(
+                                    "Orphaned but a modlist uses it: {} is {}",
+                                    entry.file.file_name, archive
+                                ),
+                            );
+                        }
+                    }
+                    self.log(
+                        LogLevel::Info,
+                        &format!(
+                            "Hash cross-check: {} disagreements ({} over-protected, {} falsely orphaned), {} agree, {} unchecked",
+                            report.disagreements(),
+                            report.over_protected.len(),
+                            report.falsely_orphaned.len(),
+                            report.agreed,
+                            report.unchecked
+                        ),
+                    );
+                }
+                AsyncMessage::ArchiveHealthChecked(report) => {
+                    self.cancel_token = None;
+                    self.is_loading = false;
+                    self.progress = None;
+                    let Some(report) = report else {
+                        self.log(LogLevel::Warning, "Archive health check stopped.");
+                        continue;
+                    };
+                    for file in &report.suspect {
+                        self.log(
+                            LogLevel::Warning,
+                            &format!(
+                                "Suspect archive: {} ({})",
+                                file.file_name,
+                                format_size(file.size)
+                            ),
+                        );
+                    }
+                    self.log(
+                        LogLevel::Info,
+                        &format!(
+                            "Archive health: {} suspect, {} ok, {} not checked (not zip)",
+                            report.suspect.len(),
+                            report.healthy,
+                            report.not_checked
+                        ),
+                    );
+                }
+                AsyncMessage::ManifestExported(path, count) => {
+                    self.log(
+                        LogLevel::Info,
+                        &format!(
+                            "Exported library manifest with {} files to {}",
+                            count,
+                            path.display()
+                        ),
+                    );
                     self.is_loading = false;
                     self.progress = None;
                 }
                 AsyncMessage::OldVersionScanComplete(res) => {
+                    self.missing_files.clear();
                     self.log(
                         LogLevel::Info,
                         &format!(
@@ -319,47 +2218,153 @@ impl WabbajackCleanerApp {
                             format_size(res.total_space)
                         ),
                     );
+                    self.notify_scan(format!(
+                        "Old versions: {} files, {}",
+                        res.total_files,
+                        format_size(res.total_space)
+                    ));
+                    let has_duplicates = !res.duplicates.is_empty();
+                    self.announce_results(tr_args("badge_old_versions", &[&res.total_files]));
+                    let folders = match &self.old_version_scope {
+                        Some(scope) => scope.folders.clone(),
+                        None => self.selected_game_folders(),
+                    };
+                    self.push_result_history(folder_names_label(&folders), None, Some(res.clone()));
                     self.old_version_result = Some(res);
+                    self.review_included.clear();
+                    self.keeper_overrides.clear();
+                    self.shown_rows.clear();
                     self.is_loading = false;
                     self.progress = None;
+                    if self.pending_delete_mode {
+                        if has_duplicates {
+                            self.modal = Modal::ReviewOldVersions;
+                        } else {
+                            self.pending_delete_mode = false;
+                            self.log(LogLevel::Info, "No old versions to clean.");
+                        }
+                    }
                 }
-                AsyncMessage::DeletionComplete(res) => {
-                    if let Some(ref path) = res.recycle_bin_path {
+                AsyncMessage::DeletionComplete(res) => self.finish_deletion(res),
+                AsyncMessage::RelocationComplete(dest, res) => self.finish_relocation(&dest, res),
+                AsyncMessage::CleanAllComplete(orphaned, old_versions) => {
+                    for (label, part) in [
+                        ("Orphaned mods", &orphaned),
+                        ("Old versions", &old_versions),
+                    ] {
                         self.log(
-                            LogLevel::Info,
+                            if part.errors.is_empty() {
+                                LogLevel::Info
+                            } else {
+                                LogLevel::Warning
+                            },
                             &format!(
-                                "Cleanup complete! {} files ({}) moved to '{}'. Verify your modlist in Wabbajack before permanently deleting this folder to free disk space.",
-                                res.deleted_count,
-                                format_size(res.space_freed),
-                                path.display()
+                                "{}: {} files ({}), {} error(s)",
+                                label,
+                                part.deleted_count,
+                                format_size(part.space_freed),
+                                part.errors.len()
                             ),
                         );
-                    } else {
+                    }
+                    let mut res = orphaned;
+                    res.merge(old_versions);
+                    self.finish_deletion(res);
+                }
+                AsyncMessage::Progress(s, prog) => {
+                    self.current_operation = s;
+                    self.progress = prog;
+                }
+                AsyncMessage::BackupsListed(backups) => {
+                    self.backup_selected = vec![false; backups.len()];
+                    self.backups = backups;
+                    self.is_loading = false;
+                    self.progress = None;
+                }
+                AsyncMessage::BackupsPurged(freed, errors) => {
+                    self.log(
+                        LogLevel::Info,
+                        &format!("Deleted backups, freed {}", format_size(freed)),
+                    );
+                    for e in &errors {
+                        self.log(LogLevel::Error, e);
+                    }
+                    self.list_backups();
+                }
+                AsyncMessage::BackupRestored(restored, errors) => {
+                    self.log(
+                        LogLevel::Info,
+                        &format!("Restored {} files from backup", restored),
+                    );
+                    for e in &errors {
+                        self.log(LogLevel::Error, e);
+                    }
+                    self.list_backups();
+                }
+                AsyncMessage::BackupVerified(name, report) => {
+                    self.is_loading = false;
+                    self.progress = None;
+                    for (file, gap) in &report.gaps {
                         self.log(
-                            LogLevel::Info,
-                            &format!(
-                                "Cleanup complete! {} files ({}) permanently deleted.",
-                                res.deleted_count,
-                                format_size(res.space_freed)
-                            ),
+                            LogLevel::Warning,
+                            &format!("Backup gap: {}: {}", file, gap.describe()),
                         );
                     }
-                    if !res.errors.is_empty() {
+                    let summary = format!(
+                        "Verified backup {}: {} files, {} read back",
+                        name,
+                        report.files,
+                        format_size(report.size)
+                    );
+                    if report.is_restorable() {
+                        self.log(LogLevel::Info, &format!("{}, restorable", summary));
+                    } else {
                         self.log(
                             LogLevel::Warning,
-                            &format!("{} error(s) occurred during cleanup.", res.errors.len()),
+                            &format!("{}, {} would not restore", summary, report.gaps.len()),
                         );
                     }
+                }
+                AsyncMessage::VersionFoldersListed(folders) => {
+                    self.version_folder_selected = folders.iter().map(|f| !f.is_newest).collect();
+                    self.version_folders = folders;
                     self.is_loading = false;
                     self.progress = None;
-                    self.run_analysis();
                 }
-                AsyncMessage::Progress(s, prog) => {
-                    self.current_operation = s;
-                    self.progress = prog;
+                AsyncMessage::VersionFoldersPurged(freed, errors) => {
+                    self.log(
+                        LogLevel::Info,
+                        &format!(
+                            "Deleted old Wabbajack versions, freed {}",
+                            format_size(freed)
+                        ),
+                    );
+                    for e in &errors {
+                        self.log(LogLevel::Error, e);
+                    }
+                    // Modlists may have moved into the newest version folder
+                    if let Some(dir) = self.wabbajack_dir.clone() {
+                        self.set_wabbajack_dir(dir);
+                    }
+                }
+                AsyncMessage::ArchivePreviewListed(path, listing) => {
+                    self.archive_previews.insert(path, Some(listing));
+                }
+                AsyncMessage::UpdateAvailable(release) => {
+                    self.log(
+                        LogLevel::Info,
+                        &format!("Update available: {} ({})", release.tag, release.url),
+                    );
+                    self.update_available = Some(release);
                 }
                 AsyncMessage::Error(e) => {
+                    self.cancel_token = None;
+                    // The failed scan may have replaced the scope of a result still shown
+                    self.orphaned_scope = None;
+                    self.old_version_scope = None;
+                    self.notify_done(&format!("Operation failed: {}", e));
                     self.log(LogLevel::Error, &format!("Error: {}", e));
+                    self.pending_delete_mode = false;
                     self.is_loading = false;
                     self.progress = None;
                 }
@@ -370,10 +2375,23 @@ impl WabbajackCleanerApp {
 
 impl eframe::App for WabbajackCleanerApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.window_minimized = ctx.input(|i| i.viewport().minimized.unwrap_or(false));
+        self.window_focused = ctx.input(|i| i.viewport().focused.unwrap_or(true));
         self.handle_messages();
+        self.run_queued_operation();
+        self.flush_notifications(ctx);
         if self.is_loading {
             ctx.request_repaint();
         }
+        if let Some(tray) = &mut self.tray {
+            tray.set_busy(self.is_loading);
+            if tray.take_clicked() {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+                ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+            }
+            // Keep polling tray clicks while idle
+            ctx.request_repaint_after(Duration::from_millis(250));
+        }
 
         // Header
         egui::TopBottomPanel::top("header")
@@ -396,22 +2414,187 @@ impl eframe::App for WabbajackCleanerApp {
                             .size(12.0)
                             .color(COLOR_TEXT_MUTED),
                     );
+                    self.render_results_badge(ui);
 
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        if ui.button("About").clicked() {
+                        if ui.button(tr("about")).clicked() {
                             self.modal = Modal::About;
                         }
+                        if ui
+                            .button(tr("report_issue"))
+                            .on_hover_text(tr("report_issue_hint"))
+                            .clicked()
+                        {
+                            self.modal = Modal::ReportIssue;
+                        }
+                        if ui
+                            .button(tr("diagnostics"))
+                            .on_hover_text(tr("diagnostics_hint"))
+                            .clicked()
+                        {
+                            self.modal = Modal::Diagnostics;
+                        }
+                        let mut density = self.settings.density;
+                        egui::ComboBox::from_id_salt("density")
+                            .selected_text(density_label(density))
+                            .width(100.0)
+                            .show_ui(ui, |ui| {
+                                for d in [UiDensity::Comfortable, UiDensity::Compact] {
+                                    ui.selectable_value(&mut density, d, density_label(d));
+                                }
+                            })
+                            .response
+                            .on_hover_text(tr("density_hint"));
+                        if density != self.settings.density {
+                            self.settings.density = density;
+                            apply_density(ui.ctx(), density);
+                            self.save_settings();
+                        }
+                        let mut language = None;
+                        egui::ComboBox::from_id_salt("language")
+                            .selected_text(i18n::current_language().name)
+                            .width(90.0)
+                            .show_ui(ui, |ui| {
+                                for lang in LANGUAGES {
+                                    if ui
+                                        .selectable_label(
+                                            lang.code == i18n::current_language().code,
+                                            lang.name,
+                                        )
+                                        .clicked()
+                                    {
+                                        language = Some(lang.code);
+                                    }
+                                }
+                            })
+                            .response
+                            .on_hover_text(tr("language"));
+                        if let Some(code) = language {
+                            i18n::set_language(code);
+                            self.settings.language = code.to_string();
+                            self.save_settings();
+                        }
                         ui.add_space(16.0);
-                        ui.checkbox(&mut self.move_to_recycle_bin, "Move to Recycle Bin")
-                            .on_hover_text("Moves deleted files to a timestamped WLC_RecycleBin folder in your downloads directory instead of permanently deleting them. This is NOT Windows' Recycle Bin — files go to WLC_RecycleBin\\<timestamp>\\ and can be manually deleted later.");
+                        let safe_mode = self.settings.safe_mode;
+                        let mut recycle = self.uses_recycle_bin();
+                        if ui
+                            .add_enabled(
+                                !safe_mode,
+                                egui::Checkbox::new(&mut recycle, tr("move_to_recycle_bin")),
+                            )
+                            .on_hover_text(tr("move_to_recycle_bin_hint"))
+                            .on_disabled_hover_text(tr("recycle_bin_locked_hint"))
+                            .changed()
+                        {
+                            self.move_to_recycle_bin = recycle;
+                        }
+                        if cfg!(target_os = "windows")
+                            && ui
+                                .checkbox(&mut self.settings.tray_icon, tr("tray_icon"))
+                                .on_hover_text(tr("tray_icon_hint"))
+                                .changed()
+                        {
+                            self.tray = if self.settings.tray_icon {
+                                Tray::new()
+                            } else {
+                                None
+                            };
+                            self.save_settings();
+                        }
+                        // Toasts are only implemented on Windows
+                        if cfg!(target_os = "windows")
+                            && ui
+                                .checkbox(
+                                    &mut self.settings.scan_notifications,
+                                    tr("scan_notifications"),
+                                )
+                                .on_hover_text(tr("scan_notifications_hint"))
+                                .changed()
+                        {
+                            self.save_settings();
+                        }
+                        if ui
+                            .add_enabled(
+                                self.uses_recycle_bin() && !self.settings.compress_backups,
+                                egui::Checkbox::new(
+                                    &mut self.settings.flat_recycle_bin,
+                                    tr("flat_recycle_bin"),
+                                ),
+                            )
+                            .on_hover_text(tr("flat_recycle_bin_hint"))
+                            .changed()
+                        {
+                            self.save_settings();
+                        }
+                        if ui
+                            .add_enabled(
+                                self.uses_recycle_bin(),
+                                egui::Checkbox::new(
+                                    &mut self.settings.compress_backups,
+                                    tr("compress_backups"),
+                                ),
+                            )
+                            .on_hover_text(tr("compress_backups_hint"))
+                            .changed()
+                        {
+                            self.save_settings();
+                        }
+                        if ui
+                            .checkbox(&mut self.settings.keep_meta, tr("keep_meta"))
+                            .on_hover_text(tr("keep_meta_hint"))
+                            .changed()
+                        {
+                            self.save_settings();
+                        }
+                        if ui
+                            .add_enabled(
+                                self.uses_recycle_bin(),
+                                egui::Checkbox::new(
+                                    &mut self.settings.copy_delete_moves,
+                                    tr("copy_delete_moves"),
+                                ),
+                            )
+                            .on_hover_text(tr("copy_delete_moves_hint"))
+                            .changed()
+                        {
+                            self.save_settings();
+                        }
+                        if ui
+                            .checkbox(
+                                &mut self.settings.check_for_updates,
+                                tr("check_for_updates"),
+                            )
+                            .on_hover_text(tr("check_for_updates_hint"))
+                            .changed()
+                        {
+                            if self.settings.check_for_updates {
+                                self.check_for_updates();
+                            }
+                            self.save_settings();
+                        }
+                        if ui
+                            .checkbox(&mut self.settings.safe_mode, tr("safe_mode"))
+                            .on_hover_text(tr("safe_mode_hint"))
+                            .changed()
+                        {
+                            self.save_settings();
+                        }
                     });
                 });
             });
 
-        // Log panel
-        egui::TopBottomPanel::bottom("log_panel")
-            .resizable(false)
-            .exact_height(120.0)
+        // Log panel; collapsed it keeps only the status row, under its own id so the
+        // expanded height is not overwritten
+        let collapsed = self.settings.log_collapsed;
+        let panel = if collapsed {
+            egui::TopBottomPanel::bottom("log_panel_collapsed").resizable(false)
+        } else {
+            egui::TopBottomPanel::bottom("log_panel")
+                .resizable(true)
+                .default_height(self.settings.log_panel_height)
+                .height_range(LOG_PANEL_MIN_HEIGHT..=LOG_PANEL_MAX_HEIGHT)
+        };
+        let log_panel = panel
             .frame(
                 egui::Frame::none()
                     .fill(COLOR_BG_HEADER)
@@ -427,53 +2610,99 @@ impl eframe::App for WabbajackCleanerApp {
                         );
                         if let Some((current, total)) = self.progress {
                             if total > 0 {
+                                let fraction = current as f32 / total as f32;
+                                // Byte counts from hashing are too long to show as-is
+                                let text = if total > 10_000 {
+                                    format!("{:.0}%", fraction * 100.0)
+                                } else {
+                                    format!("{}/{}", current, total)
+                                };
                                 ui.add(
-                                    egui::ProgressBar::new(current as f32 / total as f32)
+                                    egui::ProgressBar::new(fraction)
                                         .desired_width(120.0)
-                                        .text(format!("{}/{}", current, total)),
+                                        .text(text),
                                 );
                             }
                         }
+                        if let Some(cancel) = &self.cancel_token {
+                            let stopping = cancel.is_cancelled();
+                            if ui
+                                .add_enabled(!stopping, egui::Button::new(tr("stop")).small())
+                                .on_hover_text(tr("stop_hint"))
+                                .clicked()
+                            {
+                                cancel.cancel();
+                                self.current_operation = "Stopping...".to_string();
+                            }
+                        }
                     } else {
-                        ui.label(RichText::new("Ready").color(COLOR_SUCCESS));
+                        ui.label(RichText::new(tr("ready")).color(COLOR_SUCCESS));
                     }
 
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        if ui.small_button("Copy Log").clicked() {
+                        let toggle = if collapsed {
+                            tr("show_log")
+                        } else {
+                            tr("hide_log")
+                        };
+                        if ui.small_button(toggle).clicked() {
+                            self.settings.log_collapsed = !collapsed;
+                            self.save_settings();
+                        }
+                        if ui
+                            .small_button(tr("copy_log"))
+                            .on_hover_text(tr("copy_log_hint"))
+                            .clicked()
+                        {
                             let log_text: String = self
-                                .log_messages
-                                .iter()
+                                .visible_log()
                                 .map(|(msg, _)| msg.as_str())
                                 .collect::<Vec<_>>()
                                 .join("\n");
                             ui.ctx().copy_text(log_text);
                         }
-                        if ui.small_button("Clear Log").clicked() {
+                        if ui.small_button(tr("clear_log")).clicked() {
                             self.log_messages.clear();
                         }
+                        ui.add_space(8.0);
+                        self.render_log_filter(ui);
                     });
                 });
+                if collapsed {
+                    return;
+                }
                 ui.separator();
                 egui::ScrollArea::vertical()
                     .stick_to_bottom(true)
                     .show(ui, |ui| {
                         ui.set_width(ui.available_width());
-                        for (msg, level) in &self.log_messages {
-                            let color = match level {
-                                LogLevel::Info => COLOR_TEXT_SECONDARY,
-                                LogLevel::Warning => COLOR_WARNING,
-                                LogLevel::Error => COLOR_DANGER,
-                            };
-                            ui.label(RichText::new(msg).monospace().size(11.0).color(color));
+                        for (msg, level) in self.visible_log() {
+                            ui.label(
+                                RichText::new(msg)
+                                    .monospace()
+                                    .size(11.0)
+                                    .color(level.color()),
+                            );
                         }
                     });
             });
 
+        // Remember a resized log panel once the drag ends
+        let height = log_panel.response.rect.height();
+        if !collapsed
+            && (height - self.settings.log_panel_height).abs() >= 1.0
+            && !ctx.input(|i| i.pointer.any_down())
+        {
+            self.settings.log_panel_height = height;
+            self.save_settings();
+        }
+
         // Main content
         egui::CentralPanel::default()
             .frame(egui::Frame::none().fill(COLOR_BG_MAIN).inner_margin(16.0))
             .show(ctx, |ui| {
                 egui::ScrollArea::vertical().show(ui, |ui| {
+                    self.render_update_banner(ui);
                     self.render_paths_section(ui);
                     ui.add_space(12.0);
                     self.render_modlist_section(ui);
@@ -489,6 +2718,36 @@ impl eframe::App for WabbajackCleanerApp {
 }
 
 impl WabbajackCleanerApp {
+    fn render_update_banner(&mut self, ui: &mut egui::Ui) {
+        let Some(release) = &self.update_available else {
+            return;
+        };
+        let mut dismiss = false;
+        egui::Frame::none()
+            .fill(COLOR_BG_CARD)
+            .rounding(Rounding::same(8.0))
+            .inner_margin(8.0)
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                ui.horizontal(|ui| {
+                    ui.label(
+                        RichText::new(tr_args("update_available", &[&release.tag]))
+                            .color(COLOR_ACCENT),
+                    );
+                    ui.hyperlink_to(tr("view_release"), &release.url);
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.small_button("✕").on_hover_text(tr("dismiss")).clicked() {
+                            dismiss = true;
+                        }
+                    });
+                });
+            });
+        ui.add_space(12.0);
+        if dismiss {
+            self.update_available = None;
+        }
+    }
+
     fn section_frame(ui: &mut egui::Ui, title: &str, add_contents: impl FnOnce(&mut egui::Ui)) {
         egui::Frame::none()
             .fill(COLOR_BG_CARD)
@@ -507,50 +2766,148 @@ impl WabbajackCleanerApp {
             });
     }
 
+    /// Dropdown of recently used folders; returns the picked one
+    fn recent_folders_menu(ui: &mut egui::Ui, recent: &[PathBuf]) -> Option<PathBuf> {
+        let mut picked = None;
+        ui.add_enabled_ui(!recent.is_empty(), |ui| {
+            ui.menu_button(tr("recent"), |ui| {
+                for path in recent {
+                    if ui.button(path.display().to_string()).clicked() {
+                        picked = Some(path.clone());
+                        ui.close_menu();
+                    }
+                }
+            })
+            .response
+            .on_hover_text(tr("recent_hint"));
+        });
+        picked
+    }
+
     fn render_paths_section(&mut self, ui: &mut egui::Ui) {
-        Self::section_frame(ui, "Step 1: Select Folders", |ui| {
+        let mut recent_wabbajack = None;
+        let mut recent_downloads = None;
+        let mut remove_downloads = None;
+        let mut rescan_downloads = false;
+        let mut chart_game = None;
+        let mut stats_sort = self.stats_sort;
+
+        Self::section_frame(ui, tr("step1_title"), |ui| {
             ui.columns(2, |cols| {
                 // Wabbajack
-                cols[0].label(RichText::new("Wabbajack Installation").color(COLOR_TEXT_PRIMARY));
+                cols[0]
+                    .label(RichText::new(tr("wabbajack_installation")).color(COLOR_TEXT_PRIMARY));
                 cols[0].label(
-                    RichText::new("Folder containing Wabbajack.exe")
+                    RichText::new(tr("wabbajack_installation_hint"))
                         .size(11.0)
                         .color(COLOR_TEXT_MUTED),
                 );
                 cols[0].add_space(4.0);
                 cols[0].horizontal(|ui| {
-                    if ui.button("Browse...").clicked() {
+                    if ui.button(tr("browse")).clicked() {
                         self.select_wabbajack_dir();
                     }
+                    recent_wabbajack =
+                        Self::recent_folders_menu(ui, &self.settings.recent_wabbajack_dirs);
                     if let Some(p) = &self.wabbajack_dir {
                         ui.label(
                             RichText::new(p.file_name().unwrap_or_default().to_string_lossy())
                                 .color(COLOR_SUCCESS),
                         );
                     } else {
-                        ui.label(RichText::new("Not selected").color(COLOR_DANGER));
+                        ui.label(RichText::new(tr("not_selected")).color(COLOR_DANGER));
+                    }
+                });
+
+                // Downloads
+                cols[1].label(RichText::new(tr("downloads_folder")).color(COLOR_TEXT_PRIMARY));
+                cols[1].label(
+                    RichText::new(tr("downloads_folder_hint"))
+                        .size(11.0)
+                        .color(COLOR_TEXT_MUTED),
+                );
+                cols[1].add_space(4.0);
+                cols[1].horizontal(|ui| {
+                    if ui.button(tr("browse")).clicked() {
+                        self.select_downloads_dir();
+                    }
+                    recent_downloads =
+                        Self::recent_folders_menu(ui, &self.settings.recent_downloads_dirs);
+                    if ui
+                        .small_button(tr("add_folder"))
+                        .on_hover_text(tr("add_folder_hint"))
+                        .clicked()
+                    {
+                        self.add_downloads_dir();
+                    }
+                    if ui
+                        .small_button(tr("import_mo2"))
+                        .on_hover_text(tr("import_mo2_hint"))
+                        .clicked()
+                    {
+                        self.import_mo2_downloads();
+                    }
+                    if let [p] = self.downloads_dirs.as_slice() {
+                        ui.label(
+                            RichText::new(p.file_name().unwrap_or_default().to_string_lossy())
+                                .color(COLOR_SUCCESS),
+                        )
+                        .on_hover_text(p.display().to_string());
+                    } else if self.downloads_dirs.is_empty() {
+                        ui.label(RichText::new(tr("not_selected")).color(COLOR_DANGER));
+                    }
+                });
+                if self.downloads_dirs.len() > 1 {
+                    for (i, p) in self.downloads_dirs.iter().enumerate() {
+                        cols[1].horizontal(|ui| {
+                            if ui
+                                .small_button("x")
+                                .on_hover_text(tr("remove_folder"))
+                                .clicked()
+                            {
+                                remove_downloads = Some(i);
+                            }
+                            ui.label(RichText::new(p.display().to_string()).color(COLOR_SUCCESS));
+                        });
+                    }
+                }
+                cols[1].horizontal(|ui| {
+                    if ui
+                        .small_button(tr("import_id_mapping"))
+                        .on_hover_text(tr("import_id_mapping_hint"))
+                        .clicked()
+                    {
+                        self.import_id_mapping();
+                    }
+                    if ui
+                        .add_enabled(
+                            self.is_ready() && !self.is_loading,
+                            egui::Button::new(tr("export_manifest")).small(),
+                        )
+                        .on_hover_text(tr("export_manifest_hint"))
+                        .clicked()
+                    {
+                        self.export_library_manifest();
                     }
                 });
-
-                // Downloads
-                cols[1].label(RichText::new("Downloads Folder").color(COLOR_TEXT_PRIMARY));
-                cols[1].label(
-                    RichText::new("Wabbajack mod downloads location")
-                        .size(11.0)
-                        .color(COLOR_TEXT_MUTED),
-                );
-                cols[1].add_space(4.0);
                 cols[1].horizontal(|ui| {
-                    if ui.button("Browse...").clicked() {
-                        self.select_downloads_dir();
-                    }
-                    if let Some(p) = &self.downloads_dir {
-                        ui.label(
-                            RichText::new(p.file_name().unwrap_or_default().to_string_lossy())
-                                .color(COLOR_SUCCESS),
-                        );
-                    } else {
-                        ui.label(RichText::new("Not selected").color(COLOR_DANGER));
+                    ui.label(
+                        RichText::new(tr("excluded_folders"))
+                            .size(11.0)
+                            .color(COLOR_TEXT_SECONDARY),
+                    )
+                    .on_hover_text(tr("excluded_folders_hint"));
+                    let response = ui.add(
+                        egui::TextEdit::singleline(&mut self.excluded_folders_text)
+                            .desired_width(180.0),
+                    );
+                    if response.lost_focus() {
+                        let excluded = parse_pattern_list(&self.excluded_folders_text);
+                        self.excluded_folders_text = excluded.join(", ");
+                        if excluded != self.settings.excluded_folders {
+                            self.settings.excluded_folders = excluded;
+                            rescan_downloads = true;
+                        }
                     }
                 });
             });
@@ -561,7 +2918,7 @@ impl WabbajackCleanerApp {
                 ui.add_space(4.0);
                 ui.horizontal(|ui| {
                     ui.label(
-                        RichText::new(format!("{} files", stats.total_files))
+                        RichText::new(tr_args("n_files", &[&stats.total_files]))
                             .size(12.0)
                             .color(COLOR_TEXT_SECONDARY),
                     );
@@ -573,39 +2930,332 @@ impl WabbajackCleanerApp {
                     );
                     ui.label(RichText::new(" | ").color(COLOR_TEXT_MUTED));
                     ui.label(
-                        RichText::new(format!("{} game folders", self.game_folders.len()))
+                        RichText::new(tr_args("n_game_folders", &[&self.game_folders.len()]))
                             .size(12.0)
                             .color(COLOR_TEXT_SECONDARY),
                     );
                 });
+                if stats.old_version_files > 0 {
+                    ui.label(
+                        RichText::new(tr_args(
+                            "old_version_estimate",
+                            &[
+                                &format_size(stats.old_version_space),
+                                &stats.old_version_files,
+                            ],
+                        ))
+                        .size(12.0)
+                        .color(COLOR_WARNING),
+                    )
+                    .on_hover_text(tr("old_version_estimate_hint"));
+                }
+                if stats.by_game.len() > 1 {
+                    ui.add_space(4.0);
+                    chart_game = Self::game_size_chart(ui, stats, &mut stats_sort);
+                } else if let Some(game) = stats.by_game.first() {
+                    ui.label(
+                        RichText::new(Self::game_details(game))
+                            .size(11.0)
+                            .color(COLOR_TEXT_MUTED),
+                    );
+                }
+                if stats.activity.len() > 1 {
+                    egui::CollapsingHeader::new(
+                        RichText::new(tr("download_activity"))
+                            .size(11.0)
+                            .color(COLOR_TEXT_SECONDARY),
+                    )
+                    .id_salt("download_activity")
+                    .show(ui, |ui| Self::activity_strip(ui, &stats.activity));
+                }
+            }
+        });
+
+        self.stats_sort = stats_sort;
+        if let Some(name) = chart_game {
+            self.preselect_game_folder(&name);
+        }
+
+        if let Some(path) = recent_wabbajack {
+            self.set_wabbajack_dir(path);
+        }
+        if let Some(path) = recent_downloads {
+            self.set_downloads_dirs(vec![path]);
+        }
+        if let Some(i) = remove_downloads {
+            self.remove_downloads_dir(i);
+        } else if rescan_downloads {
+            self.set_downloads_dirs(self.downloads_dirs.clone());
+        }
+    }
+
+    /// Horizontal bars of download size per game; returns the game whose bar was clicked
+    fn game_size_chart(
+        ui: &mut egui::Ui,
+        stats: &LibraryStats,
+        sort: &mut StatsSort,
+    ) -> Option<String> {
+        let max_size = stats
+            .by_game
+            .iter()
+            .map(|g| g.size)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let mut clicked = None;
+
+        ui.horizontal(|ui| {
+            ui.label(
+                RichText::new(tr("sort_by"))
+                    .size(11.0)
+                    .color(COLOR_TEXT_MUTED),
+            );
+            ui.selectable_value(sort, StatsSort::Name, tr("sort_name"));
+            ui.selectable_value(sort, StatsSort::Size, tr("sort_size"));
+            ui.selectable_value(sort, StatsSort::LastModified, tr("sort_last_modified"))
+                .on_hover_text(tr("sort_last_modified_hint"));
+        });
+        let mut games: Vec<&GameStats> = stats.by_game.iter().collect();
+        match sort {
+            StatsSort::Name => {}
+            StatsSort::Size => games.sort_by_key(|g| std::cmp::Reverse(g.size)),
+            StatsSort::LastModified => games.sort_by_key(|g| g.newest_modified),
+        }
+
+        for game in games {
+            let (name, files, size) = (&game.name, &game.files, &game.size);
+            ui.horizontal(|ui| {
+                ui.add_sized(
+                    [180.0, 14.0],
+                    egui::Label::new(RichText::new(name).size(11.0).color(COLOR_TEXT_SECONDARY))
+                        .truncate(),
+                );
+                let width = (ui.available_width() - 220.0).max(60.0);
+                let (rect, response) =
+                    ui.allocate_exact_size(egui::vec2(width, 12.0), egui::Sense::click());
+                let fill = if response.hovered() {
+                    COLOR_ACCENT
+                } else {
+                    COLOR_ACCENT.gamma_multiply(0.7)
+                };
+                let mut bar = rect;
+                bar.set_width((rect.width() * (*size as f32 / max_size as f32)).max(2.0));
+                ui.painter().rect_filled(rect, 3.0, COLOR_BG_HEADER);
+                ui.painter().rect_filled(bar, 3.0, fill);
+
+                let share = *size as f64 / stats.total_size.max(1) as f64 * 100.0;
+                ui.label(
+                    RichText::new(tr_args(
+                        "chart_bar_label",
+                        &[&format_size(*size), &format!("{:.0}", share), files],
+                    ))
+                    .size(11.0)
+                    .color(COLOR_TEXT_MUTED),
+                );
+                if let (StatsSort::LastModified, Some(modified)) = (*sort, game.newest_modified) {
+                    ui.label(
+                        RichText::new(format_age(modified))
+                            .size(11.0)
+                            .color(COLOR_WARNING),
+                    );
+                }
+
+                let hint = format!("{}\n{}", Self::game_details(game), tr("chart_bar_hint"));
+                if response.on_hover_text(hint).clicked() {
+                    clicked = Some(name.clone());
+                }
+            });
+        }
+
+        clicked
+    }
+
+    /// One bar per month, scaled to the busiest month; hovering shows that month's numbers
+    fn activity_strip(ui: &mut egui::Ui, activity: &[MonthActivity]) {
+        let busiest = activity.iter().map(|m| m.files).max().unwrap_or(0).max(1);
+        let (rect, response) =
+            ui.allocate_exact_size(egui::vec2(ui.available_width(), 36.0), egui::Sense::hover());
+        ui.painter().rect_filled(rect, 3.0, COLOR_BG_HEADER);
+        let slot = rect.width() / activity.len() as f32;
+        let hovered = response
+            .hover_pos()
+            .map(|pos| (((pos.x - rect.left()) / slot) as usize).min(activity.len() - 1));
+        for (i, month) in activity.iter().enumerate() {
+            if month.files == 0 {
+                continue;
+            }
+            let height = (rect.height() * month.files as f32 / busiest as f32).max(2.0);
+            let left = rect.left() + i as f32 * slot;
+            let bar = egui::Rect::from_min_max(
+                egui::pos2(left + slot * 0.1, rect.bottom() - height),
+                egui::pos2(left + slot * 0.9, rect.bottom()),
+            );
+            let fill = if hovered == Some(i) {
+                COLOR_ACCENT
+            } else {
+                COLOR_ACCENT.gamma_multiply(0.7)
+            };
+            ui.painter().rect_filled(bar, 1.0, fill);
+        }
+
+        let month_label = |m: &MonthActivity| format!("{}-{:02}", m.year, m.month);
+        ui.horizontal(|ui| {
+            let (first, last) = (&activity[0], &activity[activity.len() - 1]);
+            ui.label(
+                RichText::new(month_label(first))
+                    .size(10.0)
+                    .color(COLOR_TEXT_MUTED),
+            );
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.label(
+                    RichText::new(month_label(last))
+                        .size(10.0)
+                        .color(COLOR_TEXT_MUTED),
+                );
+            });
+        });
+        if let Some(month) = hovered.map(|i| &activity[i]) {
+            response.on_hover_text(tr_args(
+                "activity_month",
+                &[&month_label(month), &month.files, &format_size(month.size)],
+            ));
+        }
+    }
+
+    /// Average and largest download of a game folder, and when it last changed
+    fn game_details(game: &GameStats) -> String {
+        let details = tr_args(
+            "game_details",
+            &[
+                &format_size(game.average_size()),
+                &game.largest_name,
+                &format_size(game.largest_size),
+            ],
+        );
+        match game.newest_modified {
+            Some(modified) => format!(
+                "{} | {}",
+                details,
+                tr_args("last_modified", &[&format_age(modified)])
+            ),
+            None => details,
+        }
+    }
+
+    /// "All <game>" buttons toggling every modlist of one game, shown once modlists cover
+    /// more than one game
+    fn render_game_quick_select(&mut self, ui: &mut egui::Ui) {
+        let games: BTreeSet<String> = self
+            .modlists
+            .iter()
+            .filter_map(|ml| ml.game.clone())
+            .collect();
+        if games.len() < 2 {
+            return;
+        }
+        ui.horizontal_wrapped(|ui| {
+            ui.label(
+                RichText::new(tr("select_by_game"))
+                    .size(11.0)
+                    .color(COLOR_TEXT_MUTED),
+            );
+            for game in &games {
+                let of_game: Vec<usize> = self
+                    .modlists
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, ml)| ml.game.as_ref() == Some(game))
+                    .map(|(i, _)| i)
+                    .collect();
+                let all_selected = of_game
+                    .iter()
+                    .all(|&i| self.modlist_selected.get(i).copied().unwrap_or(false));
+                let button = ui
+                    .small_button(tr_args("select_all_of_game", &[&game_display_name(game)]))
+                    .on_hover_text(tr_args("select_all_of_game_hint", &[&of_game.len()]));
+                if button.clicked() {
+                    for i in of_game {
+                        if let Some(sel) = self.modlist_selected.get_mut(i) {
+                            *sel = !all_selected;
+                        }
+                    }
+                }
             }
         });
     }
 
+    /// Select only the named game folder for the next old-version scan
+    fn preselect_game_folder(&mut self, name: &str) {
+        for (folder, selected) in self.game_folders.iter().zip(&mut self.game_folder_selected) {
+            *selected = folder
+                .file_name()
+                .is_some_and(|n| n.to_string_lossy() == name);
+        }
+        self.log(
+            LogLevel::Info,
+            &format!("Selected '{}' for the old-version scan.", name),
+        );
+    }
+
     fn render_modlist_section(&mut self, ui: &mut egui::Ui) {
-        Self::section_frame(ui, "Step 2: Select Modlists to Protect", |ui| {
+        let mut rescan = false;
+        Self::section_frame(ui, tr("step2_title"), |ui| {
             if self.modlists.is_empty() {
-                ui.label(RichText::new("Select Wabbajack folder first.").color(COLOR_TEXT_MUTED));
+                ui.label(RichText::new(tr("select_wabbajack_first")).color(COLOR_TEXT_MUTED));
             } else {
                 ui.horizontal(|ui| {
                     ui.label(
-                        RichText::new(format!(
-                            "{}/{} selected",
-                            self.selected_modlist_count(),
-                            self.modlists.len()
+                        RichText::new(tr_args(
+                            "n_of_m_selected",
+                            &[&self.selected_modlist_count(), &self.modlists.len()],
                         ))
                         .size(12.0)
                         .color(COLOR_TEXT_SECONDARY),
                     );
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        if ui.small_button("None").clicked() {
+                        if ui.small_button(tr("none")).clicked() {
                             self.modlist_selected.iter_mut().for_each(|x| *x = false);
                         }
-                        if ui.small_button("All").clicked() {
-                            self.modlist_selected.iter_mut().for_each(|x| *x = true);
+                        if ui
+                            .small_button(tr("all"))
+                            .on_hover_text(tr("select_all_modlists_hint"))
+                            .clicked()
+                        {
+                            self.modlist_selected = default_modlist_selection(&self.modlists);
                         }
+                        ui.add_space(8.0);
+                        rescan = ui
+                            .add_enabled(
+                                !self.is_loading,
+                                egui::Checkbox::new(
+                                    &mut self.settings.show_all_modlist_versions,
+                                    RichText::new(tr("show_all_modlist_versions")).size(11.0),
+                                ),
+                            )
+                            .on_hover_text(tr("show_all_modlist_versions_hint"))
+                            .changed();
                     });
                 });
+                self.render_game_quick_select(ui);
+                let unprotected = self
+                    .modlists
+                    .iter()
+                    .filter(|ml| ml.has_no_mod_ids())
+                    .count();
+                if unprotected > 0 {
+                    ui.add_space(4.0);
+                    egui::Frame::none()
+                        .fill(COLOR_WARNING.gamma_multiply(0.15))
+                        .rounding(Rounding::same(6.0))
+                        .inner_margin(6.0)
+                        .show(ui, |ui| {
+                            ui.set_width(ui.available_width());
+                            ui.label(
+                                RichText::new(tr_args("modlists_without_mod_ids", &[&unprotected]))
+                                    .color(COLOR_WARNING),
+                            );
+                        });
+                }
                 ui.add_space(4.0);
                 egui::ScrollArea::vertical()
                     .max_height(100.0)
@@ -623,14 +3273,43 @@ impl WabbajackCleanerApp {
                             } else {
                                 COLOR_TEXT_MUTED
                             };
-                            if ui
-                                .checkbox(
-                                    &mut new_checked,
-                                    RichText::new(format!("{} ({} mods)", ml.name, ml.mod_count))
-                                        .color(color),
-                                )
-                                .changed()
-                            {
+                            let changed = ui
+                                .horizontal(|ui| {
+                                    let changed = ui
+                                        .checkbox(
+                                            &mut new_checked,
+                                            RichText::new(tr_args(
+                                                "modlist_entry",
+                                                &[&ml.name, &ml.mod_count],
+                                            ))
+                                            .color(color),
+                                        )
+                                        .changed();
+                                    if ml.has_no_mod_ids() {
+                                        ui.label(
+                                            RichText::new(tr("no_mod_ids"))
+                                                .size(11.0)
+                                                .color(COLOR_WARNING),
+                                        )
+                                        .on_hover_text(tr("no_mod_ids_hint"));
+                                    }
+                                    if let Some(size) =
+                                        self.modlist_exclusive_sizes.get(&ml.file_path)
+                                    {
+                                        ui.label(
+                                            RichText::new(tr_args(
+                                                "modlist_exclusive",
+                                                &[&format_size(*size)],
+                                            ))
+                                            .size(11.0)
+                                            .color(COLOR_TEXT_MUTED),
+                                        )
+                                        .on_hover_text(tr("modlist_exclusive_hint"));
+                                    }
+                                    changed
+                                })
+                                .inner;
+                            if changed {
                                 if let Some(sel) = self.modlist_selected.get_mut(i) {
                                     *sel = new_checked;
                                 }
@@ -639,172 +3318,1278 @@ impl WabbajackCleanerApp {
                     });
             }
         });
+        if rescan {
+            self.save_settings();
+            if let Some(dir) = self.wabbajack_dir.clone() {
+                self.set_wabbajack_dir(dir);
+            }
+        }
     }
 
     fn render_actions_section(&mut self, ui: &mut egui::Ui) {
-        Self::section_frame(ui, "Step 3: Cleanup Actions", |ui| {
+        Self::section_frame(ui, tr("step3_title"), |ui| {
             let ready = self.is_ready() && !self.is_loading;
+            // With no modlist every file would count as orphaned
+            let selected_modlists = self.selected_modlist_count();
+            let orphan_ready = ready && selected_modlists > 0;
+            let modlist_hint = |response: egui::Response| {
+                if selected_modlists == 0 {
+                    response.on_disabled_hover_text(tr("select_one_modlist"))
+                } else {
+                    response
+                }
+            };
 
             ui.columns(2, |cols| {
                 // Orphaned Mods
                 cols[0].label(
-                    RichText::new("Orphaned Mods")
+                    RichText::new(tr("orphaned_mods"))
                         .strong()
                         .color(COLOR_TEXT_PRIMARY),
                 );
                 cols[0].label(
-                    RichText::new("Mods not used by selected modlists")
+                    RichText::new(tr("orphaned_mods_hint"))
                         .size(11.0)
                         .color(COLOR_TEXT_MUTED),
                 );
                 cols[0].add_space(4.0);
                 cols[0].horizontal(|ui| {
-                    if ui
-                        .add_enabled(ready, egui::Button::new("Analyze"))
+                    if modlist_hint(ui.add_enabled(orphan_ready, egui::Button::new(tr("analyze"))))
                         .clicked()
                     {
-                        self.run_orphaned_scan(false);
+                        self.run_orphaned_scan(false, false);
                     }
-                    if ui
-                        .add_enabled(
-                            ready,
-                            egui::Button::new(RichText::new("Clean").color(COLOR_TEXT_PRIMARY))
+                    if modlist_hint(
+                        ui.add_enabled(orphan_ready, egui::Button::new(tr("force_rescan"))),
+                    )
+                    .on_hover_text(tr("force_rescan_hint"))
+                    .clicked()
+                    {
+                        self.run_orphaned_scan(false, true);
+                    }
+                    if modlist_hint(
+                        ui.add_enabled(
+                            orphan_ready,
+                            egui::Button::new(RichText::new(tr("clean")).color(COLOR_TEXT_PRIMARY))
                                 .fill(COLOR_DANGER),
-                        )
-                        .clicked()
+                        ),
+                    )
+                    .clicked()
                     {
-                        if self.move_to_recycle_bin {
-                            self.run_orphaned_scan(true);
+                        if self.uses_recycle_bin() {
+                            self.clean_orphaned();
                         } else {
                             self.modal = Modal::ConfirmDelete(DeleteAction::Orphaned);
                         }
                     }
+                    let can_relocate = !self.is_loading
+                        && self.orphaned_result_fresh()
+                        && self
+                            .orphaned_result
+                            .as_ref()
+                            .is_some_and(|r| !r.orphaned_mods.is_empty());
+                    if ui
+                        .add_enabled(can_relocate, egui::Button::new(tr("relocate")))
+                        .on_hover_text(tr("relocate_hint"))
+                        .on_disabled_hover_text(tr("relocate_disabled_hint"))
+                        .clicked()
+                    {
+                        self.relocate_orphaned();
+                    }
+                    if ui
+                        .add_enabled(can_relocate, egui::Button::new(tr("hash_cross_check")))
+                        .on_hover_text(tr("hash_cross_check_hint"))
+                        .clicked()
+                    {
+                        self.run_hash_cross_check();
+                    }
+                });
+                if !self.modlists.is_empty() {
+                    let (scope, color) = if selected_modlists == 0 {
+                        (tr("select_one_modlist").to_string(), COLOR_WARNING)
+                    } else {
+                        (
+                            tr_args("orphan_scope", &[&selected_modlists]),
+                            COLOR_TEXT_MUTED,
+                        )
+                    };
+                    cols[0].label(RichText::new(scope).size(11.0).color(color));
+                }
+                if !self.game_folders.is_empty() {
+                    let chosen = self.orphan_folder_selected.iter().filter(|&&x| x).count();
+                    let mut changed = false;
+                    egui::CollapsingHeader::new(
+                        RichText::new(tr_args(
+                            "game_folders_count",
+                            &[&chosen, &self.game_folders.len()],
+                        ))
+                        .size(11.0)
+                        .color(COLOR_TEXT_SECONDARY),
+                    )
+                    .id_salt("orphan_folders")
+                    .show(&mut cols[0], |ui| {
+                        ui.horizontal(|ui| {
+                            if ui.small_button(tr("all")).clicked() {
+                                self.orphan_folder_selected
+                                    .iter_mut()
+                                    .for_each(|x| *x = true);
+                                changed = true;
+                            }
+                            if ui.small_button(tr("none")).clicked() {
+                                self.orphan_folder_selected
+                                    .iter_mut()
+                                    .for_each(|x| *x = false);
+                                changed = true;
+                            }
+                        });
+                        egui::ScrollArea::vertical()
+                            .id_salt("orphan_folders_scroll")
+                            .max_height(120.0)
+                            .show(ui, |ui| {
+                                for (folder, selected) in self
+                                    .game_folders
+                                    .iter()
+                                    .zip(&mut self.orphan_folder_selected)
+                                {
+                                    let name =
+                                        folder.file_name().unwrap_or_default().to_string_lossy();
+                                    changed |= ui.checkbox(selected, &*name).changed();
+                                }
+                            });
+                    });
+                    // Keep the library stats in line with the folders being indexed
+                    if changed {
+                        self.run_analysis();
+                    }
+                }
+                if cols[0]
+                    .checkbox(
+                        &mut self.settings.strict_orphan_matching,
+                        RichText::new(tr("strict_orphan_matching")).size(11.0),
+                    )
+                    .on_hover_text(tr("strict_orphan_matching_hint"))
+                    .changed()
+                {
+                    self.save_settings();
+                }
+                if cols[0]
+                    .checkbox(
+                        &mut self.settings.file_id_only_names,
+                        RichText::new(tr("file_id_only_names")).size(11.0),
+                    )
+                    .on_hover_text(tr("file_id_only_names_hint"))
+                    .changed()
+                {
+                    self.save_settings();
+                }
+                cols[0].horizontal(|ui| {
+                    ui.label(
+                        RichText::new(tr("protected_extensions"))
+                            .size(11.0)
+                            .color(COLOR_TEXT_SECONDARY),
+                    )
+                    .on_hover_text(tr("protected_extensions_hint"));
+                    let response = ui.add(
+                        egui::TextEdit::singleline(&mut self.protected_extensions_text)
+                            .desired_width(120.0),
+                    );
+                    if response.lost_focus() {
+                        self.settings.protected_extensions =
+                            parse_extension_list(&self.protected_extensions_text);
+                        self.protected_extensions_text =
+                            self.settings.protected_extensions.join(", ");
+                        self.save_settings();
+                    }
+                });
+                cols[0].horizontal(|ui| {
+                    ui.label(
+                        RichText::new(tr("mo2_installed_mods"))
+                            .size(11.0)
+                            .color(COLOR_TEXT_SECONDARY),
+                    )
+                    .on_hover_text(tr("mo2_installed_mods_hint"));
+                    if ui.small_button(tr("browse")).clicked() {
+                        self.select_mo2_mods_dir();
+                    }
+                    if let Some(dir) = self.settings.mo2_mods_dir.clone() {
+                        ui.label(
+                            RichText::new(dir.display().to_string())
+                                .size(11.0)
+                                .color(COLOR_SUCCESS),
+                        );
+                        if ui
+                            .small_button("x")
+                            .on_hover_text(tr("mo2_installed_mods_clear"))
+                            .clicked()
+                        {
+                            self.settings.mo2_mods_dir = None;
+                            self.save_settings();
+                        }
+                    } else {
+                        ui.label(RichText::new(tr("off")).size(11.0).color(COLOR_TEXT_MUTED));
+                    }
+                });
+                cols[0].horizontal(|ui| {
+                    ui.label(
+                        RichText::new(tr("ignore_newer_than"))
+                            .size(11.0)
+                            .color(COLOR_TEXT_SECONDARY),
+                    )
+                    .on_hover_text(tr("ignore_newer_than_hint"));
+                    let days = self.settings.ignore_newer_than_days;
+                    let response = ui.add(
+                        egui::DragValue::new(&mut self.settings.ignore_newer_than_days)
+                            .range(0..=365)
+                            .custom_formatter(|n, _| {
+                                if n == 0.0 {
+                                    tr("off").to_string()
+                                } else {
+                                    tr_args("n_days", &[&n])
+                                }
+                            }),
+                    );
+                    if (response.drag_stopped() || response.lost_focus())
+                        && self.settings.ignore_newer_than_days != days
+                    {
+                        self.save_settings();
+                    }
+                });
+                cols[0].horizontal(|ui| {
+                    ui.label(
+                        RichText::new(tr("min_mod_size"))
+                            .size(11.0)
+                            .color(COLOR_TEXT_SECONDARY),
+                    )
+                    .on_hover_text(tr("min_mod_size_hint"));
+                    let kb = self.settings.min_mod_size_kb;
+                    let response = ui.add(
+                        egui::DragValue::new(&mut self.settings.min_mod_size_kb)
+                            .range(0..=102_400)
+                            .custom_formatter(|n, _| {
+                                if n == 0.0 {
+                                    tr("off").to_string()
+                                } else {
+                                    format_size(n as u64 * 1024)
+                                }
+                            }),
+                    );
+                    if (response.drag_stopped() || response.lost_focus())
+                        && self.settings.min_mod_size_kb != kb
+                    {
+                        self.save_settings();
+                    }
                 });
 
                 // Old Versions
                 cols[1].label(
-                    RichText::new("Old Versions")
+                    RichText::new(tr("old_versions"))
                         .strong()
                         .color(COLOR_TEXT_PRIMARY),
                 );
                 cols[1].label(
-                    RichText::new("Duplicate mods with newer versions")
+                    RichText::new(tr("old_versions_hint"))
                         .size(11.0)
                         .color(COLOR_TEXT_MUTED),
                 );
                 cols[1].add_space(4.0);
                 cols[1].horizontal(|ui| {
                     if ui
-                        .add_enabled(ready, egui::Button::new("Analyze"))
+                        .add_enabled(ready, egui::Button::new(tr("analyze")))
                         .clicked()
                     {
                         self.run_old_version_scan(false);
                     }
-                    if ui
-                        .add_enabled(
-                            ready,
-                            egui::Button::new(RichText::new("Clean").color(COLOR_TEXT_PRIMARY))
-                                .fill(COLOR_WARNING),
-                        )
-                        .clicked()
-                    {
-                        if self.move_to_recycle_bin {
-                            self.run_old_version_scan(true);
-                        } else {
-                            self.modal = Modal::ConfirmDelete(DeleteAction::OldVersions);
-                        }
+                    if ui
+                        .add_enabled(
+                            ready,
+                            egui::Button::new(RichText::new(tr("clean")).color(COLOR_TEXT_PRIMARY))
+                                .fill(COLOR_WARNING),
+                        )
+                        .clicked()
+                    {
+                        if self.uses_recycle_bin() {
+                            self.clean_old_versions();
+                        } else {
+                            self.modal = Modal::ConfirmDelete(DeleteAction::OldVersions);
+                        }
+                    }
+                });
+                cols[1].horizontal(|ui| {
+                    if ui
+                        .add_enabled(ready, egui::Button::new(tr("find_identical")))
+                        .on_hover_text(tr("find_identical_hint"))
+                        .clicked()
+                    {
+                        self.run_identical_scan();
+                    }
+                    let has_copies = self
+                        .identical_result
+                        .as_ref()
+                        .is_some_and(|res| !res.groups.is_empty());
+                    if ui
+                        .add_enabled(ready && has_copies, egui::Button::new(tr("remove_copies")))
+                        .clicked()
+                    {
+                        if self.uses_recycle_bin() {
+                            self.clean_identical_copies();
+                        } else {
+                            self.modal = Modal::ConfirmDelete(DeleteAction::IdenticalCopies);
+                        }
+                    }
+                    if ui
+                        .add_enabled(
+                            ready && self.game_folders.len() > 1,
+                            egui::Button::new(tr("find_cross_game")),
+                        )
+                        .on_hover_text(tr("find_cross_game_hint"))
+                        .clicked()
+                    {
+                        self.run_cross_game_scan();
+                    }
+                });
+                cols[1].horizontal(|ui| {
+                    ui.label(
+                        RichText::new(tr("keep_by"))
+                            .size(11.0)
+                            .color(COLOR_TEXT_SECONDARY),
+                    )
+                    .on_hover_text(tr("keep_by_hint"));
+                    let before = self.settings.keep_by;
+                    egui::ComboBox::from_id_salt("keep_by")
+                        .selected_text(Self::keep_by_label(before))
+                        .show_ui(ui, |ui| {
+                            for keep_by in [KeepBy::NewestUpload, KeepBy::HighestVersion] {
+                                ui.selectable_value(
+                                    &mut self.settings.keep_by,
+                                    keep_by,
+                                    Self::keep_by_label(keep_by),
+                                );
+                            }
+                        });
+                    if self.settings.keep_by != before {
+                        // Earlier results were picked with the other rule
+                        self.old_version_result = None;
+                        self.save_settings();
+                    }
+                });
+                egui::CollapsingHeader::new(
+                    RichText::new(tr("advanced_safety"))
+                        .size(11.0)
+                        .color(COLOR_TEXT_SECONDARY),
+                )
+                .id_salt("advanced_safety")
+                .show(&mut cols[1], |ui| {
+                    let mut changed = false;
+                    let mut hash_changed = false;
+                    egui::Grid::new("advanced_safety_grid")
+                        .num_columns(2)
+                        .show(ui, |ui| {
+                            ui.label(RichText::new(tr("patch_size_ratio")).size(11.0))
+                                .on_hover_text(tr("patch_size_ratio_hint"));
+                            let response = ui.add(
+                                egui::DragValue::new(&mut self.settings.patch_size_ratio)
+                                    .range(0.0..=1.0)
+                                    .speed(0.01)
+                                    .fixed_decimals(2),
+                            );
+                            changed |= response.drag_stopped() || response.lost_focus();
+                            ui.end_row();
+
+                            ui.label(RichText::new(tr("suspicious_size_ratio")).size(11.0))
+                                .on_hover_text(tr("suspicious_size_ratio_hint"));
+                            let response = ui.add(
+                                egui::DragValue::new(&mut self.settings.suspicious_size_ratio)
+                                    .range(1.0..=1000.0)
+                                    .speed(0.5)
+                                    .fixed_decimals(1)
+                                    .suffix("x"),
+                            );
+                            changed |= response.drag_stopped() || response.lost_focus();
+                            ui.end_row();
+
+                            ui.label(RichText::new(tr("fast_hash_above")).size(11.0))
+                                .on_hover_text(tr("fast_hash_above_hint"));
+                            let response = ui.add(
+                                egui::DragValue::new(&mut self.settings.fast_hash_above_mb)
+                                    .range(0..=1_048_576)
+                                    .speed(16.0)
+                                    .custom_formatter(|n, _| {
+                                        if n == 0.0 {
+                                            tr("off").to_string()
+                                        } else {
+                                            format_size(n as u64 * 1024 * 1024)
+                                        }
+                                    }),
+                            );
+                            hash_changed |= response.drag_stopped() || response.lost_focus();
+                            ui.end_row();
+
+                            ui.label("");
+                            hash_changed |= ui
+                                .add_enabled(
+                                    self.settings.fast_hash_above_mb > 0,
+                                    egui::Checkbox::new(
+                                        &mut self.settings.verify_fast_hash,
+                                        RichText::new(tr("verify_fast_hash")).size(11.0),
+                                    ),
+                                )
+                                .on_hover_text(tr("verify_fast_hash_hint"))
+                                .changed();
+                            ui.end_row();
+                        });
+                    if ui.small_button(tr("reset_defaults")).clicked() {
+                        let defaults = Settings::default();
+                        self.settings.patch_size_ratio = defaults.patch_size_ratio;
+                        self.settings.suspicious_size_ratio = defaults.suspicious_size_ratio;
+                        self.settings.fast_hash_above_mb = defaults.fast_hash_above_mb;
+                        self.settings.verify_fast_hash = defaults.verify_fast_hash;
+                        changed = true;
+                    }
+                    if changed {
+                        self.old_version_result = None;
+                        self.save_settings();
+                    } else if hash_changed {
+                        self.save_settings();
                     }
                 });
             });
+
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(
+                        !self.is_loading && self.can_clean_all(),
+                        egui::Button::new(RichText::new(tr("clean_all")).color(COLOR_TEXT_PRIMARY))
+                            .fill(COLOR_DANGER),
+                    )
+                    .on_hover_text(tr("clean_all_hint"))
+                    .on_disabled_hover_text(tr("clean_all_disabled_hint"))
+                    .clicked()
+                {
+                    self.modal = Modal::ConfirmCleanAll;
+                }
+                if ui
+                    .add_enabled(
+                        !self.is_loading && !self.modlists.is_empty(),
+                        egui::Button::new(tr("quick_scan_folder")),
+                    )
+                    .on_hover_text(tr("quick_scan_folder_hint"))
+                    .clicked()
+                {
+                    self.run_quick_scan();
+                }
+                if ui
+                    .add_enabled(
+                        !self.is_loading && !self.modlists.is_empty(),
+                        egui::Button::new(tr("analyze_manifest")),
+                    )
+                    .on_hover_text(tr("analyze_manifest_hint"))
+                    .clicked()
+                {
+                    self.analyze_library_manifest();
+                }
+                if ui
+                    .add_enabled(
+                        !self.is_loading && !self.orphan_scan_folders().is_empty(),
+                        egui::Button::new(tr("check_archive_health")),
+                    )
+                    .on_hover_text(tr("check_archive_health_hint"))
+                    .clicked()
+                {
+                    self.run_archive_health_check();
+                }
+                if ui
+                    .add_enabled(
+                        !self.is_loading && !self.downloads_dirs.is_empty(),
+                        egui::Button::new(tr("manage_backups")),
+                    )
+                    .on_hover_text(tr("manage_backups_hint"))
+                    .clicked()
+                {
+                    self.backup_confirm = false;
+                    self.modal = Modal::Backups;
+                    self.list_backups();
+                }
+                if ui
+                    .add_enabled(
+                        !self.is_loading && self.wabbajack_dir.is_some(),
+                        egui::Button::new(tr("old_wabbajack_versions")),
+                    )
+                    .on_hover_text(tr("old_wabbajack_versions_hint"))
+                    .clicked()
+                {
+                    self.version_folder_confirm = false;
+                    self.modal = Modal::VersionFolders;
+                    self.list_version_folders();
+                }
+                ui.add_space(16.0);
+                ui.label(
+                    RichText::new(tr("delete_throttle"))
+                        .size(11.0)
+                        .color(COLOR_TEXT_SECONDARY),
+                )
+                .on_hover_text(tr("delete_throttle_hint"));
+                let response = ui.add(
+                    egui::DragValue::new(&mut self.settings.delete_throttle_ms)
+                        .range(0..=5000)
+                        .speed(10.0)
+                        .suffix(" ms"),
+                );
+                if response.drag_stopped() || response.lost_focus() {
+                    self.save_settings();
+                }
+            });
+        });
+    }
+
+    /// "+N new orphans, -M resolved" line with the file names on hover
+    fn scan_delta_ui(ui: &mut egui::Ui, delta: &ScanDelta) {
+        let previous = chrono::DateTime::parse_from_rfc3339(&delta.previous)
+            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|_| delta.previous.clone());
+        let list = |ui: &mut egui::Ui, files: &[(PathBuf, u64)]| {
+            for (path, size) in files.iter().take(20) {
+                let name = path
+                    .file_name()
+                    .unwrap_or(path.as_os_str())
+                    .to_string_lossy();
+                ui.label(format!("{} ({})", name, format_size(*size)));
+            }
+            if files.len() > 20 {
+                ui.label(tr_args("and_n_more", &[&(files.len() - 20)]));
+            }
+        };
+
+        ui.horizontal(|ui| {
+            ui.label(
+                RichText::new(tr_args("since_last_scan", &[&previous]))
+                    .size(11.0)
+                    .color(COLOR_TEXT_MUTED),
+            );
+            if delta.is_empty() {
+                ui.label(
+                    RichText::new(tr("no_changes"))
+                        .size(11.0)
+                        .color(COLOR_TEXT_SECONDARY),
+                );
+                return;
+            }
+            let added = ui.label(
+                RichText::new(tr_args(
+                    "delta_new_orphans",
+                    &[
+                        &delta.new_orphans.len(),
+                        &format_size(delta.new_orphan_size()),
+                    ],
+                ))
+                .size(11.0)
+                .color(COLOR_DANGER),
+            );
+            if !delta.new_orphans.is_empty() {
+                added.on_hover_ui(|ui| list(ui, &delta.new_orphans));
+            }
+            let resolved = ui.label(
+                RichText::new(tr_args(
+                    "delta_resolved",
+                    &[
+                        &delta.resolved.len(),
+                        &format_size(delta.resolved_size()),
+                        &delta.now_used.len(),
+                    ],
+                ))
+                .size(11.0)
+                .color(COLOR_SUCCESS),
+            );
+            if !delta.resolved.is_empty() {
+                resolved.on_hover_ui(|ui| list(ui, &delta.resolved));
+            }
         });
     }
 
+    /// Fading "scan complete" note; clicking it scrolls back to the results
+    fn render_results_badge(&mut self, ui: &mut egui::Ui) {
+        const BADGE_DURATION: Duration = Duration::from_secs(8);
+        let Some((text, shown_at)) = &self.results_badge else {
+            return;
+        };
+        let elapsed = shown_at.elapsed();
+        if elapsed >= BADGE_DURATION {
+            self.results_badge = None;
+            return;
+        }
+        ui.add_space(12.0);
+        let clicked = ui
+            .add(
+                egui::Label::new(RichText::new(text).size(12.0).color(COLOR_SUCCESS))
+                    .sense(egui::Sense::click()),
+            )
+            .on_hover_text(tr("badge_hint"))
+            .clicked();
+        if clicked {
+            self.scroll_to_results = true;
+        }
+        ui.ctx().request_repaint_after(BADGE_DURATION - elapsed);
+    }
+
     fn render_results_section(&mut self, ui: &mut egui::Ui) {
-        if self.orphaned_result.is_none() && self.old_version_result.is_none() {
+        if self.orphaned_result.is_none()
+            && self.old_version_result.is_none()
+            && self.identical_result.is_none()
+            && self.cross_game_result.is_none()
+            && self.result_history.is_empty()
+        {
             return;
         }
+        if std::mem::take(&mut self.scroll_to_results) {
+            ui.scroll_to_cursor(Some(egui::Align::TOP));
+        }
+
+        let mut reveal: Option<PathBuf> = None;
+        let mut copied = false;
+        let mut clear_history = false;
+        let mut export_script: Option<Vec<ModFile>> = None;
+        // (automatic keeper, picked keeper); `None` goes back to the automatic choice
+        let mut pick_keeper: Option<(PathBuf, Option<PathBuf>)> = None;
+        // (list, rows to show) after a "Show more" click
+        let mut show_more: Option<(&'static str, usize)> = None;
+        let mut row_limit_changed = false;
+        let row_limit = self.settings.result_row_limit as usize;
+        let shown_rows = |list: &str| {
+            if row_limit == 0 {
+                usize::MAX
+            } else {
+                self.shown_rows.get(list).copied().unwrap_or(row_limit)
+            }
+        };
+        let (orphaned_rows, used_rows, small_rows, old_version_rows, identical_rows) = (
+            shown_rows("orphaned"),
+            shown_rows("used"),
+            shown_rows("small"),
+            shown_rows("oldver"),
+            shown_rows("identical"),
+        );
+        let cross_game_rows = shown_rows("cross_game");
+
+        // A history entry replaces the current orphaned and old-version results
+        let viewed = self.viewed_history.and_then(|i| self.result_history.get(i));
+        let (orphaned_result, old_version_result) = match viewed {
+            Some(entry) => (entry.orphaned.as_ref(), entry.old_versions.as_ref()),
+            None => (
+                self.orphaned_result.as_ref(),
+                self.old_version_result.as_ref(),
+            ),
+        };
 
-        Self::section_frame(ui, "Results", |ui| {
-            if let Some(res) = &self.orphaned_result {
+        let old_versions = old_version_result
+            .map(|res| self.cleanable_old_versions(&res.duplicates))
+            .unwrap_or_default();
+        let (orphaned_space, old_version_space) = reclaimable_sizes(orphaned_result, &old_versions);
+
+        Self::section_frame(ui, tr("results"), |ui| {
+            ui.horizontal(|ui| {
+                if !self.result_history.is_empty() {
+                    let selected_text =
+                        viewed.map_or_else(|| tr("current_results").to_string(), |e| e.label());
+                    egui::ComboBox::from_id_salt("result_history")
+                        .selected_text(selected_text)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut self.viewed_history,
+                                None,
+                                tr("current_results"),
+                            );
+                            for (i, entry) in self.result_history.iter().enumerate() {
+                                ui.selectable_value(
+                                    &mut self.viewed_history,
+                                    Some(i),
+                                    entry.label(),
+                                );
+                            }
+                        })
+                        .response
+                        .on_hover_text(tr("result_history_hint"));
+                    if ui.small_button(tr("clear_history")).clicked() {
+                        clear_history = true;
+                    }
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let response = ui
+                        .add(
+                            egui::DragValue::new(&mut self.settings.result_row_limit)
+                                .range(0..=10_000)
+                                .speed(10.0),
+                        )
+                        .on_hover_text(tr("result_rows_hint"));
+                    row_limit_changed = response.drag_stopped() || response.lost_focus();
+                    ui.label(
+                        RichText::new(tr("result_rows"))
+                            .size(11.0)
+                            .color(COLOR_TEXT_MUTED),
+                    );
+                    if ui
+                        .small_button(tr("copy_summary"))
+                        .on_hover_text(tr("copy_summary_hint"))
+                        .clicked()
+                    {
+                        ui.ctx().copy_text(summary_markdown(
+                            self.stats.as_ref(),
+                            orphaned_result,
+                            old_version_result,
+                        ));
+                        copied = true;
+                    }
+                });
+            });
+            if viewed.is_some() {
+                ui.label(
+                    RichText::new(tr("viewing_history_note"))
+                        .size(11.0)
+                        .color(COLOR_WARNING),
+                );
+            } else if let Some(delta) = &self.scan_delta {
+                Self::scan_delta_ui(ui, delta);
+            }
+            if orphaned_space + old_version_space > 0 {
+                ui.label(
+                    RichText::new(tr_args(
+                        "reclaimable_headline",
+                        &[&format_size(orphaned_space + old_version_space)],
+                    ))
+                    .size(20.0)
+                    .strong()
+                    .color(COLOR_ACCENT),
+                )
+                .on_hover_text(tr("reclaimable_hint"));
+                ui.label(
+                    RichText::new(tr_args(
+                        "reclaimable_breakdown",
+                        &[
+                            &format_size(orphaned_space),
+                            &format_size(old_version_space),
+                        ],
+                    ))
+                    .size(11.0)
+                    .color(COLOR_TEXT_SECONDARY),
+                );
+                ui.add_space(8.0);
+            }
+            if let Some(res) = orphaned_result {
                 ui.horizontal(|ui| {
                     ui.label(
-                        RichText::new("Orphaned Mods:")
+                        RichText::new(format!("{}:", tr("orphaned_mods")))
                             .strong()
                             .color(COLOR_TEXT_PRIMARY),
                     );
                     ui.label(
-                        RichText::new(format!("{} files", res.orphaned_mods.len()))
+                        RichText::new(tr_args("n_files", &[&res.orphaned_mods.len()]))
                             .color(COLOR_TEXT_SECONDARY),
                     );
                     ui.label(RichText::new(format_size(res.orphaned_size)).color(COLOR_DANGER));
+                    if !res.orphaned_mods.is_empty()
+                        && ui
+                            .small_button(tr("export_script"))
+                            .on_hover_text(tr("export_script_hint"))
+                            .clicked()
+                    {
+                        export_script =
+                            Some(res.orphaned_mods.iter().map(|m| m.file.clone()).collect());
+                    }
                 });
                 egui::ScrollArea::vertical()
                     .max_height(120.0)
                     .id_salt("orphaned")
                     .show(ui, |ui| {
-                        for m in &res.orphaned_mods {
+                        for m in res.orphaned_mods.iter().take(orphaned_rows) {
                             ui.horizontal(|ui| {
+                                Self::archive_kind_chip(ui, &m.file);
                                 ui.label(
                                     RichText::new(&m.file.file_name)
                                         .size(11.0)
                                         .color(COLOR_TEXT_PRIMARY),
-                                );
+                                )
+                                .on_hover_ui(|ui| Self::mod_details_ui(ui, &m.file));
                                 ui.with_layout(
                                     egui::Layout::right_to_left(egui::Align::Center),
                                     |ui| {
+                                        if Self::reveal_button(
+                                            ui,
+                                            &m.file.full_path,
+                                            &self.missing_files,
+                                        ) {
+                                            reveal = Some(m.file.full_path.clone());
+                                        }
+                                        Self::copy_path_button(ui, &m.file.full_path);
                                         ui.label(
                                             RichText::new(format_size(m.file.size))
                                                 .size(11.0)
                                                 .color(COLOR_TEXT_MUTED),
                                         );
+                                        Self::mod_detail_columns(ui, &m.file);
                                     },
                                 );
                             });
                         }
+                        if let Some(rows) = Self::show_more_rows(
+                            ui,
+                            res.orphaned_mods.len(),
+                            orphaned_rows,
+                            row_limit,
+                        ) {
+                            show_more = Some(("orphaned", rows));
+                        }
+                    });
+                if !res.recent_mods.is_empty() {
+                    ui.label(
+                        RichText::new(tr_args(
+                            "recent_mods_count",
+                            &[&res.recent_mods.len(), &format_size(res.recent_size)],
+                        ))
+                        .size(11.0)
+                        .color(COLOR_WARNING),
+                    )
+                    .on_hover_ui(|ui| {
+                        for m in res.recent_mods.iter().take(20) {
+                            ui.label(format!(
+                                "{} ({})",
+                                m.file.file_name,
+                                format_size(m.file.size)
+                            ));
+                        }
+                        if res.recent_mods.len() > 20 {
+                            ui.label(tr_args("and_n_more", &[&(res.recent_mods.len() - 20)]));
+                        }
+                    });
+                }
+                if !res.small_mods.is_empty() {
+                    egui::CollapsingHeader::new(
+                        RichText::new(tr_args(
+                            "small_mods_count",
+                            &[&res.small_mods.len(), &format_size(res.small_size)],
+                        ))
+                        .size(11.0)
+                        .color(COLOR_TEXT_SECONDARY),
+                    )
+                    .id_salt("small_files")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        egui::ScrollArea::vertical()
+                            .max_height(120.0)
+                            .id_salt("small")
+                            .show(ui, |ui| {
+                                for m in res.small_mods.iter().take(small_rows) {
+                                    ui.horizontal(|ui| {
+                                        Self::archive_kind_chip(ui, &m.file);
+                                        ui.label(
+                                            RichText::new(&m.file.file_name)
+                                                .size(11.0)
+                                                .color(COLOR_TEXT_PRIMARY),
+                                        );
+                                        ui.with_layout(
+                                            egui::Layout::right_to_left(egui::Align::Center),
+                                            |ui| {
+                                                if Self::reveal_button(
+                                                    ui,
+                                                    &m.file.full_path,
+                                                    &self.missing_files,
+                                                ) {
+                                                    reveal = Some(m.file.full_path.clone());
+                                                }
+                                                Self::copy_path_button(ui, &m.file.full_path);
+                                                ui.label(
+                                                    RichText::new(format_size(m.file.size))
+                                                        .size(11.0)
+                                                        .color(COLOR_TEXT_MUTED),
+                                                );
+                                            },
+                                        );
+                                    });
+                                }
+                                if let Some(rows) = Self::show_more_rows(
+                                    ui,
+                                    res.small_mods.len(),
+                                    small_rows,
+                                    row_limit,
+                                ) {
+                                    show_more = Some(("small", rows));
+                                }
+                            });
                     });
+                }
+                egui::CollapsingHeader::new(
+                    RichText::new(tr_args(
+                        "protected_mods_count",
+                        &[&res.used_mods.len(), &format_size(res.used_size)],
+                    ))
+                    .size(11.0)
+                    .color(COLOR_TEXT_SECONDARY),
+                )
+                .id_salt("used_files")
+                .default_open(false)
+                .show(ui, |ui| {
+                    egui::ScrollArea::vertical()
+                        .max_height(120.0)
+                        .id_salt("used")
+                        .show(ui, |ui| {
+                            for m in res.used_mods.iter().take(used_rows) {
+                                ui.horizontal(|ui| {
+                                    Self::archive_kind_chip(ui, &m.file);
+                                    ui.label(
+                                        RichText::new(&m.file.file_name)
+                                            .size(11.0)
+                                            .color(COLOR_TEXT_PRIMARY),
+                                    );
+                                    // Loose ModID matches can over-protect, so make them stand out
+                                    let tag_color = if m.reason == MatchReason::ModId {
+                                        COLOR_WARNING
+                                    } else {
+                                        COLOR_TEXT_MUTED
+                                    };
+                                    ui.label(
+                                        RichText::new(format!("[{}]", m.reason.label()))
+                                            .size(10.0)
+                                            .color(tag_color),
+                                    );
+                                    if !m.modlists.is_empty() {
+                                        ui.with_layout(
+                                            egui::Layout::right_to_left(egui::Align::Center),
+                                            |ui| {
+                                                ui.add(
+                                                    egui::Label::new(
+                                                        RichText::new(m.modlists.join(", "))
+                                                            .size(10.0)
+                                                            .color(COLOR_TEXT_MUTED),
+                                                    )
+                                                    .truncate(),
+                                                )
+                                                .on_hover_text(m.modlists.join("\n"));
+                                            },
+                                        );
+                                    }
+                                });
+                            }
+                            if let Some(rows) =
+                                Self::show_more_rows(ui, res.used_mods.len(), used_rows, row_limit)
+                            {
+                                show_more = Some(("used", rows));
+                            }
+                        });
+                });
                 ui.add_space(8.0);
             }
 
-            if let Some(res) = &self.old_version_result {
+            if let Some(res) = old_version_result {
                 ui.horizontal(|ui| {
                     ui.label(
-                        RichText::new("Old Versions:")
+                        RichText::new(format!("{}:", tr("old_versions")))
                             .strong()
                             .color(COLOR_TEXT_PRIMARY),
                     );
                     ui.label(
-                        RichText::new(format!("{} files", res.total_files))
+                        RichText::new(tr_args("n_files", &[&res.total_files]))
                             .color(COLOR_TEXT_SECONDARY),
                     );
                     ui.label(RichText::new(format_size(res.total_space)).color(COLOR_WARNING));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.checkbox(
+                            &mut self.only_cleanable,
+                            RichText::new(tr("only_cleanable")).size(11.0),
+                        )
+                        .on_hover_text(tr("only_cleanable_hint"));
+                    });
                 });
+                let only_cleanable = self.only_cleanable;
                 egui::ScrollArea::vertical()
                     .max_height(150.0)
                     .id_salt("oldver")
                     .show(ui, |ui| {
-                        for group in &res.duplicates {
-                            ui.label(
-                                RichText::new(&group.mod_key)
-                                    .size(11.0)
-                                    .strong()
-                                    .color(COLOR_ACCENT),
-                            );
-                            for (i, f) in group.files.iter().enumerate() {
+                        for auto_group in res.duplicates.iter().take(old_version_rows) {
+                            let auto_keeper = &auto_group.files[auto_group.newest_idx].full_path;
+                            let group = with_keeper_override(auto_group, &self.keeper_overrides);
+                            let overridden = matches!(group, Cow::Owned(_));
+                            ui.horizontal(|ui| {
+                                ui.label(
+                                    RichText::new(&group.mod_key)
+                                        .size(11.0)
+                                        .strong()
+                                        .color(COLOR_ACCENT),
+                                );
+                                if group.review_recommended {
+                                    ui.label(
+                                        RichText::new(tr("review_recommended"))
+                                            .size(10.0)
+                                            .color(COLOR_WARNING),
+                                    )
+                                    .on_hover_text(tr("review_recommended_hint"));
+                                }
+                            });
+                            let shown = if only_cleanable {
+                                group.deletable_files()
+                            } else {
+                                &group.files
+                            };
+                            if !only_cleanable {
+                                ui.label(
+                                    RichText::new(format!(
+                                        "  {}",
+                                        tr_args(
+                                            "group_dates",
+                                            &[
+                                                &timestamp_to_date(group.newest_timestamp()),
+                                                &group.oldest_deletable_timestamp().map_or_else(
+                                                    || "-".to_string(),
+                                                    timestamp_to_date
+                                                ),
+                                            ],
+                                        )
+                                    ))
+                                    .size(10.0)
+                                    .color(COLOR_TEXT_MUTED),
+                                );
+                            }
+                            for (i, f) in shown.iter().enumerate() {
                                 let is_keep = i == group.newest_idx;
                                 let (status, color) = if is_keep {
-                                    ("KEEP", COLOR_SUCCESS)
+                                    (tr("keep"), COLOR_SUCCESS)
+                                } else {
+                                    (tr("delete"), COLOR_DANGER)
+                                };
+                                ui.horizontal(|ui| {
+                                    ui.add_space(8.0);
+                                    Self::archive_kind_chip(ui, f);
+                                    ui.label(
+                                        RichText::new(format!("{} - {}", status, f.file_name))
+                                            .size(11.0)
+                                            .color(color),
+                                    )
+                                    .on_hover_ui(|ui| {
+                                        Self::mod_details_ui(ui, f);
+                                        ui.separator();
+                                        Self::archive_preview_ui(
+                                            ui,
+                                            &mut self.archive_previews,
+                                            &self.tx,
+                                            &f.full_path,
+                                        );
+                                    });
+                                    if is_keep && overridden {
+                                        ui.label(
+                                            RichText::new(tr("kept_by_choice"))
+                                                .size(10.0)
+                                                .color(COLOR_ACCENT),
+                                        );
+                                        if ui
+                                            .small_button(tr("reset"))
+                                            .on_hover_text(tr("reset_keeper_hint"))
+                                            .clicked()
+                                        {
+                                            pick_keeper = Some((auto_keeper.clone(), None));
+                                        }
+                                    } else if is_keep {
+                                        ui.label(
+                                            RichText::new(tr("kept_newest"))
+                                                .size(10.0)
+                                                .color(COLOR_SUCCESS),
+                                        )
+                                        .on_hover_text(tr("kept_newest_hint"));
+                                    }
+                                    ui.with_layout(
+                                        egui::Layout::right_to_left(egui::Align::Center),
+                                        |ui| {
+                                            if Self::reveal_button(
+                                                ui,
+                                                &f.full_path,
+                                                &self.missing_files,
+                                            ) {
+                                                reveal = Some(f.full_path.clone());
+                                            }
+                                            if !is_keep
+                                                && ui
+                                                    .small_button(tr("keep_this"))
+                                                    .on_hover_text(tr("keep_this_hint"))
+                                                    .clicked()
+                                            {
+                                                let keeper = (f.full_path != *auto_keeper)
+                                                    .then(|| f.full_path.clone());
+                                                pick_keeper = Some((auto_keeper.clone(), keeper));
+                                            }
+                                            Self::copy_path_button(ui, &f.full_path);
+                                            ui.label(
+                                                RichText::new(format_size(f.size))
+                                                    .size(11.0)
+                                                    .color(COLOR_TEXT_MUTED),
+                                            );
+                                            Self::mod_detail_columns(ui, f);
+                                        },
+                                    );
+                                });
+                            }
+                        }
+                        if let Some(rows) = Self::show_more_rows(
+                            ui,
+                            res.duplicates.len(),
+                            old_version_rows,
+                            row_limit,
+                        ) {
+                            show_more = Some(("oldver", rows));
+                        }
+                    });
+                if !res.skipped_groups.is_empty() {
+                    egui::CollapsingHeader::new(
+                        RichText::new(tr_args(
+                            "skipped_groups_count",
+                            &[&res.skipped_groups.len()],
+                        ))
+                        .size(11.0)
+                        .color(COLOR_TEXT_SECONDARY),
+                    )
+                    .id_salt("skipped_groups")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        egui::ScrollArea::vertical()
+                            .max_height(120.0)
+                            .id_salt("skipped")
+                            .show(ui, |ui| {
+                                for (mod_key, reason) in &res.skipped_groups {
+                                    ui.horizontal(|ui| {
+                                        ui.label(
+                                            RichText::new(mod_key)
+                                                .size(11.0)
+                                                .color(COLOR_TEXT_PRIMARY),
+                                        );
+                                        ui.label(
+                                            RichText::new(Self::skip_reason_label(*reason))
+                                                .size(10.0)
+                                                .color(COLOR_TEXT_MUTED),
+                                        );
+                                    });
+                                }
+                            });
+                    });
+                }
+            }
+
+            if let Some(res) = &self.identical_result {
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    ui.label(
+                        RichText::new(format!("{}:", tr("identical_copies")))
+                            .strong()
+                            .color(COLOR_TEXT_PRIMARY),
+                    );
+                    ui.label(
+                        RichText::new(tr_args("n_files", &[&res.total_files]))
+                            .color(COLOR_TEXT_SECONDARY),
+                    );
+                    ui.label(RichText::new(format_size(res.total_space)).color(COLOR_WARNING));
+                });
+                egui::ScrollArea::vertical()
+                    .max_height(120.0)
+                    .id_salt("identical")
+                    .show(ui, |ui| {
+                        for group in res.groups.iter().take(identical_rows) {
+                            for (i, f) in group.files.iter().enumerate() {
+                                let (status, color) = if i == group.newest_idx {
+                                    (tr("keep"), COLOR_SUCCESS)
                                 } else {
-                                    ("DELETE", COLOR_DANGER)
+                                    (tr("delete"), COLOR_DANGER)
                                 };
                                 ui.horizontal(|ui| {
+                                    Self::archive_kind_chip(ui, f);
+                                    ui.label(
+                                        RichText::new(format!("{} - {}", status, f.file_name))
+                                            .size(11.0)
+                                            .color(color),
+                                    )
+                                    .on_hover_text(f.full_path.display().to_string());
+                                    ui.with_layout(
+                                        egui::Layout::right_to_left(egui::Align::Center),
+                                        |ui| {
+                                            if Self::reveal_button(
+                                                ui,
+                                                &f.full_path,
+                                                &self.missing_files,
+                                            ) {
+                                                reveal = Some(f.full_path.clone());
+                                            }
+                                            Self::copy_path_button(ui, &f.full_path);
+                                            ui.label(
+                                                RichText::new(format_size(f.size))
+                                                    .size(11.0)
+                                                    .color(COLOR_TEXT_MUTED),
+                                            );
+                                        },
+                                    );
+                                });
+                            }
+                        }
+                        if let Some(rows) =
+                            Self::show_more_rows(ui, res.groups.len(), identical_rows, row_limit)
+                        {
+                            show_more = Some(("identical", rows));
+                        }
+                    });
+            }
+
+            if let Some(groups) = &self.cross_game_result {
+                ui.add_space(8.0);
+                let redundant: u64 = groups.iter().map(|g| g.redundant_size()).sum();
+                ui.horizontal(|ui| {
+                    ui.label(
+                        RichText::new(format!("{}:", tr("cross_game_copies")))
+                            .strong()
+                            .color(COLOR_TEXT_PRIMARY),
+                    )
+                    .on_hover_text(tr("cross_game_copies_hint"));
+                    ui.label(
+                        RichText::new(tr_args("n_mods", &[&groups.len()]))
+                            .color(COLOR_TEXT_SECONDARY),
+                    );
+                    ui.label(RichText::new(format_size(redundant)).color(COLOR_WARNING));
+                });
+                egui::ScrollArea::vertical()
+                    .max_height(120.0)
+                    .id_salt("cross_game")
+                    .show(ui, |ui| {
+                        for group in groups.iter().take(cross_game_rows) {
+                            ui.label(
+                                RichText::new(tr_args(
+                                    "mod_in_n_games",
+                                    &[
+                                        &format!("{}-{}", group.mod_id, group.file_key),
+                                        &group.game_count(),
+                                    ],
+                                ))
+                                .size(11.0)
+                                .strong()
+                                .color(COLOR_ACCENT),
+                            );
+                            for f in &group.copies {
+                                let game = f
+                                    .full_path
+                                    .parent()
+                                    .and_then(|p| p.file_name())
+                                    .unwrap_or_default()
+                                    .to_string_lossy();
+                                ui.horizontal(|ui| {
+                                    ui.add_space(8.0);
+                                    Self::archive_kind_chip(ui, f);
                                     ui.label(
-                                        RichText::new(format!("  {} - {}", status, f.file_name))
+                                        RichText::new(format!("{}: {}", game, f.file_name))
                                             .size(11.0)
-                                            .color(color),
-                                    );
+                                            .color(COLOR_TEXT_PRIMARY),
+                                    )
+                                    .on_hover_text(f.full_path.display().to_string());
                                     ui.with_layout(
                                         egui::Layout::right_to_left(egui::Align::Center),
                                         |ui| {
+                                            if Self::reveal_button(
+                                                ui,
+                                                &f.full_path,
+                                                &self.missing_files,
+                                            ) {
+                                                reveal = Some(f.full_path.clone());
+                                            }
+                                            Self::copy_path_button(ui, &f.full_path);
                                             ui.label(
                                                 RichText::new(format_size(f.size))
                                                     .size(11.0)
@@ -815,14 +4600,257 @@ impl WabbajackCleanerApp {
                                 });
                             }
                         }
+                        if let Some(rows) =
+                            Self::show_more_rows(ui, groups.len(), cross_game_rows, row_limit)
+                        {
+                            show_more = Some(("cross_game", rows));
+                        }
                     });
             }
         });
+
+        if let Some((list, rows)) = show_more {
+            self.shown_rows.insert(list, rows);
+        }
+        if row_limit_changed {
+            self.shown_rows.clear();
+            self.save_settings();
+        }
+
+        if copied {
+            self.log(LogLevel::Info, "Summary copied to clipboard.");
+        }
+        if let Some(files) = export_script {
+            self.export_deletion_script(&files);
+        }
+        match pick_keeper {
+            Some((auto, Some(keeper))) => {
+                self.keeper_overrides.insert(auto, keeper);
+            }
+            Some((auto, None)) => {
+                self.keeper_overrides.remove(&auto);
+            }
+            None => {}
+        }
+        if clear_history {
+            self.result_history.clear();
+            self.viewed_history = None;
+        }
+        if let Some(path) = reveal {
+            if let Err(e) = reveal_in_file_manager(&path) {
+                self.log(
+                    LogLevel::Error,
+                    &format!("Failed to open folder for {}: {}", path.display(), e),
+                );
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    self.missing_files.insert(path);
+                }
+            }
+        }
+    }
+
+    fn skip_reason_label(reason: SkipReason) -> &'static str {
+        match reason {
+            SkipReason::SameTimestamp => tr("skip_same_timestamp"),
+            SkipReason::SuspiciousPattern => tr("skip_suspicious_pattern"),
+            SkipReason::PatchAndMain => tr("skip_patch_and_main"),
+            SkipReason::NewestIsPatch => tr("skip_newest_is_patch"),
+        }
+    }
+
+    fn keep_by_label(keep_by: KeepBy) -> &'static str {
+        match keep_by {
+            KeepBy::NewestUpload => tr("keep_newest_upload"),
+            KeepBy::HighestVersion => tr("keep_highest_version"),
+        }
+    }
+
+    /// Fixed-width version, mod ID and mod name columns (added inside a right-to-left layout)
+    fn mod_detail_columns(ui: &mut egui::Ui, file: &ModFile) {
+        let height = ui.spacing().interact_size.y;
+        let column = |ui: &mut egui::Ui, width: f32, text: &str, color: Color32| {
+            ui.add_sized(
+                [width, height],
+                egui::Label::new(RichText::new(text).size(11.0).color(color)).truncate(),
+            );
+        };
+        if file.is_unparsed() {
+            column(ui, 320.0, tr("unparsed"), COLOR_TEXT_MUTED);
+            return;
+        }
+        let mod_id = if file.mod_id == "0" || file.mod_id.is_empty() {
+            "-"
+        } else {
+            &file.mod_id
+        };
+        column(ui, 80.0, &file.version, COLOR_TEXT_MUTED);
+        column(ui, 60.0, mod_id, COLOR_TEXT_MUTED);
+        column(ui, 180.0, &file.mod_name, COLOR_TEXT_SECONDARY);
+    }
+
+    /// Tooltip with the parsed name, IDs and version of a download
+    fn mod_details_ui(ui: &mut egui::Ui, file: &ModFile) {
+        if file.is_unparsed() {
+            ui.label(RichText::new(tr("unparsed")).color(COLOR_TEXT_MUTED));
+            return;
+        }
+        egui::Grid::new("mod_details")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label(tr("mod_name"));
+                ui.label(&file.mod_name);
+                ui.end_row();
+                ui.label(tr("mod_id"));
+                ui.label(if file.mod_id == "0" || file.mod_id.is_empty() {
+                    "-"
+                } else {
+                    &file.mod_id
+                });
+                ui.end_row();
+                if let Some(file_id) = &file.file_id {
+                    ui.label(tr("file_id"));
+                    ui.label(file_id);
+                    ui.end_row();
+                }
+                ui.label(tr("version"));
+                ui.label(&file.version);
+                ui.end_row();
+                ui.label(tr("downloaded"));
+                ui.label(timestamp_to_date(&file.timestamp));
+                ui.end_row();
+            });
+    }
+
+    /// Tooltip listing an archive's top-level contents. The listing runs in the background
+    /// on first hover and is cached per path.
+    fn archive_preview_ui(
+        ui: &mut egui::Ui,
+        previews: &mut HashMap<PathBuf, Option<Result<Vec<String>, String>>>,
+        tx: &Sender<(u64, AsyncMessage)>,
+        path: &std::path::Path,
+    ) {
+        const MAX_ENTRIES: usize = 20;
+        let listing = previews.entry(path.to_path_buf()).or_insert_with(|| {
+            let tx = OpSender {
+                generation: BACKGROUND_GENERATION,
+                tx: tx.clone(),
+            };
+            let path = path.to_path_buf();
+            thread::spawn(move || {
+                let listing = list_archive_entries(&path).map_err(|e| e.to_string());
+                tx.send(AsyncMessage::ArchivePreviewListed(path, listing))
+                    .ok();
+            });
+            None
+        });
+
+        match listing {
+            None => {
+                ui.label(RichText::new(tr("archive_loading")).color(COLOR_TEXT_MUTED));
+                ui.ctx().request_repaint_after(Duration::from_millis(100));
+            }
+            Some(Ok(entries)) if entries.is_empty() => {
+                ui.label(RichText::new(tr("archive_empty")).color(COLOR_TEXT_MUTED));
+            }
+            Some(Ok(entries)) => {
+                ui.label(RichText::new(tr("archive_contents")).strong());
+                for entry in entries.iter().take(MAX_ENTRIES) {
+                    ui.label(RichText::new(entry).monospace().size(11.0));
+                }
+                if entries.len() > MAX_ENTRIES {
+                    ui.label(
+                        RichText::new(tr_args("and_n_more", &[&(entries.len() - MAX_ENTRIES)]))
+                            .color(COLOR_TEXT_MUTED),
+                    );
+                }
+            }
+            Some(Err(_)) => {
+                ui.label(RichText::new(tr("preview_unavailable")).color(COLOR_TEXT_MUTED));
+            }
+        }
+    }
+
+    /// "Showing X of Y" and a button revealing the next batch, under a list cut off at
+    /// `shown` rows; returns the new row count when clicked
+    fn show_more_rows(
+        ui: &mut egui::Ui,
+        total: usize,
+        shown: usize,
+        batch: usize,
+    ) -> Option<usize> {
+        if shown >= total {
+            return None;
+        }
+        let mut more = None;
+        ui.horizontal(|ui| {
+            ui.label(
+                RichText::new(tr_args("showing_n_of_m", &[&shown, &total]))
+                    .size(11.0)
+                    .color(COLOR_TEXT_MUTED),
+            );
+            let next = batch.min(total - shown);
+            if ui
+                .small_button(tr_args("show_n_more", &[&next]))
+                .on_hover_text(tr("show_more_hint"))
+                .clicked()
+            {
+                more = Some(shown + next);
+            }
+        });
+        more
+    }
+
+    /// Colored tag like `7Z` or `EXE` in front of a file name
+    fn archive_kind_chip(ui: &mut egui::Ui, file: &ModFile) {
+        let kind = file.archive_kind();
+        let color = match kind {
+            ArchiveKind::SevenZip => COLOR_ACCENT,
+            ArchiveKind::Zip => COLOR_SUCCESS,
+            ArchiveKind::Rar => Color32::from_rgb(168, 85, 247),
+            ArchiveKind::Tar => Color32::from_rgb(20, 184, 166),
+            ArchiveKind::Exe => COLOR_WARNING,
+            ArchiveKind::Bethesda => Color32::from_rgb(234, 88, 12),
+            ArchiveKind::Other => COLOR_TEXT_MUTED,
+        };
+        ui.label(
+            RichText::new(format!("{:^3}", kind.label()))
+                .monospace()
+                .size(9.0)
+                .strong()
+                .color(COLOR_BG_HEADER)
+                .background_color(color),
+        );
+    }
+
+    /// Small button copying a result row's full path; still works after the file is gone
+    fn copy_path_button(ui: &mut egui::Ui, path: &std::path::Path) {
+        if ui
+            .add(egui::Button::new(RichText::new(tr("copy_path")).size(11.0)).small())
+            .on_hover_text(path.display().to_string())
+            .clicked()
+        {
+            ui.ctx().copy_text(path.display().to_string());
+        }
+    }
+
+    /// Small open-folder button for a result row, disabled once the file was found gone
+    fn reveal_button(
+        ui: &mut egui::Ui,
+        path: &std::path::Path,
+        missing_files: &HashSet<PathBuf>,
+    ) -> bool {
+        ui.add_enabled(
+            !missing_files.contains(path),
+            egui::Button::new(RichText::new(tr("open_folder")).size(11.0)).small(),
+        )
+        .on_hover_text(tr("open_folder_hint"))
+        .on_disabled_hover_text(tr("file_missing"))
+        .clicked()
     }
 
     fn render_modals(&mut self, ctx: &egui::Context) {
         if self.modal == Modal::About {
-            egui::Window::new("About")
+            egui::Window::new(tr("about"))
                 .collapsible(false)
                 .resizable(false)
                 .default_width(800.0)
@@ -839,33 +4867,34 @@ impl WabbajackCleanerApp {
                                     .color(COLOR_TEXT_PRIMARY),
                             );
                             ui.label(
-                                RichText::new(format!("Version {}", APP_VERSION))
+                                RichText::new(tr_args("version_n", &[&APP_VERSION]))
                                     .size(14.0)
                                     .color(COLOR_TEXT_SECONDARY),
                             );
                             ui.add_space(20.0);
                             ui.label(
-                                RichText::new("Clean up your Wabbajack downloads folder")
+                                RichText::new(tr("about_line1"))
                                     .size(14.0)
                                     .color(COLOR_TEXT_SECONDARY),
                             );
                             ui.label(
-                                RichText::new("safely and efficiently.")
+                                RichText::new(tr("about_line2"))
                                     .size(14.0)
                                     .color(COLOR_TEXT_SECONDARY),
                             );
                             ui.add_space(30.0);
                             ui.label(
-                                RichText::new("Created by Berkay Yetgin").color(COLOR_TEXT_MUTED),
+                                RichText::new(tr_args("created_by", &[&"Berkay Yetgin"]))
+                                    .color(COLOR_TEXT_MUTED),
                             );
                             ui.add_space(8.0);
                             ui.hyperlink_to(
-                                "GitHub Repository",
+                                tr("github_repository"),
                                 "https://github.com/Yakrel/wabbajack-library-cleaner",
                             );
                             ui.add_space(8.0);
                             ui.label(
-                                RichText::new("License: GPL-3.0")
+                                RichText::new(tr_args("license", &[&"GPL-3.0"]))
                                     .size(11.0)
                                     .color(COLOR_TEXT_MUTED),
                             );
@@ -874,7 +4903,7 @@ impl WabbajackCleanerApp {
                         // Right Column: Changelog
                         cols[1].vertical(|ui| {
                             ui.label(
-                                RichText::new("Changelog")
+                                RichText::new(tr("changelog"))
                                     .strong()
                                     .size(16.0)
                                     .color(COLOR_TEXT_PRIMARY),
@@ -911,121 +4940,959 @@ impl WabbajackCleanerApp {
                     ui.separator();
                     ui.add_space(10.0);
                     ui.vertical_centered(|ui| {
-                        if ui.button(RichText::new("Close").size(14.0)).clicked() {
+                        if ui.button(RichText::new(tr("close")).size(14.0)).clicked() {
+                            self.modal = Modal::None;
+                        }
+                    });
+                });
+        }
+
+        if let Modal::ConfirmDelete(action) = self.modal {
+            egui::Window::new(tr("confirm_deletion"))
+                .collapsible(false)
+                .resizable(false)
+                .default_width(350.0)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.vertical_centered(|ui| {
+                        ui.label(
+                            RichText::new(tr("warning"))
+                                .size(20.0)
+                                .strong()
+                                .color(COLOR_DANGER),
+                        );
+                        ui.add_space(12.0);
+                        ui.label(tr("confirm_line1"));
+                        ui.label(tr("confirm_line2"));
+                        ui.label(tr("confirm_line3"));
+                        let fresh = match action {
+                            DeleteAction::Orphaned => self.orphaned_result_fresh(),
+                            DeleteAction::OldVersions => self.old_version_result_fresh(),
+                            DeleteAction::IdenticalCopies => true,
+                        };
+                        ui.add_space(8.0);
+                        ui.label(
+                            RichText::new(if fresh {
+                                tr("confirm_uses_shown_results")
+                            } else {
+                                tr("confirm_scans_first")
+                            })
+                            .size(11.0)
+                            .color(COLOR_TEXT_SECONDARY),
+                        );
+                        ui.add_space(20.0);
+                        ui.horizontal(|ui| {
+                            if ui
+                                .button(
+                                    RichText::new(tr("yes_delete")).strong().color(COLOR_DANGER),
+                                )
+                                .clicked()
+                            {
+                                match action {
+                                    DeleteAction::Orphaned => {
+                                        self.modal = Modal::None;
+                                        self.clean_orphaned();
+                                    }
+                                    DeleteAction::OldVersions => {
+                                        // clean_old_versions opens the folder selection or
+                                        // review modal; do not override it with None here
+                                        self.clean_old_versions();
+                                    }
+                                    DeleteAction::IdenticalCopies => {
+                                        self.modal = Modal::None;
+                                        self.clean_identical_copies();
+                                    }
+                                }
+                            }
+                            if ui.button(tr("cancel")).clicked() {
+                                self.modal = Modal::None;
+                            }
+                        });
+                    });
+                });
+        }
+
+        if self.modal == Modal::ReviewOldVersions {
+            self.render_review_old_versions(ctx);
+        }
+
+        if self.modal == Modal::ConfirmCleanAll {
+            self.render_confirm_clean_all(ctx);
+        }
+
+        if self.modal == Modal::ReportIssue {
+            self.render_report_issue(ctx);
+        }
+
+        if self.modal == Modal::Backups {
+            self.render_backups(ctx);
+        }
+
+        if self.modal == Modal::VersionFolders {
+            self.render_version_folders(ctx);
+        }
+
+        if self.modal == Modal::Diagnostics {
+            self.render_diagnostics(ctx);
+        }
+
+        if self.modal == Modal::ResumeDeletion {
+            self.render_resume_deletion(ctx);
+        }
+
+        if self.modal == Modal::FolderSelect {
+            let is_clean = self.pending_delete_mode;
+            let dialog_desc = if is_clean {
+                tr("folder_select_clean")
+            } else {
+                tr("folder_select_scan")
+            };
+            let any_selected = self.game_folder_selected.iter().any(|&x| x);
+            egui::Window::new(tr("select_game_folder"))
+                .collapsible(false)
+                .resizable(false)
+                .default_width(350.0)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(dialog_desc);
+                    ui.add_space(4.0);
+                    ui.horizontal(|ui| {
+                        if ui.small_button(tr("all")).clicked() {
+                            self.game_folder_selected.iter_mut().for_each(|x| *x = true);
+                        }
+                        if ui.small_button(tr("none")).clicked() {
+                            self.game_folder_selected
+                                .iter_mut()
+                                .for_each(|x| *x = false);
+                        }
+                    });
+                    ui.add_space(4.0);
+                    egui::ScrollArea::vertical()
+                        .max_height(200.0)
+                        .show(ui, |ui| {
+                            for (folder, selected) in
+                                self.game_folders.iter().zip(&mut self.game_folder_selected)
+                            {
+                                let name = folder.file_name().unwrap_or_default().to_string_lossy();
+                                ui.checkbox(selected, &*name);
+                            }
+                        });
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        let btn_label = if is_clean {
+                            tr("start_clean")
+                        } else {
+                            tr("start_scan")
+                        };
+                        let btn_color = if is_clean {
+                            COLOR_WARNING
+                        } else {
+                            COLOR_ACCENT
+                        };
+                        if ui
+                            .add_enabled(any_selected, egui::Button::new(btn_label).fill(btn_color))
+                            .clicked()
+                        {
+                            self.start_old_version_scan();
+                        }
+                        if ui.button(tr("cancel")).clicked() {
                             self.modal = Modal::None;
+                            self.pending_delete_mode = false;
+                        }
+                    });
+                });
+        }
+    }
+
+    fn list_backups(&mut self) {
+        if self.downloads_dirs.is_empty() {
+            return;
+        }
+        if self.defer(QueuedOperation::ListBackups) {
+            return;
+        }
+        let dirs = self.downloads_dirs.clone();
+        self.begin_operation();
+        self.current_operation = "Listing backups...".to_string();
+        let tx = self.sender();
+        thread::spawn(move || {
+            let mut backups: Vec<BackupInfo> = dirs.iter().flat_map(|d| list_backups(d)).collect();
+            backups.sort_by_key(|b| b.created);
+            tx.send(AsyncMessage::BackupsListed(backups)).ok();
+        });
+    }
+
+    fn purge_selected_backups(&mut self) {
+        let paths: Vec<PathBuf> = self
+            .backups
+            .iter()
+            .zip(&self.backup_selected)
+            .filter(|(_, &selected)| selected)
+            .map(|(b, _)| b.path.clone())
+            .collect();
+        if paths.is_empty() {
+            return;
+        }
+        if self.settings.safe_mode {
+            self.log(
+                LogLevel::Warning,
+                "Safe mode is on: backups are not deleted.",
+            );
+            return;
+        }
+        self.begin_operation();
+        self.current_operation = "Deleting backups...".to_string();
+        let tx = self.sender();
+        thread::spawn(move || {
+            let mut freed = 0;
+            let mut errors = Vec::new();
+            for (i, path) in paths.iter().enumerate() {
+                tx.send(AsyncMessage::Progress(
+                    format!("Deleting backups... {}/{}", i + 1, paths.len()),
+                    Some((i + 1, paths.len())),
+                ))
+                .ok();
+                match purge_backup(path) {
+                    Ok(size) => {
+                        log::info!("Deleted backup {:?} ({})", path, format_size(size));
+                        freed += size;
+                    }
+                    Err(e) => errors.push(format!("{:#}", e)),
+                }
+            }
+            tx.send(AsyncMessage::BackupsPurged(freed, errors)).ok();
+        });
+    }
+
+    fn restore_backup(&mut self, archive: PathBuf) {
+        let Some(root) = backup_root(&archive).map(Path::to_path_buf) else {
+            return;
+        };
+        self.begin_operation();
+        self.current_operation = "Restoring backup...".to_string();
+        let tx = self.sender();
+        thread::spawn(move || {
+            let msg = match restore_backup_archive(&archive, &root) {
+                Ok((restored, errors)) => AsyncMessage::BackupRestored(restored, errors),
+                Err(e) => AsyncMessage::Error(format!("{:#}", e)),
+            };
+            tx.send(msg).ok();
+        });
+    }
+
+    fn verify_backup(&mut self, backup: BackupInfo) {
+        self.begin_operation();
+        self.current_operation = "Verifying backup...".to_string();
+        let tx = self.sender();
+        thread::spawn(move || {
+            let msg = match verify_backup(&backup.path) {
+                Ok(report) => AsyncMessage::BackupVerified(backup.name, report),
+                Err(e) => AsyncMessage::Error(format!("{:#}", e)),
+            };
+            tx.send(msg).ok();
+        });
+    }
+
+    fn render_backups(&mut self, ctx: &egui::Context) {
+        let mut close = false;
+        let mut purge = false;
+        let mut restore = None;
+        let mut verify = None;
+        let now = chrono::Local::now();
+        let (count, size) = self
+            .backups
+            .iter()
+            .zip(&self.backup_selected)
+            .filter(|(_, &selected)| selected)
+            .fold((0, 0), |(n, s), (b, _)| (n + 1, s + b.size));
+
+        egui::Window::new(tr("backups"))
+            .collapsible(false)
+            .resizable(true)
+            .default_width(520.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                if self.backup_confirm {
+                    ui.vertical_centered(|ui| {
+                        ui.label(
+                            RichText::new(tr("warning"))
+                                .size(20.0)
+                                .strong()
+                                .color(COLOR_DANGER),
+                        );
+                        ui.add_space(12.0);
+                        ui.label(tr_args(
+                            "confirm_purge_backups",
+                            &[&count, &format_size(size)],
+                        ));
+                        ui.label(tr("confirm_line3"));
+                        ui.add_space(20.0);
+                        ui.horizontal(|ui| {
+                            if ui
+                                .button(
+                                    RichText::new(tr("yes_delete")).strong().color(COLOR_DANGER),
+                                )
+                                .clicked()
+                            {
+                                purge = true;
+                            }
+                            if ui.button(tr("cancel")).clicked() {
+                                self.backup_confirm = false;
+                            }
+                        });
+                    });
+                    return;
+                }
+
+                if self.backups.is_empty() {
+                    ui.label(RichText::new(tr("no_backups")).color(COLOR_TEXT_MUTED));
+                } else {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(tr("select_older_than")).size(11.0));
+                        ui.add(
+                            egui::DragValue::new(&mut self.backup_older_than_days)
+                                .range(0..=3650)
+                                .suffix(tr("days_suffix")),
+                        );
+                        if ui.small_button(tr("select")).clicked() {
+                            self.backup_selected = backups_older_than(
+                                &self.backups,
+                                self.backup_older_than_days as i64,
+                                now,
+                            );
+                        }
+                        if ui.small_button(tr("all")).clicked() {
+                            self.backup_selected.iter_mut().for_each(|s| *s = true);
+                        }
+                        if ui.small_button(tr("none")).clicked() {
+                            self.backup_selected.iter_mut().for_each(|s| *s = false);
                         }
                     });
+                    ui.add_space(4.0);
+                    egui::ScrollArea::vertical()
+                        .max_height(300.0)
+                        .show(ui, |ui| {
+                            egui::Grid::new("backups_grid")
+                                .num_columns(5)
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    for (backup, selected) in
+                                        self.backups.iter().zip(&mut self.backup_selected)
+                                    {
+                                        let label = if backup.loose {
+                                            tr_args("loose_backup_files", &[&backup.name])
+                                        } else {
+                                            backup.name.clone()
+                                        };
+                                        ui.checkbox(selected, label);
+                                        ui.label(tr_args("n_files", &[&backup.files]));
+                                        ui.label(format_size(backup.size));
+                                        ui.label(match backup.created {
+                                            Some(_) => tr_args("n_days", &[&backup.age_days(now)]),
+                                            None => "-".to_string(),
+                                        });
+                                        if backup.archive {
+                                            ui.horizontal(|ui| {
+                                                if ui
+                                                    .add_enabled(
+                                                        !self.is_loading,
+                                                        egui::Button::new(tr("verify")).small(),
+                                                    )
+                                                    .on_hover_text(tr("verify_backup_hint"))
+                                                    .clicked()
+                                                {
+                                                    verify = Some(backup.clone());
+                                                }
+                                                if ui
+                                                    .add_enabled(
+                                                        !self.is_loading,
+                                                        egui::Button::new(tr("restore")).small(),
+                                                    )
+                                                    .on_hover_text(tr("restore_backup_hint"))
+                                                    .clicked()
+                                                {
+                                                    restore = Some(backup.path.clone());
+                                                }
+                                            });
+                                        } else {
+                                            ui.label("");
+                                        }
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                }
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    let delete = ui.add_enabled(
+                        count > 0 && !self.is_loading && !self.settings.safe_mode,
+                        egui::Button::new(
+                            RichText::new(tr_args(
+                                "delete_selected_backups",
+                                &[&count, &format_size(size)],
+                            ))
+                            .color(COLOR_TEXT_PRIMARY),
+                        )
+                        .fill(COLOR_DANGER),
+                    );
+                    let delete = if self.settings.safe_mode {
+                        delete.on_disabled_hover_text(tr("purge_locked_hint"))
+                    } else {
+                        delete
+                    };
+                    if delete.clicked() {
+                        self.backup_confirm = true;
+                    }
+                    if ui.button(tr("close")).clicked() {
+                        close = true;
+                    }
                 });
+            });
+
+        if purge {
+            self.backup_confirm = false;
+            self.purge_selected_backups();
+        }
+        if let Some(archive) = restore {
+            self.restore_backup(archive);
         }
+        if let Some(backup) = verify {
+            self.verify_backup(backup);
+        }
+        if close {
+            self.modal = Modal::None;
+        }
+    }
 
-        if let Modal::ConfirmDelete(action) = self.modal {
-            egui::Window::new("Confirm Deletion")
-                .collapsible(false)
-                .resizable(false)
-                .default_width(350.0)
-                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
-                .show(ctx, |ui| {
+    fn list_version_folders(&mut self) {
+        let Some(dir) = self.wabbajack_dir.clone() else {
+            return;
+        };
+        self.begin_operation();
+        self.current_operation = "Listing Wabbajack versions...".to_string();
+        let tx = self.sender();
+        thread::spawn(move || {
+            let folders = scan_wabbajack_version_folders(&dir);
+            tx.send(AsyncMessage::VersionFoldersListed(folders)).ok();
+        });
+    }
+
+    fn purge_selected_version_folders(&mut self) {
+        let Some(newest) = self.version_folders.iter().find(|f| f.is_newest).cloned() else {
+            return;
+        };
+        let folders: Vec<VersionFolderInfo> = self
+            .version_folders
+            .iter()
+            .zip(&self.version_folder_selected)
+            .filter(|(f, &selected)| selected && !f.is_newest)
+            .map(|(f, _)| f.clone())
+            .collect();
+        if folders.is_empty() {
+            return;
+        }
+        if self.settings.safe_mode {
+            self.log(
+                LogLevel::Warning,
+                "Safe mode is on: old Wabbajack versions are not deleted.",
+            );
+            return;
+        }
+        self.begin_operation();
+        self.current_operation = "Deleting old Wabbajack versions...".to_string();
+        let tx = self.sender();
+        thread::spawn(move || {
+            let dir = newest.path.parent().map(Path::to_path_buf);
+            let mut freed = 0;
+            let mut errors = Vec::new();
+            for (i, folder) in folders.iter().enumerate() {
+                tx.send(AsyncMessage::Progress(
+                    format!(
+                        "Deleting old Wabbajack versions... {}/{}",
+                        i + 1,
+                        folders.len()
+                    ),
+                    Some((i + 1, folders.len())),
+                ))
+                .ok();
+                match purge_version_folder(folder, &newest) {
+                    Ok(size) => freed += size,
+                    Err(e) => errors.push(format!("{:#}", e)),
+                }
+            }
+            let remaining = dir
+                .map(|d| scan_wabbajack_version_folders(&d))
+                .unwrap_or_default();
+            tx.send(AsyncMessage::VersionFoldersListed(remaining)).ok();
+            tx.send(AsyncMessage::VersionFoldersPurged(freed, errors))
+                .ok();
+        });
+    }
+
+    fn render_version_folders(&mut self, ctx: &egui::Context) {
+        let mut close = false;
+        let mut purge = false;
+        let (count, size) = self
+            .version_folders
+            .iter()
+            .zip(&self.version_folder_selected)
+            .filter(|(f, &selected)| selected && !f.is_newest)
+            .fold((0, 0), |(n, s), (f, _)| (n + 1, s + f.size));
+
+        egui::Window::new(tr("old_wabbajack_versions"))
+            .collapsible(false)
+            .resizable(true)
+            .default_width(520.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                if self.version_folder_confirm {
                     ui.vertical_centered(|ui| {
                         ui.label(
-                            RichText::new("WARNING")
+                            RichText::new(tr("warning"))
                                 .size(20.0)
                                 .strong()
                                 .color(COLOR_DANGER),
                         );
                         ui.add_space(12.0);
-                        ui.label("Move to Recycle Bin is DISABLED.");
-                        ui.label("Files will be PERMANENTLY DELETED.");
-                        ui.label("This action cannot be undone.");
+                        ui.label(tr_args(
+                            "confirm_purge_version_folders",
+                            &[&count, &format_size(size)],
+                        ));
+                        ui.label(tr("confirm_line3"));
                         ui.add_space(20.0);
                         ui.horizontal(|ui| {
                             if ui
                                 .button(
-                                    RichText::new("Yes, Delete Files")
-                                        .strong()
-                                        .color(COLOR_DANGER),
+                                    RichText::new(tr("yes_delete")).strong().color(COLOR_DANGER),
                                 )
                                 .clicked()
                             {
-                                match action {
-                                    DeleteAction::Orphaned => {
-                                        self.run_orphaned_scan(true);
-                                        self.modal = Modal::None;
-                                    }
-                                    DeleteAction::OldVersions => {
-                                        // run_old_version_scan sets modal = FolderSelect;
-                                        // do not override it with None here
-                                        self.run_old_version_scan(true);
-                                    }
-                                }
+                                purge = true;
                             }
-                            if ui.button("Cancel").clicked() {
-                                self.modal = Modal::None;
+                            if ui.button(tr("cancel")).clicked() {
+                                self.version_folder_confirm = false;
                             }
                         });
                     });
+                    return;
+                }
+
+                if self.version_folders.len() < 2 {
+                    ui.label(
+                        RichText::new(tr("no_old_wabbajack_versions")).color(COLOR_TEXT_MUTED),
+                    );
+                } else {
+                    ui.label(
+                        RichText::new(tr("old_wabbajack_versions_body"))
+                            .size(11.0)
+                            .color(COLOR_TEXT_SECONDARY),
+                    );
+                    ui.add_space(4.0);
+                    egui::ScrollArea::vertical()
+                        .max_height(300.0)
+                        .show(ui, |ui| {
+                            egui::Grid::new("version_folders_grid")
+                                .num_columns(4)
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    for (folder, selected) in self
+                                        .version_folders
+                                        .iter()
+                                        .zip(&mut self.version_folder_selected)
+                                    {
+                                        if folder.is_newest {
+                                            ui.add_enabled(
+                                                false,
+                                                egui::Checkbox::new(
+                                                    &mut false,
+                                                    tr_args("newest_version", &[&folder.version]),
+                                                ),
+                                            );
+                                        } else {
+                                            ui.checkbox(selected, &folder.version);
+                                        }
+                                        ui.label(tr_args("n_modlists", &[&folder.modlists]));
+                                        ui.label(format_size(folder.size));
+                                        if folder.unique_modlists.is_empty() {
+                                            ui.label("");
+                                        } else {
+                                            ui.label(
+                                                RichText::new(tr_args(
+                                                    "n_modlists_moved",
+                                                    &[&folder.unique_modlists.len()],
+                                                ))
+                                                .size(11.0)
+                                                .color(COLOR_TEXT_MUTED),
+                                            )
+                                            .on_hover_text(folder.unique_modlists.join("\n"));
+                                        }
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                }
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    let delete = ui.add_enabled(
+                        count > 0 && !self.is_loading && !self.settings.safe_mode,
+                        egui::Button::new(
+                            RichText::new(tr_args(
+                                "delete_selected_backups",
+                                &[&count, &format_size(size)],
+                            ))
+                            .color(COLOR_TEXT_PRIMARY),
+                        )
+                        .fill(COLOR_DANGER),
+                    );
+                    let delete = if self.settings.safe_mode {
+                        delete.on_disabled_hover_text(tr("purge_locked_hint"))
+                    } else {
+                        delete
+                    };
+                    if delete.clicked() {
+                        self.version_folder_confirm = true;
+                    }
+                    if ui.button(tr("close")).clicked() {
+                        close = true;
+                    }
                 });
+            });
+
+        if purge {
+            self.version_folder_confirm = false;
+            self.purge_selected_version_folders();
         }
+        if close {
+            self.modal = Modal::None;
+        }
+    }
 
-        if self.modal == Modal::FolderSelect {
-            let is_clean = self.pending_delete_mode;
-            let dialog_desc = if is_clean {
-                "Select which game's download folder to clean old versions from:"
-            } else {
-                "Select which game's download folder to scan for old mod versions:"
-            };
-            egui::Window::new("Select Game Folder")
-                .collapsible(false)
-                .resizable(false)
-                .default_width(350.0)
-                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
-                .show(ctx, |ui| {
-                    ui.label(dialog_desc);
-                    ui.add_space(8.0);
-                    egui::ScrollArea::vertical()
-                        .max_height(200.0)
+    /// Bug report built from the log; with redaction, known folders become placeholders
+    fn issue_report(&self, max_log_lines: usize) -> String {
+        let redactions: Vec<(PathBuf, &str)> = if self.report_redact {
+            let home = std::env::var_os("USERPROFILE").or_else(|| std::env::var_os("HOME"));
+            self.downloads_dirs
+                .iter()
+                .map(|dir| (Some(dir.clone()), "<downloads>"))
+                .chain([
+                    (self.wabbajack_dir.clone(), "<wabbajack>"),
+                    (config_dir(), "<config>"),
+                    (home.map(PathBuf::from), "~"),
+                ])
+                .filter_map(|(path, placeholder)| path.map(|p| (p, placeholder)))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let log: Vec<String> = self
+            .log_messages
+            .iter()
+            .map(|(msg, _)| msg.clone())
+            .collect();
+        issue_report(&log, &self.game_folders, &redactions, max_log_lines)
+    }
+
+    fn render_report_issue(&mut self, ctx: &egui::Context) {
+        let mut report = self.issue_report(REPORT_LOG_LINES);
+        let mut close = false;
+
+        egui::Window::new(tr("report_issue"))
+            .collapsible(false)
+            .resizable(true)
+            .default_width(600.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new(tr("report_issue_note"))
+                        .size(11.0)
+                        .color(COLOR_TEXT_MUTED),
+                );
+                ui.checkbox(&mut self.report_redact, tr("redact_paths"))
+                    .on_hover_text(tr("redact_paths_hint"));
+                ui.add_space(8.0);
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        ui.add(
+                            egui::TextEdit::multiline(&mut report)
+                                .font(egui::TextStyle::Monospace)
+                                .desired_width(f32::INFINITY)
+                                .interactive(false),
+                        );
+                    });
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button(tr("copy_report")).clicked() {
+                        ui.ctx().copy_text(report.clone());
+                    }
+                    if ui
+                        .button(tr("open_github_issue"))
+                        .on_hover_text(tr("open_github_issue_hint"))
+                        .clicked()
+                    {
+                        let body = self.issue_report(ISSUE_URL_LOG_LINES);
+                        ui.ctx().open_url(egui::OpenUrl::new_tab(issue_url(
+                            &format!("Bug report (v{})", APP_VERSION),
+                            &body,
+                        )));
+                    }
+                    if ui.button(tr("close")).clicked() {
+                        close = true;
+                    }
+                });
+            });
+
+        if close {
+            self.modal = Modal::None;
+        }
+    }
+
+    /// Shows how the scanner interprets pasted or picked file names
+    fn render_diagnostics(&mut self, ctx: &egui::Context) {
+        let results = diagnose_filenames(&self.diagnostics_input);
+        let mut close = false;
+
+        egui::Window::new(tr("diagnostics"))
+            .collapsible(false)
+            .resizable(true)
+            .default_width(700.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new(tr("diagnostics_note"))
+                        .size(11.0)
+                        .color(COLOR_TEXT_MUTED),
+                );
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.diagnostics_input)
+                        .font(egui::TextStyle::Monospace)
+                        .desired_rows(4)
+                        .desired_width(f32::INFINITY),
+                );
+                ui.add_space(8.0);
+                egui::ScrollArea::both().max_height(300.0).show(ui, |ui| {
+                    egui::Grid::new("diagnostics_grid")
+                        .striped(true)
                         .show(ui, |ui| {
-                            for (i, folder) in self.game_folders.iter().enumerate() {
-                                let name = folder.file_name().unwrap_or_default().to_string_lossy();
-                                if ui
-                                    .selectable_label(self.selected_game_folder == Some(i), &*name)
-                                    .clicked()
-                                {
-                                    self.selected_game_folder = Some(i);
+                            for header in [
+                                "diag_file",
+                                "diag_result",
+                                "diag_mod_name",
+                                "diag_mod_id",
+                                "diag_file_id",
+                                "diag_version",
+                                "diag_uploaded",
+                            ] {
+                                ui.label(RichText::new(tr(header)).strong());
+                            }
+                            ui.end_row();
+                            for d in &results {
+                                ui.label(RichText::new(&d.file_name).size(11.0));
+                                let (verdict, color) = if d.is_partial_download {
+                                    (tr("diag_partial"), COLOR_WARNING)
+                                } else if !d.is_wabbajack_file {
+                                    (tr("diag_not_archive"), COLOR_DANGER)
+                                } else if d.parsed.is_none() {
+                                    (tr("diag_unparsed"), COLOR_WARNING)
+                                } else if d.unidentified {
+                                    (tr("diag_no_mod_id"), COLOR_WARNING)
+                                } else if d.parsed.as_ref().is_some_and(|f| f.is_patch) {
+                                    (tr("diag_patch"), COLOR_SUCCESS)
+                                } else {
+                                    (tr("diag_ok"), COLOR_SUCCESS)
+                                };
+                                ui.label(RichText::new(verdict).size(11.0).color(color));
+                                match &d.parsed {
+                                    Some(f) => {
+                                        ui.label(RichText::new(&f.mod_name).size(11.0));
+                                        ui.label(RichText::new(&f.mod_id).size(11.0));
+                                        ui.label(
+                                            RichText::new(f.file_id.as_deref().unwrap_or("-"))
+                                                .size(11.0),
+                                        );
+                                        ui.label(RichText::new(&f.version).size(11.0));
+                                        ui.label(
+                                            RichText::new(timestamp_to_date(&f.timestamp))
+                                                .size(11.0),
+                                        );
+                                    }
+                                    None => {
+                                        for _ in 0..5 {
+                                            ui.label(RichText::new("-").color(COLOR_TEXT_MUTED));
+                                        }
+                                    }
                                 }
+                                ui.end_row();
                             }
                         });
-                    ui.add_space(8.0);
-                    ui.horizontal(|ui| {
-                        let btn_label = if is_clean {
-                            "Start Clean"
-                        } else {
-                            "Start Scan"
-                        };
-                        let btn_color = if is_clean {
-                            COLOR_WARNING
-                        } else {
-                            COLOR_ACCENT
-                        };
-                        if ui
-                            .add_enabled(
-                                self.selected_game_folder.is_some(),
-                                egui::Button::new(btn_label).fill(btn_color),
-                            )
-                            .clicked()
-                        {
-                            self.start_old_version_scan();
+                });
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button(tr("pick_files")).clicked() {
+                        if let Some(paths) = rfd::FileDialog::new().pick_files() {
+                            for path in paths {
+                                if !self.diagnostics_input.is_empty()
+                                    && !self.diagnostics_input.ends_with('\n')
+                                {
+                                    self.diagnostics_input.push('\n');
+                                }
+                                self.diagnostics_input.push_str(
+                                    &path.file_name().unwrap_or_default().to_string_lossy(),
+                                );
+                                self.diagnostics_input.push('\n');
+                            }
                         }
-                        if ui.button("Cancel").clicked() {
-                            self.modal = Modal::None;
+                    }
+                    if ui
+                        .add_enabled(!results.is_empty(), egui::Button::new(tr("copy_report")))
+                        .clicked()
+                    {
+                        let text: Vec<String> = results.iter().map(|d| d.describe()).collect();
+                        ui.ctx().copy_text(text.join("\n"));
+                    }
+                    if ui.button(tr("close")).clicked() {
+                        close = true;
+                    }
+                });
+            });
+
+        if close {
+            self.modal = Modal::None;
+        }
+    }
+
+    fn render_review_old_versions(&mut self, ctx: &egui::Context) {
+        let Some(res) = &self.old_version_result else {
+            self.modal = Modal::None;
+            return;
+        };
+        let review_included = &mut self.review_included;
+        let keeper_overrides = &self.keeper_overrides;
+        let mut confirm = false;
+        let mut cancel = false;
+
+        egui::Window::new(tr("review_old_versions"))
+            .collapsible(false)
+            .resizable(true)
+            .default_width(600.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new(tr_args(
+                        "review_summary",
+                        &[
+                            &res.total_files,
+                            &res.duplicates.len(),
+                            &format_size(res.total_space),
+                        ],
+                    ))
+                    .strong()
+                    .color(COLOR_TEXT_PRIMARY),
+                );
+                ui.label(
+                    RichText::new(tr("review_keep_note"))
+                        .size(11.0)
+                        .color(COLOR_TEXT_MUTED),
+                );
+                ui.add_space(8.0);
+                egui::ScrollArea::vertical()
+                    .max_height(400.0)
+                    .auto_shrink([false, true])
+                    .show(ui, |ui| {
+                        for group in &res.duplicates {
+                            let group = with_keeper_override(group, keeper_overrides);
+                            ui.horizontal(|ui| {
+                                ui.label(
+                                    RichText::new(&group.mod_key)
+                                        .size(12.0)
+                                        .strong()
+                                        .color(COLOR_ACCENT),
+                                );
+                                ui.label(
+                                    RichText::new(format_size(group.space_to_free))
+                                        .size(11.0)
+                                        .color(COLOR_WARNING),
+                                );
+                                if group.review_recommended {
+                                    let mut include = review_included.contains(&group.mod_key);
+                                    if ui
+                                        .checkbox(
+                                            &mut include,
+                                            RichText::new(tr("review_recommended"))
+                                                .size(11.0)
+                                                .color(COLOR_WARNING),
+                                        )
+                                        .on_hover_text(tr("review_recommended_hint"))
+                                        .changed()
+                                    {
+                                        if include {
+                                            review_included.insert(group.mod_key.clone());
+                                        } else {
+                                            review_included.remove(&group.mod_key);
+                                        }
+                                    }
+                                }
+                            });
+                            let keep = &group.files[group.newest_idx];
+                            ui.label(
+                                RichText::new(format!("  {:<7}{}", tr("keep"), keep.file_name))
+                                    .size(11.0)
+                                    .strong()
+                                    .color(COLOR_SUCCESS),
+                            );
+                            for (i, f) in group.files.iter().enumerate() {
+                                if i == group.newest_idx {
+                                    continue;
+                                }
+                                ui.label(
+                                    RichText::new(format!(
+                                        "  {:<7}{} ({})",
+                                        tr("delete"),
+                                        f.file_name,
+                                        format_size(f.size)
+                                    ))
+                                    .size(11.0)
+                                    .color(COLOR_DANGER),
+                                );
+                            }
+                            ui.add_space(4.0);
                         }
                     });
+                ui.add_space(8.0);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .add(
+                            egui::Button::new(
+                                RichText::new(tr("confirm_clean")).color(COLOR_TEXT_PRIMARY),
+                            )
+                            .fill(COLOR_WARNING),
+                        )
+                        .clicked()
+                    {
+                        confirm = true;
+                    }
+                    if ui.button(tr("cancel")).clicked() {
+                        cancel = true;
+                    }
                 });
+            });
+
+        if confirm {
+            self.confirm_old_version_clean();
+        } else if cancel {
+            self.modal = Modal::None;
+            self.pending_delete_mode = false;
+            self.log(LogLevel::Info, "Old version cleanup cancelled.");
         }
     }
 }
 
 // Async helpers
-fn scan_wabbajack_dir(path: PathBuf, tx: Sender<AsyncMessage>) {
+
+/// Find modlists under a Wabbajack folder. With `dedup`, only the newest version folder's
+/// copy of each modlist is kept; otherwise every copy is listed, labeled with its version.
+fn scan_wabbajack_dir(path: PathBuf, dedup: bool, tx: OpSender) {
     tx.send(AsyncMessage::Progress("Scanning...".to_string(), None))
         .ok();
     let mut modlist_map: std::collections::HashMap<String, (PathBuf, String)> =
@@ -1067,30 +5934,51 @@ fn scan_wabbajack_dir(path: PathBuf, tx: Sender<AsyncMessage>) {
                 return;
             }
         };
+        let version_dirs: Vec<PathBuf> = entries
+            .flatten()
+            .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
+            .map(|e| e.path())
+            .collect();
 
-        for entry in entries.flatten() {
-            if !entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
-                continue;
-            }
-            let version_name = entry.file_name().to_string_lossy().to_string();
-            let modlists_path = entry.path().join("downloaded_mod_lists");
-            if modlists_path.exists() {
-                if let Ok(files) = find_wabbajack_files(&modlists_path) {
-                    for wbfile in files {
-                        let basename = wbfile
-                            .file_name()
-                            .map(|n| n.to_string_lossy().to_string())
-                            .unwrap_or_default();
-                        let key = basename;
-                        if modlist_map
-                            .get(&key)
-                            .map(|(_, v)| &version_name > v)
-                            .unwrap_or(true)
-                        {
-                            modlist_map.insert(key, (wbfile, version_name.clone()));
-                        }
-                    }
-                }
+        // Collect candidates per version folder in parallel, then keep the newest per modlist
+        let total = version_dirs.len();
+        let done = AtomicUsize::new(0);
+        let candidates: Vec<(String, PathBuf, String)> = version_dirs
+            .par_iter()
+            .flat_map_iter(|dir| {
+                let version_name = dir
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let files =
+                    find_wabbajack_files(&dir.join("downloaded_mod_lists")).unwrap_or_default();
+                let finished = done.fetch_add(1, AtomicOrdering::Relaxed) + 1;
+                tx.send(AsyncMessage::Progress(
+                    format!("Scanning version folder {}/{}...", finished, total),
+                    Some((finished, total)),
+                ))
+                .ok();
+                files.into_iter().map(move |wbfile| {
+                    let basename = wbfile
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    (basename, wbfile, version_name.clone())
+                })
+            })
+            .collect();
+
+        for (key, wbfile, version_name) in candidates {
+            if !dedup {
+                modlist_map.insert(format!("{}/{}", version_name, key), (wbfile, version_name));
+                continue;
+            }
+            let is_newer = modlist_map
+                .get(&key)
+                .map(|(_, v)| compare_versions(&version_name, v) == std::cmp::Ordering::Greater)
+                .unwrap_or(true);
+            if is_newer {
+                modlist_map.insert(key, (wbfile, version_name));
             }
         }
     }
@@ -1103,131 +5991,470 @@ fn scan_wabbajack_dir(path: PathBuf, tx: Sender<AsyncMessage>) {
 
     let total = modlist_map.len();
     let mut modlists = Vec::new();
-    for (i, (_, (p, _))) in modlist_map.into_iter().enumerate() {
+    for (i, (_, (p, version_name))) in modlist_map.into_iter().enumerate() {
         tx.send(AsyncMessage::Progress(
             "Parsing modlists...".to_string(),
             Some((i + 1, total)),
         ))
         .ok();
-        if let Ok(info) = parse_wabbajack_file(&p) {
+        if let Ok(mut info) = parse_wabbajack_file(&p) {
+            if !dedup && !version_name.is_empty() {
+                info.name = format!("{} [{}]", info.name, version_name);
+            }
             modlists.push(info);
         }
     }
     tx.send(AsyncMessage::ModlistsParsed(modlists)).ok();
 }
 
-fn scan_orphaned_mods_async(
-    path: PathBuf,
+/// `group` with the keeper picked by hand in `overrides`, if one is set and still in it
+fn with_keeper_override<'a>(
+    group: &'a ModGroup,
+    overrides: &HashMap<PathBuf, PathBuf>,
+) -> Cow<'a, ModGroup> {
+    overrides
+        .get(&group.files[group.newest_idx].full_path)
+        .and_then(|keeper| group.files.iter().position(|f| f.full_path == *keeper))
+        .and_then(|idx| group.with_keeper(idx))
+        .map_or(Cow::Borrowed(group), Cow::Owned)
+}
+
+/// Spacing and row height for `density`; takes effect from the next frame
+fn apply_density(ctx: &egui::Context, density: UiDensity) {
+    ctx.style_mut(|style| {
+        let spacing = &mut style.spacing;
+        match density {
+            UiDensity::Comfortable => {
+                spacing.item_spacing = Vec2::new(8.0, 6.0);
+                spacing.button_padding = Vec2::new(12.0, 6.0);
+                spacing.interact_size.y = 18.0;
+            }
+            UiDensity::Compact => {
+                spacing.item_spacing = Vec2::new(6.0, 2.0);
+                spacing.button_padding = Vec2::new(8.0, 2.0);
+                spacing.interact_size.y = 14.0;
+            }
+        }
+    });
+}
+
+fn density_label(density: UiDensity) -> &'static str {
+    match density {
+        UiDensity::Comfortable => tr("density_comfortable"),
+        UiDensity::Compact => tr("density_compact"),
+    }
+}
+
+/// Index downloads through the scan cache and apply manual IDs; errors are reported to the UI
+fn index_mod_files(folders: &[PathBuf], force_rescan: bool, tx: &OpSender) -> Option<Vec<ModFile>> {
+    let cache_path = scan_cache_path();
+    let mut cache = match (&cache_path, force_rescan) {
+        (Some(p), false) => ScanCache::load(p),
+        _ => ScanCache::default(),
+    };
+    let mut files = match get_all_mod_files_cached(folders, &mut cache) {
+        Ok(f) => f,
+        Err(e) => {
+            tx.send(AsyncMessage::Error(e.to_string())).ok();
+            return None;
+        }
+    };
+    if let Some(p) = &cache_path {
+        if let Err(e) = cache.save(p) {
+            log::warn!("Failed to save scan cache: {}", e);
+        }
+    }
+    if let Some(p) = manual_mapping_path() {
+        let applied = ManualMapping::load(&p).apply(&mut files);
+        if applied > 0 {
+            log::info!("Applied manual IDs to {} files", applied);
+        }
+    }
+    Some(files)
+}
+
+/// Modlists ticked after parsing: all except those without any ModID, which would
+/// protect far less than their mod count suggests and need an explicit opt-in
+fn default_modlist_selection(modlists: &[ModlistInfo]) -> Vec<bool> {
+    modlists.iter().map(|ml| !ml.has_no_mod_ids()).collect()
+}
+
+fn quick_scan_async(
+    folder: PathBuf,
     modlists: Vec<ModlistInfo>,
-    delete: bool,
-    recycle_bin: Option<PathBuf>,
-    tx: Sender<AsyncMessage>,
+    orphan_options: OrphanScanOptions,
+    old_options: OldVersionScanOptions,
+    tx: OpSender,
 ) {
     tx.send(AsyncMessage::Progress(
         "Indexing files...".to_string(),
         None,
     ))
     .ok();
-    let folders = match get_game_folders(&path) {
-        Ok(f) => f,
-        Err(e) => {
-            tx.send(AsyncMessage::Error(e.to_string())).ok();
-            return;
-        }
+    let folders = [folder.clone()];
+    let Some(files) = index_mod_files(&folders, false, &tx) else {
+        return;
     };
-    let files = match get_all_mod_files(&folders) {
-        Ok(f) => f,
+    tx.send(AsyncMessage::Progress(
+        format!("Analyzing {} files...", files.len()),
+        None,
+    ))
+    .ok();
+    let orphaned = detect_orphaned_mods_with_options(&files, &modlists, &orphan_options);
+    match scan_folders_for_duplicates_with_options(&folders, None, &old_options) {
+        Ok(old_versions) => {
+            tx.send(AsyncMessage::QuickScanComplete(
+                format!("Quick scan of {}", folder.display()),
+                orphaned,
+                old_versions,
+            ))
+            .ok();
+        }
         Err(e) => {
             tx.send(AsyncMessage::Error(e.to_string())).ok();
-            return;
         }
+    }
+}
+
+fn scan_orphaned_mods_async(
+    folders: Vec<PathBuf>,
+    modlists: Vec<ModlistInfo>,
+    options: OrphanScanOptions,
+    delete: Option<DeleteOptions>,
+    force_rescan: bool,
+    recycle_bins: Vec<(PathBuf, PathBuf)>,
+    tx: OpSender,
+) {
+    tx.send(AsyncMessage::Progress(
+        "Indexing files...".to_string(),
+        None,
+    ))
+    .ok();
+    let Some(files) = index_mod_files(&folders, force_rescan, &tx) else {
+        return;
     };
     tx.send(AsyncMessage::Progress(
         format!("Analyzing {} files...", files.len()),
         None,
     ))
     .ok();
-    let result = detect_orphaned_mods(&files, &modlists);
-    if delete && !result.orphaned_mods.is_empty() {
-        let total = result.orphaned_mods.len();
+    let result = detect_orphaned_mods_with_options(&files, &modlists, &options);
+    if let Some(delete_options) = delete.filter(|_| !result.orphaned_mods.is_empty()) {
+        delete_orphaned_async(result.orphaned_mods, recycle_bins, delete_options, tx);
+    } else {
+        tx.send(AsyncMessage::OrphanedScanComplete(result)).ok();
+    }
+}
+
+/// Split `items` by the downloads root they live under, paired with that root's recycle
+/// bin, since files can only be moved within their own drive. Items outside every root go
+/// to the first bin. Without recycle bins everything is one batch of permanent deletes.
+fn group_by_recycle_bin<T>(
+    items: Vec<T>,
+    recycle_bins: &[(PathBuf, PathBuf)],
+    path_of: impl Fn(&T) -> &Path,
+) -> Vec<(Option<PathBuf>, Vec<T>)> {
+    if recycle_bins.is_empty() {
+        return vec![(None, items)];
+    }
+    let roots: Vec<PathBuf> = recycle_bins.iter().map(|(root, _)| root.clone()).collect();
+    let mut groups: Vec<(Option<PathBuf>, Vec<T>)> = recycle_bins
+        .iter()
+        .map(|(_, bin)| (Some(bin.clone()), Vec::new()))
+        .collect();
+    for item in items {
+        let i = root_index_for(path_of(&item), &roots).unwrap_or(0);
+        groups[i].1.push(item);
+    }
+    groups.retain(|(_, batch)| !batch.is_empty());
+    groups
+}
+
+/// Journal every file a run is about to remove, with the recycle bin it goes to, so a
+/// crash mid-run can be resumed on the next start. A failed write only costs that.
+fn begin_journal<'a>(
+    options: &DeleteOptions,
+    files: impl Iterator<Item = &'a ModFile>,
+    recycle_bins: &[(PathBuf, PathBuf)],
+) {
+    let Some(journal) = &options.journal else {
+        return;
+    };
+    let roots: Vec<PathBuf> = recycle_bins.iter().map(|(root, _)| root.clone()).collect();
+    let entries = files
+        .map(|file| JournalEntry {
+            file: file.clone(),
+            recycle_bin: (!recycle_bins.is_empty()).then(|| {
+                let i = root_index_for(&file.full_path, &roots).unwrap_or(0);
+                recycle_bins[i].1.clone()
+            }),
+        })
+        .collect();
+    if let Err(e) = begin_deletion_journal(journal, entries) {
+        log::warn!("{:#}", e);
+    }
+}
+
+/// The run is over, finished or stopped, so there is nothing left to resume
+fn end_journal(options: &DeleteOptions) {
+    if let Some(journal) = &options.journal {
+        finish_deletion_journal(journal);
+    }
+}
+
+/// Progress callback for one batch of a cleanup that spans several recycle bins
+fn batch_progress(tx: &OpSender, done: usize, total: usize) -> impl Fn(usize, usize) {
+    let tx = tx.clone();
+    move |i: usize, _: usize| {
         tx.send(AsyncMessage::Progress(
-            "Cleaning...".to_string(),
-            Some((0, total)),
+            format!("Cleaning... {}/{}", done + i, total),
+            Some((done + i, total)),
         ))
         .ok();
-        let tx_cb = tx.clone();
-        let progress_cb = move |i: usize, t: usize| {
-            tx_cb
-                .send(AsyncMessage::Progress(
-                    format!("Cleaning... {}/{}", i, t),
-                    Some((i, t)),
-                ))
-                .ok();
-        };
-        let del = delete_orphaned_mods(
-            &result.orphaned_mods,
+    }
+}
+
+fn delete_orphaned_async(
+    orphaned: Vec<OrphanedMod>,
+    recycle_bins: Vec<(PathBuf, PathBuf)>,
+    options: DeleteOptions,
+    tx: OpSender,
+) {
+    let start = Instant::now();
+    let total = orphaned.len();
+    tx.send(AsyncMessage::Progress(
+        "Cleaning...".to_string(),
+        Some((0, total)),
+    ))
+    .ok();
+    begin_journal(&options, orphaned.iter().map(|m| &m.file), &recycle_bins);
+    let mut del = delete_orphaned_batches(orphaned, &recycle_bins, &options, &tx, (0, total));
+    end_journal(&options);
+    del.elapsed = start.elapsed();
+    tx.send(AsyncMessage::DeletionComplete(del)).ok();
+}
+
+/// Delete orphaned files one recycle bin at a time; `progress` is `(done, total)` of the
+/// whole run this is part of
+fn delete_orphaned_batches(
+    orphaned: Vec<OrphanedMod>,
+    recycle_bins: &[(PathBuf, PathBuf)],
+    options: &DeleteOptions,
+    tx: &OpSender,
+    progress: (usize, usize),
+) -> DeletionResult {
+    let (mut done, total) = progress;
+    let mut del = DeletionResult::default();
+    for (recycle_bin, batch) in group_by_recycle_bin(orphaned, recycle_bins, |m| &m.file.full_path)
+    {
+        let progress_cb = batch_progress(tx, done, total);
+        del.merge(delete_orphaned_mods_with_options(
+            &batch,
             recycle_bin.as_deref(),
             Some(&progress_cb),
-        );
-        tx.send(AsyncMessage::DeletionComplete(del)).ok();
-    } else {
-        tx.send(AsyncMessage::OrphanedScanComplete(result)).ok();
+            options,
+        ));
+        done += batch.len();
+        if del.cancelled {
+            break;
+        }
     }
+    del
 }
 
-fn scan_old_versions_async(
-    path: PathBuf,
-    delete: bool,
-    recycle_bin: Option<PathBuf>,
-    tx: Sender<AsyncMessage>,
-) {
+fn scan_old_versions_async(folders: Vec<PathBuf>, options: OldVersionScanOptions, tx: OpSender) {
     tx.send(AsyncMessage::Progress("Scanning...".to_string(), None))
         .ok();
-    let result = match scan_folder_for_duplicates(&path) {
-        Ok(r) => r,
+    let tx_scan = tx.clone();
+    let scan_cb = move |i: usize, t: usize| {
+        tx_scan
+            .send(AsyncMessage::Progress(
+                format!("Scanning folder {}/{}...", i, t),
+                Some((i, t)),
+            ))
+            .ok();
+    };
+    match scan_folders_for_duplicates_with_options(&folders, Some(&scan_cb), &options) {
+        Ok(result) => {
+            tx.send(AsyncMessage::OldVersionScanComplete(result)).ok();
+        }
         Err(e) => {
             tx.send(AsyncMessage::Error(e.to_string())).ok();
-            return;
         }
-    };
-    if delete && !result.duplicates.is_empty() {
-        let total = result.total_files;
-        tx.send(AsyncMessage::Progress(
-            "Cleaning...".to_string(),
-            Some((0, total)),
-        ))
-        .ok();
-        let tx_cb = tx.clone();
-        let progress_cb = move |i: usize, t: usize| {
-            tx_cb
-                .send(AsyncMessage::Progress(
-                    format!("Cleaning... {}/{}", i, t),
-                    Some((i, t)),
-                ))
-                .ok();
-        };
-        let del = delete_old_versions(
-            &result.duplicates,
+    }
+}
+
+fn delete_old_versions_async(
+    duplicates: Vec<ModGroup>,
+    recycle_bins: Vec<(PathBuf, PathBuf)>,
+    options: DeleteOptions,
+    tx: OpSender,
+) {
+    let start = Instant::now();
+    let total: usize = duplicates.iter().map(|g| g.newest_idx).sum();
+    tx.send(AsyncMessage::Progress(
+        "Cleaning...".to_string(),
+        Some((0, total)),
+    ))
+    .ok();
+    begin_journal(
+        &options,
+        duplicates.iter().flat_map(|g| g.deletable_files()),
+        &recycle_bins,
+    );
+    let mut del = delete_old_version_batches(duplicates, &recycle_bins, &options, &tx, (0, total));
+    end_journal(&options);
+    del.elapsed = start.elapsed();
+    tx.send(AsyncMessage::DeletionComplete(del)).ok();
+}
+
+/// Delete old versions one recycle bin at a time; `progress` as in `delete_orphaned_batches`
+fn delete_old_version_batches(
+    duplicates: Vec<ModGroup>,
+    recycle_bins: &[(PathBuf, PathBuf)],
+    options: &DeleteOptions,
+    tx: &OpSender,
+    progress: (usize, usize),
+) -> DeletionResult {
+    let (mut done, total) = progress;
+    let mut del = DeletionResult::default();
+    let groups = group_by_recycle_bin(duplicates, recycle_bins, |g| {
+        g.files.first().map_or(Path::new(""), |f| &f.full_path)
+    });
+    for (recycle_bin, batch) in groups {
+        let progress_cb = batch_progress(tx, done, total);
+        del.merge(delete_old_versions_with_options(
+            &batch,
             recycle_bin.as_deref(),
             Some(&progress_cb),
-        );
-        tx.send(AsyncMessage::DeletionComplete(del)).ok();
-    } else {
-        tx.send(AsyncMessage::OldVersionScanComplete(result)).ok();
+            options,
+        ));
+        done += batch.iter().map(|g| g.newest_idx).sum::<usize>();
+        if del.cancelled {
+            break;
+        }
+    }
+    del
+}
+
+/// Old-version groups to clean alongside the orphaned files. Orphaned files are left to
+/// the orphaned pass, and groups whose kept file is orphaned are dropped so the run never
+/// removes every version of a mod. Returns the groups and how many were dropped.
+fn plan_clean_all(orphaned: &[OrphanedMod], duplicates: &[ModGroup]) -> (Vec<ModGroup>, usize) {
+    let orphan_paths: HashSet<&Path> = orphaned
+        .iter()
+        .map(|m| m.file.full_path.as_path())
+        .collect();
+    let mut skipped = 0;
+    let mut groups = Vec::new();
+    for group in duplicates {
+        if orphan_paths.contains(group.files[group.newest_idx].full_path.as_path()) {
+            skipped += 1;
+            continue;
+        }
+        let mut files: Vec<ModFile> = group.files[..group.newest_idx]
+            .iter()
+            .filter(|f| !orphan_paths.contains(f.full_path.as_path()))
+            .cloned()
+            .collect();
+        if files.is_empty() {
+            continue;
+        }
+        let newest_idx = files.len();
+        let space_to_free = files.iter().map(|f| f.size).sum();
+        files.extend(group.files[group.newest_idx..].iter().cloned());
+        groups.push(ModGroup {
+            mod_key: group.mod_key.clone(),
+            files,
+            newest_idx,
+            space_to_free,
+            review_recommended: group.review_recommended,
+        });
     }
+    (groups, skipped)
+}
+
+/// Space a cleanup of both results would free: (orphaned, old versions). Old versions that
+/// are also orphaned count only once, on the orphaned side.
+fn reclaimable_sizes(orphaned: Option<&ScanResult>, old_versions: &[ModGroup]) -> (u64, u64) {
+    let orphans = orphaned.map_or(&[][..], |res| &res.orphaned_mods);
+    let (groups, _) = plan_clean_all(orphans, old_versions);
+    (
+        orphaned.map_or(0, |res| res.orphaned_size),
+        groups.iter().map(|g| g.space_to_free).sum(),
+    )
+}
+
+/// Orphaned files first, then old versions. Errors in one part do not stop the other;
+/// only cancelling does.
+fn delete_all_detected_async(
+    orphaned: Vec<OrphanedMod>,
+    duplicates: Vec<ModGroup>,
+    recycle_bins: Vec<(PathBuf, PathBuf)>,
+    options: DeleteOptions,
+    tx: OpSender,
+) {
+    let orphaned_count = orphaned.len();
+    let total = orphaned_count + duplicates.iter().map(|g| g.newest_idx).sum::<usize>();
+    tx.send(AsyncMessage::Progress(
+        "Cleaning...".to_string(),
+        Some((0, total)),
+    ))
+    .ok();
+    begin_journal(
+        &options,
+        orphaned
+            .iter()
+            .map(|m| &m.file)
+            .chain(duplicates.iter().flat_map(|g| g.deletable_files())),
+        &recycle_bins,
+    );
+    let start = Instant::now();
+    let mut orphaned_del =
+        delete_orphaned_batches(orphaned, &recycle_bins, &options, &tx, (0, total));
+    orphaned_del.elapsed = start.elapsed();
+    let start = Instant::now();
+    let mut old_del = if orphaned_del.cancelled {
+        DeletionResult::default()
+    } else {
+        delete_old_version_batches(
+            duplicates,
+            &recycle_bins,
+            &options,
+            &tx,
+            (orphaned_count, total),
+        )
+    };
+    old_del.elapsed = start.elapsed();
+    end_journal(&options);
+    tx.send(AsyncMessage::CleanAllComplete(orphaned_del, old_del))
+        .ok();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::{parse_mod_filename, RECYCLE_BIN_DIR_NAME};
     use std::fs::{self, File};
     use std::io::Write;
-    use std::sync::mpsc;
     use tempfile::TempDir;
     use zip::write::SimpleFileOptions;
     use zip::ZipWriter;
 
+    fn test_channel() -> (OpSender, Receiver<(u64, AsyncMessage)>) {
+        let (tx, rx) = channel();
+        let sender = OpSender {
+            generation: BACKGROUND_GENERATION,
+            tx,
+        };
+        (sender, rx)
+    }
+
     fn create_dummy_wabbajack(path: &std::path::Path, name: &str) {
+        create_wabbajack_with_archives(path, name, "[]");
+    }
+
+    fn create_wabbajack_with_archives(path: &std::path::Path, name: &str, archives: &str) {
         let file = File::create(path).unwrap();
         let mut zip = ZipWriter::new(file);
         let options =
@@ -1238,14 +6465,356 @@ mod tests {
                 "Name": "{}",
                 "Version": "1.0.0",
                 "Author": "TestAuthor",
-                "Archives": []
+                "Archives": {}
             }}"#,
-            name
+            name, archives
         );
         zip.write_all(json.as_bytes()).unwrap();
         zip.finish().unwrap();
     }
 
+    fn orphaned_scope(folders: &[&str], modlists: &[&str], extensions: &[&str]) -> ResultScope {
+        ResultScope::new(
+            vec![PathBuf::from("D:/Downloads")],
+            folders.iter().map(PathBuf::from).collect(),
+            modlists.iter().map(PathBuf::from).collect(),
+            ScopeOptions::Orphaned(OrphanScanOptions {
+                protected_extensions: extensions.iter().map(|e| e.to_string()).collect(),
+                ..Default::default()
+            }),
+        )
+    }
+
+    #[test]
+    fn test_clean_reuses_result_only_for_same_selection() {
+        let shown = orphaned_scope(&["Skyrim", "Fallout4"], &["a.wabbajack"], &[".exe"]);
+
+        // Same selection in a different order is still fresh
+        let same = orphaned_scope(&["Fallout4", "Skyrim"], &["a.wabbajack"], &[".exe"]);
+        assert!(can_reuse_result(Some(&shown), &same));
+
+        // Never scanned, or anything that changes the classified set, forces a rescan
+        assert!(!can_reuse_result(None, &same));
+        let fewer_folders = orphaned_scope(&["Skyrim"], &["a.wabbajack"], &[".exe"]);
+        assert!(!can_reuse_result(Some(&shown), &fewer_folders));
+        let other_modlist = orphaned_scope(&["Skyrim", "Fallout4"], &["b.wabbajack"], &[".exe"]);
+        assert!(!can_reuse_result(Some(&shown), &other_modlist));
+        let other_extensions = orphaned_scope(&["Skyrim", "Fallout4"], &["a.wabbajack"], &[]);
+        assert!(!can_reuse_result(Some(&shown), &other_extensions));
+    }
+
+    #[test]
+    fn test_cleanup_batches_use_recycle_bin_of_own_root() {
+        let bins = vec![
+            (
+                PathBuf::from("C:/Downloads"),
+                PathBuf::from("C:/Downloads/WLC_RecycleBin"),
+            ),
+            (
+                PathBuf::from("E:/Mods"),
+                PathBuf::from("E:/Mods/WLC_RecycleBin"),
+            ),
+        ];
+        let files = vec![
+            PathBuf::from("E:/Mods/Skyrim/a.7z"),
+            PathBuf::from("C:/Downloads/Skyrim/b.7z"),
+            PathBuf::from("E:/Mods/Fallout4/c.7z"),
+        ];
+
+        let groups = group_by_recycle_bin(files.clone(), &bins, |p| p.as_path());
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0.as_ref(), Some(&bins[0].1));
+        assert_eq!(groups[0].1, vec![files[1].clone()]);
+        assert_eq!(groups[1].0.as_ref(), Some(&bins[1].1));
+        assert_eq!(groups[1].1, vec![files[0].clone(), files[2].clone()]);
+
+        // Permanent deletion needs no split
+        let groups = group_by_recycle_bin(files.clone(), &[], |p| p.as_path());
+        assert_eq!(groups, vec![(None, files)]);
+    }
+
+    #[test]
+    fn test_clean_all_runs_both_parts_despite_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let game = temp_dir.path().join("Skyrim");
+        fs::create_dir(&game).unwrap();
+        let file = |name: &str| {
+            let path = game.join(name);
+            fs::write(&path, b"data").unwrap();
+            ModFile {
+                full_path: path,
+                size: 4,
+                ..parse_mod_filename(name).unwrap()
+            }
+        };
+        let old = file("ModA-11111-1-0-1600000000.7z");
+        let new = file("ModA-11111-2-0-1700000000.7z");
+        let orphan_old = file("ModB-22222-1-0-1600000000.7z");
+        let orphan_new = file("ModB-22222-2-0-1700000000.7z");
+        let group = |files: Vec<ModFile>| ModGroup {
+            mod_key: files[0].mod_id.clone(),
+            space_to_free: 4,
+            newest_idx: 1,
+            review_recommended: false,
+            files,
+        };
+        let duplicates = vec![
+            group(vec![old.clone(), new.clone()]),
+            group(vec![orphan_old.clone(), orphan_new.clone()]),
+        ];
+        let gone = ModFile {
+            full_path: game.join("Gone-33333-1-0-1600000000.7z"),
+            ..orphan_old.clone()
+        };
+        let orphaned: Vec<OrphanedMod> = [orphan_old, orphan_new, gone]
+            .into_iter()
+            .map(|file| OrphanedMod { file })
+            .collect();
+
+        // The group whose kept file is orphaned is left to the orphaned pass
+        let (planned, skipped) = plan_clean_all(&orphaned, &duplicates);
+        assert_eq!(skipped, 1);
+        assert_eq!(planned.len(), 1);
+        let paths: Vec<&PathBuf> = planned[0].files.iter().map(|f| &f.full_path).collect();
+        assert_eq!(paths, [&old.full_path, &new.full_path]);
+
+        // A missing orphaned file fails without stopping the old-version part
+        let (tx, rx) = test_channel();
+        delete_all_detected_async(
+            orphaned,
+            planned,
+            Vec::new(),
+            DeleteOptions::new(vec![temp_dir.path().to_path_buf()]),
+            tx,
+        );
+        let (orphaned_del, old_del) = rx
+            .iter()
+            .find_map(|(_, msg)| match msg {
+                AsyncMessage::CleanAllComplete(a, b) => Some((a, b)),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(orphaned_del.deleted_count, 2);
+        assert_eq!(orphaned_del.errors.len(), 1);
+        assert_eq!(old_del.deleted_count, 1);
+        assert!(!old.full_path.exists());
+        assert!(new.full_path.exists());
+    }
+
+    #[test]
+    fn test_reclaimable_sizes_count_overlap_once() {
+        let file = |name: &str, size: u64| ModFile {
+            full_path: PathBuf::from(name),
+            size,
+            ..parse_mod_filename(name).unwrap()
+        };
+        let old = file("ModA-11111-1-0-1600000000.7z", 30);
+        let older = file("ModA-11111-0-9-1500000000.7z", 20);
+        let new = file("ModA-11111-2-0-1700000000.7z", 40);
+        let group = ModGroup {
+            mod_key: "11111".to_string(),
+            files: vec![older.clone(), old, new],
+            newest_idx: 2,
+            space_to_free: 50,
+            review_recommended: false,
+        };
+        assert_eq!(
+            reclaimable_sizes(None, std::slice::from_ref(&group)),
+            (0, 50)
+        );
+
+        // The older version is also orphaned, so it only counts there
+        let orphaned = ScanResult {
+            orphaned_mods: vec![OrphanedMod { file: older }],
+            orphaned_size: 20,
+            used_mods: Vec::new(),
+            used_size: 0,
+            recent_mods: Vec::new(),
+            recent_size: 0,
+            small_mods: Vec::new(),
+            small_size: 0,
+        };
+        assert_eq!(reclaimable_sizes(Some(&orphaned), &[group]), (20, 30));
+        assert_eq!(reclaimable_sizes(Some(&orphaned), &[]), (20, 0));
+    }
+
+    #[test]
+    fn test_result_history_is_bounded_and_newest_first() {
+        let mut app = WabbajackCleanerApp::default();
+        for i in 0..RESULT_HISTORY_LIMIT + 2 {
+            app.viewed_history = Some(0);
+            app.push_result_history(format!("Run {}", i), None, None);
+        }
+        assert_eq!(app.result_history.len(), RESULT_HISTORY_LIMIT);
+        assert_eq!(
+            app.result_history[0].folders,
+            format!("Run {}", RESULT_HISTORY_LIMIT + 1)
+        );
+        assert_eq!(app.viewed_history, None);
+
+        let folders: Vec<PathBuf> = ["D:/dl/Skyrim", "D:/dl/Fallout4", "D:/dl/Oblivion"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+        assert_eq!(folder_names_label(&folders[..1]), "Skyrim");
+        assert_eq!(folder_names_label(&folders), "Skyrim, Fallout4, +1");
+    }
+
+    #[test]
+    fn test_superseded_operation_messages_ignored() {
+        let mut app = WabbajackCleanerApp::default();
+        let stats = |files| LibraryStats {
+            total_files: files,
+            ..Default::default()
+        };
+
+        // A second analysis starts before the first one reports back
+        app.begin_operation();
+        let first = app.sender();
+        app.begin_operation();
+        let second = app.sender();
+        second.send(AsyncMessage::StatsComplete(stats(2))).ok();
+        first.send(AsyncMessage::StatsComplete(stats(1))).ok();
+        first
+            .send(AsyncMessage::Progress("stale".to_string(), Some((1, 2))))
+            .ok();
+        app.handle_messages();
+        assert_eq!(app.stats.as_ref().map(|s| s.total_files), Some(2));
+        assert!(!app.is_loading);
+        assert_eq!(app.progress, None);
+
+        // Background messages are never stale
+        app.begin_operation();
+        app.background_sender()
+            .send(AsyncMessage::StatsComplete(stats(3)))
+            .ok();
+        app.handle_messages();
+        assert_eq!(app.stats.as_ref().map(|s| s.total_files), Some(3));
+
+        // Work the app starts itself waits for the running operation
+        app.begin_operation();
+        assert!(app.defer(QueuedOperation::Analysis));
+        assert!(app.defer(QueuedOperation::Analysis));
+        assert_eq!(app.queued_operations.len(), 1);
+        app.is_loading = false;
+        assert!(!app.defer(QueuedOperation::Analysis));
+        assert!(app.queued_operations.is_empty());
+    }
+
+    /// Handle messages and start queued work until nothing is left running
+    fn run_until_idle(app: &mut WabbajackCleanerApp) {
+        let start = Instant::now();
+        while app.is_loading || !app.queued_operations.is_empty() {
+            assert!(
+                start.elapsed() < Duration::from_secs(10),
+                "operations never finished"
+            );
+            app.handle_messages();
+            app.run_queued_operation();
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_results_refreshed_after_deletion() {
+        let temp_dir = TempDir::new().unwrap();
+        let skyrim = temp_dir.path().join("Skyrim");
+        fs::create_dir(&skyrim).unwrap();
+        let old = skyrim.join("ModA-11111-1-0-1600000000.7z");
+        fs::write(&old, b"old data").unwrap();
+        fs::write(skyrim.join("ModA-11111-2-0-1700000000.7z"), b"new data").unwrap();
+
+        let mut app = WabbajackCleanerApp {
+            downloads_dirs: vec![temp_dir.path().to_path_buf()],
+            game_folders: vec![skyrim],
+            game_folder_selected: vec![true],
+            ..Default::default()
+        };
+        app.start_old_version_scan();
+        run_until_idle(&mut app);
+        assert_eq!(app.old_version_result.as_ref().unwrap().total_files, 1);
+
+        // A quick scan result has no scan to repeat
+        app.orphaned_result = Some(ScanResult {
+            used_mods: Vec::new(),
+            orphaned_mods: Vec::new(),
+            used_size: 0,
+            orphaned_size: 0,
+            recent_mods: Vec::new(),
+            recent_size: 0,
+            small_mods: Vec::new(),
+            small_size: 0,
+        });
+
+        fs::remove_file(&old).unwrap();
+        app.finish_deletion(DeletionResult {
+            deleted_count: 1,
+            ..Default::default()
+        });
+        run_until_idle(&mut app);
+
+        let refreshed = app.old_version_result.as_ref().unwrap();
+        assert!(refreshed.duplicates.is_empty());
+        assert!(app.old_version_result_fresh());
+        assert!(app.orphaned_result.is_none());
+
+        // A result from another selection is dropped, not rescanned with the current one
+        app.game_folder_selected = vec![false];
+        app.finish_deletion(DeletionResult::default());
+        run_until_idle(&mut app);
+        assert!(app.old_version_result.is_none());
+    }
+
+    #[test]
+    fn test_safe_mode_keeps_purged_folders() {
+        let dir = TempDir::new().unwrap();
+        for version in ["3.9.0.0", "3.10.0.0"] {
+            fs::create_dir_all(dir.path().join(version)).unwrap();
+            fs::write(dir.path().join(version).join("Wabbajack.exe"), b"x").unwrap();
+        }
+        let run = dir
+            .path()
+            .join(RECYCLE_BIN_DIR_NAME)
+            .join("20240101_120000");
+        fs::create_dir_all(&run).unwrap();
+        fs::write(run.join("Mod-1-1-0-1600000000.7z"), b"data").unwrap();
+
+        let mut app = WabbajackCleanerApp::default();
+        app.settings.safe_mode = true;
+        app.version_folders = scan_wabbajack_version_folders(dir.path());
+        app.version_folder_selected = vec![true; app.version_folders.len()];
+        app.backups = list_backups(dir.path());
+        app.backup_selected = vec![true; app.backups.len()];
+        assert_eq!(app.backups.len(), 1);
+
+        app.purge_selected_version_folders();
+        app.purge_selected_backups();
+        run_until_idle(&mut app);
+        assert!(dir.path().join("3.9.0.0").exists());
+        assert!(run.join("Mod-1-1-0-1600000000.7z").exists());
+    }
+
+    #[test]
+    fn test_old_version_result_stale_after_option_change() {
+        let scope = |keep_by| {
+            ResultScope::new(
+                vec![PathBuf::from("D:/Downloads")],
+                vec![PathBuf::from("Skyrim")],
+                Vec::new(),
+                ScopeOptions::OldVersions(OldVersionScanOptions {
+                    keep_by,
+                    ..Default::default()
+                }),
+            )
+        };
+        let shown = scope(KeepBy::NewestUpload);
+        assert!(can_reuse_result(Some(&shown), &scope(KeepBy::NewestUpload)));
+        assert!(!can_reuse_result(
+            Some(&shown),
+            &scope(KeepBy::HighestVersion)
+        ));
+    }
+
     #[test]
     fn test_scan_wabbajack_dir_direct_files() {
         let temp_dir = TempDir::new().unwrap();
@@ -1255,12 +6824,12 @@ mod tests {
         let file_path = path.join("TestModlist@@Game.wabbajack");
         create_dummy_wabbajack(&file_path, "TestModlist");
 
-        let (tx, rx) = mpsc::channel();
-        scan_wabbajack_dir(path.to_path_buf(), tx);
+        let (tx, rx) = test_channel();
+        scan_wabbajack_dir(path.to_path_buf(), true, tx);
 
         // Expect ModlistsParsed message
         let mut parsed = false;
-        while let Ok(msg) = rx.recv() {
+        while let Ok((_, msg)) = rx.recv() {
             if let AsyncMessage::ModlistsParsed(modlists) = msg {
                 assert_eq!(modlists.len(), 1);
                 assert_eq!(modlists[0].name, "TestModlist");
@@ -1271,6 +6840,41 @@ mod tests {
         assert!(parsed);
     }
 
+    #[test]
+    fn test_modlist_without_mod_ids_not_selected_by_default() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+        create_wabbajack_with_archives(
+            &path.join("Nexus@@Game.wabbajack"),
+            "Nexus",
+            r#"[{"Name": "SkyUI-12604-5-2-1615410779.7z",
+                 "State": {"ModID": 12604, "FileID": 52344}}]"#,
+        );
+        create_wabbajack_with_archives(
+            &path.join("Elsewhere@@Game.wabbajack"),
+            "Elsewhere",
+            r#"[{"Name": "Tool.zip", "State": {"Url": "https://example.com/Tool.zip"}},
+                {"Name": "Patch.7z", "State": {}}]"#,
+        );
+
+        let (tx, rx) = test_channel();
+        scan_wabbajack_dir(path.to_path_buf(), true, tx);
+        let mut modlists = rx
+            .iter()
+            .find_map(|(_, msg)| match msg {
+                AsyncMessage::ModlistsParsed(modlists) => Some(modlists),
+                _ => None,
+            })
+            .unwrap();
+        modlists.sort_by(|a, b| a.name.cmp(&b.name));
+
+        assert_eq!(modlists[0].name, "Elsewhere");
+        assert_eq!(modlists[0].mod_count, 2);
+        assert!(modlists[0].has_no_mod_ids());
+        assert!(!modlists[1].has_no_mod_ids());
+        assert_eq!(default_modlist_selection(&modlists), [false, true]);
+    }
+
     #[test]
     fn test_scan_wabbajack_dir_direct_subdir() {
         let temp_dir = TempDir::new().unwrap();
@@ -1283,11 +6887,11 @@ mod tests {
         let file_path = sub_dir.join("TestModlist@@Game.wabbajack");
         create_dummy_wabbajack(&file_path, "TestModlist");
 
-        let (tx, rx) = mpsc::channel();
-        scan_wabbajack_dir(path.to_path_buf(), tx);
+        let (tx, rx) = test_channel();
+        scan_wabbajack_dir(path.to_path_buf(), true, tx);
 
         let mut parsed = false;
-        while let Ok(msg) = rx.recv() {
+        while let Ok((_, msg)) = rx.recv() {
             if let AsyncMessage::ModlistsParsed(modlists) = msg {
                 assert_eq!(modlists.len(), 1);
                 assert_eq!(modlists[0].name, "TestModlist");
@@ -1316,11 +6920,11 @@ mod tests {
         let file_path2 = v2_dir.join("TestModlist@@Game.wabbajack");
         create_dummy_wabbajack(&file_path2, "TestModlistV2");
 
-        let (tx, rx) = mpsc::channel();
-        scan_wabbajack_dir(path.to_path_buf(), tx);
+        let (tx, rx) = test_channel();
+        scan_wabbajack_dir(path.to_path_buf(), true, tx);
 
         let mut parsed = false;
-        while let Ok(msg) = rx.recv() {
+        while let Ok((_, msg)) = rx.recv() {
             if let AsyncMessage::ModlistsParsed(modlists) = msg {
                 assert_eq!(modlists.len(), 1);
                 // Should keep the one from the higher version (3.6.0.0)
@@ -1331,4 +6935,70 @@ mod tests {
         }
         assert!(parsed);
     }
+
+    #[test]
+    fn test_scan_wabbajack_dir_many_version_folders() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+
+        // Lexically "3.9.0.0" > "3.10.0.0", numerically it is older
+        for (version, list_name) in [
+            ("3.9.0.0", "SharedV39"),
+            ("3.10.0.0", "SharedV310"),
+            ("3.2.0.0", "SharedV32"),
+        ] {
+            let dir = path.join(version).join("downloaded_mod_lists");
+            fs::create_dir_all(&dir).unwrap();
+            create_dummy_wabbajack(&dir.join("Shared@@Game.wabbajack"), list_name);
+        }
+        let only_old = path.join("3.2.0.0").join("downloaded_mod_lists");
+        create_dummy_wabbajack(&only_old.join("Legacy@@Game.wabbajack"), "Legacy");
+        let only_new = path.join("3.10.0.0").join("downloaded_mod_lists");
+        create_dummy_wabbajack(&only_new.join("Fresh@@Game.wabbajack"), "Fresh");
+
+        let (tx, rx) = test_channel();
+        scan_wabbajack_dir(path.to_path_buf(), true, tx);
+
+        let mut version_progress = 0;
+        let mut names = Vec::new();
+        while let Ok((_, msg)) = rx.recv() {
+            match msg {
+                AsyncMessage::Progress(text, Some((_, 3))) if text.contains("version folder") => {
+                    version_progress += 1;
+                }
+                AsyncMessage::ModlistsParsed(modlists) => {
+                    names = modlists.into_iter().map(|m| m.name).collect();
+                    break;
+                }
+                _ => {}
+            }
+        }
+        names.sort();
+        assert_eq!(names, vec!["Fresh", "Legacy", "SharedV310"]);
+        assert_eq!(version_progress, 3);
+    }
+
+    #[test]
+    fn test_scan_wabbajack_dir_all_versions_without_dedup() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path();
+        for (version, list_name) in [("3.9.0.0", "Shared"), ("3.10.0.0", "Shared")] {
+            let dir = path.join(version).join("downloaded_mod_lists");
+            fs::create_dir_all(&dir).unwrap();
+            create_dummy_wabbajack(&dir.join("Shared@@Game.wabbajack"), list_name);
+        }
+
+        let (tx, rx) = test_channel();
+        scan_wabbajack_dir(path.to_path_buf(), false, tx);
+
+        let mut names = Vec::new();
+        while let Ok((_, msg)) = rx.recv() {
+            if let AsyncMessage::ModlistsParsed(modlists) = msg {
+                names = modlists.into_iter().map(|m| m.name).collect();
+                break;
+            }
+        }
+        names.sort();
+        assert_eq!(names, vec!["Shared [3.10.0.0]", "Shared [3.9.0.0]"]);
+    }
 }
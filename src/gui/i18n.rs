@@ -0,0 +1,155 @@
+// Copyright (C) 2025 Berkay Yetgin
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+//! UI string tables embedded from `lang/*.json`, falling back to English per key
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+/// A selectable UI language
+pub struct Language {
+    pub code: &'static str,
+    pub name: &'static str,
+    source: &'static str,
+}
+
+/// Available languages; the first entry is the fallback
+pub const LANGUAGES: &[Language] = &[
+    Language {
+        code: "en",
+        name: "English",
+        source: include_str!("lang/en.json"),
+    },
+    Language {
+        code: "de",
+        name: "Deutsch",
+        source: include_str!("lang/de.json"),
+    },
+    Language {
+        code: "tr",
+        name: "Türkçe",
+        source: include_str!("lang/tr.json"),
+    },
+];
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+fn tables() -> &'static [HashMap<String, String>] {
+    static TABLES: OnceLock<Vec<HashMap<String, String>>> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        LANGUAGES
+            .iter()
+            .map(|lang| {
+                serde_json::from_str(lang.source).unwrap_or_else(|e| {
+                    log::warn!("Invalid translation table '{}': {}", lang.code, e);
+                    HashMap::new()
+                })
+            })
+            .collect()
+    })
+}
+
+/// Switch the UI language; unknown codes fall back to English
+pub fn set_language(code: &str) {
+    let idx = LANGUAGES.iter().position(|l| l.code == code).unwrap_or(0);
+    CURRENT.store(idx, Ordering::Relaxed);
+}
+
+pub fn current_language() -> &'static Language {
+    &LANGUAGES[CURRENT.load(Ordering::Relaxed)]
+}
+
+fn lookup<'a>(tables: &'a [HashMap<String, String>], lang_idx: usize, key: &'a str) -> &'a str {
+    tables[lang_idx]
+        .get(key)
+        .or_else(|| tables[0].get(key))
+        .map(String::as_str)
+        .unwrap_or(key)
+}
+
+/// Translated UI string for `key`
+pub fn tr(key: &'static str) -> &'static str {
+    lookup(tables(), CURRENT.load(Ordering::Relaxed), key)
+}
+
+/// Translated UI string with each `{}` replaced by the next argument
+pub fn tr_args(key: &'static str, args: &[&dyn Display]) -> String {
+    let mut out = String::new();
+    let mut args = args.iter();
+    let mut parts = tr(key).split("{}");
+    if let Some(first) = parts.next() {
+        out.push_str(first);
+    }
+    for part in parts {
+        if let Some(arg) = args.next() {
+            out.push_str(&arg.to_string());
+        }
+        out.push_str(part);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translations_only_use_known_keys() {
+        let tables = tables();
+        assert!(!tables[0].is_empty(), "English table must parse");
+        for (lang, table) in LANGUAGES.iter().zip(tables).skip(1) {
+            assert!(!table.is_empty(), "{} table must parse", lang.code);
+            for (key, value) in table {
+                let english = tables[0]
+                    .get(key)
+                    .unwrap_or_else(|| panic!("{}: unknown key '{}'", lang.code, key));
+                assert_eq!(
+                    english.matches("{}").count(),
+                    value.matches("{}").count(),
+                    "{}: placeholder mismatch for '{}'",
+                    lang.code,
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_translations_use_plain_placeholders() {
+        // `tr_args` only fills `{}`; anything else inside braces would be shown as is
+        for (lang, table) in LANGUAGES.iter().zip(tables()) {
+            for (key, value) in table {
+                assert_eq!(
+                    value.matches('{').count(),
+                    value.matches("{}").count(),
+                    "{}: placeholder other than {{}} in '{}'",
+                    lang.code,
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_missing_key_falls_back_to_english() {
+        let table = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+        let tables = vec![
+            table(&[("analyze", "Analyze"), ("clean", "Clean")]),
+            table(&[("analyze", "Analysieren")]),
+        ];
+        assert_eq!(lookup(&tables, 1, "analyze"), "Analysieren");
+        assert_eq!(lookup(&tables, 1, "clean"), "Clean");
+        assert_eq!(lookup(&tables, 1, "no_such_key"), "no_such_key");
+    }
+}
@@ -6,5 +6,7 @@
 // (at your option) any later version.
 
 pub mod app;
+pub mod i18n;
+pub mod tray;
 
 pub use app::WabbajackCleanerApp;
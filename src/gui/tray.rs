@@ -0,0 +1,111 @@
+// Copyright (C) 2025 Berkay Yetgin
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+//! System tray icon and completion toasts (Windows only, no-ops elsewhere)
+
+pub const APP_NAME: &str = "Wabbajack Library Cleaner";
+
+/// Tray icon whose tooltip reflects whether a scan or cleanup is running
+pub struct Tray {
+    #[cfg(target_os = "windows")]
+    icon: tray_icon::TrayIcon,
+    busy: bool,
+}
+
+impl Tray {
+    /// Create the tray icon, or `None` where unsupported
+    pub fn new() -> Option<Self> {
+        #[cfg(target_os = "windows")]
+        {
+            let icon = tray_icon::TrayIconBuilder::new()
+                .with_tooltip(APP_NAME)
+                .with_icon(load_tray_icon()?)
+                .build()
+                .map_err(|e| log::warn!("Failed to create tray icon: {}", e))
+                .ok()?;
+            Some(Self { icon, busy: false })
+        }
+
+        #[cfg(not(target_os = "windows"))]
+        None
+    }
+
+    pub fn set_busy(&mut self, busy: bool) {
+        if self.busy == busy {
+            return;
+        }
+        self.busy = busy;
+
+        #[cfg(target_os = "windows")]
+        {
+            let tooltip = if busy {
+                format!("{} - working...", APP_NAME)
+            } else {
+                APP_NAME.to_string()
+            };
+            if let Err(e) = self.icon.set_tooltip(Some(tooltip)) {
+                log::warn!("Failed to update tray tooltip: {}", e);
+            }
+        }
+    }
+
+    /// Whether the tray icon was left-clicked since the last call
+    pub fn take_clicked(&self) -> bool {
+        #[cfg(target_os = "windows")]
+        {
+            use tray_icon::{MouseButton, MouseButtonState, TrayIconEvent};
+
+            let mut clicked = false;
+            while let Ok(event) = TrayIconEvent::receiver().try_recv() {
+                if let TrayIconEvent::Click {
+                    button: MouseButton::Left,
+                    button_state: MouseButtonState::Up,
+                    ..
+                } = event
+                {
+                    clicked = true;
+                }
+            }
+            clicked
+        }
+
+        #[cfg(not(target_os = "windows"))]
+        false
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn load_tray_icon() -> Option<tray_icon::Icon> {
+    let icon_bytes = include_bytes!("../../winres/icon_main.png");
+    let image = image::load_from_memory(icon_bytes)
+        .map_err(|e| log::warn!("Failed to decode tray icon: {}", e))
+        .ok()?
+        .to_rgba8();
+    let (width, height) = image.dimensions();
+    tray_icon::Icon::from_rgba(image.into_raw(), width, height)
+        .map_err(|e| log::warn!("Invalid tray icon: {}", e))
+        .ok()
+}
+
+/// Show a desktop notification (best effort)
+pub fn notify(title: &str, body: &str) {
+    #[cfg(target_os = "windows")]
+    {
+        use tauri_winrt_notification::Toast;
+
+        if let Err(e) = Toast::new(Toast::POWERSHELL_APP_ID)
+            .title(title)
+            .text1(body)
+            .show()
+        {
+            log::warn!("Failed to show notification: {}", e);
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    let _ = (title, body);
+}
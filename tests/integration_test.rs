@@ -6,8 +6,10 @@ use std::io::Write;
 use std::path::Path;
 use tempfile::TempDir;
 use wabbajack_library_cleaner::core::{
-    delete_old_versions, delete_orphaned_mods, detect_orphaned_mods, get_all_mod_files,
-    parse_wabbajack_file, scan_folder_for_duplicates, OrphanedMod,
+    delete_old_versions, delete_orphaned_mods, detect_orphaned_mods,
+    detect_orphaned_mods_with_options, get_all_mod_files, get_game_folders_in_roots,
+    parse_wabbajack_file, scan_folder_for_duplicates, scan_folders_for_duplicates,
+    OrphanScanOptions, OrphanedMod,
 };
 use zip::write::SimpleFileOptions;
 use zip::ZipWriter;
@@ -215,12 +217,13 @@ fn test_orphan_detection_basic() {
     let modlist_info = parse_wabbajack_file(&wabbajack_file).unwrap();
     let game_folders = vec![downloads_dir.clone()];
     let all_files = get_all_mod_files(&game_folders).unwrap();
-    let scan_result = detect_orphaned_mods(&all_files, &[modlist_info]);
+    let scan_result =
+        detect_orphaned_mods_with_options(&all_files, &[modlist_info], &strict_options());
 
     // Verify results
     assert_eq!(all_files.len(), 4, "Should find 4 files on disk");
 
-    // With file name matching:
+    // With strict file name matching:
     // - SkyUI (5.2, 52344) -> USED (exact match)
     // - SKSE64 (2.0.20, 111593) -> USED (exact match)
     // - UnusedMod -> ORPHANED (not in modlist)
@@ -241,7 +244,7 @@ fn test_orphan_detection_basic() {
         scan_result
             .used_mods
             .iter()
-            .any(|f| f.mod_id == "12604" && f.file_id == Some("52344".to_string())),
+            .any(|m| m.file.mod_id == "12604" && m.file.file_id == Some("52344".to_string())),
         "SkyUI 5.2 should be marked as used"
     );
     assert!(
@@ -339,9 +342,56 @@ fn test_orphan_detection_multiple_modlists() {
     assert_eq!(scan_result.orphaned_mods[0].file.mod_id, "9000");
 }
 
+#[test]
+fn test_orphan_detection_across_two_download_roots() {
+    let temp_dir = TempDir::new().unwrap();
+    let ssd = temp_dir.path().join("ssd");
+    let hdd = temp_dir.path().join("hdd");
+    let wabbajack_dir = temp_dir.path().join("wabbajack");
+    for dir in [
+        ssd.join("Skyrim"),
+        hdd.join("Skyrim"),
+        wabbajack_dir.clone(),
+    ] {
+        fs::create_dir_all(dir).unwrap();
+    }
+
+    let modlist = wabbajack_dir.join("Modlist.wabbajack");
+    create_dummy_wabbajack(
+        &modlist,
+        &[
+            TestArchive::new("OnSsd", 1000, 1001, "1.0", "1600000000"),
+            TestArchive::new("OnHdd", 2000, 2001, "1.0", "1600000000"),
+        ],
+    );
+
+    // Used files are spread over both roots, one orphan on each
+    let ssd_game = ssd.join("Skyrim");
+    let hdd_game = hdd.join("Skyrim");
+    create_mod_file(&ssd_game, "OnSsd", 1000, 1001, "1.0", "1600000000", 100);
+    create_mod_file(&hdd_game, "OnHdd", 2000, 2001, "1.0", "1600000000", 100);
+    create_mod_file(&ssd_game, "OldSsd", 3000, 3001, "1.0", "1600000000", 100);
+    create_mod_file(&hdd_game, "OldHdd", 4000, 4001, "1.0", "1600000000", 100);
+
+    let game_folders = get_game_folders_in_roots(&[ssd.clone(), hdd.clone()]).unwrap();
+    assert_eq!(game_folders.len(), 2);
+    let all_files = get_all_mod_files(&game_folders).unwrap();
+    let info = parse_wabbajack_file(&modlist).unwrap();
+    let scan_result = detect_orphaned_mods(&all_files, &[info]);
+
+    assert_eq!(scan_result.used_mods.len(), 2);
+    let mut orphan_ids: Vec<_> = scan_result
+        .orphaned_mods
+        .iter()
+        .map(|m| m.file.mod_id.as_str())
+        .collect();
+    orphan_ids.sort();
+    assert_eq!(orphan_ids, ["3000", "4000"]);
+}
+
 #[test]
 fn test_orphan_detection_modid_fallback() {
-    // Tests that with file name matching, different FileID = orphaned (not fallback)
+    // Tests that with strict file name matching, different FileID = orphaned (not fallback)
     let temp_dir = TempDir::new().unwrap();
     let downloads_dir = temp_dir.path().join("downloads");
     let wabbajack_dir = temp_dir.path().join("wabbajack");
@@ -367,7 +417,11 @@ fn test_orphan_detection_modid_fallback() {
 
     let modlist_info = parse_wabbajack_file(&wabbajack_file).unwrap();
     let all_files = get_all_mod_files(&[downloads_dir]).unwrap();
-    let scan_result = detect_orphaned_mods(&all_files, &[modlist_info]);
+    let scan_result = detect_orphaned_mods_with_options(
+        &all_files,
+        std::slice::from_ref(&modlist_info),
+        &strict_options(),
+    );
 
     // With file name matching, different FileID = different file name = ORPHANED
     assert_eq!(
@@ -376,6 +430,19 @@ fn test_orphan_detection_modid_fallback() {
         "File with different file name should NOT be used"
     );
     assert_eq!(scan_result.orphaned_mods.len(), 1, "Should be orphaned");
+
+    // The default falls back to the ModID and keeps it
+    let scan_result = detect_orphaned_mods(&all_files, &[modlist_info]);
+    assert_eq!(scan_result.used_mods.len(), 1);
+    assert!(scan_result.orphaned_mods.is_empty());
+}
+
+/// Matching without the ModID fallback: only file names and FileIDs count
+fn strict_options() -> OrphanScanOptions {
+    OrphanScanOptions {
+        mod_id_fallback: false,
+        ..Default::default()
+    }
 }
 
 // ============================================================================
@@ -431,6 +498,38 @@ fn test_old_version_keeps_newest() {
     );
 }
 
+#[test]
+fn test_old_version_scan_multiple_folders() {
+    let temp_dir = TempDir::new().unwrap();
+    let skyrim_dir = temp_dir.path().join("Skyrim Special Edition");
+    let fallout_dir = temp_dir.path().join("Fallout 4");
+    fs::create_dir(&skyrim_dir).unwrap();
+    fs::create_dir(&fallout_dir).unwrap();
+
+    create_simple_mod_file(&skyrim_dir, "SkyUI-12604-52344-5-0-1600000000.7z", 1000);
+    create_simple_mod_file(&skyrim_dir, "SkyUI-12604-52344-5-1-1610000000.7z", 1000);
+
+    create_simple_mod_file(&fallout_dir, "F4SE-42147-1000-1-0-1600000000.7z", 300);
+    create_simple_mod_file(&fallout_dir, "F4SE-42147-1000-1-1-1610000000.7z", 300);
+    create_simple_mod_file(&fallout_dir, "F4SE-42147-1000-1-2-1620000000.7z", 300);
+
+    let result = scan_folders_for_duplicates(&[skyrim_dir, fallout_dir], None).unwrap();
+
+    assert_eq!(result.duplicates.len(), 2, "One group per folder");
+    assert_eq!(result.total_files, 3, "1 old SkyUI + 2 old F4SE");
+    assert_eq!(result.total_space, 1000 + 600);
+
+    // Largest age gap first: F4SE spans 20M seconds, SkyUI 10M
+    assert!(result.duplicates[0].mod_key.contains("42147"));
+    assert_eq!(result.duplicates[0].newest_timestamp(), "1620000000");
+    assert_eq!(
+        result.duplicates[0].oldest_deletable_timestamp(),
+        Some("1600000000")
+    );
+    assert_eq!(result.duplicates[0].age_gap_secs(), 20_000_000);
+    assert_eq!(result.duplicates[1].age_gap_secs(), 10_000_000);
+}
+
 #[test]
 fn test_different_mods_not_grouped() {
     let temp_dir = TempDir::new().unwrap();
@@ -493,13 +592,18 @@ fn test_delete_orphaned_to_backup() {
     let filename = "OrphanMod-9999-8888-1-0-1234567890.7z";
     create_simple_mod_file(&downloads_dir, filename, 1000);
 
-    let files = get_all_mod_files(&[downloads_dir.clone()]).unwrap();
+    let files = get_all_mod_files(std::slice::from_ref(&downloads_dir)).unwrap();
     let orphaned = OrphanedMod {
         file: files[0].clone(),
     };
 
     // Delete with backup
-    let result = delete_orphaned_mods(&[orphaned], Some(&backup_dir), None);
+    let result = delete_orphaned_mods(
+        &[orphaned],
+        Some(&backup_dir),
+        &[temp_dir.path().to_path_buf()],
+        None,
+    );
 
     assert_eq!(result.deleted_count, 1);
     assert_eq!(result.errors.len(), 0);
@@ -520,13 +624,13 @@ fn test_delete_orphaned_permanent() {
     let filename = "ToDelete-9999-8888-1-0-1234567890.7z";
     create_simple_mod_file(&downloads_dir, filename, 1000);
 
-    let files = get_all_mod_files(&[downloads_dir.clone()]).unwrap();
+    let files = get_all_mod_files(std::slice::from_ref(&downloads_dir)).unwrap();
     let orphaned = OrphanedMod {
         file: files[0].clone(),
     };
 
     // Delete without backup (permanent)
-    let result = delete_orphaned_mods(&[orphaned], None, None);
+    let result = delete_orphaned_mods(&[orphaned], None, &[temp_dir.path().to_path_buf()], None);
 
     assert_eq!(result.deleted_count, 1);
     assert!(!downloads_dir.join(filename).exists());
@@ -547,7 +651,12 @@ fn test_delete_old_versions_safety() {
     let scan_result = scan_folder_for_duplicates(&downloads_dir).unwrap();
 
     // Delete old versions
-    let deletion_result = delete_old_versions(&scan_result.duplicates, Some(&backup_dir), None);
+    let deletion_result = delete_old_versions(
+        &scan_result.duplicates,
+        Some(&backup_dir),
+        &[temp_dir.path().to_path_buf()],
+        None,
+    );
 
     assert_eq!(
         deletion_result.deleted_count, 2,
@@ -813,13 +922,18 @@ fn test_meta_file_cleanup() {
         .write_all(b"meta content")
         .unwrap();
 
-    let files = get_all_mod_files(&[downloads_dir.clone()]).unwrap();
+    let files = get_all_mod_files(std::slice::from_ref(&downloads_dir)).unwrap();
     let orphaned = OrphanedMod {
         file: files[0].clone(),
     };
 
     // Delete with backup
-    delete_orphaned_mods(&[orphaned], Some(&backup_dir), None);
+    delete_orphaned_mods(
+        &[orphaned],
+        Some(&backup_dir),
+        &[temp_dir.path().to_path_buf()],
+        None,
+    );
 
     // Both files should be moved
     assert!(!downloads_dir.join(mod_filename).exists());
@@ -901,6 +1015,10 @@ fn test_parse_file_id() {
                 "  file_id: {:?} (expected: {:?})",
                 parsed.file_id, expected_file_id
             );
+            assert_eq!(parsed.mod_id, expected_mod_id);
+            assert_eq!(parsed.file_id.as_deref(), expected_file_id);
+        } else {
+            panic!("Expected to parse {} but failed", filename);
         }
     }
 }
@@ -1061,7 +1179,7 @@ fn test_simulation_with_real_modlist() {
     create_simple_mod_file(&downloads_dir, "BHYSYS-71112-13-02-1766329383.rar", 1024);
 
     // 3. Run Analysis
-    let all_files = get_all_mod_files(&[downloads_dir.clone()]).unwrap();
+    let all_files = get_all_mod_files(std::slice::from_ref(&downloads_dir)).unwrap();
     let orphan_result = detect_orphaned_mods(&all_files, &[modlist_info]);
     let old_ver_result = scan_folder_for_duplicates(&downloads_dir).unwrap();
 